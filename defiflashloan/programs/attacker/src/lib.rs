@@ -0,0 +1,79 @@
+// Test fixture: a malicious flash loan callback program. Never deploy it
+// outside a test validator.
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+declare_id!("BKnqmF2oWiMUo6WBFEHaz3SvFTERzBdpHgBnfCCoytce");
+
+pub const FLASH_DELEGATE_SEED: &[u8] = b"flash_delegate"; // Must match the flash loan program's seed
+
+#[program]
+pub mod attacker {
+    use super::*;
+
+    // Callback that calls straight back into the flash loan program with
+    // `data`, forwarding the remaining accounts as the inner instruction's
+    pub fn reenter<'info>(ctx: Context<'_, '_, '_, 'info, Reenter<'info>>, data: Vec<u8>) -> Result<()> {
+        let ix = Instruction {
+            program_id: ctx.accounts.flash_loan_program.key(),
+            accounts: ctx
+                .remaining_accounts
+                .iter()
+                .map(|account| AccountMeta {
+                    pubkey: account.key(),
+                    is_signer: account.is_signer,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data,
+        };
+        let mut account_infos = ctx.remaining_accounts.to_vec();
+        account_infos.push(ctx.accounts.flash_loan_program.to_account_info());
+        invoke(&ix, &account_infos)?;
+        Ok(())
+    }
+
+    // Callback that moves the escrowed loan out with the flash delegate's
+    // approval and returns without repaying anything
+    pub fn skip_repayment(ctx: Context<SkipRepayment>) -> Result<()> {
+        let bump = [ctx.bumps.flash_delegate];
+        let delegate_seeds: &[&[u8]] = &[FLASH_DELEGATE_SEED, &bump];
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.loan_escrow.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+            authority: ctx.accounts.flash_delegate.to_account_info(),
+        };
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                &[delegate_seeds],
+            ),
+            ctx.accounts.loan_escrow.delegated_amount,
+            ctx.accounts.token_mint.decimals,
+        )?;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Reenter<'info> {
+    /// CHECK: Any program; the point is to call back into the lender
+    pub flash_loan_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SkipRepayment<'info> {
+    #[account(mut)]
+    pub loan_escrow: InterfaceAccount<'info, TokenAccount>, // Escrow the loan was disbursed into
+    /// CHECK: This program's PDA, approved over the escrowed loan
+    #[account(seeds = [FLASH_DELEGATE_SEED], bump)]
+    pub flash_delegate: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub destination: InterfaceAccount<'info, TokenAccount>, // Where the loan is taken
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,119 @@
+// Defaults for new pools; live values are read from each pool's PoolConfig
+pub const DEFAULT_FEE_BPS: u64 = 50; // Default fee is 0.5%
+pub const DEFAULT_MAX_LOAN_AMOUNT: u64 = 1_000_000; // Maximum loan amount allowed
+pub const DEFAULT_LOAN_COOLDOWN: i64 = 60; // Cooldown between loans in seconds
+pub const DEFAULT_GRACE_PERIOD: i64 = 30; // Grace period for repayment in seconds
+pub const DEFAULT_CONFIG_TIMELOCK: i64 = 86_400; // Delay before queued config changes apply, in seconds
+pub const DEFAULT_KINK_UTILIZATION_BPS: u64 = 8_000; // Utilization at which new pools charge exactly the base fee
+pub const DEFAULT_MIN_FEE_MULTIPLIER_BPS: u64 = 5_000; // New pools charge half the base fee at zero utilization
+pub const DEFAULT_MAX_FEE_MULTIPLIER_BPS: u64 = 40_000; // New pools charge four times the base fee when a loan drains the vault
+pub const BPS_DENOMINATOR: u64 = 10_000; // 100% expressed in basis points
+pub const MAX_FEE_MULTIPLIER_BPS: u64 = 100_000; // Steepest fee curve an admin may set, 10x the base fee
+pub const MAX_CREATOR_FEE_BPS: u16 = 2_000; // Largest share of each loan fee a pool creator may claim
+pub const MAX_BUYBACK_SLIPPAGE_BPS: u16 = 1_000; // Widest slippage below the oracle price a buyback may be configured with
+pub const MAX_BUYBACK_KEEPER_REWARD_BPS: u16 = 100; // Largest share of a buyback the keeper running it may take
+pub const DEFAULT_SAME_SLOT_SURCHARGE_BPS: u64 = 10; // New pools charge an extra 0.1% per repeated loan within one slot
+pub const PAUSE_BORROW: u8 = 1 << 0; // Pause flag halting flash loans
+pub const PAUSE_DEPOSIT: u8 = 1 << 1; // Pause flag halting deposits
+pub const PAUSE_WITHDRAW: u8 = 1 << 2; // Pause flag halting withdrawals
+pub const PAUSE_ALL: u8 = PAUSE_BORROW | PAUSE_DEPOSIT | PAUSE_WITHDRAW;
+pub const WIND_DOWN_PAUSED: u8 = PAUSE_BORROW | PAUSE_DEPOSIT; // Operations a winding-down pool never resumes
+pub const DEFAULT_EMERGENCY_WITHDRAW_DELAY: i64 = 7 * 86_400; // New pools let LPs take out principal after a week-long withdrawal pause
+pub const MIN_EMERGENCY_WITHDRAW_DELAY: i64 = 86_400; // Shortest withdrawal pause an admin may allow before emergency withdrawals
+pub const THROTTLE_UTILIZATION_BPS: u64 = 8_000; // Loan utilization above which large withdrawals wait a slot
+pub const THROTTLED_WITHDRAW_BPS: u64 = 1_000; // Largest withdrawal, as a share of the vault, allowed while throttled
+pub const MIN_RECOVERY_INACTIVITY_EPOCHS: u64 = 30; // Shortest admin silence, in epochs, before recovery may start
+pub const RECOVERY_DELAY: i64 = 7 * 86_400; // Public window in which the admin can cancel a recovery, in seconds
+pub const REPUTATION_MAX_LOAN_POINTS: u64 = 500; // Most reputation points earned from the loan count
+pub const REPUTATION_MAX_AGE_POINTS: u64 = 365; // Most reputation points earned from record age, one per day
+pub const REPUTATION_POINTS_PER_VOLUME_BIT: u64 = 10; // Reputation points per binary order of magnitude of volume
+pub const REPUTATION_FULL_STAKE: u64 = 10_000_000_000; // Lamports staked for a reputation score to count in full (10 SOL)
+pub const REPUTATION_STAKE_LOCK: i64 = 30 * 86_400; // Seconds a reputation stake stays locked after each top-up
+pub const DEFAULT_REPUTATION_MIN_LOAN_AMOUNT: u64 = 100_000; // Smallest loan new pools count towards reputation
+pub const FORCE_UNLOCK_TIMEOUT: i64 = 300; // Seconds before anyone may clear a stuck loan state
+pub const EWMA_ALPHA_BPS: u64 = 1_000; // Weight of each new loan in the EWMA loan size
+pub const STATS_WINDOW_HOURS: usize = 24; // Hourly buckets kept for the windowed average
+pub const CONFIG_HISTORY_LEN: usize = 32; // Parameter changes kept in each pool's ConfigHistory
+pub const MAX_REGISTERED_PROGRAMS: usize = 16; // Callback targets a pool's ProgramRegistry can hold
+pub const REGISTRY_PAGE_SIZE: usize = 32; // Pools listed per RegistryPage
+pub const MAX_FEE_TIERS: usize = 4; // Size tiers a pool's fee table can hold
+pub const RATE_CARD_ENTRIES: usize = 5; // Representative loan sizes quoted on each RateCard
+pub const RATE_CARD_UTILIZATION_BPS: [u64; RATE_CARD_ENTRIES] = [100, 1_000, 2_500, 5_000, 10_000]; // Quoted sizes as shares of vault liquidity
+pub const MAX_DIRECTORY_POOLS: usize = 8; // Alternate pools a PoolDirectory can list per mint
+pub const MAX_CALLBACK_INSTRUCTIONS: usize = 8; // Instructions a batched flash loan callback may run
+pub const MAX_CALLBACK_DATA_LEN: usize = 1_024; // Bytes of instruction data one flash loan callback may carry
+pub const MAX_CALLBACK_ACCOUNT_INDICES: usize = 32; // Accounts one batched callback instruction may reference
+pub const MAX_LOAN_LEGS: usize = 4; // Mints a single multi-mint flash loan may borrow
+pub const LOAN_LEG_ACCOUNTS: usize = 9; // Remaining accounts supplied per multi-mint loan leg
+pub const MAX_REPAY_SOURCES: usize = 4; // Extra borrower token accounts `flash_repay` may draw on
+pub const MAX_ADMIN_OPS: usize = 8; // Operations one `batch_admin` call may carry
+pub const RECENT_CLIENT_IDS: usize = 8; // `flash_borrow` client ids each loan state remembers
+pub const RECENT_INSURED_LOANS: usize = 8; // Insured loans each policy keeps open to claims
+pub const MAX_INSURANCE_PREMIUM_BPS: u16 = 100; // Largest premium a fund may charge, 1% of the principal
+pub const OPS_TAG_LEN: usize = 16; // Bytes in a pool's opaque ops tag
+pub const MAX_KEEPER_UNBONDING_PERIOD: i64 = 30 * 86_400; // Longest a keeper bond may stay locked after exiting, in seconds
+pub const SNAPSHOT_HOLD_SLOTS: u64 = 9_000; // Slots, about an hour, deposited shares wait before counting in governance snapshots
+pub const MIN_LP_FEE_EPOCH_DURATION: i64 = 3_600; // Shortest time-weighted LP fee epoch, so volume is weighed by the hour
+pub const MAX_LP_FEE_EPOCH_DURATION: i64 = 7 * 86_400; // Longest time-weighted LP fee epoch
+pub const LP_FEE_EPOCH_HISTORY: usize = 16; // Closed LP fee epochs kept for positions to settle against
+pub const MAX_PRE_AUTH_WINDOW: i64 = 7 * 86_400; // Longest window a pre-authorized loan may stay executable for, in seconds
+pub const MAX_DUST_THRESHOLD: u64 = 10_000; // Largest settlement shortfall a pool may write off, in raw units of its mint
+pub const MAX_PUBLIC_GOODS_CHARGE: u64 = 10_000_000; // Largest anti-spam charge a public goods loan may carry, in lamports
+pub const FEE_EXPERIMENT_BUCKETS: usize = 2; // Control and variant buckets of a fee experiment
+pub const FEE_EXPERIMENT_CONTROL: usize = 0; // Bucket charged the pool's usual base fee
+pub const FEE_EXPERIMENT_VARIANT: usize = 1; // Bucket charged the experiment's variant fee
+pub const DEFAULT_MAX_INVOKE_DEPTH: u8 = 2; // New pools accept a top-level call or one CPI deep
+pub const MAX_INVOKE_DEPTH_LIMIT: u8 = 4; // Deepest a pool may allow, leaving the runtime room for the callback CPI
+pub const MAX_FEE_LOCK_DURATION: i64 = 4 * 365 * 86_400; // Lock duration earning full weight, in seconds
+pub const FAUCET_MAX_AIRDROP: u64 = 1_000_000_000_000; // Largest devnet faucet mint per call
+pub const FEE_PRICE_SCALE: u128 = 1_000_000_000; // Fixed-point scale of FeePrice::price
+pub const SHARE_PRICE_SCALE: u128 = 1_000_000_000; // Fixed-point scale of SharePriceUpdated prices
+pub const REWARD_PRECISION: u128 = 1_000_000_000_000; // Scale of FeeLocker::reward_per_weight
+pub const SHARE_SECOND_PRECISION: u128 = 1_000_000_000_000_000_000; // Scale of LpFeeEpoch::reward_per_share_second
+pub const PROTOCOL_CONFIG_SEED: &[u8] = b"protocol_config"; // Seed for the protocol config singleton
+pub const POOL_SEED: &[u8] = b"pool"; // Seed prefix for pool PDAs, followed by the mint and the pool's registry slot
+pub const POOL_CONFIG_SEED: &[u8] = b"pool_config"; // Seed prefix for pool configs, followed by the pool
+pub const CONFIG_HISTORY_SEED: &[u8] = b"config_history"; // Seed prefix for config histories, followed by the pool
+pub const PROGRAM_REGISTRY_SEED: &[u8] = b"program_registry"; // Seed prefix for callback allowlists, followed by the pool
+pub const SHARE_MINT_SEED: &[u8] = b"share_mint"; // Seed prefix for LP share mints, followed by the pool
+pub const LP_POSITION_SEED: &[u8] = b"lp_position"; // Seed prefix for LP positions, followed by pool and owner
+pub const SHARE_LOCK_SEED: &[u8] = b"share_lock"; // Seed prefix for share locks, followed by pool, owner and locker
+pub const SHARE_ESCROW_SEED: &[u8] = b"share_escrow"; // Seed prefix for share escrow accounts, followed by the lock
+pub const UNWRAP_SEED: &[u8] = b"unwrap"; // Seed prefix for temporary WSOL unwrap accounts, followed by the pool
+pub const PROTOCOL_LIQUIDITY_SEED: &[u8] = b"protocol_liquidity"; // Seed prefix for protocol-owned share accounts, followed by the pool
+pub const LOAN_ESCROW_SEED: &[u8] = b"loan_escrow"; // Seed prefix for loan escrow accounts, followed by the pool
+pub const FLASH_DELEGATE_SEED: &[u8] = b"flash_delegate"; // Seed of the callback program PDA approved over escrowed loans
+pub const FEE_LOCKER_SEED: &[u8] = b"fee_locker"; // Seed prefix for fee lockers, followed by the pool
+pub const FEE_LOCKER_VAULT_SEED: &[u8] = b"fee_locker_vault"; // Seed prefix for locked token vaults, followed by the locker
+pub const FEE_LOCKER_REWARDS_SEED: &[u8] = b"fee_locker_rewards"; // Seed prefix for streamed fee vaults, followed by the locker
+pub const LOCKER_POSITION_SEED: &[u8] = b"locker_position"; // Seed prefix for locker positions, followed by locker and owner
+pub const LOAN_RECEIPT_SEED: &[u8] = b"loan_receipt"; // Seed prefix for split loan receipts, followed by the loan state
+pub const FAUCET_SEED: &[u8] = b"faucet"; // Seed of the devnet faucet mint authority
+pub const SOL_POOL_SEED: &[u8] = b"sol_pool"; // Seed for the lamport pool singleton
+pub const SOL_VAULT_SEED: &[u8] = b"sol_vault"; // Seed for the lamport pool's system-owned vault
+pub const POOL_DIRECTORY_SEED: &[u8] = b"pool_directory"; // Seed prefix for alternate pool listings, followed by the mint
+pub const ADMIN_RECOVERY_SEED: &[u8] = b"admin_recovery"; // Seed prefix for dead-man's-switch recovery, followed by the pool
+pub const REPUTATION_SEED: &[u8] = b"reputation"; // Seed prefix for borrower reputations, followed by the borrower
+pub const REGISTRY_SEED: &[u8] = b"registry"; // Seed for the global pool registry singleton
+pub const REGISTRY_PAGE_SEED: &[u8] = b"registry_page"; // Seed prefix for registry pages, followed by the little-endian page index
+pub const FEE_SETTLEMENT_SEED: &[u8] = b"fee_settlement"; // Seed for the fee settlement singleton
+pub const FEE_PRICE_SEED: &[u8] = b"fee_price"; // Seed prefix for settlement prices, followed by the mint
+pub const RATE_CARD_SEED: &[u8] = b"rate_card"; // Seed prefix for fee disclosure cards, followed by the pool
+pub const LOAN_STATE_SEED: &[u8] = b"loan_state"; // Seed prefix for loan states, followed by the pool and borrower
+pub const FEE_EXPERIMENT_SEED: &[u8] = b"fee_experiment"; // Seed prefix for fee experiment stats, followed by the pool
+pub const SESSION_KEY_SEED: &[u8] = b"session_key"; // Seed prefix for borrower session keys
+pub const STATS_ACCUMULATOR_SEED: &[u8] = b"stats_accumulator"; // Seed prefix for fast path stats accumulators, followed by the pool
+pub const INTEGRATOR_SEED: &[u8] = b"integrator"; // Seed prefix for registered receiver programs, followed by the program id
+pub const BUYBACK_SEED: &[u8] = b"buyback"; // Seed prefix for fee buybacks, followed by the fee mint
+pub const KEEPER_REGISTRY_SEED: &[u8] = b"keeper_registry"; // Seed for the keeper registry singleton
+pub const KEEPER_STAKE_VAULT_SEED: &[u8] = b"keeper_stake_vault"; // Seed for the vault holding keeper bonds
+pub const KEEPER_REWARD_VAULT_SEED: &[u8] = b"keeper_reward_vault"; // Seed for the vault paying crank rewards
+pub const KEEPER_SEED: &[u8] = b"keeper"; // Seed prefix for registered keepers, followed by the authority
+pub const INSURANCE_FUND_SEED: &[u8] = b"insurance_fund"; // Seed prefix for loan insurance funds, followed by the pool
+pub const INSURANCE_VAULT_SEED: &[u8] = b"insurance_vault"; // Seed prefix for insurance fund vaults, followed by the fund
+pub const INSURANCE_POLICY_SEED: &[u8] = b"insurance_policy"; // Seed prefix for insurance policies, followed by the fund and borrower
+pub const INSURANCE_CLAIM_SEED: &[u8] = b"insurance_claim"; // Seed prefix for insurance claims, followed by the policy and loan slot
+pub const LP_FEE_ACCUMULATOR_SEED: &[u8] = b"lp_fee_accumulator"; // Seed prefix for time-weighted LP fee accumulators, followed by the pool
+pub const REBALANCE_ROUTE_SEED: &[u8] = b"rebalance_route"; // Seed prefix for rebalance routes, followed by the source and target pools
+pub const REBALANCE_IOU_SEED: &[u8] = b"rebalance_iou"; // Seed prefix for a route's IOU share account, followed by the route
+pub const PRE_AUTH_SEED: &[u8] = b"pre_auth"; // Seed prefix for pre-authorized loans, followed by the pool, borrower and nonce
@@ -0,0 +1,266 @@
+use anchor_lang::prelude::*;
+
+// Error handling
+#[error_code]
+pub enum FlashLoanError {
+    #[msg("Insufficient funds in the loan vault.")]
+    InsufficientFunds,
+    #[msg("Borrower did not repay the loan.")]
+    LoanNotRepaid,
+    #[msg("Invalid fee structure.")]
+    InvalidFeeStructure,
+    #[msg("Reentrancy detected.")]
+    Reentrancy,
+    #[msg("Flash loan expired.")]
+    LoanExpired,
+    #[msg("Loan amount exceeds the maximum allowed.")]
+    LoanAmountTooLarge,
+    #[msg("Borrower repaid an incorrect amount.")]
+    IncorrectRepayment,
+    #[msg("Cooldown period not over.")]
+    CooldownPeriodNotOver,
+    #[msg("Loan percentage must be between 1 and 10000 basis points.")]
+    InvalidLoanBps,
+    #[msg("Borrow credits must cover at least one loan within the maximum loan size.")]
+    InvalidBorrowCredit,
+    #[msg("Borrow credit is owned by a different borrower.")]
+    BorrowCreditOwnerMismatch,
+    #[msg("The stats account required by this loan path was not provided.")]
+    StatsAccountMissing,
+    #[msg("Token program does not own the token mint.")]
+    TokenProgramMismatch,
+    #[msg("Session key does not match the signer or borrower.")]
+    SessionKeyMismatch,
+    #[msg("Session key has expired.")]
+    SessionKeyExpired,
+    #[msg("Loan amount exceeds the session key limit.")]
+    SessionLoanTooLarge,
+    #[msg("Flash loan invoked from too deep in the CPI stack.")]
+    InvokeDepthExceeded,
+    #[msg("Accounting invariant violated.")]
+    InvariantViolated,
+    #[msg("Amount must be greater than zero.")]
+    InvalidAmount,
+    #[msg("Withdrawal exceeds the LP position balance.")]
+    InsufficientLpBalance,
+    #[msg("RFQ quotes are not enabled for this pool.")]
+    QuoteSignerNotSet,
+    #[msg("Quote has expired.")]
+    QuoteExpired,
+    #[msg("Missing Ed25519 signature instruction for the quote.")]
+    QuoteSignatureMissing,
+    #[msg("Quote signature does not match the pool quote signer and terms.")]
+    InvalidQuoteSignature,
+    #[msg("Reserve factor cannot exceed 10000 basis points.")]
+    InvalidReserveFactor,
+    #[msg("Loan fee exceeds the loan amount.")]
+    FeeExceedsLoan,
+    #[msg("Shares are locked until the lock expires.")]
+    SharesLocked,
+    #[msg("Only the locker, or the owner after expiry, can unlock these shares.")]
+    UnlockNotAuthorized,
+    #[msg("Invalid pool configuration parameters.")]
+    InvalidPoolConfig,
+    #[msg("No pool configuration change is queued.")]
+    NoPendingConfigChange,
+    #[msg("The queued configuration change is still timelocked.")]
+    ConfigChangeNotReady,
+    #[msg("Signer is not the proposed pool admin.")]
+    NotPendingAdmin,
+    #[msg("This operation is paused for the pool.")]
+    PoolPaused,
+    #[msg("Unknown pause flags.")]
+    InvalidPauseFlags,
+    #[msg("The loan state is not locked.")]
+    LoanStateNotLocked,
+    #[msg("Only the protocol admin can unlock before the timeout.")]
+    UnlockTimeoutNotReached,
+    #[msg("No matching flash_repay follows this flash_borrow.")]
+    MissingFlashRepay,
+    #[msg("Callback target must be an executable program other than this one.")]
+    InvalidCallbackProgram,
+    #[msg("Native SOL settlement is only available for WSOL pools.")]
+    NotNativeMint,
+    #[msg("Callback target program is not in the pool's program registry.")]
+    CallbackProgramNotRegistered,
+    #[msg("The program registry is full.")]
+    ProgramRegistryFull,
+    #[msg("Too many instructions in the callback batch.")]
+    TooManyCallbackInstructions,
+    #[msg("Callback account index is out of range.")]
+    InvalidAccountIndex,
+    #[msg("Callback delegate is not the callback program's flash delegate PDA.")]
+    InvalidCallbackDelegate,
+    #[msg("Lock duration must be positive and at most the maximum lock duration.")]
+    InvalidLockDuration,
+    #[msg("Locked tokens cannot be withdrawn before the lock expires.")]
+    FeeLockActive,
+    #[msg("The current fee epoch has not elapsed.")]
+    EpochNotElapsed,
+    #[msg("Loan receipt does not match the repayment.")]
+    LoanReceiptMismatch,
+    #[msg("Token account mint does not match the pool mint.")]
+    MintMismatch,
+    #[msg("Pool utilization is high; large withdrawals must wait for a later slot.")]
+    WithdrawalThrottled,
+    #[msg("Token-2022 transfer fee calculation overflowed.")]
+    TransferFeeOverflow,
+    #[msg("Pool directory is full.")]
+    PoolDirectoryFull,
+    #[msg("Recovery inactivity period is too short.")]
+    InvalidRecoveryPeriod,
+    #[msg("Signer is not the pool's recovery key.")]
+    NotRecoveryKey,
+    #[msg("Pool admin has sent a heartbeat too recently for recovery.")]
+    AdminStillActive,
+    #[msg("No admin recovery is pending.")]
+    RecoveryNotStarted,
+    #[msg("Admin recovery delay has not elapsed.")]
+    RecoveryDelayNotElapsed,
+    #[msg("Multi-mint flash loans take between one and four legs.")]
+    TooManyLoanLegs,
+    #[msg("Loan leg accounts do not match the requested pool.")]
+    LoanLegMismatch,
+    #[msg("A pool may appear in only one loan leg.")]
+    DuplicateLoanLeg,
+    #[msg("Reputation record belongs to another borrower.")]
+    ReputationMismatch,
+    #[msg("Pool is not listed in the given registry page.")]
+    PoolNotInRegistryPage,
+    #[msg("Pool still holds liquidity, shares or an open loan.")]
+    PoolNotEmpty,
+    #[msg("Fee price is stale or out of range.")]
+    StaleFeePrice,
+    #[msg("Fee price account does not match the loan leg.")]
+    FeePriceMismatch,
+    #[msg("Creator fee share exceeds the protocol maximum.")]
+    InvalidCreatorFee,
+    #[msg("Fee curve kink or multipliers are out of range.")]
+    InvalidFeeCurve,
+    #[msg("Fee tiers must start at zero, rise in size and fall in fee.")]
+    InvalidFeeTiers,
+    #[msg("Loan state belongs to a different pool or borrower.")]
+    LoanStateMismatch,
+    #[msg("Borrow credit was bought from a different pool.")]
+    BorrowCreditPoolMismatch,
+    #[msg("Reputation stake is still locked.")]
+    StakeLocked,
+    #[msg("Reputation stake is smaller than the amount requested.")]
+    InsufficientStake,
+    #[msg("Callback instruction data or account list is too long.")]
+    CallbackTooLarge,
+    #[msg("Deposit or withdrawal would settle below the caller's minimum.")]
+    SlippageExceeded,
+    #[msg("Pool already has its maximum number of borrowers with open loans.")]
+    PoolSaturated,
+    #[msg("A recent borrow already used this client id.")]
+    DuplicateClientId,
+    #[msg("Too many token accounts supplied to repay from.")]
+    TooManyRepaySources,
+    #[msg("Repayment source is not a borrower token account for the pool mint.")]
+    InvalidRepaySource,
+    #[msg("Amount cannot be converted to or from a UI amount for this mint.")]
+    InvalidUiAmount,
+    #[msg("Pool runs a fee experiment; pass its FeeExperimentStats account.")]
+    FeeExperimentStatsMissing,
+    #[msg("Withdrawals have not been paused long enough for an emergency withdrawal.")]
+    EmergencyWithdrawUnavailable,
+    #[msg("Pool is winding down.")]
+    PoolWindingDown,
+    #[msg("First loan from the pool exceeds the probation amount.")]
+    ProbationLoanTooLarge,
+    #[msg("A multi-mint leg's pool is running a fee experiment.")]
+    FeeExperimentRunning,
+    #[msg("Receiver interface version is not one this program implements.")]
+    InvalidInterfaceVersion,
+    #[msg("Receiver loans need the receiver program's Integrator account.")]
+    IntegratorNotRegistered,
+    #[msg("Integrator account is for a different program.")]
+    IntegratorMismatch,
+    #[msg("Receiver program implements an incompatible interface version.")]
+    IncompatibleReceiverInterface,
+    #[msg("Pool refuses durable nonce transactions; pass the Instructions sysvar.")]
+    NonceCheckUnavailable,
+    #[msg("Pool refuses loans from durable nonce transactions.")]
+    DurableNonceRejected,
+    #[msg("Pool reports fees in USD; pass its accounting price.")]
+    AccountingPriceMissing,
+    #[msg("Pool parameter is outside the protocol's envelope.")]
+    OutsideParamEnvelope,
+    #[msg("Pool parameter was frozen by the protocol admin.")]
+    ParamFrozen,
+    #[msg("Invalid parameter envelope.")]
+    InvalidParamEnvelope,
+    #[msg("Forwarding the protocol fee needs the protocol config.")]
+    FeeForwardingAccountMissing,
+    #[msg("Fee forwarding account is not the treasury's or the creator's for the pool mint.")]
+    InvalidFeeForwardingAccount,
+    #[msg("Invalid buyback configuration or buyback account.")]
+    InvalidBuybackConfig,
+    #[msg("The buyback's minimum interval has not elapsed.")]
+    BuybackTooSoon,
+    #[msg("Buyback swap returned less than the minimum output.")]
+    BuybackSlippageExceeded,
+    #[msg("Buyback swap spent more than it was given.")]
+    BuybackOverspent,
+    #[msg("Too many or no admin operations in the batch.")]
+    InvalidAdminBatch,
+    #[msg("Netting needs two distinct pools of the same mint.")]
+    InvalidNettingPools,
+    #[msg("Invalid public goods limits, or the pool has LP shares outstanding.")]
+    InvalidPublicGoodsMode,
+    #[msg("Borrower has taken the public goods pool's daily loan limit.")]
+    DailyLoanLimitReached,
+    #[msg("Public goods loans pay their charge to the pool admin; pass it.")]
+    ChargeRecipientMissing,
+    #[msg("Invalid keeper registry parameters.")]
+    InvalidKeeperConfig,
+    #[msg("Keeper bond is below the registry minimum.")]
+    KeeperStakeTooLow,
+    #[msg("Keeper is unbonding.")]
+    KeeperExiting,
+    #[msg("Keeper has not begun exiting.")]
+    KeeperNotExiting,
+    #[msg("Keeper bond is still unbonding.")]
+    KeeperStillUnbonding,
+    #[msg("Keeper crank rewards need the registry, keeper and keeper authority accounts.")]
+    KeeperAccountsMissing,
+    #[msg("Keeper account belongs to another authority.")]
+    KeeperMismatch,
+    #[msg("Invalid insurance fund parameters, or the fund is not selling cover.")]
+    InvalidInsuranceConfig,
+    #[msg("Insuring a loan needs the pool's insurance fund, its vault and the borrower's policy.")]
+    InsuranceAccountsMissing,
+    #[msg("No insured loan was borrowed in that slot, or it has left the policy's claim window.")]
+    LoanNotInsured,
+    #[msg("Invalid insurance claim amount or claimant account.")]
+    InvalidInsuranceClaim,
+    #[msg("Insurance claim has already been adjudicated.")]
+    ClaimAlreadyResolved,
+    #[msg("Invalid LP fee epoch duration, or the pool already has LP shares or time-weighted fees.")]
+    InvalidLpFeeConfig,
+    #[msg("Pools with time-weighted LP fees need their LP fee accumulator passed.")]
+    LpFeeAccumulatorMissing,
+    #[msg("Time-weighted pools redeem shares only through the position they were minted to.")]
+    SharesNotTracked,
+    #[msg("Invalid rebalance route: pools must differ and share a mint, with limits set.")]
+    InvalidRebalanceRoute,
+    #[msg("Rebalance exceeds the route's move or outstanding limits.")]
+    RebalanceLimitExceeded,
+    #[msg("Only the route operator or the protocol admin may rebalance.")]
+    RebalanceNotAuthorized,
+    #[msg("Rebalancing would lower a pool's share price.")]
+    SharePriceMoved,
+    #[msg("Dust threshold exceeds the maximum.")]
+    InvalidDustThreshold,
+    #[msg("Invalid pre-authorization window.")]
+    InvalidPreAuthWindow,
+    #[msg("Loan does not match its pre-authorization.")]
+    PreAuthMismatch,
+    #[msg("Pre-authorized loan is outside its execution window.")]
+    PreAuthOutsideWindow,
+    #[msg("Pre-authorized loan has already been executed.")]
+    PreAuthAlreadyExecuted,
+    #[msg("Loan fee exceeds the most its pre-authorization accepts.")]
+    PreAuthFeeTooHigh,
+}
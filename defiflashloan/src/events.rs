@@ -0,0 +1,719 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::OPS_TAG_LEN;
+
+// Flash loan executed event
+// Enable the `serde` feature to reuse event types in off-chain consumers
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlashLoanExecuted {
+    pub pool: Pubkey,
+    pub borrower: Pubkey,
+    pub loan_amount: u64,
+    pub fee: u64,
+    pub protocol_fee: u64,          // Portion of `fee` reserved for the protocol
+    pub fee_deducted: bool,         // Fee was withheld from the disbursement
+    pub callback_program: Pubkey,   // Program invoked between disbursal and repayment
+    pub callback_success: bool,     // The callback reported success through its return data
+    pub sequence: u64,              // Per-pool event sequence, consecutive from 1
+}
+
+// Fee revenue of a settled loan, in token units and, when the pool's
+// accounting price is fresh, in USD cents
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeeRevenueRecorded {
+    pub pool: Pubkey,
+    pub sequence: u64,              // Sequence of the loan's `FlashLoanExecuted`
+    pub fee: u64,
+    pub protocol_fee: u64,
+    pub fee_usd_cents: Option<u64>, // None when the accounting price was stale
+    pub protocol_fee_usd_cents: Option<u64>,
+}
+
+// Pool accounting oracle changed event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccountingOracleSet {
+    pub pool: Pubkey,
+    pub accounting_oracle: Pubkey,  // Default when reporting was turned off
+}
+
+// Fee shares paid out at settlement instead of reserved in the vault event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeesForwarded {
+    pub pool: Pubkey,
+    pub sequence: u64,              // Sequence of the loan's FlashLoanExecuted event
+    pub protocol_fee: u64,          // Zero when the protocol's share was reserved
+    pub creator_fee: u64,           // Zero when the creator's share was reserved
+}
+
+// Pool ops tag changed event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OpsTagSet {
+    pub pool: Pubkey,
+    pub ops_tag: [u8; OPS_TAG_LEN], // All zero when cleared
+}
+
+// Pool made fee-free for public goods, retuned or made standard again event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PublicGoodsModeSet {
+    pub pool: Pubkey,
+    pub daily_loan_limit: u32,      // Zero when the pool is standard again
+    pub charge_lamports: u64,       // Paid to the pool admin by each loan
+}
+
+// Public goods loan paid its anti-spam charge event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PublicGoodsLoanCharged {
+    pub pool: Pubkey,
+    pub borrower: Pubkey,
+    pub charge_lamports: u64,
+    pub day_loan_count: u32,        // Borrower's loans on the pool so far this UTC day
+}
+
+// Fee mint buyback created or retuned event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BuybackConfigured {
+    pub fee_mint: Pubkey,
+    pub protocol_mint: Pubkey,
+    pub amm_program: Pubkey,
+    pub collect_share_bps: u16,
+    pub max_slippage_bps: u16,
+    pub keeper_reward_bps: u16,
+}
+
+// Protocol fees routed to the buyback by `collect_protocol_fees` event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BuybackFunded {
+    pub pool: Pubkey,
+    pub fee_mint: Pubkey,
+    pub amount: u64,
+}
+
+// Buyback swapped and burned event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BuybackExecuted {
+    pub fee_mint: Pubkey,
+    pub keeper: Pubkey,
+    pub keeper_reward: u64,         // Fee mint paid to the keeper
+    pub spent: u64,                 // Fee mint the swap took
+    pub burned: u64,                // Protocol tokens bought and burned
+    pub min_amount_out: u64,        // Floor the swap had to clear
+}
+
+// Prepaid borrow credits purchased event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BorrowCreditsPurchased {
+    pub owner: Pubkey,
+    pub pool: Pubkey,
+    pub loan_count: u64,
+    pub max_loan_size: u64,
+    pub total_price: u64,
+}
+
+// Pool initialized event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PoolInitialized {
+    pub pool: Pubkey,
+    pub admin: Pubkey,
+    pub token_mint: Pubkey,
+    pub vault: Pubkey,
+    pub share_mint: Pubkey,
+    pub creator_fee_bps: u16,       // Share of each loan fee owed to the creator
+    pub index: u64,                 // Registry slot in the pool's PDA seeds, never reused
+}
+
+// Emitted by `clone_pool_config`, after the new pool's PoolInitialized
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PoolConfigCloned {
+    pub pool: Pubkey,               // Pool just created
+    pub source_pool: Pubkey,        // Pool its parameters and roles were copied from
+}
+
+// Emitted when the protocol admin designates a mint's canonical pool
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CanonicalPoolSet {
+    pub token_mint: Pubkey,
+    pub pool: Pubkey,
+}
+
+// Receiver program registered with its interface version
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IntegratorRegistered {
+    pub program_id: Pubkey,
+    pub min_interface_version: u16,
+    pub interface_version: u16,     // Interface this program currently calls receivers with
+}
+
+// Receiver loan refused because the program's registered interface no longer
+// matches; logged in the failed transaction
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReceiverInterfaceMismatch {
+    pub pool: Pubkey,
+    pub program_id: Pubkey,
+    pub min_interface_version: u16,
+    pub interface_version: u16,
+}
+
+// Pool closed and removed from the registry event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PoolClosed {
+    pub pool: Pubkey,
+    pub token_mint: Pubkey,
+}
+
+// Emitted when the loan cooldown rejects a borrow
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RateLimitExhausted {
+    pub pool: Pubkey,
+    pub borrower: Pubkey,
+    pub loan_amount: u64,           // Amount that was requested
+    pub retry_at: i64,              // Unix timestamp when the cooldown ends
+    pub ops_tag: [u8; OPS_TAG_LEN], // Pool's alert routing tag
+}
+
+// Emitted when the concurrent borrower cap rejects a `flash_borrow`
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PoolSaturated {
+    pub pool: Pubkey,
+    pub borrower: Pubkey,
+    pub loan_amount: u64,           // Amount that was requested
+    pub open_borrowers: u32,        // Borrowers holding an open loan receipt
+    pub max_concurrent_borrowers: u32,
+    pub ops_tag: [u8; OPS_TAG_LEN], // Pool's alert routing tag
+}
+
+// Liquidity deposited event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LiquidityDeposited {
+    pub pool: Pubkey,
+    pub lp: Pubkey,
+    pub amount: u64,
+    pub shares: u64,                // LP shares minted for the deposit
+}
+
+// Liquidity withdrawn event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LiquidityWithdrawn {
+    pub pool: Pubkey,
+    pub lp: Pubkey,
+    pub amount: u64,
+    pub shares: u64,                // LP shares burned for the withdrawal
+}
+
+// LP principal withdrawn during a prolonged withdrawal pause
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EmergencyWithdrawn {
+    pub pool: Pubkey,
+    pub lp: Pubkey,
+    pub amount: u64,                // Principal paid out, fee yield left in the pool
+    pub shares: u64,                // LP shares burned for it
+}
+
+// LP share price moved by a deposit, withdrawal or fee accrual
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SharePriceUpdated {
+    pub pool: Pubkey,
+    pub price_before: u64,          // Underlying per share, scaled by SHARE_PRICE_SCALE
+    pub price_after: u64,
+    pub lp_assets: u64,             // LP assets after the update
+    pub share_supply: u64,          // LP shares outstanding after the update
+}
+
+// Protocol fees swept to the treasury event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProtocolFeesCollected {
+    pub pool: Pubkey,
+    pub treasury_account: Pubkey,
+    pub amount: u64,
+}
+
+// LP shares escrowed for an external protocol event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SharesLocked {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub locker: Pubkey,             // External protocol the shares are locked for
+    pub shares: u64,
+    pub unlock_at: i64,
+}
+
+// Escrowed LP shares released event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SharesUnlocked {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub authority: Pubkey,          // Locker or owner that released the shares
+    pub destination: Pubkey,        // Share account that received them
+    pub shares: u64,
+}
+
+// Pool parameter change queued behind the timelock event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PoolConfigQueued {
+    pub pool: Pubkey,
+    pub fee_bps: u64,
+    pub max_loan_amount: u64,
+    pub loan_cooldown: i64,
+    pub timelock: i64,
+    pub activates_at: i64,          // Unix timestamp when the change can be applied
+}
+
+// Queued pool parameter change activated event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PoolConfigApplied {
+    pub pool: Pubkey,
+    pub fee_bps: u64,
+    pub max_loan_amount: u64,
+    pub loan_cooldown: i64,
+    pub timelock: i64,
+}
+
+// Protocol parameter envelope changed event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParamEnvelopeSet {
+    pub max_fee_bps: u64,
+    pub max_loan_amount: u64,
+    pub min_loan_cooldown: i64,
+    pub min_timelock: i64,
+}
+
+// Pool parameter overridden by the protocol admin event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PoolParamOverridden {
+    pub pool: Pubkey,
+    pub param: u8,                  // `PoolParam` discriminant
+    pub old_value: u64,             // Signed parameters are given as their two's complement bits
+    pub new_value: u64,
+    pub frozen: bool,               // Whether the curator is locked out of the parameter
+    pub authority: Pubkey,
+}
+
+// Pool admin transfer proposed event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdminProposed {
+    pub pool: Pubkey,
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,      // Default when a pending transfer was cancelled
+}
+
+// Pool admin transfer accepted event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdminTransferred {
+    pub pool: Pubkey,
+    pub previous_admin: Pubkey,
+    pub admin: Pubkey,
+}
+
+// Pool pause flags changed event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PoolPauseUpdated {
+    pub pool: Pubkey,
+    pub pause_flags: u8,            // PAUSE_* bits now in effect
+    pub ops_tag: [u8; OPS_TAG_LEN], // Pool's alert routing tag
+}
+
+// Emitted when the admin starts retiring a pool with `begin_wind_down`
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WindDownStarted {
+    pub pool: Pubkey,
+    pub started_at: i64,            // Unix timestamp borrows and deposits stopped
+    pub ops_tag: [u8; OPS_TAG_LEN], // Pool's alert routing tag
+}
+
+// Stuck loan state cleared by `force_unlock` event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnlockForced {
+    pub loan_state: Pubkey,
+    pub authority: Pubkey,          // Protocol admin or the caller after the timeout
+    pub active_since: i64,          // When the loan state was locked
+}
+
+// Protocol-owned liquidity seeded from the treasury event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProtocolLiquiditySeeded {
+    pub pool: Pubkey,
+    pub treasury_account: Pubkey,   // Treasury token account that funded the seed
+    pub amount: u64,
+    pub shares: u64,                // Shares minted to the protocol liquidity account
+}
+
+// Protocol tokens locked or relocked in a fee locker event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeeTokensLocked {
+    pub fee_locker: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,                // Total tokens now locked in the position
+    pub weight: u64,                // Position weight after the lock
+    pub unlock_at: i64,
+}
+
+// Protocol fees streamed to a fee locker for an epoch event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProtocolFeesStreamed {
+    pub fee_locker: Pubkey,
+    pub epoch: u64,
+    pub amount: u64,
+    pub total_weight: u64,          // Weight the fees were split across
+}
+
+// Fee rewards claimed from a fee locker event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeeRewardsClaimed {
+    pub fee_locker: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+// Expired lock withdrawn from a fee locker event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeeTokensUnlocked {
+    pub fee_locker: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+// Dead-man's-switch recovery started event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdminRecoveryStarted {
+    pub pool: Pubkey,
+    pub recovery_key: Pubkey,
+    pub executable_at: i64,         // Earliest time `complete_admin_recovery` succeeds
+}
+
+// Pending recovery cancelled by a pool admin heartbeat event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdminRecoveryCancelled {
+    pub pool: Pubkey,
+    pub admin: Pubkey,
+}
+
+// Borrower incident recorded by the protocol admin event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BorrowerIncidentFlagged {
+    pub borrower: Pubkey,
+    pub incidents: u32,             // Incidents on record, including this one
+}
+
+// Multi-mint loan fees paid in the settlement mint event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeesSettled {
+    pub borrower: Pubkey,
+    pub settlement_mint: Pubkey,
+    pub amount: u64,                // Aggregate fee across every leg, in the settlement mint
+    pub pools: Vec<Pubkey>,         // Pool of each leg settled
+    pub sequences: Vec<u64>,        // Each leg's `FlashLoanExecuted` sequence on its pool
+}
+
+// Creator fee share swept to the pool creator event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CreatorFeesCollected {
+    pub pool: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+}
+
+// Pool fee tier table replaced event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeeTiersUpdated {
+    pub pool: Pubkey,
+    pub tier_count: u8,             // Tiers now in use, zero when the table was cleared
+}
+
+// Lamports staked behind a borrower reputation event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReputationStaked {
+    pub borrower: Pubkey,
+    pub amount: u64,
+    pub staked_lamports: u64,       // Stake after the top-up
+    pub unlocks_at: i64,
+}
+
+// Reputation stake withdrawn event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReputationUnstaked {
+    pub borrower: Pubkey,
+    pub amount: u64,
+    pub staked_lamports: u64,       // Stake left behind the score
+}
+
+// Fee lock position reweighted for its remaining lock time event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeeLockCheckpointed {
+    pub fee_locker: Pubkey,
+    pub owner: Pubkey,
+    pub weight: u64,                // Position weight after the checkpoint
+    pub total_weight: u64,          // Locker weight after the checkpoint
+}
+
+// Pool RFQ quote signer changed event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuoteSignerUpdated {
+    pub pool: Pubkey,
+    pub old_quote_signer: Pubkey,
+    pub quote_signer: Pubkey,       // Default when RFQ was disabled
+}
+
+// Loan from one pool netted against a deposit into another pool of the mint event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LoanNetted {
+    pub source_pool: Pubkey,
+    pub target_pool: Pubkey,
+    pub borrower: Pubkey,
+    pub loan_amount: u64,           // Principal that netted out and never moved
+    pub deposit_amount: u64,        // Deposit credited to the target pool
+    pub fee: u64,                   // Loan fee paid to the source pool
+    pub sequence: u64,              // Source pool's `FlashLoanExecuted` sequence for the loan
+}
+
+// Keeper registry created or retuned event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeeperRegistryConfigured {
+    pub stake_mint: Pubkey,
+    pub min_stake: u64,
+    pub reward_per_crank: u64,
+    pub unbonding_period: i64,
+}
+
+// Keeper bond posted or topped up event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeeperStaked {
+    pub keeper: Pubkey,
+    pub authority: Pubkey,
+    pub amount: u64,                // Bonded after any transfer fee
+    pub staked_amount: u64,         // Keeper bond after the top-up
+}
+
+// Keeper started unbonding event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeeperExitRequested {
+    pub keeper: Pubkey,
+    pub authority: Pubkey,
+    pub withdrawable_at: i64,       // Unix timestamp the bond may be withdrawn from
+}
+
+// Unbonded keeper bond returned event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeeperStakeWithdrawn {
+    pub keeper: Pubkey,
+    pub authority: Pubkey,
+    pub amount: u64,
+}
+
+// Crank reward credited to a keeper event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeeperCrankCredited {
+    pub keeper: Pubkey,
+    pub authority: Pubkey,
+    pub crank: u8,                  // `KeeperCrank` discriminant
+    pub reward: u64,
+    pub pending_rewards: u64,       // Keeper's unclaimed rewards after the credit
+}
+
+// Keeper crank rewards paid out event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeeperRewardsClaimed {
+    pub keeper: Pubkey,
+    pub authority: Pubkey,
+    pub amount: u64,
+    pub pending_rewards: u64,       // Left unpaid when the reward vault ran short
+}
+
+// Keeper bond slashed by the protocol admin event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeeperSlashed {
+    pub keeper: Pubkey,
+    pub authority: Pubkey,
+    pub amount: u64,                // Moved to the reward vault
+    pub staked_amount: u64,         // Keeper bond left
+    pub evidence: [u8; 32],         // Hash of the misbehavior proof
+}
+
+// LP statement generated event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LpStatementGenerated {
+    pub pool: Pubkey,
+    pub lp: Pubkey,
+    pub slot: u64,
+    pub total_deposited: u64,       // Underlying ever deposited through the position
+    pub total_withdrawn: u64,       // Underlying ever paid out through the position
+    pub principal: u64,             // Deposits not yet drawn down
+    pub share_balance: u64,
+    pub current_value: u64,         // Underlying the shares redeem for now
+    pub fee_income: i64,            // Current value plus withdrawals less deposits
+}
+
+// Pool insurance fund created or retuned event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InsuranceFundConfigured {
+    pub pool: Pubkey,
+    pub insurance_fund: Pubkey,
+    pub premium_bps: u16,
+    pub max_claim_amount: u64,
+}
+
+// Split loan insured event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LoanInsured {
+    pub pool: Pubkey,
+    pub borrower: Pubkey,
+    pub insurance_fund: Pubkey,
+    pub loan_amount: u64,
+    pub premium: u64,               // Paid into the fund vault
+    pub loan_slot: u64,             // Names the loan in a claim
+}
+
+// Insurance claim filed event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InsuranceClaimFiled {
+    pub insurance_fund: Pubkey,
+    pub claim: Pubkey,
+    pub claimant: Pubkey,
+    pub loan_slot: u64,
+    pub requested_amount: u64,
+    pub evidence: [u8; 32],         // Hash of the failed settlement's evidence
+}
+
+// Insurance claim paid or rejected event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InsuranceClaimResolved {
+    pub insurance_fund: Pubkey,
+    pub claim: Pubkey,
+    pub claimant: Pubkey,
+    pub requested_amount: u64,
+    pub paid_amount: u64,           // Zero when rejected
+}
+
+// Time-weighted LP fee epoch closed event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LpFeeEpochClosed {
+    pub pool: Pubkey,
+    pub epoch: u32,
+    pub fees: u64,                  // Zero when no position held shares through the epoch
+    pub share_seconds: u128,        // Share-seconds the fees were split across
+}
+
+// Time-weighted LP fees claimed event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LpFeesClaimed {
+    pub pool: Pubkey,
+    pub lp: Pubkey,
+    pub amount: u64,
+}
+
+// Rebalance route created or retuned event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RebalanceRouteConfigured {
+    pub route: Pubkey,
+    pub source_pool: Pubkey,
+    pub target_pool: Pubkey,
+    pub operator: Pubkey,
+    pub max_move_amount: u64,
+    pub max_outstanding: u64,
+}
+
+// Liquidity moved between pools of a mint event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LiquidityRebalanced {
+    pub route: Pubkey,
+    pub source_pool: Pubkey,
+    pub target_pool: Pubkey,
+    pub amount: u64,
+    pub iou_shares: u64,            // Target shares minted to the source pool
+    pub outstanding_principal: u64,
+}
+
+// Rebalanced liquidity returned to its source pool event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RebalanceUnwound {
+    pub route: Pubkey,
+    pub source_pool: Pubkey,
+    pub target_pool: Pubkey,
+    pub iou_shares: u64,            // Target shares burned
+    pub amount: u64,                // Underlying returned, principal plus any target fees earned
+    pub principal: u64,
+    pub outstanding_principal: u64,
+}
+
+// Pool dust threshold set event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DustThresholdSet {
+    pub pool: Pubkey,
+    pub dust_threshold: u64,
+}
+
+// Written-off dust settled from the protocol's fees event
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DustReconciled {
+    pub pool: Pubkey,
+    pub amount: u64,                // Protocol fees handed to LPs to cover the dust
+    pub dust_owed: u64,             // Dust still owed, waiting for more protocol fees
+}
@@ -0,0 +1,50 @@
+// Protocol and creator fee shares paid straight to their accounts at
+// settlement, instead of being reserved in the vault for a later collect
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::errors::FlashLoanError;
+use crate::fees::calculate_protocol_fee;
+use crate::state::{Pool, ProtocolConfig};
+
+// Fee shares a settlement pays out rather than reserves
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ForwardedFees {
+    pub protocol_fee: u64,          // Paid to the treasury's token account
+    pub creator_fee: u64,           // Paid to the pool creator's token account
+}
+
+impl ForwardedFees {
+    // Shares of `fee` to forward: the protocol's when the treasury's token
+    // account for the mint is passed, the creator's when the creator's is
+    pub fn resolve(
+        pool: &Pool,
+        protocol_config: Option<&ProtocolConfig>,
+        treasury_account: Option<&TokenAccount>,
+        creator_fee_account: Option<&TokenAccount>,
+        fee: u64,
+        protocol_fee: u64,
+    ) -> Result<Self> {
+        let mut forwarded = ForwardedFees::default();
+        if let Some(account) = treasury_account {
+            let protocol_config = protocol_config.ok_or(FlashLoanError::FeeForwardingAccountMissing)?;
+            require!(
+                account.mint == pool.token_mint && account.owner == protocol_config.treasury,
+                FlashLoanError::InvalidFeeForwardingAccount
+            );
+            forwarded.protocol_fee = protocol_fee;
+        }
+        if let Some(account) = creator_fee_account {
+            require!(
+                account.mint == pool.token_mint && account.owner == pool.creator,
+                FlashLoanError::InvalidFeeForwardingAccount
+            );
+            forwarded.creator_fee = calculate_protocol_fee(fee, pool.creator_fee_bps);
+        }
+        Ok(forwarded)
+    }
+
+    pub fn total(&self) -> u64 {
+        self.protocol_fee + self.creator_fee
+    }
+}
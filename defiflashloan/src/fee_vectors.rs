@@ -0,0 +1,219 @@
+// Fee engine test vectors for out-of-crate SDKs and audits, built with `fee-vectors`
+// Every expected value comes from the functions in `fees.rs`, so the vectors
+// always match the on-chain math. Write them out with
+// `FEE_VECTORS_OUT=fee_vectors.json cargo test --features fee-vectors emit_fee_vectors`
+use std::fmt::Write;
+
+use crate::constants::*;
+use crate::fees::{calculate_dynamic_fee, calculate_protocol_fee, calculate_same_slot_surcharge, calculate_tiered_fee_bps};
+use crate::state::{FeeCurve, FeeTier};
+
+// Loan sizes straddle the kinks and tier thresholds against each vault depth
+const LOAN_AMOUNTS: [u64; 8] = [0, 1, 99_999, 400_000, 400_001, 800_000, 800_001, DEFAULT_MAX_LOAN_AMOUNT];
+const VAULT_LIQUIDITY: [u64; 3] = [0, 500_000, DEFAULT_MAX_LOAN_AMOUNT];
+const FEE_BPS: [u64; 4] = [0, 30, DEFAULT_FEE_BPS, 100];
+const SAME_SLOT_LOANS: [u64; 2] = [0, 3];
+// (reserve_factor_bps, creator_fee_bps) pairs; `set_reserve_factor` keeps
+// their sum within 100%
+const FEE_SPLITS_BPS: [(u16, u16); 5] = [(0, 0), (1_000, 0), (1_000, MAX_CREATOR_FEE_BPS), (8_000, MAX_CREATOR_FEE_BPS), (10_000, 0)];
+const FEE_CURVES: [FeeCurve; 3] = [
+    FeeCurve::DEFAULT,
+    FeeCurve { kink_utilization_bps: 5_000, min_multiplier_bps: 2_000, max_multiplier_bps: MAX_FEE_MULTIPLIER_BPS },
+    FeeCurve { kink_utilization_bps: BPS_DENOMINATOR, min_multiplier_bps: BPS_DENOMINATOR, max_multiplier_bps: BPS_DENOMINATOR },
+];
+// A tiered pool ignores the config `fee_bps`, so it is priced once per loan
+const FEE_TIERS: [FeeTier; 3] = [
+    FeeTier { min_loan_amount: 0, fee_bps: 80 },
+    FeeTier { min_loan_amount: 100_000, fee_bps: DEFAULT_FEE_BPS },
+    FeeTier { min_loan_amount: 800_000, fee_bps: 20 },
+];
+
+// One loan priced by the fee engine
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeeVector {
+    pub loan_amount: u64,
+    pub vault_liquidity: u64,       // Vault balance the loan is drawn from
+    pub fee_bps: u64,               // Config base fee, used when no tier applies
+    pub fee_tiers: Vec<FeeTier>,    // Pool fee tiers, empty when untiered
+    pub fee_curve: FeeCurve,
+    pub same_slot_loans: u64,       // Loans already taken in the slot
+    pub reserve_factor_bps: u16,
+    pub creator_fee_bps: u16,
+    pub base_fee_bps: u64,          // Base fee picked by the tiers
+    pub dynamic_fee: u64,           // Utilization-scaled fee before the surcharge
+    pub surcharge: u64,             // Same-slot surcharge
+    pub fee: u64,                   // Total fee charged to the borrower
+    pub protocol_fee: u64,          // Share of `fee` reserved for the protocol
+    pub creator_fee: u64,           // Share of `fee` reserved for the pool creator
+    pub lp_fee: u64,                // Share of `fee` accruing to LPs
+}
+
+impl FeeVector {
+    // Mirrors a standard loan: tiers pick the base fee, the curve scales it,
+    // and `Pool::accrue_fees` reserves the protocol and creator shares
+    #[allow(clippy::too_many_arguments)]
+    pub fn compute(
+        loan_amount: u64,
+        vault_liquidity: u64,
+        fee_bps: u64,
+        fee_tiers: &[FeeTier],
+        fee_curve: FeeCurve,
+        same_slot_loans: u64,
+        reserve_factor_bps: u16,
+        creator_fee_bps: u16,
+    ) -> Self {
+        let base_fee_bps = calculate_tiered_fee_bps(fee_tiers, loan_amount, fee_bps);
+        let dynamic_fee = calculate_dynamic_fee(loan_amount, vault_liquidity, base_fee_bps, &fee_curve);
+        let surcharge = calculate_same_slot_surcharge(loan_amount, same_slot_loans, DEFAULT_SAME_SLOT_SURCHARGE_BPS);
+        let fee = dynamic_fee + surcharge;
+        let protocol_fee = calculate_protocol_fee(fee, reserve_factor_bps);
+        let creator_fee = calculate_protocol_fee(fee, creator_fee_bps);
+        FeeVector {
+            loan_amount,
+            vault_liquidity,
+            fee_bps,
+            fee_tiers: fee_tiers.to_vec(),
+            fee_curve,
+            same_slot_loans,
+            reserve_factor_bps,
+            creator_fee_bps,
+            base_fee_bps,
+            dynamic_fee,
+            surcharge,
+            fee,
+            protocol_fee,
+            creator_fee,
+            lp_fee: fee - protocol_fee - creator_fee,
+        }
+    }
+}
+
+// Every combination of the input grids, untiered at each config fee and tiered once
+pub fn fee_vectors() -> Vec<FeeVector> {
+    let tier_tables: Vec<(&[FeeTier], u64)> = FEE_BPS
+        .iter()
+        .map(|&fee_bps| (&[][..], fee_bps))
+        .chain(std::iter::once((&FEE_TIERS[..], DEFAULT_FEE_BPS)))
+        .collect();
+    let mut vectors = Vec::new();
+    for &loan_amount in &LOAN_AMOUNTS {
+        for &vault_liquidity in &VAULT_LIQUIDITY {
+            for &(fee_tiers, fee_bps) in &tier_tables {
+                for &fee_curve in &FEE_CURVES {
+                    for &same_slot_loans in &SAME_SLOT_LOANS {
+                        for &(reserve_factor_bps, creator_fee_bps) in &FEE_SPLITS_BPS {
+                            vectors.push(FeeVector::compute(
+                                loan_amount,
+                                vault_liquidity,
+                                fee_bps,
+                                fee_tiers,
+                                fee_curve,
+                                same_slot_loans,
+                                reserve_factor_bps,
+                                creator_fee_bps,
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    vectors
+}
+
+// Vectors as a JSON document; amounts are strings so u64 values survive JS parsers
+pub fn fee_vectors_json() -> String {
+    let mut json = String::from("{\n  \"version\": 3,\n");
+    writeln!(json, "  \"same_slot_surcharge_bps\": \"{}\",", DEFAULT_SAME_SLOT_SURCHARGE_BPS).unwrap();
+    json.push_str("  \"vectors\": [\n");
+    let vectors = fee_vectors();
+    for (i, v) in vectors.iter().enumerate() {
+        let separator = if i + 1 < vectors.len() { "," } else { "" };
+        let tiers = v
+            .fee_tiers
+            .iter()
+            .map(|tier| format!("{{\"min_loan_amount\": \"{}\", \"fee_bps\": \"{}\"}}", tier.min_loan_amount, tier.fee_bps))
+            .collect::<Vec<_>>()
+            .join(", ");
+        writeln!(
+            json,
+            "    {{\"inputs\": {{\"loan_amount\": \"{}\", \"vault_liquidity\": \"{}\", \"fee_bps\": \"{}\", \"fee_tiers\": [{}], \
+             \"fee_curve\": {{\"kink_utilization_bps\": \"{}\", \"min_multiplier_bps\": \"{}\", \"max_multiplier_bps\": \"{}\"}}, \
+             \"same_slot_loans\": \"{}\", \"reserve_factor_bps\": {}, \"creator_fee_bps\": {}}}, \
+             \"expected\": {{\"base_fee_bps\": \"{}\", \"dynamic_fee\": \"{}\", \"surcharge\": \"{}\", \"fee\": \"{}\", \
+             \"protocol_fee\": \"{}\", \"creator_fee\": \"{}\", \"lp_fee\": \"{}\"}}}}{}",
+            v.loan_amount,
+            v.vault_liquidity,
+            v.fee_bps,
+            tiers,
+            v.fee_curve.kink_utilization_bps,
+            v.fee_curve.min_multiplier_bps,
+            v.fee_curve.max_multiplier_bps,
+            v.same_slot_loans,
+            v.reserve_factor_bps,
+            v.creator_fee_bps,
+            v.base_fee_bps,
+            v.dynamic_fee,
+            v.surcharge,
+            v.fee,
+            v.protocol_fee,
+            v.creator_fee,
+            v.lp_fee,
+            separator,
+        )
+        .unwrap();
+    }
+    json.push_str("  ]\n}\n");
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Worked by hand: 80% utilization sits on the default kink, so the base
+    // fee applies unscaled, plus one same-slot surcharge
+    #[test]
+    fn default_curve_vector_at_the_kink() {
+        let v = FeeVector::compute(400_000, 500_000, DEFAULT_FEE_BPS, &[], FeeCurve::DEFAULT, 1, 1_000, MAX_CREATOR_FEE_BPS);
+        assert_eq!(v.base_fee_bps, 50);
+        assert_eq!(v.dynamic_fee, 2_000);
+        assert_eq!(v.surcharge, 400);
+        assert_eq!(v.fee, 2_400);
+        assert_eq!(v.protocol_fee, 240);
+        assert_eq!(v.creator_fee, 480);
+        assert_eq!(v.lp_fee, 1_680);
+    }
+
+    // Worked by hand: the top tier's 20 bps scaled 6.4x at 80% utilization on
+    // the steep curve, with the protocol and creator taking the whole fee
+    #[test]
+    fn tiered_steep_curve_vector() {
+        let v = FeeVector::compute(800_000, 1_000_000, 100, &FEE_TIERS, FEE_CURVES[1], 0, 8_000, MAX_CREATOR_FEE_BPS);
+        assert_eq!(v.base_fee_bps, 20);
+        assert_eq!(v.dynamic_fee, 10_240);
+        assert_eq!(v.surcharge, 0);
+        assert_eq!(v.protocol_fee, 8_192);
+        assert_eq!(v.creator_fee, 2_048);
+        assert_eq!(v.lp_fee, 0);
+    }
+
+    #[test]
+    fn every_vector_splits_its_whole_fee() {
+        let vectors = fee_vectors();
+        assert_eq!(vectors.len(), LOAN_AMOUNTS.len() * VAULT_LIQUIDITY.len() * (FEE_BPS.len() + 1) * FEE_CURVES.len() * SAME_SLOT_LOANS.len() * FEE_SPLITS_BPS.len());
+        for v in &vectors {
+            assert_eq!(v.protocol_fee + v.creator_fee + v.lp_fee, v.fee);
+        }
+    }
+
+    // The emitter: writes the JSON document to `FEE_VECTORS_OUT` when set
+    #[test]
+    fn emit_fee_vectors() {
+        let json = fee_vectors_json();
+        assert_eq!(json.matches("\"inputs\"").count(), fee_vectors().len());
+        if let Ok(path) = std::env::var("FEE_VECTORS_OUT") {
+            std::fs::write(&path, json).unwrap();
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use crate::constants::*;
+use crate::state::{FeeCurve, FeeTier};
+
+// Share of `vault_liquidity` a loan of `loan_amount` draws, capped at 100%
+pub fn calculate_utilization_bps(loan_amount: u64, vault_liquidity: u64) -> u64 {
+    if vault_liquidity == 0 {
+        return BPS_DENOMINATOR;
+    }
+    ((loan_amount as u128 * BPS_DENOMINATOR as u128 / vault_liquidity as u128) as u64).min(BPS_DENOMINATOR)
+}
+
+// Calculate a dynamic fee from the loan's utilization of the vault: the base
+// fee is scaled along `curve`, so loans that drain the vault pay more
+pub fn calculate_dynamic_fee(loan_amount: u64, vault_liquidity: u64, fee_bps: u64, curve: &FeeCurve) -> u64 {
+    let utilization_bps = calculate_utilization_bps(loan_amount, vault_liquidity);
+    let effective_bps = fee_bps as u128 * curve.multiplier_bps(utilization_bps) as u128 / BPS_DENOMINATOR as u128;
+    (loan_amount as u128 * effective_bps / BPS_DENOMINATOR as u128) as u64
+}
+
+// Base fee from a tier table ordered by ascending `min_loan_amount`: the
+// largest tier `loan_amount` reaches, or `fee_bps` when none applies
+pub fn calculate_tiered_fee_bps(tiers: &[FeeTier], loan_amount: u64, fee_bps: u64) -> u64 {
+    tiers
+        .iter()
+        .rev()
+        .find(|tier| loan_amount >= tier.min_loan_amount)
+        .map_or(fee_bps, |tier| tier.fee_bps)
+}
+
+// Surcharge for the n-th additional loan taken within the same slot, at
+// `surcharge_bps` per loan already taken
+pub fn calculate_same_slot_surcharge(loan_amount: u64, same_slot_loans: u64, surcharge_bps: u64) -> u64 {
+    (loan_amount as u128 * surcharge_bps as u128 * same_slot_loans as u128
+        / BPS_DENOMINATOR as u128) as u64
+}
+
+// Protocol share of a loan fee at `reserve_factor_bps`
+pub fn calculate_protocol_fee(fee: u64, reserve_factor_bps: u16) -> u64 {
+    (fee as u128 * reserve_factor_bps as u128 / BPS_DENOMINATOR as u128) as u64
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::FlashLoanError;
+use crate::events::AdminTransferred;
+use crate::state::Pool;
+
+// Context for accepting a proposed pool admin role
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        constraint = pool.pending_admin == pending_admin.key() @ FlashLoanError::NotPendingAdmin
+    )]
+    pub pool: Account<'info, Pool>,
+    pub pending_admin: Signer<'info>,              // Key proposed by the current admin
+}
+
+// Complete the admin rotation; the new key proves it can sign
+pub(crate) fn handler(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let previous_admin = pool.admin;
+    pool.admin = pool.pending_admin;
+    pool.pending_admin = Pubkey::default();
+
+    emit!(AdminTransferred {
+        pool: pool.key(),
+        previous_admin,
+        admin: pool.admin,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::InsuranceClaimResolved;
+use crate::state::{ClaimStatus, InsuranceClaim, InsuranceFund, Pool, ProtocolConfig};
+
+// Context for the protocol admin settling an insurance claim
+#[derive(Accounts)]
+pub struct AdjudicateInsuranceClaim<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump, has_one = admin)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(has_one = token_mint)]
+    pub pool: Account<'info, Pool>,                // Pool the fund insures
+    #[account(mut, has_one = pool, seeds = [INSURANCE_FUND_SEED, pool.key().as_ref()], bump = insurance_fund.bump)]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+    #[account(mut, address = insurance_fund.vault)]
+    pub insurance_vault: InterfaceAccount<'info, TokenAccount>, // Pays the refund
+    #[account(mut, has_one = insurance_fund)]
+    pub insurance_claim: Account<'info, InsuranceClaim>,
+    #[account(
+        mut,
+        token::mint = token_mint,
+        constraint = claimant_account.owner == insurance_claim.claimant @ FlashLoanError::InvalidInsuranceClaim
+    )]
+    pub claimant_account: InterfaceAccount<'info, TokenAccount>, // Claimant's account receiving the refund
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    pub admin: Signer<'info>,                      // Protocol admin
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> AdjudicateInsuranceClaim<'info> {
+    // Context for paying the refund out of the fund vault, signed by the fund PDA
+    pub fn into_transfer_refund_context<'a, 'b, 'c>(
+        &self,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
+            from: self.insurance_vault.to_account_info().clone(),
+            mint: self.token_mint.to_account_info().clone(),
+            to: self.claimant_account.to_account_info().clone(),
+            authority: self.insurance_fund.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+}
+
+// Protocol admin: pay `approved_amount`, at most what was requested, out of
+// the fund to the claimant, or reject the claim with zero
+pub(crate) fn handler(ctx: Context<AdjudicateInsuranceClaim>, approved_amount: u64) -> Result<()> {
+    let claim = &ctx.accounts.insurance_claim;
+    require!(claim.status == ClaimStatus::Pending, FlashLoanError::ClaimAlreadyResolved);
+    require!(approved_amount <= claim.requested_amount, FlashLoanError::InvalidInsuranceClaim);
+    require!(ctx.accounts.insurance_vault.amount >= approved_amount, FlashLoanError::InsufficientFunds);
+
+    if approved_amount > 0 {
+        let fund_seeds = ctx.accounts.insurance_fund.seeds();
+        token_interface::transfer_checked(
+            ctx.accounts.into_transfer_refund_context(&[&fund_seeds[..]]),
+            approved_amount,
+            ctx.accounts.token_mint.decimals,
+        )?;
+    }
+
+    let fund = &mut ctx.accounts.insurance_fund;
+    fund.pending_claims -= 1;
+    fund.total_paid_out += approved_amount;
+    let claim = &mut ctx.accounts.insurance_claim;
+    claim.paid_amount = approved_amount;
+    claim.status = if approved_amount > 0 { ClaimStatus::Paid } else { ClaimStatus::Rejected };
+
+    emit!(InsuranceClaimResolved {
+        insurance_fund: fund.key(),
+        claim: claim.key(),
+        claimant: claim.claimant,
+        requested_amount: claim.requested_amount,
+        paid_amount: approved_amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::events::AdminRecoveryCancelled;
+use crate::state::{AdminRecovery, Pool};
+
+// Context for the pool admin's liveness heartbeat
+#[derive(Accounts)]
+pub struct AdminHeartbeat<'info> {
+    #[account(has_one = admin)]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [ADMIN_RECOVERY_SEED, pool.key().as_ref()],
+        bump = admin_recovery.bump
+    )]
+    pub admin_recovery: Account<'info, AdminRecovery>,
+    pub admin: Signer<'info>,                      // Current pool admin
+}
+
+// Prove the admin key is still in use, cancelling any pending recovery
+pub(crate) fn handler(ctx: Context<AdminHeartbeat>) -> Result<()> {
+    let admin_recovery = &mut ctx.accounts.admin_recovery;
+    admin_recovery.last_heartbeat_epoch = Clock::get()?.epoch;
+
+    if admin_recovery.is_pending() {
+        admin_recovery.recovery_started_at = 0;
+        emit!(AdminRecoveryCancelled {
+            pool: ctx.accounts.pool.key(),
+            admin: ctx.accounts.admin.key(),
+        });
+    }
+
+    Ok(())
+}
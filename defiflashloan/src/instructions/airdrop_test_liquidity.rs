@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::state::Pool;
+
+// Context for minting devnet test tokens straight into a pool vault
+#[derive(Accounts)]
+pub struct AirdropTestLiquidity<'info> {
+    #[account(seeds = [POOL_SEED, token_mint.key().as_ref(), pool.index.as_ref()], bump = pool.bump, has_one = token_mint)]
+    pub pool: Account<'info, Pool>,
+    #[account(mut, address = pool.vault)]
+    pub loan_vault: Account<'info, TokenAccount>,  // Pool vault receiving the test tokens
+    #[account(mut, mint::authority = faucet_authority)]
+    pub token_mint: Account<'info, Mint>,          // Test mint whose authority is the faucet PDA
+    /// CHECK: PDA only signs as the mint authority
+    #[account(seeds = [FAUCET_SEED], bump)]
+    pub faucet_authority: UncheckedAccount<'info>,
+    pub requester: Signer<'info>,                  // Anyone may request test liquidity
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> AirdropTestLiquidity<'info> {
+    // Context for minting into the vault, signed by the faucet PDA
+    pub fn into_mint_to_vault_context<'a, 'b, 'c>(
+        &self,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, MintTo<'info>> {
+        let cpi_accounts = MintTo {
+            mint: self.token_mint.to_account_info().clone(),
+            to: self.loan_vault.to_account_info().clone(),
+            authority: self.faucet_authority.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+}
+
+// Mint up to FAUCET_MAX_AIRDROP test tokens into the pool vault; only mints
+// that handed their authority to the faucet PDA can be airdropped
+pub(crate) fn handler(ctx: Context<AirdropTestLiquidity>, amount: u64) -> Result<()> {
+    require!(amount > 0 && amount <= FAUCET_MAX_AIRDROP, FlashLoanError::InvalidAmount);
+
+    let bump = [ctx.bumps.faucet_authority];
+    let faucet_seeds: [&[u8]; 2] = [FAUCET_SEED, &bump];
+    token::mint_to(
+        ctx.accounts.into_mint_to_vault_context(&[&faucet_seeds[..]]),
+        amount,
+    )?;
+
+    Ok(())
+}
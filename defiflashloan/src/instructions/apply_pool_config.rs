@@ -0,0 +1,180 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::{FeeTiersUpdated, PoolConfigApplied};
+use crate::state::{ConfigField, ConfigHistory, Keeper, KeeperCrank, KeeperRegistry, Pool, PoolConfig, ProtocolConfig};
+
+// Context for activating a queued pool parameter change
+#[derive(Accounts)]
+pub struct ApplyPoolConfig<'info> {
+    #[account(mut)]
+    pub pool: Account<'info, Pool>,                // Holds the fee tiers
+    #[account(
+        mut,
+        seeds = [POOL_CONFIG_SEED, pool.key().as_ref()],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+    #[account(
+        mut,
+        seeds = [CONFIG_HISTORY_SEED, pool.key().as_ref()],
+        bump = config_history.bump
+    )]
+    pub config_history: Account<'info, ConfigHistory>,
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>, // Holds the parameter envelope
+    #[account(mut, seeds = [KEEPER_REGISTRY_SEED], bump = keeper_registry.bump)]
+    pub keeper_registry: Option<Account<'info, KeeperRegistry>>, // Passed with the keeper accounts to pay the keeper running the crank
+    #[account(mut)]
+    pub keeper: Option<Account<'info, Keeper>>,   // Registered keeper credited for the crank
+    pub keeper_authority: Option<Signer<'info>>,   // Keeper's authority
+}
+
+// Crank: activate the queued parameters once the timelock has passed; frozen
+// parameters keep their live values, and the rest must still lie within the
+// protocol's envelope, which may have tightened since they were queued
+pub(crate) fn handler(ctx: Context<ApplyPoolConfig>) -> Result<()> {
+    let config = &mut ctx.accounts.pool_config;
+    let pool = &mut ctx.accounts.pool;
+    let mut pending = config.pending.ok_or(FlashLoanError::NoPendingConfigChange)?;
+    require!(
+        Clock::get()?.unix_timestamp >= config.pending_activates_at,
+        FlashLoanError::ConfigChangeNotReady
+    );
+    pending.params = config.keep_frozen(pending.params);
+    let params = pending.params;
+    ctx.accounts.protocol_config.envelope.check(&params)?;
+
+    let history = &mut ctx.accounts.config_history;
+    let authority = config.pending_queued_by;
+    let slot = Clock::get()?.slot;
+    history.record(ConfigField::FeeBps, config.fee_bps, params.fee_bps, authority, slot);
+    history.record(ConfigField::MaxLoanAmount, config.max_loan_amount, params.max_loan_amount, authority, slot);
+    history.record(ConfigField::LoanCooldown, config.loan_cooldown as u64, params.loan_cooldown as u64, authority, slot);
+    history.record(ConfigField::Timelock, config.timelock as u64, params.timelock as u64, authority, slot);
+
+    history.record(ConfigField::TrustedMinScore, config.trusted_min_score, pending.trusted_min_score, authority, slot);
+    history.record(
+        ConfigField::TrustedMaxLoanAmount,
+        config.trusted_max_loan_amount,
+        pending.trusted_max_loan_amount,
+        authority,
+        slot,
+    );
+    history.record(ConfigField::TrustedFeeBps, config.trusted_fee_bps, pending.trusted_fee_bps, authority, slot);
+    history.record(
+        ConfigField::ReputationMinLoanAmount,
+        config.reputation_min_loan_amount,
+        pending.reputation_min_loan_amount,
+        authority,
+        slot,
+    );
+
+    history.record(
+        ConfigField::MaxInvokeDepth,
+        config.max_invoke_depth as u64,
+        pending.max_invoke_depth as u64,
+        authority,
+        slot,
+    );
+    history.record(
+        ConfigField::SameSlotSurchargeBps,
+        config.same_slot_surcharge_bps,
+        pending.same_slot_surcharge_bps,
+        authority,
+        slot,
+    );
+    history.record(
+        ConfigField::MaxConcurrentBorrowers,
+        config.max_concurrent_borrowers as u64,
+        pending.max_concurrent_borrowers as u64,
+        authority,
+        slot,
+    );
+    history.record(
+        ConfigField::ExperimentFeeBps,
+        config.experiment_fee_bps,
+        pending.experiment_fee_bps,
+        authority,
+        slot,
+    );
+    history.record(
+        ConfigField::ExperimentShareBps,
+        config.experiment_share_bps,
+        pending.experiment_share_bps,
+        authority,
+        slot,
+    );
+    history.record(
+        ConfigField::EmergencyWithdrawDelay,
+        config.emergency_withdraw_delay as u64,
+        pending.emergency_withdraw_delay as u64,
+        authority,
+        slot,
+    );
+    history.record(
+        ConfigField::ProbationLoanAmount,
+        config.probation_loan_amount,
+        pending.probation_loan_amount,
+        authority,
+        slot,
+    );
+    history.record(
+        ConfigField::DurableNoncePolicy,
+        config.reject_durable_nonce as u64,
+        pending.reject_durable_nonce as u64,
+        authority,
+        slot,
+    );
+
+    let (old, new) = (config.fee_curve, pending.fee_curve);
+    history.record(ConfigField::FeeCurveKink, old.kink_utilization_bps, new.kink_utilization_bps, authority, slot);
+    history.record(ConfigField::FeeCurveMinMultiplier, old.min_multiplier_bps, new.min_multiplier_bps, authority, slot);
+    history.record(ConfigField::FeeCurveMaxMultiplier, old.max_multiplier_bps, new.max_multiplier_bps, authority, slot);
+
+    let tiers_changed = pool.fee_tier_count != pending.fee_tier_count
+        || pool.fee_tiers != pending.fee_tiers
+        || pool.fee_tiers_in_usd != pending.fee_tiers_in_usd;
+    history.record(ConfigField::FeeTierCount, pool.fee_tier_count as u64, pending.fee_tier_count as u64, authority, slot);
+    history.record(
+        ConfigField::FeeTiersInUsd,
+        pool.fee_tiers_in_usd as u64,
+        pending.fee_tiers_in_usd as u64,
+        authority,
+        slot,
+    );
+    for (index, (old, new)) in pool.fee_tiers.iter().zip(pending.fee_tiers.iter()).enumerate() {
+        let index = index as u8;
+        history.record(ConfigField::FeeTierMinLoanAmount(index), old.min_loan_amount, new.min_loan_amount, authority, slot);
+        history.record(ConfigField::FeeTierFeeBps(index), old.fee_bps, new.fee_bps, authority, slot);
+    }
+
+    config.apply(pool, &pending);
+    config.pending = None;
+    config.pending_activates_at = 0;
+    config.pending_queued_by = Pubkey::default();
+
+    emit!(PoolConfigApplied {
+        pool: config.pool,
+        fee_bps: params.fee_bps,
+        max_loan_amount: params.max_loan_amount,
+        loan_cooldown: params.loan_cooldown,
+        timelock: params.timelock,
+    });
+    if tiers_changed {
+        emit!(FeeTiersUpdated {
+            pool: config.pool,
+            tier_count: pool.fee_tier_count,
+        });
+    }
+
+    crate::keeper_rewards::credit_crank(
+        ctx.accounts.keeper_registry.as_mut(),
+        ctx.accounts.keeper.as_mut(),
+        ctx.accounts.keeper_authority.as_ref(),
+        KeeperCrank::ApplyPoolConfig,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::PoolConfigQueued;
+use crate::instructions::pause_pool::apply_pause_flags;
+use crate::instructions::set_max_invoke_depth::check_max_invoke_depth;
+use crate::instructions::update_pool_config::check_params;
+use crate::state::{ConfigHistory, Pool, PoolConfig, PoolConfigParams, ProtocolConfig};
+
+// One pool admin change inside `batch_admin`, checked like its own instruction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub enum AdminOp {
+    Pause(u8),                      // As `pause_pool`, takes effect at once
+    Unpause(u8),                    // As `unpause_pool`, takes effect at once
+    UpdatePoolConfig(PoolConfigParams), // As `update_pool_config`, queued
+    SetMaxConcurrentBorrowers(u32), // As `set_max_concurrent_borrowers`, queued
+    SetMaxInvokeDepth(u8),          // As `set_max_invoke_depth`, queued
+    SetProbationLoanAmount(u64),    // As `set_probation_loan_amount`, queued
+}
+
+// Context for applying several pool admin changes at once
+#[derive(Accounts)]
+pub struct BatchAdmin<'info> {
+    #[account(mut, has_one = admin)]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [POOL_CONFIG_SEED, pool.key().as_ref()],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+    #[account(
+        mut,
+        seeds = [CONFIG_HISTORY_SEED, pool.key().as_ref()],
+        bump = config_history.bump
+    )]
+    pub config_history: Account<'info, ConfigHistory>,
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>, // Holds the parameter envelope
+    pub admin: Signer<'info>,                      // Pool admin
+}
+
+// Apply `ops` in order; any failing op fails the whole batch, so a governance
+// execution never lands half-applied. Queued ops are built into one pending
+// change behind a single timelock
+pub(crate) fn handler(ctx: Context<BatchAdmin>, ops: Vec<AdminOp>) -> Result<()> {
+    require!(
+        !ops.is_empty() && ops.len() <= MAX_ADMIN_OPS,
+        FlashLoanError::InvalidAdminBatch
+    );
+
+    let admin = ctx.accounts.admin.key();
+    let mut pending = ctx.accounts.pool_config.queued_or_live(&ctx.accounts.pool);
+    let mut queued = false;
+    for op in ops {
+        match op {
+            AdminOp::Pause(flags) => {
+                let pause_flags = ctx.accounts.pool.pause_flags | flags;
+                apply_pause_flags(&mut ctx.accounts.pool, &mut ctx.accounts.config_history, admin, pause_flags)?;
+            }
+            AdminOp::Unpause(flags) => {
+                let pause_flags = ctx.accounts.pool.pause_flags & !flags;
+                apply_pause_flags(&mut ctx.accounts.pool, &mut ctx.accounts.config_history, admin, pause_flags)?;
+            }
+            AdminOp::UpdatePoolConfig(params) => {
+                check_params(&ctx.accounts.pool_config, &ctx.accounts.protocol_config.envelope, &params)?;
+                pending.params = params;
+                queued = true;
+            }
+            AdminOp::SetMaxConcurrentBorrowers(max_concurrent_borrowers) => {
+                pending.max_concurrent_borrowers = max_concurrent_borrowers;
+                queued = true;
+            }
+            AdminOp::SetMaxInvokeDepth(max_invoke_depth) => {
+                check_max_invoke_depth(max_invoke_depth)?;
+                pending.max_invoke_depth = max_invoke_depth;
+                queued = true;
+            }
+            AdminOp::SetProbationLoanAmount(probation_loan_amount) => {
+                pending.probation_loan_amount = probation_loan_amount;
+                queued = true;
+            }
+        }
+    }
+
+    if queued {
+        let config = &mut ctx.accounts.pool_config;
+        let activates_at = config.queue(pending, admin, Clock::get()?.unix_timestamp);
+        emit!(PoolConfigQueued {
+            pool: config.pool,
+            fee_bps: pending.params.fee_bps,
+            max_loan_amount: pending.params.max_loan_amount,
+            loan_cooldown: pending.params.loan_cooldown,
+            timelock: pending.params.timelock,
+            activates_at,
+        });
+    }
+    Ok(())
+}
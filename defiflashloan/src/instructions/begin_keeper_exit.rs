@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::KeeperExitRequested;
+use crate::state::{Keeper, KeeperRegistry};
+
+// Context for a keeper starting to unbond
+#[derive(Accounts)]
+pub struct BeginKeeperExit<'info> {
+    #[account(seeds = [KEEPER_REGISTRY_SEED], bump = keeper_registry.bump)]
+    pub keeper_registry: Account<'info, KeeperRegistry>,
+    #[account(mut, seeds = [KEEPER_SEED, authority.key().as_ref()], bump = keeper.bump, has_one = authority)]
+    pub keeper: Account<'info, Keeper>,
+    pub authority: Signer<'info>,
+}
+
+// Stop being paid for cranks and start the unbonding period; the bond stays
+// slashable until it is withdrawn
+pub(crate) fn handler(ctx: Context<BeginKeeperExit>) -> Result<()> {
+    let keeper = &mut ctx.accounts.keeper;
+    require!(keeper.staked_amount > 0, FlashLoanError::KeeperStakeTooLow);
+    require!(keeper.exit_requested_at == 0, FlashLoanError::KeeperExiting);
+
+    keeper.exit_requested_at = Clock::get()?.unix_timestamp;
+
+    emit!(KeeperExitRequested {
+        keeper: keeper.key(),
+        authority: keeper.authority,
+        withdrawable_at: keeper.exit_requested_at + ctx.accounts.keeper_registry.unbonding_period,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::WindDownStarted;
+use crate::instructions::pause_pool::{set_pause_flags, PausePool};
+
+// Start the pool's end of life: borrows and deposits stop and cannot be
+// unpaused, withdrawals reopen so LPs can redeem, and fees stay collectable;
+// `close_pool` then succeeds once the vault has been drained
+pub(crate) fn handler(ctx: Context<PausePool>) -> Result<()> {
+    require!(!ctx.accounts.pool.is_winding_down(), FlashLoanError::PoolWindingDown);
+
+    let started_at = Clock::get()?.unix_timestamp;
+    ctx.accounts.pool.wind_down_at = started_at;
+    let pool = ctx.accounts.pool.key();
+    let ops_tag = ctx.accounts.pool.ops_tag;
+    let pause_flags = (ctx.accounts.pool.pause_flags | WIND_DOWN_PAUSED) & !PAUSE_WITHDRAW;
+    set_pause_flags(ctx, pause_flags)?;
+
+    emit!(WindDownStarted {
+        pool,
+        started_at,
+        ops_tag,
+    });
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::state::PreAuth;
+
+// Context for cancelling a pre-authorized loan
+#[derive(Accounts)]
+pub struct CancelPreAuth<'info> {
+    #[account(
+        mut,
+        close = borrower,
+        has_one = borrower @ FlashLoanError::PreAuthMismatch,
+        seeds = [PRE_AUTH_SEED, pre_auth.pool.as_ref(), borrower.key().as_ref(), pre_auth.nonce.as_ref()],
+        bump = pre_auth.bump
+    )]
+    pub pre_auth: Account<'info, PreAuth>,
+    #[account(mut)]
+    pub borrower: Signer<'info>,                   // Borrower that created the pre-authorization
+}
+
+// Cancel a pre-authorization, executed or not, and reclaim its rent
+pub(crate) fn handler(_ctx: Context<CancelPreAuth>) -> Result<()> {
+    Ok(())
+}
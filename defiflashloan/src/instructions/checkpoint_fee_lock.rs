@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::events::FeeLockCheckpointed;
+use crate::state::{FeeLocker, LockerPosition};
+
+// Context for decaying a fee lock position to its remaining lock time
+#[derive(Accounts)]
+pub struct CheckpointFeeLock<'info> {
+    /// CHECK: Only keys the fee locker PDA
+    pub pool: UncheckedAccount<'info>,             // Pool the fee locker streams from
+    #[account(mut, seeds = [FEE_LOCKER_SEED, pool.key().as_ref()], bump = fee_locker.bump)]
+    pub fee_locker: Account<'info, FeeLocker>,
+    #[account(
+        mut,
+        has_one = fee_locker,
+        has_one = owner,
+        seeds = [LOCKER_POSITION_SEED, fee_locker.key().as_ref(), owner.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, LockerPosition>,
+    /// CHECK: Only keys the position PDA
+    pub owner: UncheckedAccount<'info>,            // Owner of the position being reweighted
+}
+
+// Permissionless: settle the position's rewards at its current weight, then
+// reweight it for the lock time it has left, so positions stop earning full
+// weight as their lock runs down and nothing once it has expired
+pub(crate) fn handler(ctx: Context<CheckpointFeeLock>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    let fee_locker = &mut ctx.accounts.fee_locker;
+    let position = &mut ctx.accounts.position;
+    position.settle(fee_locker.reward_per_weight);
+    fee_locker.total_weight -= position.weight;
+    position.reweight(now, fee_locker.reward_per_weight);
+    fee_locker.total_weight += position.weight;
+
+    emit!(FeeLockCheckpointed {
+        fee_locker: position.fee_locker,
+        owner: position.owner,
+        weight: position.weight,
+        total_weight: fee_locker.total_weight,
+    });
+
+    Ok(())
+}
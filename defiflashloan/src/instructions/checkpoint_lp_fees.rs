@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::LpFeeEpochClosed;
+use crate::state::{Keeper, KeeperCrank, KeeperRegistry, LpFeeAccumulator, Pool};
+
+// Context for closing a pool's time-weighted LP fee epoch
+#[derive(Accounts)]
+pub struct CheckpointLpFees<'info> {
+    #[account(seeds = [POOL_SEED, pool.token_mint.as_ref(), pool.index.as_ref()], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+    #[account(mut, has_one = pool, seeds = [LP_FEE_ACCUMULATOR_SEED, pool.key().as_ref()], bump = lp_fee_accumulator.bump)]
+    pub lp_fee_accumulator: Account<'info, LpFeeAccumulator>,
+    #[account(mut, seeds = [KEEPER_REGISTRY_SEED], bump = keeper_registry.bump)]
+    pub keeper_registry: Option<Account<'info, KeeperRegistry>>, // Passed with the keeper accounts to pay the keeper running the crank
+    #[account(mut)]
+    pub keeper: Option<Account<'info, Keeper>>,   // Registered keeper credited for the crank
+    pub keeper_authority: Option<Signer<'info>>,   // Keeper's authority
+}
+
+// Permissionless, once per epoch: spread the LP fees withheld since the last
+// checkpoint over the share-seconds LP positions held through the epoch
+pub(crate) fn handler(ctx: Context<CheckpointLpFees>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let accumulator = &mut ctx.accounts.lp_fee_accumulator;
+    require!(
+        now >= accumulator.epoch_started_at + accumulator.epoch_duration,
+        FlashLoanError::EpochNotElapsed
+    );
+
+    let fees = ctx.accounts.pool.lp_fees_withheld.saturating_sub(accumulator.fees_unclaimed);
+    let epoch = accumulator.epoch;
+    accumulator.accrue(now);
+    let share_seconds = accumulator.share_seconds;
+    let distributed = accumulator.close_epoch(now, fees);
+
+    emit!(LpFeeEpochClosed {
+        pool: ctx.accounts.pool.key(),
+        epoch,
+        fees: distributed,
+        share_seconds,
+    });
+
+    crate::keeper_rewards::credit_crank(
+        ctx.accounts.keeper_registry.as_mut(),
+        ctx.accounts.keeper.as_mut(),
+        ctx.accounts.keeper_authority.as_ref(),
+        KeeperCrank::CheckpointLpFees,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::events::FeeRewardsClaimed;
+use crate::state::{FeeLocker, LockerPosition};
+
+// Context for claiming streamed protocol fees
+#[derive(Accounts)]
+pub struct ClaimFeeRewards<'info> {
+    /// CHECK: Only keys the fee locker PDA
+    pub pool: UncheckedAccount<'info>,             // Pool the fee locker streams from
+    #[account(seeds = [FEE_LOCKER_SEED, pool.key().as_ref()], bump = fee_locker.bump)]
+    pub fee_locker: Account<'info, FeeLocker>,
+    #[account(
+        mut,
+        has_one = owner,
+        has_one = fee_locker,
+        seeds = [LOCKER_POSITION_SEED, fee_locker.key().as_ref(), owner.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, LockerPosition>,
+    #[account(mut, address = fee_locker.reward_vault)]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>, // Holds streamed fees until claimed
+    #[account(mut, token::mint = reward_vault.mint)]
+    pub destination_account: InterfaceAccount<'info, TokenAccount>, // Receives the claimed fees
+    #[account(address = reward_vault.mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>, // Pool mint the fees are paid in
+    pub owner: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>, // Token or Token-2022 program
+}
+
+impl<'info> ClaimFeeRewards<'info> {
+    // Context for paying out of the reward vault, signed by the locker PDA
+    pub fn into_transfer_from_rewards_context<'a, 'b, 'c>(
+        &self,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
+            from: self.reward_vault.to_account_info().clone(),
+            mint: self.token_mint.to_account_info().clone(),
+            to: self.destination_account.to_account_info().clone(),
+            authority: self.fee_locker.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+}
+
+// Pay out every fee reward the position has earned so far
+pub(crate) fn handler(ctx: Context<ClaimFeeRewards>) -> Result<()> {
+    let reward_per_weight = ctx.accounts.fee_locker.reward_per_weight;
+    let position = &mut ctx.accounts.position;
+    position.settle(reward_per_weight);
+    let amount = position.pending_rewards;
+    if amount == 0 {
+        return Ok(());
+    }
+    position.pending_rewards = 0;
+
+    let locker_seeds = ctx.accounts.fee_locker.seeds();
+    token_interface::transfer_checked(
+        ctx.accounts.into_transfer_from_rewards_context(&[&locker_seeds[..]]),
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    emit!(FeeRewardsClaimed {
+        fee_locker: ctx.accounts.fee_locker.key(),
+        owner: ctx.accounts.owner.key(),
+        amount,
+    });
+
+    Ok(())
+}
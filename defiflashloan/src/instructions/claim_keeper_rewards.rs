@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::events::KeeperRewardsClaimed;
+use crate::state::{Keeper, KeeperRegistry};
+
+// Context for claiming a keeper's crank rewards
+#[derive(Accounts)]
+pub struct ClaimKeeperRewards<'info> {
+    #[account(mut, seeds = [KEEPER_REGISTRY_SEED], bump = keeper_registry.bump)]
+    pub keeper_registry: Account<'info, KeeperRegistry>,
+    #[account(mut, seeds = [KEEPER_SEED, authority.key().as_ref()], bump = keeper.bump, has_one = authority)]
+    pub keeper: Account<'info, Keeper>,
+    #[account(mut, address = keeper_registry.reward_vault)]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>, // Pays the rewards
+    #[account(mut, token::mint = stake_mint)]
+    pub destination_account: InterfaceAccount<'info, TokenAccount>, // Receives the rewards
+    #[account(address = keeper_registry.stake_mint)]
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+    pub authority: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ClaimKeeperRewards<'info> {
+    // Context for paying out of the reward vault, signed by the registry PDA
+    pub fn into_transfer_from_rewards_context<'a, 'b, 'c>(
+        &self,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
+            from: self.reward_vault.to_account_info().clone(),
+            mint: self.stake_mint.to_account_info().clone(),
+            to: self.destination_account.to_account_info().clone(),
+            authority: self.keeper_registry.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+}
+
+// Pay out as much of the keeper's credited rewards as the reward vault holds;
+// the rest stays pending until the vault is topped up
+pub(crate) fn handler(ctx: Context<ClaimKeeperRewards>) -> Result<()> {
+    let amount = ctx.accounts.keeper.pending_rewards.min(ctx.accounts.reward_vault.amount);
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let registry_seeds = ctx.accounts.keeper_registry.seeds();
+    token_interface::transfer_checked(
+        ctx.accounts.into_transfer_from_rewards_context(&[&registry_seeds[..]]),
+        amount,
+        ctx.accounts.stake_mint.decimals,
+    )?;
+
+    ctx.accounts.keeper_registry.rewards_owed -= amount;
+    let keeper = &mut ctx.accounts.keeper;
+    keeper.pending_rewards -= amount;
+
+    emit!(KeeperRewardsClaimed {
+        keeper: keeper.key(),
+        authority: keeper.authority,
+        amount,
+        pending_rewards: keeper.pending_rewards,
+    });
+
+    Ok(())
+}
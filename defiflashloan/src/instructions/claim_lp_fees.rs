@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::events::LpFeesClaimed;
+use crate::state::{LpFeeAccumulator, LpPosition, Pool};
+
+// Context for claiming an LP position's time-weighted fees
+#[derive(Accounts)]
+pub struct ClaimLpFees<'info> {
+    #[account(
+        mut,
+        seeds = [POOL_SEED, pool.token_mint.as_ref(), pool.index.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(mut, address = pool.vault)]
+    pub loan_vault: InterfaceAccount<'info, TokenAccount>, // Pool vault holding the withheld fees
+    #[account(mut, has_one = pool, seeds = [LP_FEE_ACCUMULATOR_SEED, pool.key().as_ref()], bump = lp_fee_accumulator.bump)]
+    pub lp_fee_accumulator: Account<'info, LpFeeAccumulator>,
+    #[account(
+        mut,
+        has_one = owner,
+        has_one = pool,
+        seeds = [LP_POSITION_SEED, pool.key().as_ref(), owner.key().as_ref()],
+        bump = lp_position.bump
+    )]
+    pub lp_position: Account<'info, LpPosition>,
+    #[account(mut, token::mint = pool.token_mint)]
+    pub destination_account: InterfaceAccount<'info, TokenAccount>, // Receives the claimed fees
+    #[account(address = pool.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>, // Pool mint the fees are paid in
+    pub owner: Signer<'info>,                      // LP owning the position
+    pub token_program: Interface<'info, TokenInterface>, // Token or Token-2022 program
+}
+
+impl<'info> ClaimLpFees<'info> {
+    // Context for paying the fees out of the vault, signed by the pool PDA
+    pub fn into_transfer_fees_context<'a, 'b, 'c>(
+        &self,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
+            from: self.loan_vault.to_account_info().clone(),
+            mint: self.token_mint.to_account_info().clone(),
+            to: self.destination_account.to_account_info().clone(),
+            authority: self.pool.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+}
+
+// Pay out every time-weighted fee the position has earned in closed epochs
+pub(crate) fn handler(ctx: Context<ClaimLpFees>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let accumulator = &mut ctx.accounts.lp_fee_accumulator;
+    let position = &mut ctx.accounts.lp_position;
+    accumulator.settle(position, now);
+    // Rounding in the epoch split never lets claims exceed what was spread
+    let amount = position.pending_lp_fees.min(accumulator.fees_unclaimed);
+    if amount == 0 {
+        return Ok(());
+    }
+    position.pending_lp_fees -= amount;
+    position.total_withdrawn += amount;
+    accumulator.fees_unclaimed -= amount;
+    ctx.accounts.pool.lp_fees_withheld -= amount;
+
+    let pool_seeds = ctx.accounts.pool.seeds();
+    token_interface::transfer_checked(
+        ctx.accounts.into_transfer_fees_context(&[&pool_seeds[..]]),
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    emit!(LpFeesClaimed {
+        pool: ctx.accounts.pool.key(),
+        lp: ctx.accounts.owner.key(),
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,161 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer as SystemTransfer};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::events::{PoolConfigCloned, PoolInitialized};
+use crate::state::{ConfigHistory, Pool, PoolConfig, ProtocolConfig, Registry, RegistryEntry, RegistryPage};
+
+// Context for creating a pool like `initialize_pool` does, with the parameters
+// and roles of an existing pool the signer administers
+#[derive(Accounts)]
+pub struct ClonePoolConfig<'info> {
+    #[account(seeds = [POOL_SEED, source_pool.token_mint.as_ref(), source_pool.index.as_ref()], bump = source_pool.bump, has_one = admin)]
+    pub source_pool: Account<'info, Pool>,         // Pool to copy, administered by the signer
+    #[account(seeds = [POOL_CONFIG_SEED, source_pool.key().as_ref()], bump = source_pool_config.bump)]
+    pub source_pool_config: Account<'info, PoolConfig>,
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>, // Sets the creation deposit
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = Registry::LEN,
+        seeds = [REGISTRY_SEED],
+        bump
+    )]
+    pub registry: Account<'info, Registry>,        // Global pool registry, created with the first pool
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = RegistryPage::LEN,
+        seeds = [REGISTRY_PAGE_SEED, registry.open_page().to_le_bytes().as_ref()],
+        bump
+    )]
+    pub registry_page: Account<'info, RegistryPage>, // Registry page the pool is listed on
+    #[account(
+        init,
+        payer = admin,
+        space = Pool::LEN,
+        seeds = [POOL_SEED, token_mint.key().as_ref(), registry.next_slot.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        init,
+        payer = admin,
+        space = PoolConfig::LEN,
+        seeds = [POOL_CONFIG_SEED, pool.key().as_ref()],
+        bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,   // Risk parameters, copied from the source pool
+    #[account(
+        init,
+        payer = admin,
+        space = ConfigHistory::LEN,
+        seeds = [CONFIG_HISTORY_SEED, pool.key().as_ref()],
+        bump
+    )]
+    pub config_history: Account<'info, ConfigHistory>, // Log of later parameter changes
+    #[account(
+        init,
+        payer = admin,
+        associated_token::mint = token_mint,
+        associated_token::authority = pool,
+        associated_token::token_program = token_program
+    )]
+    pub loan_vault: InterfaceAccount<'info, TokenAccount>, // Vault owned by the pool PDA
+    #[account(
+        init,
+        payer = admin,
+        seeds = [SHARE_MINT_SEED, pool.key().as_ref()],
+        bump,
+        mint::decimals = token_mint.decimals,
+        mint::authority = pool,
+        mint::token_program = token_program
+    )]
+    pub share_mint: InterfaceAccount<'info, Mint>, // LP share mint controlled by the pool PDA
+    pub token_mint: InterfaceAccount<'info, Mint>, // Mint lent out by the pool
+    #[account(mut)]
+    pub admin: Signer<'info>,                      // Admin of both pools, pays for the accounts
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClonePoolConfig<'info> {
+    // Context for locking the creation deposit in the pool account
+    pub fn into_deposit_context(&self) -> CpiContext<'_, '_, '_, 'info, SystemTransfer<'info>> {
+        let cpi_accounts = SystemTransfer {
+            from: self.admin.to_account_info().clone(),
+            to: self.pool.to_account_info().clone(),
+        };
+        CpiContext::new(self.system_program.to_account_info().clone(), cpi_accounts)
+    }
+}
+
+// Create a pool for `token_mint`, which may be the source pool's own mint,
+// that starts with the source pool's fee curve, fee tiers, caps and roles
+// instead of the defaults; the signer locks the creation deposit and becomes
+// the creator as with `initialize_pool`, at the source pool's creator fee
+pub(crate) fn handler(ctx: Context<ClonePoolConfig>) -> Result<()> {
+    let deposit = ctx.accounts.protocol_config.pool_creation_deposit;
+    if deposit > 0 {
+        system_program::transfer(ctx.accounts.into_deposit_context(), deposit)?;
+    }
+
+    ctx.accounts.pool_config.init_from(
+        &ctx.accounts.source_pool_config,
+        ctx.accounts.pool.key(),
+        ctx.bumps.pool_config,
+    );
+    ctx.accounts.config_history.pool = ctx.accounts.pool.key();
+    ctx.accounts.config_history.bump = ctx.bumps.config_history;
+
+    // List the pool in the registry's open page
+    let registry = &mut ctx.accounts.registry;
+    let registry_page = &mut ctx.accounts.registry_page;
+    registry_page.index = registry.open_page();
+    registry_page.bump = ctx.bumps.registry_page;
+    registry_page.entries.push(RegistryEntry {
+        token_mint: ctx.accounts.token_mint.key(),
+        pool: ctx.accounts.pool.key(),
+    });
+    let index = registry.next_slot;
+    registry.pool_count += 1;
+    registry.next_slot += 1;
+    registry.bump = ctx.bumps.registry;
+
+    let source = &ctx.accounts.source_pool;
+    let pool = &mut ctx.accounts.pool;
+    pool.admin = source.admin;
+    pool.quote_signer = source.quote_signer;
+    pool.ops_tag = source.ops_tag;
+    pool.reserve_factor_bps = source.reserve_factor_bps;
+    pool.fee_tiers = source.fee_tiers;
+    pool.fee_tier_count = source.fee_tier_count;
+    pool.fee_tiers_in_usd = source.fee_tiers_in_usd;
+    pool.token_mint = ctx.accounts.token_mint.key();
+    pool.index = index.to_le_bytes();
+    pool.vault = ctx.accounts.loan_vault.key();
+    pool.share_mint = ctx.accounts.share_mint.key();
+    pool.creator = ctx.accounts.admin.key();
+    pool.creator_fee_bps = source.creator_fee_bps;
+    pool.bump = ctx.bumps.pool;
+
+    emit!(PoolInitialized {
+        pool: pool.key(),
+        admin: pool.admin,
+        token_mint: pool.token_mint,
+        vault: pool.vault,
+        share_mint: pool.share_mint,
+        index,
+        creator_fee_bps: pool.creator_fee_bps,
+    });
+    emit!(PoolConfigCloned {
+        pool: pool.key(),
+        source_pool: source.key(),
+    });
+
+    Ok(())
+}
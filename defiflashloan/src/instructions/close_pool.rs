@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::PoolClosed;
+use crate::state::{ConfigHistory, Pool, PoolConfig, Registry, RegistryPage};
+
+// Context for closing an empty pool and delisting it from the registry
+#[derive(Accounts)]
+#[instruction(page: u32)]
+pub struct ClosePool<'info> {
+    #[account(
+        mut,
+        seeds = [POOL_SEED, pool.token_mint.as_ref(), pool.index.as_ref()],
+        bump = pool.bump,
+        has_one = admin,
+        close = admin
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [POOL_CONFIG_SEED, pool.key().as_ref()],
+        bump = pool_config.bump,
+        close = admin
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+    #[account(
+        mut,
+        seeds = [CONFIG_HISTORY_SEED, pool.key().as_ref()],
+        bump = config_history.bump,
+        close = admin
+    )]
+    pub config_history: Account<'info, ConfigHistory>,
+    #[account(mut, address = pool.vault)]
+    pub loan_vault: InterfaceAccount<'info, TokenAccount>, // Empty vault, closed to the admin
+    #[account(address = pool.share_mint)]
+    pub share_mint: InterfaceAccount<'info, Mint>, // LP share mint, must have no supply left
+    #[account(mut, seeds = [REGISTRY_SEED], bump = registry.bump)]
+    pub registry: Account<'info, Registry>,
+    #[account(
+        mut,
+        seeds = [REGISTRY_PAGE_SEED, page.to_le_bytes().as_ref()],
+        bump = registry_page.bump
+    )]
+    pub registry_page: Account<'info, RegistryPage>, // Page listing the pool
+    #[account(mut)]
+    pub admin: Signer<'info>,                      // Pool admin, receives the rent
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ClosePool<'info> {
+    // Context for closing the vault, signed by the pool PDA
+    pub fn into_close_vault_context<'a, 'b, 'c>(
+        &self,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, CloseAccount<'info>> {
+        let cpi_accounts = CloseAccount {
+            account: self.loan_vault.to_account_info().clone(),
+            destination: self.admin.to_account_info().clone(),
+            authority: self.pool.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+}
+
+// Close a pool with no liquidity, shares or open loan, returning the rent of
+// its accounts to the admin and removing it from registry page `page`; for a
+// winding-down pool this is once LPs have redeemed every share and the
+// protocol and creator fees have been collected
+pub(crate) fn handler(ctx: Context<ClosePool>, page: u32) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+    require!(
+        ctx.accounts.loan_vault.amount == 0
+            && ctx.accounts.share_mint.supply == 0
+            && pool.open_loan_amount == 0,
+        FlashLoanError::PoolNotEmpty
+    );
+
+    let pool_seeds = pool.seeds();
+    token_interface::close_account(ctx.accounts.into_close_vault_context(&[&pool_seeds[..]]))?;
+
+    ctx.accounts.registry_page.remove(&pool.key())?;
+    ctx.accounts.registry.pool_count -= 1;
+
+    emit!(PoolClosed {
+        pool: pool.key(),
+        token_mint: pool.token_mint,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::events::CreatorFeesCollected;
+use crate::state::Pool;
+
+// Context for sweeping creator fees to the pool creator
+#[derive(Accounts)]
+pub struct CollectCreatorFees<'info> {
+    #[account(
+        mut,
+        seeds = [POOL_SEED, pool.token_mint.as_ref(), pool.index.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(mut, address = pool.vault)]
+    pub loan_vault: InterfaceAccount<'info, TokenAccount>, // Pool vault holding the accrued fees
+    #[account(
+        mut,
+        token::mint = pool.token_mint,
+        token::authority = pool.creator
+    )]
+    pub creator_account: InterfaceAccount<'info, TokenAccount>, // Creator token account for the pool mint
+    #[account(address = pool.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>, // Mint lent out by the pool
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> CollectCreatorFees<'info> {
+    // Context for paying the creator out of the vault, signed by the pool PDA
+    pub fn into_transfer_to_creator_context<'a, 'b, 'c>(
+        &self,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
+            from: self.loan_vault.to_account_info().clone(),
+            mint: self.token_mint.to_account_info().clone(),
+            to: self.creator_account.to_account_info().clone(),
+            authority: self.pool.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+}
+
+// Permissionless crank: sweep the creator's accrued fee share to their token account
+pub(crate) fn handler(ctx: Context<CollectCreatorFees>) -> Result<()> {
+    let amount = ctx
+        .accounts
+        .pool
+        .creator_fees_accrued
+        .min(ctx.accounts.loan_vault.amount);
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let pool_seeds = ctx.accounts.pool.seeds();
+    token_interface::transfer_checked(
+        ctx.accounts.into_transfer_to_creator_context(&[&pool_seeds[..]]),
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    ctx.accounts.pool.creator_fees_accrued -= amount;
+
+    emit!(CreatorFeesCollected {
+        pool: ctx.accounts.pool.key(),
+        creator: ctx.accounts.pool.creator,
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::{BuybackFunded, ProtocolFeesCollected};
+use crate::state::{Buyback, Pool, ProtocolConfig};
+
+// Context for sweeping protocol fees to the treasury
+#[derive(Accounts)]
+pub struct CollectProtocolFees<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump, has_one = admin)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [POOL_SEED, pool.token_mint.as_ref(), pool.index.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(mut, address = pool.vault)]
+    pub loan_vault: InterfaceAccount<'info, TokenAccount>, // Pool vault holding the accrued fees
+    #[account(
+        mut,
+        token::mint = pool.token_mint,
+        token::authority = protocol_config.treasury
+    )]
+    pub treasury_account: InterfaceAccount<'info, TokenAccount>, // Treasury token account for the pool mint
+    pub admin: Signer<'info>,                      // Protocol admin
+    #[account(address = pool.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>, // Mint lent out by the pool
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(mut, seeds = [BUYBACK_SEED, pool.token_mint.as_ref()], bump = buyback.bump)]
+    pub buyback: Option<Account<'info, Buyback>>, // The pool mint's buyback, to route its share there
+    #[account(mut)]
+    pub buyback_vault: Option<InterfaceAccount<'info, TokenAccount>>, // The buyback's fee vault, with `buyback`
+}
+
+impl<'info> CollectProtocolFees<'info> {
+    // Context for paying the treasury, or the buyback, out of the vault, signed by the pool PDA
+    pub fn into_transfer_to_treasury_context<'a, 'b, 'c>(
+        &self,
+        to: AccountInfo<'info>,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
+            from: self.loan_vault.to_account_info().clone(),
+            mint: self.token_mint.to_account_info().clone(),
+            to,
+            authority: self.pool.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+}
+
+// Sweep the protocol's accrued share of loan fees to the treasury; with the
+// pool mint's buyback passed, its configured share goes to the buyback instead
+pub(crate) fn handler(ctx: Context<CollectProtocolFees>) -> Result<()> {
+    let amount = ctx
+        .accounts
+        .pool
+        .protocol_fees_accrued
+        .min(ctx.accounts.loan_vault.amount);
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let buyback_share = match (ctx.accounts.buyback.as_ref(), ctx.accounts.buyback_vault.as_ref()) {
+        (Some(buyback), Some(vault)) => {
+            require_keys_eq!(vault.key(), buyback.fee_vault, FlashLoanError::InvalidBuybackConfig);
+            buyback.collect_share(amount)
+        }
+        (None, None) => 0,
+        _ => return err!(FlashLoanError::InvalidBuybackConfig),
+    };
+
+    let pool_seeds = ctx.accounts.pool.seeds();
+    let treasury_amount = amount - buyback_share;
+    if treasury_amount > 0 {
+        token_interface::transfer_checked(
+            ctx.accounts.into_transfer_to_treasury_context(ctx.accounts.treasury_account.to_account_info(), &[&pool_seeds[..]]),
+            treasury_amount,
+            ctx.accounts.token_mint.decimals,
+        )?;
+    }
+    if let Some(vault) = ctx.accounts.buyback_vault.as_ref().filter(|_| buyback_share > 0) {
+        token_interface::transfer_checked(
+            ctx.accounts.into_transfer_to_treasury_context(vault.to_account_info(), &[&pool_seeds[..]]),
+            buyback_share,
+            ctx.accounts.token_mint.decimals,
+        )?;
+        emit!(BuybackFunded {
+            pool: ctx.accounts.pool.key(),
+            fee_mint: ctx.accounts.pool.token_mint,
+            amount: buyback_share,
+        });
+    }
+
+    ctx.accounts.pool.protocol_fees_accrued -= amount;
+
+    emit!(ProtocolFeesCollected {
+        pool: ctx.accounts.pool.key(),
+        treasury_account: ctx.accounts.treasury_account.key(),
+        amount: treasury_amount,
+    });
+
+    Ok(())
+}
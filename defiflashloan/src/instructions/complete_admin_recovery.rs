@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::AdminTransferred;
+use crate::state::{AdminRecovery, Pool};
+
+// Context for completing a dead-man's-switch recovery
+#[derive(Accounts)]
+pub struct CompleteAdminRecovery<'info> {
+    #[account(mut)]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [ADMIN_RECOVERY_SEED, pool.key().as_ref()],
+        bump = admin_recovery.bump,
+        has_one = recovery_key @ FlashLoanError::NotRecoveryKey
+    )]
+    pub admin_recovery: Account<'info, AdminRecovery>,
+    pub recovery_key: Signer<'info>,               // Designated recovery key, becomes the admin
+}
+
+// Hand the admin role to the recovery key once the delay window has passed
+// without a heartbeat
+pub(crate) fn handler(ctx: Context<CompleteAdminRecovery>) -> Result<()> {
+    let clock = Clock::get()?;
+    let admin_recovery = &mut ctx.accounts.admin_recovery;
+    require!(admin_recovery.is_pending(), FlashLoanError::RecoveryNotStarted);
+    require!(
+        clock.unix_timestamp >= admin_recovery.recovery_started_at + RECOVERY_DELAY,
+        FlashLoanError::RecoveryDelayNotElapsed
+    );
+
+    let pool = &mut ctx.accounts.pool;
+    let previous_admin = pool.admin;
+    pool.admin = admin_recovery.recovery_key;
+    pool.pending_admin = Pubkey::default();
+
+    // The new admin starts with a fresh heartbeat
+    admin_recovery.recovery_started_at = 0;
+    admin_recovery.last_heartbeat_epoch = clock.epoch;
+
+    emit!(AdminTransferred {
+        pool: pool.key(),
+        previous_admin,
+        admin: pool.admin,
+    });
+
+    Ok(())
+}
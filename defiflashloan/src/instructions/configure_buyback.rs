@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::BuybackConfigured;
+use crate::state::{Buyback, ProtocolConfig};
+
+// Context for setting up the buyback of a fee mint
+#[derive(Accounts)]
+pub struct ConfigureBuyback<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump, has_one = admin)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = Buyback::LEN,
+        seeds = [BUYBACK_SEED, fee_mint.key().as_ref()],
+        bump
+    )]
+    pub buyback: Account<'info, Buyback>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = fee_mint,
+        associated_token::authority = buyback,
+        associated_token::token_program = fee_token_program
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>, // Holds the fees routed to the buyback
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = protocol_mint,
+        associated_token::authority = buyback,
+        associated_token::token_program = protocol_token_program
+    )]
+    pub protocol_vault: InterfaceAccount<'info, TokenAccount>, // Receives swapped protocol tokens until burned
+    pub fee_mint: InterfaceAccount<'info, Mint>,   // Mint the protocol fees are collected in
+    pub protocol_mint: InterfaceAccount<'info, Mint>, // Protocol token to buy and burn
+    /// CHECK: Only recorded; `execute_buyback` refuses to swap through any other program
+    pub amm_program: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub admin: Signer<'info>,                      // Protocol admin
+    pub fee_token_program: Interface<'info, TokenInterface>,
+    pub protocol_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+// Create or retune the fee mint's buyback; the protocol mint is fixed once set,
+// since the protocol vault is its associated account
+pub(crate) fn handler(
+    ctx: Context<ConfigureBuyback>,
+    collect_share_bps: u16,
+    max_slippage_bps: u16,
+    keeper_reward_bps: u16,
+    min_interval: i64,
+) -> Result<()> {
+    require!(ctx.accounts.amm_program.executable, FlashLoanError::InvalidBuybackConfig);
+    require!(
+        collect_share_bps as u64 <= BPS_DENOMINATOR
+            && max_slippage_bps <= MAX_BUYBACK_SLIPPAGE_BPS
+            && keeper_reward_bps <= MAX_BUYBACK_KEEPER_REWARD_BPS
+            && min_interval >= 0,
+        FlashLoanError::InvalidBuybackConfig
+    );
+    require_keys_neq!(ctx.accounts.fee_mint.key(), ctx.accounts.protocol_mint.key(), FlashLoanError::InvalidBuybackConfig);
+
+    let buyback = &mut ctx.accounts.buyback;
+    if buyback.protocol_mint != Pubkey::default() {
+        require_keys_eq!(buyback.protocol_mint, ctx.accounts.protocol_mint.key(), FlashLoanError::InvalidBuybackConfig);
+    }
+    buyback.fee_mint = ctx.accounts.fee_mint.key();
+    buyback.protocol_mint = ctx.accounts.protocol_mint.key();
+    buyback.fee_vault = ctx.accounts.fee_vault.key();
+    buyback.protocol_vault = ctx.accounts.protocol_vault.key();
+    buyback.amm_program = ctx.accounts.amm_program.key();
+    buyback.collect_share_bps = collect_share_bps;
+    buyback.max_slippage_bps = max_slippage_bps;
+    buyback.keeper_reward_bps = keeper_reward_bps;
+    buyback.min_interval = min_interval;
+    buyback.bump = ctx.bumps.buyback;
+
+    emit!(BuybackConfigured {
+        fee_mint: buyback.fee_mint,
+        protocol_mint: buyback.protocol_mint,
+        amm_program: buyback.amm_program,
+        collect_share_bps,
+        max_slippage_bps,
+        keeper_reward_bps,
+    });
+    Ok(())
+}
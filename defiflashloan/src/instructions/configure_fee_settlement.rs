@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::state::{FeeSettlement, ProtocolConfig};
+
+// Context for configuring aggregate fee settlement
+#[derive(Accounts)]
+pub struct ConfigureFeeSettlement<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump, has_one = admin)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = FeeSettlement::LEN,
+        seeds = [FEE_SETTLEMENT_SEED],
+        bump
+    )]
+    pub fee_settlement: Account<'info, FeeSettlement>,
+    pub settlement_mint: InterfaceAccount<'info, Mint>, // Mint aggregate fees are paid in
+    #[account(mut)]
+    pub admin: Signer<'info>,                      // Protocol admin
+    pub system_program: Program<'info, System>,
+}
+
+// Set the settlement mint, the oracle key pricing pool mints in it, and how
+// old a price may be before settlement refuses it
+pub(crate) fn handler(ctx: Context<ConfigureFeeSettlement>, price_authority: Pubkey, max_price_age: i64) -> Result<()> {
+    require!(max_price_age > 0, FlashLoanError::InvalidPoolConfig);
+
+    let fee_settlement = &mut ctx.accounts.fee_settlement;
+    fee_settlement.settlement_mint = ctx.accounts.settlement_mint.key();
+    fee_settlement.price_authority = price_authority;
+    fee_settlement.max_price_age = max_price_age;
+    fee_settlement.bump = ctx.bumps.fee_settlement;
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::InsuranceFundConfigured;
+use crate::state::{InsuranceFund, Pool, ProtocolConfig};
+
+// Context for creating or retuning a pool's loan insurance fund
+#[derive(Accounts)]
+pub struct ConfigureInsuranceFund<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump, has_one = admin)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(seeds = [POOL_SEED, pool.token_mint.as_ref(), pool.index.as_ref()], bump = pool.bump, has_one = token_mint)]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = InsuranceFund::LEN,
+        seeds = [INSURANCE_FUND_SEED, pool.key().as_ref()],
+        bump
+    )]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        seeds = [INSURANCE_VAULT_SEED, insurance_fund.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = insurance_fund,
+        token::token_program = token_program
+    )]
+    pub insurance_vault: InterfaceAccount<'info, TokenAccount>, // Holds premiums until paid out as refunds
+    pub token_mint: InterfaceAccount<'info, Mint>, // Pool mint premiums are paid in
+    #[account(mut)]
+    pub admin: Signer<'info>,                      // Protocol admin, who adjudicates claims
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+// Set the premium insured split loans pay and the largest refund one claim
+// may ask for; a zero premium stops new loans from being insured
+pub(crate) fn handler(ctx: Context<ConfigureInsuranceFund>, premium_bps: u16, max_claim_amount: u64) -> Result<()> {
+    require!(premium_bps <= MAX_INSURANCE_PREMIUM_BPS, FlashLoanError::InvalidInsuranceConfig);
+
+    let fund = &mut ctx.accounts.insurance_fund;
+    fund.pool = ctx.accounts.pool.key();
+    fund.vault = ctx.accounts.insurance_vault.key();
+    fund.premium_bps = premium_bps;
+    fund.max_claim_amount = max_claim_amount;
+    fund.bump = ctx.bumps.insurance_fund;
+
+    emit!(InsuranceFundConfigured {
+        pool: fund.pool,
+        insurance_fund: fund.key(),
+        premium_bps,
+        max_claim_amount,
+    });
+
+    Ok(())
+}
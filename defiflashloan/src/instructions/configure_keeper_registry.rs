@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::KeeperRegistryConfigured;
+use crate::state::{KeeperRegistry, ProtocolConfig};
+
+// Context for creating or retuning the keeper registry and its vaults
+#[derive(Accounts)]
+pub struct ConfigureKeeperRegistry<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump, has_one = admin)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = KeeperRegistry::LEN,
+        seeds = [KEEPER_REGISTRY_SEED],
+        bump
+    )]
+    pub keeper_registry: Account<'info, KeeperRegistry>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        seeds = [KEEPER_STAKE_VAULT_SEED],
+        bump,
+        token::mint = stake_mint,
+        token::authority = keeper_registry,
+        token::token_program = token_program
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>, // Holds the keeper bonds
+    #[account(
+        init_if_needed,
+        payer = admin,
+        seeds = [KEEPER_REWARD_VAULT_SEED],
+        bump,
+        token::mint = stake_mint,
+        token::authority = keeper_registry,
+        token::token_program = token_program
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>, // Pays crank rewards
+    pub stake_mint: InterfaceAccount<'info, Mint>, // Token keepers bond and are paid in
+    #[account(mut)]
+    pub admin: Signer<'info>,                      // Protocol admin
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+// Set the bond keepers must post, what each paid crank earns and how long
+// exiting keepers stay slashable; the stake mint is fixed once set
+pub(crate) fn handler(
+    ctx: Context<ConfigureKeeperRegistry>,
+    min_stake: u64,
+    reward_per_crank: u64,
+    unbonding_period: i64,
+) -> Result<()> {
+    require!(
+        min_stake > 0 && (0..=MAX_KEEPER_UNBONDING_PERIOD).contains(&unbonding_period),
+        FlashLoanError::InvalidKeeperConfig
+    );
+
+    let registry = &mut ctx.accounts.keeper_registry;
+    if registry.stake_mint != Pubkey::default() {
+        require_keys_eq!(registry.stake_mint, ctx.accounts.stake_mint.key(), FlashLoanError::InvalidKeeperConfig);
+    }
+    registry.stake_mint = ctx.accounts.stake_mint.key();
+    registry.stake_vault = ctx.accounts.stake_vault.key();
+    registry.reward_vault = ctx.accounts.reward_vault.key();
+    registry.min_stake = min_stake;
+    registry.reward_per_crank = reward_per_crank;
+    registry.unbonding_period = unbonding_period;
+    registry.bump = ctx.bumps.keeper_registry;
+
+    emit!(KeeperRegistryConfigured {
+        stake_mint: registry.stake_mint,
+        min_stake,
+        reward_per_crank,
+        unbonding_period,
+    });
+
+    Ok(())
+}
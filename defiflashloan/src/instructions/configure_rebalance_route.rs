@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::RebalanceRouteConfigured;
+use crate::state::{Pool, ProtocolConfig, RebalanceRoute};
+
+// Context for creating or retuning a route between two pools of a mint
+#[derive(Accounts)]
+pub struct ConfigureRebalanceRoute<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump, has_one = admin)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(seeds = [POOL_SEED, source_pool.token_mint.as_ref(), source_pool.index.as_ref()], bump = source_pool.bump)]
+    pub source_pool: Account<'info, Pool>,
+    #[account(
+        seeds = [POOL_SEED, source_pool.token_mint.as_ref(), target_pool.index.as_ref()],
+        bump = target_pool.bump,
+        constraint = target_pool.key() != source_pool.key() @ FlashLoanError::InvalidRebalanceRoute
+    )]
+    pub target_pool: Account<'info, Pool>,
+    #[account(address = target_pool.share_mint)]
+    pub target_share_mint: InterfaceAccount<'info, Mint>, // Mint of the IOU shares
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = RebalanceRoute::LEN,
+        seeds = [REBALANCE_ROUTE_SEED, source_pool.key().as_ref(), target_pool.key().as_ref()],
+        bump
+    )]
+    pub rebalance_route: Account<'info, RebalanceRoute>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        seeds = [REBALANCE_IOU_SEED, rebalance_route.key().as_ref()],
+        bump,
+        token::mint = target_share_mint,
+        token::authority = source_pool,
+        token::token_program = token_program
+    )]
+    pub iou_share_account: InterfaceAccount<'info, TokenAccount>, // Holds the target shares owed to the source pool
+    #[account(mut)]
+    pub admin: Signer<'info>,                      // Protocol admin
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+// Let `operator` or the admin move up to `max_move_amount` at a time from the
+// source pool into the target, with at most `max_outstanding` principal moved
+// and not yet unwound. Lowering the limits never forces an unwind; it only
+// blocks further moves until the outstanding principal falls below them
+pub(crate) fn handler(
+    ctx: Context<ConfigureRebalanceRoute>,
+    max_move_amount: u64,
+    max_outstanding: u64,
+    operator: Pubkey,
+) -> Result<()> {
+    require!(
+        max_move_amount > 0 && max_move_amount <= max_outstanding,
+        FlashLoanError::InvalidRebalanceRoute
+    );
+
+    let route = &mut ctx.accounts.rebalance_route;
+    route.source_pool = ctx.accounts.source_pool.key();
+    route.target_pool = ctx.accounts.target_pool.key();
+    route.operator = operator;
+    route.iou_share_account = ctx.accounts.iou_share_account.key();
+    route.max_move_amount = max_move_amount;
+    route.max_outstanding = max_outstanding;
+    route.bump = ctx.bumps.rebalance_route;
+
+    emit!(RebalanceRouteConfigured {
+        route: route.key(),
+        source_pool: route.source_pool,
+        target_pool: route.target_pool,
+        operator,
+        max_move_amount,
+        max_outstanding,
+    });
+    Ok(())
+}
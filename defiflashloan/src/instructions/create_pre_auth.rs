@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::state::{Pool, PreAuth};
+
+// Context for pre-authorizing a loan a keeper may run later
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct CreatePreAuth<'info> {
+    #[account(
+        init,
+        payer = borrower,
+        space = PreAuth::LEN,
+        seeds = [PRE_AUTH_SEED, pool.key().as_ref(), borrower.key().as_ref(), nonce.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pre_auth: Account<'info, PreAuth>,
+    pub pool: Account<'info, Pool>,                // Pool the loan will be drawn from
+    #[account(mut)]
+    pub borrower: Signer<'info>,                   // Borrower authorizing the loan
+    pub system_program: Program<'info, System>,
+}
+
+// Authorize one loan, with its exact callback, for a keeper to run within a window
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn handler(
+    ctx: Context<CreatePreAuth>,
+    nonce: u64,
+    loan_amount: u64,
+    max_fee: u64,
+    callback_program: Pubkey,
+    callback_data_hash: [u8; 32],
+    window_start: i64,
+    window_end: i64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(
+        window_start < window_end
+            && window_end > clock.unix_timestamp
+            && window_end - window_start <= MAX_PRE_AUTH_WINDOW,
+        FlashLoanError::InvalidPreAuthWindow
+    );
+
+    let pre_auth = &mut ctx.accounts.pre_auth;
+    pre_auth.borrower = ctx.accounts.borrower.key();
+    pre_auth.pool = ctx.accounts.pool.key();
+    pre_auth.nonce = nonce.to_le_bytes();
+    pre_auth.loan_amount = loan_amount;
+    pre_auth.max_fee = max_fee;
+    pre_auth.callback_program = callback_program;
+    pre_auth.callback_data_hash = callback_data_hash;
+    pre_auth.window_start = window_start;
+    pre_auth.window_end = window_end;
+    pre_auth.executed = false;
+    pre_auth.bump = ctx.bumps.pre_auth;
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::state::SessionKey;
+
+// Context for authorizing a session key
+#[derive(Accounts)]
+#[instruction(session_signer: Pubkey)]
+pub struct CreateSessionKey<'info> {
+    #[account(
+        init,
+        payer = borrower,
+        space = SessionKey::LEN,
+        seeds = [SESSION_KEY_SEED, borrower.key().as_ref(), session_signer.as_ref()],
+        bump
+    )]
+    pub session_key: Account<'info, SessionKey>,
+    #[account(mut)]
+    pub borrower: Signer<'info>,                   // Borrower delegating to the session key
+    pub system_program: Program<'info, System>,
+}
+
+// Authorize an ephemeral key to flash borrow on the borrower's behalf
+pub(crate) fn handler(
+    ctx: Context<CreateSessionKey>,
+    session_signer: Pubkey,
+    max_loan_amount: u64,
+    expires_at: i64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(expires_at > clock.unix_timestamp, FlashLoanError::SessionKeyExpired);
+
+    let session_key = &mut ctx.accounts.session_key;
+    session_key.borrower = ctx.accounts.borrower.key();
+    session_key.session_signer = session_signer;
+    session_key.max_loan_amount = max_loan_amount;
+    session_key.expires_at = expires_at;
+    session_key.bump = ctx.bumps.session_key;
+
+    Ok(())
+}
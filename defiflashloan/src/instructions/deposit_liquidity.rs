@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::{LiquidityDeposited, SharePriceUpdated};
+use crate::shares::shares_for_deposit;
+use crate::transfer_fee::transfer_fee;
+use crate::state::{LpFeeAccumulator, LpPosition, Pool};
+
+// Context for adding liquidity to a pool
+#[derive(Accounts)]
+pub struct DepositLiquidity<'info> {
+    #[account(
+        mut,
+        seeds = [POOL_SEED, pool.token_mint.as_ref(), pool.index.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(mut, address = pool.vault)]
+    pub loan_vault: InterfaceAccount<'info, TokenAccount>, // Pool vault receiving the deposit
+    #[account(mut, address = pool.share_mint)]
+    pub share_mint: InterfaceAccount<'info, Mint>, // LP share mint
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        space = LpPosition::LEN,
+        seeds = [LP_POSITION_SEED, pool.key().as_ref(), depositor.key().as_ref()],
+        bump
+    )]
+    pub lp_position: Account<'info, LpPosition>,
+    #[account(mut, seeds = [LP_FEE_ACCUMULATOR_SEED, pool.key().as_ref()], bump = lp_fee_accumulator.bump)]
+    pub lp_fee_accumulator: Option<Account<'info, LpFeeAccumulator>>, // Required by pools with time-weighted LP fees
+    #[account(mut, token::mint = pool.token_mint, token::authority = depositor)]
+    pub depositor_account: InterfaceAccount<'info, TokenAccount>, // Depositor's token account
+    #[account(mut, token::mint = share_mint)]
+    pub depositor_share_account: InterfaceAccount<'info, TokenAccount>, // Receives the minted shares
+    #[account(mut)]
+    pub depositor: Signer<'info>,                  // LP providing the liquidity
+    #[account(address = pool.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>, // Mint lent out by the pool
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DepositLiquidity<'info> {
+    // Context for moving the deposit into the vault
+    pub fn into_transfer_to_vault_context(&self) -> CpiContext<'_, '_, '_, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
+            from: self.depositor_account.to_account_info().clone(),
+            mint: self.token_mint.to_account_info().clone(),
+            to: self.loan_vault.to_account_info().clone(),
+            authority: self.depositor.to_account_info().clone(),
+        };
+        CpiContext::new(self.token_program.to_account_info().clone(), cpi_accounts)
+    }
+
+    // Context for minting LP shares, signed by the pool PDA
+    pub fn into_mint_shares_context<'a, 'b, 'c>(
+        &self,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, MintTo<'info>> {
+        let cpi_accounts = MintTo {
+            mint: self.share_mint.to_account_info().clone(),
+            to: self.depositor_share_account.to_account_info().clone(),
+            authority: self.pool.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+}
+
+// Deposit tokens into the pool vault and mint LP shares at the current exchange rate
+pub(crate) fn handler(ctx: Context<DepositLiquidity>, amount: u64, min_shares_out: u64) -> Result<()> {
+    require!(!ctx.accounts.pool.is_paused(PAUSE_DEPOSIT), FlashLoanError::PoolPaused);
+    require!(amount > 0, FlashLoanError::InvalidAmount);
+
+    // Only what reaches the vault after any Token-2022 transfer fee is credited
+    let received = amount - transfer_fee(&ctx.accounts.token_mint.to_account_info(), amount)?;
+
+    // Price the deposit before the vault balance changes
+    let price_before = ctx.accounts.pool.share_price(ctx.accounts.loan_vault.amount, ctx.accounts.share_mint.supply);
+    let shares = shares_for_deposit(
+        received,
+        ctx.accounts.pool.lp_assets(ctx.accounts.loan_vault.amount),
+        ctx.accounts.share_mint.supply,
+    );
+    require!(shares > 0, FlashLoanError::InvalidAmount);
+    require!(shares >= min_shares_out, FlashLoanError::SlippageExceeded);
+
+    token_interface::transfer_checked(
+        ctx.accounts.into_transfer_to_vault_context(),
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    let pool_seeds = ctx.accounts.pool.seeds();
+    token_interface::mint_to(
+        ctx.accounts.into_mint_shares_context(&[&pool_seeds[..]]),
+        shares,
+    )?;
+
+    let lp_position = &mut ctx.accounts.lp_position;
+    crate::lp_fees::track_position_shares(
+        &ctx.accounts.pool,
+        ctx.accounts.lp_fee_accumulator.as_mut(),
+        lp_position,
+        shares,
+        0,
+        Clock::get()?.unix_timestamp,
+    )?;
+    lp_position.owner = ctx.accounts.depositor.key();
+    lp_position.pool = ctx.accounts.pool.key();
+    lp_position.deposited_amount += received;
+    lp_position.record_deposit(received, shares, Clock::get()?.slot);
+    lp_position.bump = ctx.bumps.lp_position;
+
+    ctx.accounts.pool.total_deposits += received;
+
+    emit!(LiquidityDeposited {
+        pool: lp_position.pool,
+        lp: lp_position.owner,
+        amount: received,
+        shares,
+    });
+
+    ctx.accounts.loan_vault.reload()?;
+    ctx.accounts.share_mint.reload()?;
+    emit!(SharePriceUpdated {
+        pool: ctx.accounts.pool.key(),
+        price_before,
+        price_after: ctx.accounts.pool.share_price(ctx.accounts.loan_vault.amount, ctx.accounts.share_mint.supply),
+        lp_assets: ctx.accounts.pool.lp_assets(ctx.accounts.loan_vault.amount),
+        share_supply: ctx.accounts.share_mint.supply,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,134 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Burn, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::{EmergencyWithdrawn, SharePriceUpdated};
+use crate::shares::assets_for_shares;
+use crate::state::{LpFeeAccumulator, LpPosition, Pool, PoolConfig};
+
+// Context for taking LP principal out of a pool whose withdrawals stay paused
+#[derive(Accounts)]
+pub struct EmergencyWithdraw<'info> {
+    #[account(
+        mut,
+        seeds = [POOL_SEED, pool.token_mint.as_ref(), pool.index.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(seeds = [POOL_CONFIG_SEED, pool.key().as_ref()], bump = pool_config.bump)]
+    pub pool_config: Account<'info, PoolConfig>,   // Holds the emergency withdrawal delay
+    #[account(mut, address = pool.vault)]
+    pub loan_vault: InterfaceAccount<'info, TokenAccount>, // Pool vault paying out the withdrawal
+    #[account(mut, address = pool.share_mint)]
+    pub share_mint: InterfaceAccount<'info, Mint>, // LP share mint
+    #[account(
+        mut,
+        has_one = owner,
+        has_one = pool,
+        seeds = [LP_POSITION_SEED, pool.key().as_ref(), owner.key().as_ref()],
+        bump = lp_position.bump
+    )]
+    pub lp_position: Account<'info, LpPosition>,
+    #[account(mut, seeds = [LP_FEE_ACCUMULATOR_SEED, pool.key().as_ref()], bump = lp_fee_accumulator.bump)]
+    pub lp_fee_accumulator: Option<Account<'info, LpFeeAccumulator>>, // Required by pools with time-weighted LP fees
+    #[account(mut, token::mint = pool.token_mint)]
+    pub owner_account: InterfaceAccount<'info, TokenAccount>, // LP's token account receiving funds
+    #[account(mut, token::mint = share_mint, token::authority = owner)]
+    pub owner_share_account: InterfaceAccount<'info, TokenAccount>, // LP's shares to burn
+    pub owner: Signer<'info>,                      // LP withdrawing the liquidity
+    #[account(address = pool.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>, // Mint lent out by the pool
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> EmergencyWithdraw<'info> {
+    // Context for paying the LP out of the vault, signed by the pool PDA
+    pub fn into_transfer_to_owner_context<'a, 'b, 'c>(
+        &self,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
+            from: self.loan_vault.to_account_info().clone(),
+            mint: self.token_mint.to_account_info().clone(),
+            to: self.owner_account.to_account_info().clone(),
+            authority: self.pool.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+
+    // Context for burning the LP's shares
+    pub fn into_burn_shares_context(&self) -> CpiContext<'_, '_, '_, 'info, Burn<'info>> {
+        let cpi_accounts = Burn {
+            mint: self.share_mint.to_account_info().clone(),
+            from: self.owner_share_account.to_account_info().clone(),
+            authority: self.owner.to_account_info().clone(),
+        };
+        CpiContext::new(self.token_program.to_account_info().clone(), cpi_accounts)
+    }
+}
+
+// Once withdrawals have stayed paused past the pool's emergency delay, burn
+// `shares` and pay out the principal they carry, pro rata to the LP's
+// deposits, leaving any fee yield in the pool; a lost admin key then cannot
+// trap LP funds for good
+pub(crate) fn handler(ctx: Context<EmergencyWithdraw>, shares: u64) -> Result<()> {
+    require!(
+        ctx.accounts.pool.can_emergency_withdraw(Clock::get()?.unix_timestamp, ctx.accounts.pool_config.emergency_withdraw_delay),
+        FlashLoanError::EmergencyWithdrawUnavailable
+    );
+    require!(shares > 0, FlashLoanError::InvalidAmount);
+    let share_balance = ctx.accounts.owner_share_account.amount;
+    require!(share_balance >= shares, FlashLoanError::InsufficientLpBalance);
+
+    // Principal carried by the shares, never more than they are worth
+    let price_before = ctx.accounts.pool.share_price(ctx.accounts.loan_vault.amount, ctx.accounts.share_mint.supply);
+    let principal = (ctx.accounts.lp_position.deposited_amount as u128 * shares as u128 / share_balance as u128) as u64;
+    let amount = principal.min(assets_for_shares(
+        shares,
+        ctx.accounts.pool.lp_assets(ctx.accounts.loan_vault.amount),
+        ctx.accounts.share_mint.supply,
+    ));
+    require!(amount > 0, FlashLoanError::InvalidAmount);
+    require!(ctx.accounts.loan_vault.amount >= amount, FlashLoanError::InsufficientFunds);
+
+    token_interface::burn(ctx.accounts.into_burn_shares_context(), shares)?;
+
+    let pool_seeds = ctx.accounts.pool.seeds();
+    token_interface::transfer_checked(
+        ctx.accounts.into_transfer_to_owner_context(&[&pool_seeds[..]]),
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    ctx.accounts.lp_position.deposited_amount -= principal;
+    crate::lp_fees::track_position_shares(
+        &ctx.accounts.pool,
+        ctx.accounts.lp_fee_accumulator.as_mut(),
+        &mut ctx.accounts.lp_position,
+        0,
+        shares,
+        Clock::get()?.unix_timestamp,
+    )?;
+    ctx.accounts.lp_position.record_withdrawal(amount, shares);
+    ctx.accounts.pool.total_deposits = ctx.accounts.pool.total_deposits.saturating_sub(principal);
+
+    emit!(EmergencyWithdrawn {
+        pool: ctx.accounts.pool.key(),
+        lp: ctx.accounts.owner.key(),
+        amount,
+        shares,
+    });
+
+    ctx.accounts.loan_vault.reload()?;
+    ctx.accounts.share_mint.reload()?;
+    emit!(SharePriceUpdated {
+        pool: ctx.accounts.pool.key(),
+        price_before,
+        price_after: ctx.accounts.pool.share_price(ctx.accounts.loan_vault.amount, ctx.accounts.share_mint.supply),
+        lp_assets: ctx.accounts.pool.lp_assets(ctx.accounts.loan_vault.amount),
+        share_supply: ctx.accounts.share_mint.supply,
+    });
+
+    Ok(())
+}
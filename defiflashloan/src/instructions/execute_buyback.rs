@@ -0,0 +1,156 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{instruction::{AccountMeta, Instruction}, program::invoke_signed};
+use anchor_spl::token_interface::{self, Burn, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::BuybackExecuted;
+use crate::state::{Buyback, FeePrice, FeeSettlement};
+
+// Context for swapping buyback fees into the protocol token and burning it
+// The AMM's swap accounts follow in `remaining_accounts`; the buyback PDA
+// signs wherever it appears among them
+#[derive(Accounts)]
+pub struct ExecuteBuyback<'info> {
+    #[account(mut, seeds = [BUYBACK_SEED, fee_mint.key().as_ref()], bump = buyback.bump)]
+    pub buyback: Account<'info, Buyback>,
+    #[account(mut, address = buyback.fee_vault)]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>, // Fees the swap spends
+    #[account(mut, address = buyback.protocol_vault)]
+    pub protocol_vault: InterfaceAccount<'info, TokenAccount>, // Swap output, burned here
+    #[account(address = buyback.fee_mint)]
+    pub fee_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, address = buyback.protocol_mint)]
+    pub protocol_mint: InterfaceAccount<'info, Mint>,
+    #[account(seeds = [FEE_SETTLEMENT_SEED], bump = fee_settlement.bump)]
+    pub fee_settlement: Account<'info, FeeSettlement>, // Sets how fresh the prices must be
+    #[account(seeds = [FEE_PRICE_SEED, fee_mint.key().as_ref()], bump = fee_price.bump)]
+    pub fee_price: Account<'info, FeePrice>,       // Fee mint in the settlement mint
+    #[account(seeds = [FEE_PRICE_SEED, protocol_mint.key().as_ref()], bump = protocol_price.bump)]
+    pub protocol_price: Account<'info, FeePrice>,  // Protocol token in the settlement mint
+    /// CHECK: Address is constrained to the configured AMM
+    #[account(executable, address = buyback.amm_program)]
+    pub amm_program: UncheckedAccount<'info>,
+    #[account(mut, token::mint = fee_mint)]
+    pub keeper_account: InterfaceAccount<'info, TokenAccount>, // Receives the keeper reward
+    pub keeper: Signer<'info>,                     // Anyone running the buyback
+    pub fee_token_program: Interface<'info, TokenInterface>,
+    pub protocol_token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ExecuteBuyback<'info> {
+    // Context for paying the keeper out of the fee vault, signed by the buyback PDA
+    pub fn into_pay_keeper_context<'a, 'b, 'c>(
+        &self,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
+            from: self.fee_vault.to_account_info().clone(),
+            mint: self.fee_mint.to_account_info().clone(),
+            to: self.keeper_account.to_account_info().clone(),
+            authority: self.buyback.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.fee_token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+
+    // Context for burning the bought protocol tokens, signed by the buyback PDA
+    pub fn into_burn_context<'a, 'b, 'c>(
+        &self,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, Burn<'info>> {
+        let cpi_accounts = Burn {
+            mint: self.protocol_mint.to_account_info().clone(),
+            from: self.protocol_vault.to_account_info().clone(),
+            authority: self.buyback.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.protocol_token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+}
+
+// Permissionless, at most once per interval: pay the keeper its reward out of
+// `amount_in`, swap the rest through the configured AMM and burn the output.
+// The swap may spend no more than that rest and must return at least
+// `min_amount_out` and at least the oracle value less the configured slippage
+pub(crate) fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteBuyback<'info>>,
+    amount_in: u64,
+    min_amount_out: u64,
+    swap_data: Vec<u8>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let buyback = &ctx.accounts.buyback;
+    require!(
+        now >= buyback.last_buyback_at.saturating_add(buyback.min_interval),
+        FlashLoanError::BuybackTooSoon
+    );
+    require!(
+        amount_in > 0 && amount_in <= ctx.accounts.fee_vault.amount,
+        FlashLoanError::InvalidAmount
+    );
+
+    let keeper_reward = buyback.keeper_reward(amount_in);
+    let swap_in = amount_in - keeper_reward;
+    let oracle_floor = buyback.min_amount_out(
+        swap_in,
+        &ctx.accounts.fee_price,
+        &ctx.accounts.protocol_price,
+        now,
+        ctx.accounts.fee_settlement.max_price_age,
+    )?;
+    let min_amount_out = min_amount_out.max(oracle_floor);
+
+    let buyback_key = buyback.key();
+    let buyback_seeds = buyback.seeds();
+    if keeper_reward > 0 {
+        token_interface::transfer_checked(
+            ctx.accounts.into_pay_keeper_context(&[&buyback_seeds[..]]),
+            keeper_reward,
+            ctx.accounts.fee_mint.decimals,
+        )?;
+        ctx.accounts.fee_vault.reload()?;
+    }
+
+    // Judge the swap by the vault balances, not by what the AMM reports
+    let fee_before = ctx.accounts.fee_vault.amount;
+    let protocol_before = ctx.accounts.protocol_vault.amount;
+    let ix = Instruction {
+        program_id: ctx.accounts.amm_program.key(),
+        accounts: ctx
+            .remaining_accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer || account.key() == buyback_key,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data: swap_data,
+    };
+    let mut account_infos = ctx.remaining_accounts.to_vec();
+    account_infos.push(ctx.accounts.amm_program.to_account_info());
+    invoke_signed(&ix, &account_infos, &[&buyback_seeds[..]])?;
+
+    ctx.accounts.fee_vault.reload()?;
+    ctx.accounts.protocol_vault.reload()?;
+    let spent = fee_before.saturating_sub(ctx.accounts.fee_vault.amount);
+    require!(spent <= swap_in, FlashLoanError::BuybackOverspent);
+    let bought = ctx.accounts.protocol_vault.amount.saturating_sub(protocol_before);
+    require!(bought >= min_amount_out, FlashLoanError::BuybackSlippageExceeded);
+
+    token_interface::burn(ctx.accounts.into_burn_context(&[&buyback_seeds[..]]), bought)?;
+
+    let buyback = &mut ctx.accounts.buyback;
+    buyback.last_buyback_at = now;
+    buyback.total_spent += spent;
+    buyback.total_burned += bought;
+
+    emit!(BuybackExecuted {
+        fee_mint: buyback.fee_mint,
+        keeper: ctx.accounts.keeper.key(),
+        keeper_reward,
+        spent,
+        burned: bought,
+        min_amount_out,
+    });
+    Ok(())
+}
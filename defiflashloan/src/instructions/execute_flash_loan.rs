@@ -0,0 +1,771 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{clock::Clock, instruction::{get_stack_height, Instruction, AccountMeta}, program::{invoke, set_return_data}, sysvar::instructions as sysvar_instructions};
+use anchor_spl::token_interface::{self, Approve, Mint, Revoke, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::{FeeRevenueRecorded, FeesForwarded, FlashLoanExecuted, RateLimitExhausted, SharePriceUpdated};
+use crate::fee_forwarding::ForwardedFees;
+use crate::fees::{calculate_dynamic_fee, calculate_same_slot_surcharge};
+use crate::introspection::require_nonce_policy;
+use crate::pda::flash_delegate_address;
+use crate::instructions::execute_flash_loan_batch::{invoke_callback_batch, CallbackInstruction};
+use crate::receiver::{callback_reported_success, receiver_account_metas, OnFlashLoanArgs};
+use crate::transfer_fee::gross_for_net;
+use crate::state::{BorrowCredit, BorrowerReputation, FeeExperimentStats, FeePrice, Integrator, LoanState, LoanStats, Pool, PoolConfig, PreAuth, ProgramRegistry, ProtocolConfig, SessionKey, StatsAccumulator};
+
+// Context for flash loan
+#[derive(Accounts)]
+pub struct ExecuteFlashLoan<'info> {
+    #[account(
+        mut,
+        seeds = [POOL_SEED, token_mint.key().as_ref(), pool.index.as_ref()],
+        bump = pool.bump,
+        has_one = token_mint
+    )]
+    pub pool: Account<'info, Pool>,                // Pool PDA, signs for the vault
+    #[account(seeds = [POOL_CONFIG_SEED, pool.key().as_ref()], bump = pool_config.bump)]
+    pub pool_config: Account<'info, PoolConfig>,   // Pool risk parameters
+    #[account(
+        mut,
+        address = pool.vault,
+        constraint = loan_vault.mint == token_mint.key() @ FlashLoanError::MintMismatch,
+        constraint = *loan_vault.to_account_info().owner == token_program.key() @ FlashLoanError::TokenProgramMismatch
+    )]
+    pub loan_vault: InterfaceAccount<'info, TokenAccount>, // Flash loan pool
+    #[account(
+        mut,
+        constraint = borrower_account.mint == token_mint.key() @ FlashLoanError::MintMismatch,
+        constraint = *borrower_account.to_account_info().owner == token_program.key() @ FlashLoanError::TokenProgramMismatch
+    )]
+    pub borrower_account: InterfaceAccount<'info, TokenAccount>, // Borrower’s token account
+    #[account(mut)]
+    pub borrower: Signer<'info>,                   // Borrower signing the transaction
+    /// CHECK: Checked in the handler to be `borrower`, or the owner of `session_key` or `pre_auth`
+    pub loan_owner: UncheckedAccount<'info>,       // Borrower the loan is served to, keys the loan state
+    pub token_program: Interface<'info, TokenInterface>, // Token or Token-2022 program
+    #[account(mut)]
+    pub loan_stats: Option<Account<'info, LoanStats>>, // Loan statistics account (omitted on the fast path)
+    #[account(
+        mut,
+        seeds = [LOAN_STATE_SEED, pool.key().as_ref(), loan_owner.key().as_ref()],
+        bump = loan_state.bump
+    )]
+    pub loan_state: Account<'info, LoanState>,     // Reentrancy check and state, of the borrower served
+    #[account(
+        constraint = *token_mint.to_account_info().owner == token_program.key() @ FlashLoanError::TokenProgramMismatch
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>, // Token mint for multi-token support
+    #[account(address = pool.share_mint)]
+    pub share_mint: InterfaceAccount<'info, Mint>, // LP share mint, read to report the share price
+    #[account(mut)]
+    pub borrow_credit: Option<Account<'info, BorrowCredit>>, // Optional prepaid fee credits
+    #[account(
+        mut,
+        seeds = [STATS_ACCUMULATOR_SEED, pool.key().as_ref()],
+        bump = stats_accumulator.bump
+    )]
+    pub stats_accumulator: Option<Account<'info, StatsAccumulator>>, // The pool's deferred stats, for the fast path
+    #[account(
+        seeds = [SESSION_KEY_SEED, loan_owner.key().as_ref(), borrower.key().as_ref()],
+        bump = session_key.bump
+    )]
+    pub session_key: Option<Account<'info, SessionKey>>, // Set when `borrower` is a session signer
+    #[account(
+        mut,
+        seeds = [PRE_AUTH_SEED, pool.key().as_ref(), loan_owner.key().as_ref(), pre_auth.nonce.as_ref()],
+        bump = pre_auth.bump
+    )]
+    pub pre_auth: Option<Account<'info, PreAuth>>, // Set when a keeper runs a pre-authorized loan
+    /// CHECK: Address is constrained to the Instructions sysvar
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>, // Required for quoted loans and by pools refusing durable nonces
+    #[account(seeds = [PROGRAM_REGISTRY_SEED, pool.key().as_ref()], bump = program_registry.bump)]
+    pub program_registry: Option<Account<'info, ProgramRegistry>>, // Required for callbacks
+    #[account(
+        mut,
+        seeds = [LOAN_ESCROW_SEED, pool.key().as_ref()],
+        bump = pool.escrow_bump,
+        token::mint = token_mint,
+        token::authority = pool
+    )]
+    pub loan_escrow: Option<InterfaceAccount<'info, TokenAccount>>, // Set to disburse into escrow instead of `borrower_account`
+    /// CHECK: Checked against the callback program's flash delegate PDA
+    pub callback_delegate: Option<UncheckedAccount<'info>>, // Approved over the escrowed loan
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, loan_owner.key().as_ref()],
+        bump = borrower_reputation.bump
+    )]
+    pub borrower_reputation: Option<Account<'info, BorrowerReputation>>, // Updated on settlement, may unlock the trusted tier
+    #[account(
+        mut,
+        seeds = [FEE_EXPERIMENT_SEED, pool.key().as_ref()],
+        bump = fee_experiment_stats.bump
+    )]
+    pub fee_experiment_stats: Option<Account<'info, FeeExperimentStats>>, // Required while the pool runs a fee experiment
+    #[account(seeds = [INTEGRATOR_SEED, integrator.program_id.as_ref()], bump = integrator.bump)]
+    pub integrator: Option<Account<'info, Integrator>>, // Receiver program's registration, required for receiver loans
+    #[account(address = pool.accounting_oracle)]
+    pub accounting_price: Option<Account<'info, FeePrice>>, // Required while the pool reports fees in USD
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Option<Account<'info, ProtocolConfig>>, // Names the treasury, with `treasury_account`
+    #[account(mut)]
+    pub treasury_account: Option<InterfaceAccount<'info, TokenAccount>>, // Set to forward the protocol fee share
+    #[account(mut)]
+    pub creator_fee_account: Option<InterfaceAccount<'info, TokenAccount>>, // Set to forward the creator fee share
+}
+
+impl<'info> ExecuteFlashLoan<'info> {
+    // Context for transferring tokens to borrower, signed by the pool PDA
+    pub fn into_transfer_to_borrower_context<'a, 'b, 'c>(
+        &self,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
+            from: self.loan_vault.to_account_info().clone(),
+            mint: self.token_mint.to_account_info().clone(),
+            to: self.borrower_account.to_account_info().clone(),
+            authority: self.pool.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+
+    // Context for borrower repaying the loan into `to`, the vault or a fee
+    // account; a pre-authorization the borrower approved as delegate signs
+    // with `signer_seeds` in place of the borrower
+    pub fn into_repay_transfer_context<'a, 'b, 'c>(
+        &self,
+        to: AccountInfo<'info>,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, TransferChecked<'info>> {
+        let authority = match self.pre_auth.as_ref() {
+            Some(pre_auth) => pre_auth.to_account_info(),
+            None => self.borrower.to_account_info(),
+        };
+        let cpi_accounts = TransferChecked {
+            from: self.borrower_account.to_account_info().clone(),
+            mint: self.token_mint.to_account_info().clone(),
+            to,
+            authority,
+        };
+        CpiContext::new_with_signer(self.token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+
+    // Context for moving funds between the vault and the escrow, signed by the pool PDA
+    pub fn into_escrow_transfer_context<'a, 'b, 'c>(
+        &self,
+        from: AccountInfo<'info>,
+        to: AccountInfo<'info>,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
+            from,
+            mint: self.token_mint.to_account_info().clone(),
+            to,
+            authority: self.pool.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+
+    // Context for approving `delegate` over `escrow`, signed by the pool PDA
+    pub fn into_approve_delegate_context<'a, 'b, 'c>(
+        &self,
+        escrow: AccountInfo<'info>,
+        delegate: AccountInfo<'info>,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, Approve<'info>> {
+        let cpi_accounts = Approve {
+            to: escrow,
+            delegate,
+            authority: self.pool.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+
+    // Context for revoking the delegate over `escrow`, signed by the pool PDA
+    pub fn into_revoke_delegate_context<'a, 'b, 'c>(
+        &self,
+        escrow: AccountInfo<'info>,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, Revoke<'info>> {
+        let cpi_accounts = Revoke {
+            source: escrow,
+            authority: self.pool.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+}
+
+// Borrower logic run between disbursal and repayment
+pub(crate) enum LoanCallback {
+    Raw(Vec<u8>),                   // Data sent as-is to the first remaining account
+    Receiver(Vec<u8>),              // Standard `on_flash_loan` call carrying these bytes
+    Batch(Vec<CallbackInstruction>), // Instructions run in order over `remaining_accounts`
+}
+
+impl LoanCallback {
+    // Program the callback starts in, if any
+    fn target_program(&self, remaining_accounts: &[AccountInfo]) -> Option<Pubkey> {
+        match self {
+            LoanCallback::Raw(_) | LoanCallback::Receiver(_) => {
+                remaining_accounts.first().map(|program| program.key())
+            }
+            LoanCallback::Batch(instructions) => instructions.first().map(|callback| callback.program_id),
+        }
+    }
+
+    // Bound the borrower-supplied callback before anything is lent
+    fn check_len(&self) -> Result<()> {
+        match self {
+            LoanCallback::Raw(data) | LoanCallback::Receiver(data) => {
+                require!(data.len() <= MAX_CALLBACK_DATA_LEN, FlashLoanError::CallbackTooLarge);
+            }
+            LoanCallback::Batch(instructions) => {
+                require!(
+                    instructions.len() <= MAX_CALLBACK_INSTRUCTIONS,
+                    FlashLoanError::TooManyCallbackInstructions
+                );
+                for callback in instructions {
+                    require!(
+                        callback.data.len() <= MAX_CALLBACK_DATA_LEN
+                            && callback.account_indices.len() <= MAX_CALLBACK_ACCOUNT_INDICES,
+                        FlashLoanError::CallbackTooLarge
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteFlashLoan<'info>>,
+    loan_amount: u64,
+    loan_expiration: i64,
+    deduct_fee: bool,
+    callback_data: Vec<u8>,
+) -> Result<()> {
+    process_flash_loan(
+        ctx,
+        loan_amount,
+        loan_expiration,
+        false,
+        None,
+        deduct_fee,
+        LoanCallback::Raw(callback_data),
+    )
+}
+
+// Shared flash loan flow used by every borrowing instruction
+// `remaining_accounts` carry the optional borrower callback: target program first
+pub(crate) fn process_flash_loan<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteFlashLoan<'info>>,
+    loan_amount: u64,
+    loan_expiration: i64,
+    fast_path: bool,
+    quoted_fee: Option<u64>,
+    deduct_fee: bool,
+    callback: LoanCallback,
+) -> Result<()> {
+    let loan = &ctx.accounts.loan_vault;
+    let config = &ctx.accounts.pool_config;
+    let clock = Clock::get()?;
+
+    require!(!ctx.accounts.pool.is_paused(PAUSE_BORROW), FlashLoanError::PoolPaused);
+    callback.check_len()?;
+
+    // Refuse nested CPI constructions beyond the allowed depth
+    require!(
+        config.allows_invoke_depth(get_stack_height()),
+        FlashLoanError::InvokeDepthExceeded
+    );
+    require_nonce_policy(config, ctx.accounts.instructions_sysvar.as_deref())?;
+    ctx.accounts.pool.require_accounting_price(ctx.accounts.accounting_price.as_deref())?;
+
+    // A session key lets its signer borrow for the borrower, within its
+    // scope; a pre-authorization lets any keeper run the one loan it describes
+    let borrower_key = match (ctx.accounts.session_key.as_ref(), ctx.accounts.pre_auth.as_ref()) {
+        (Some(session_key), None) => {
+            require_keys_eq!(
+                session_key.session_signer,
+                ctx.accounts.borrower.key(),
+                FlashLoanError::SessionKeyMismatch
+            );
+            require!(
+                clock.unix_timestamp < session_key.expires_at,
+                FlashLoanError::SessionKeyExpired
+            );
+            require!(
+                loan_amount <= session_key.max_loan_amount,
+                FlashLoanError::SessionLoanTooLarge
+            );
+            session_key.borrower
+        }
+        (None, Some(pre_auth)) => {
+            let callback_data = match &callback {
+                LoanCallback::Raw(data) => data.as_slice(),
+                _ => return err!(FlashLoanError::PreAuthMismatch),
+            };
+            pre_auth.require_executable(
+                ctx.accounts.pool.key(),
+                clock.unix_timestamp,
+                callback.target_program(ctx.remaining_accounts),
+                callback_data,
+            )?;
+            require!(
+                loan_amount == pre_auth.loan_amount && !deduct_fee && quoted_fee.is_none(),
+                FlashLoanError::PreAuthMismatch
+            );
+            require_keys_eq!(ctx.accounts.borrower_account.owner, pre_auth.borrower, FlashLoanError::PreAuthMismatch);
+            pre_auth.borrower
+        }
+        (Some(_), Some(_)) => return err!(FlashLoanError::PreAuthMismatch),
+        (None, None) => ctx.accounts.borrower.key(),
+    };
+    require_keys_eq!(
+        ctx.accounts.loan_owner.key(),
+        borrower_key,
+        FlashLoanError::LoanStateMismatch
+    );
+
+    if let Some(credit) = ctx.accounts.borrow_credit.as_ref() {
+        require_keys_eq!(credit.owner, borrower_key, FlashLoanError::BorrowCreditOwnerMismatch);
+        require_keys_eq!(credit.pool, ctx.accounts.pool.key(), FlashLoanError::BorrowCreditPoolMismatch);
+    }
+
+    // A reputation at or above the pool's trusted score unlocks its trusted
+    // loan cap and fee
+    let trusted = match ctx.accounts.borrower_reputation.as_ref() {
+        Some(reputation) => {
+            require_keys_eq!(reputation.borrower, borrower_key, FlashLoanError::ReputationMismatch);
+            config.is_trusted(reputation.score(clock.unix_timestamp))
+        }
+        None => false,
+    };
+    let (max_loan_amount, fee_bps) = config.limits(trusted);
+    // Size tiers price standard borrowers; the trusted tier keeps its own fee
+    let fee_bps = if trusted {
+        fee_bps
+    } else {
+        let accounting_price = ctx.accounts.accounting_price.as_deref();
+        ctx.accounts.pool.base_fee_bps(loan_amount, fee_bps, accounting_price, clock.unix_timestamp)
+    };
+
+    // Standard borrowers in a running fee experiment are priced by their
+    // bucket, and the experiment's stats must come along to record the loan
+    let experiment_bucket = if trusted { None } else { config.fee_experiment_bucket(&borrower_key) };
+    require!(
+        experiment_bucket.is_none() || ctx.accounts.fee_experiment_stats.is_some(),
+        FlashLoanError::FeeExperimentStatsMissing
+    );
+    let fee_bps = if experiment_bucket == Some(FEE_EXPERIMENT_VARIANT) { config.experiment_fee_bps } else { fee_bps };
+
+    // Ensure loan does not exceed maximum allowed amount
+    require!(loan_amount <= max_loan_amount, FlashLoanError::LoanAmountTooLarge);
+    require!(
+        config.allows_probation(loan_amount, ctx.accounts.loan_state.loan_count),
+        FlashLoanError::ProbationLoanTooLarge
+    );
+
+    // Ensure the loan vault has enough liquidity
+    require!(loan.amount >= loan_amount, FlashLoanError::InsufficientFunds);
+
+    // Ensure the loan has not expired (with grace period)
+    require!(
+        clock.unix_timestamp <= loan_expiration + config.grace_period,
+        FlashLoanError::LoanExpired
+    );
+
+    // Cooldown check, signalled to operators before failing
+    let cooldown_ends_at = ctx.accounts.loan_state.last_loan_timestamp + config.loan_cooldown;
+    if clock.unix_timestamp < cooldown_ends_at {
+        emit!(RateLimitExhausted {
+            pool: ctx.accounts.pool.key(),
+            borrower: borrower_key,
+            loan_amount,
+            retry_at: cooldown_ends_at,
+            ops_tag: ctx.accounts.pool.ops_tag,
+        });
+        ctx.accounts.pool.log_ops_tag();
+        return err!(FlashLoanError::CooldownPeriodNotOver);
+    }
+
+    // Reentrancy check
+    require!(!ctx.accounts.loan_state.active, FlashLoanError::Reentrancy);
+    ctx.accounts.loan_state.active = true;
+    ctx.accounts.loan_state.active_since = clock.unix_timestamp;
+    // Persist the flag now, so a callback re-entering through another path
+    // sees the loan as open rather than the idle state loaded with the account
+    ctx.accounts.loan_state.exit(&crate::ID)?;
+
+    // A signed quote sets the fee; otherwise a prepaid credit covers it when
+    // it has loans left and the size fits
+    let fee = match (quoted_fee, ctx.accounts.borrow_credit.as_mut()) {
+        (Some(quoted_fee), _) => quoted_fee,
+        (None, Some(credit)) if credit.remaining_loans > 0 && loan_amount <= credit.max_loan_size => {
+            credit.remaining_loans -= 1;
+            0
+        }
+        _ => calculate_dynamic_fee(loan_amount, ctx.accounts.loan_vault.amount, fee_bps, &config.fee_curve), // Calculate fee from vault utilization
+    };
+
+    // Each extra loan in the same slot pays a growing surcharge on top
+    let same_slot_loans = if ctx.accounts.loan_state.last_loan_slot == clock.slot {
+        ctx.accounts.loan_state.same_slot_loan_count
+    } else {
+        0
+    };
+    let fee = fee + calculate_same_slot_surcharge(loan_amount, same_slot_loans, config.same_slot_surcharge_bps);
+
+    // A pre-authorized loan runs once, and only at a fee the borrower accepted
+    if let Some(pre_auth) = ctx.accounts.pre_auth.as_mut() {
+        require!(fee <= pre_auth.max_fee, FlashLoanError::PreAuthFeeTooHigh);
+        pre_auth.executed = true;
+    }
+
+    // Reserve the protocol's share of the fee; the rest accrues to LPs
+    let protocol_fee = ctx.accounts.pool.protocol_fee(fee);
+
+    // With `deduct_fee` the fee is withheld from the disbursement and only the
+    // principal is repaid; the vault nets the same fee either way
+    let (disbursement, total_repayment) = if deduct_fee {
+        let disbursement = loan_amount
+            .checked_sub(fee)
+            .ok_or(FlashLoanError::FeeExceedsLoan)?;
+        (disbursement, loan_amount)
+    } else {
+        (loan_amount, loan_amount + fee)
+    };
+
+    // Fee shares with a fee account passed are repaid straight to it
+    let forwarded = ForwardedFees::resolve(
+        &ctx.accounts.pool,
+        ctx.accounts.protocol_config.as_deref(),
+        ctx.accounts.treasury_account.as_deref(),
+        ctx.accounts.creator_fee_account.as_deref(),
+        fee,
+        protocol_fee,
+    )?;
+
+    // A Token-2022 transfer fee is withheld on the way back too, so the
+    // borrower sends enough for the rest of `total_repayment` to reach the
+    // vault and each forwarded share to reach its account
+    let mint = ctx.accounts.token_mint.to_account_info();
+    let vault_transfer = gross_for_net(&mint, total_repayment - forwarded.total())?;
+    let mut fee_transfers = Vec::new();
+    let fee_accounts = [
+        (ctx.accounts.treasury_account.as_ref(), forwarded.protocol_fee),
+        (ctx.accounts.creator_fee_account.as_ref(), forwarded.creator_fee),
+    ];
+    for (account, share) in fee_accounts {
+        if let Some(account) = account.filter(|_| share > 0) {
+            fee_transfers.push((account.to_account_info(), gross_for_net(&mint, share)?));
+        }
+    }
+    let repayment_transfer = vault_transfer + fee_transfers.iter().map(|(_, transfer)| transfer).sum::<u64>();
+
+    // Repayment is judged by what the vault gains, not by the borrower's balance
+    let vault_before = ctx.accounts.loan_vault.amount;
+    let price_before = ctx.accounts.pool.share_price(vault_before, ctx.accounts.share_mint.supply);
+    #[cfg(feature = "strict-invariants")]
+    let snapshot = crate::invariants::SettlementSnapshot::capture(&ctx.accounts.pool, vault_before, ctx.accounts.share_mint.supply);
+    ctx.accounts.pool.note_utilization(loan_amount, vault_before, clock.slot);
+
+    // The loan still belongs to LPs while it is out; persist that before any
+    // CPI so shares minted or burned from the callback are priced correctly
+    ctx.accounts.pool.open_loan_amount += loan_amount;
+    ctx.accounts.pool.exit(&crate::ID)?;
+
+    //  Transfer loan amount to borrower, or into the escrow with the callback
+    // program's delegate PDA approved for exactly that amount, so the callback
+    // cannot reach the borrower's own balance
+    let pool_seeds = ctx.accounts.pool.seeds();
+    let escrow = ctx.accounts.loan_escrow.as_ref().map(|escrow| escrow.to_account_info());
+    let escrow_before = ctx.accounts.loan_escrow.as_ref().map_or(0, |escrow| escrow.amount);
+    match escrow.clone() {
+        Some(escrow) => {
+            let target = callback
+                .target_program(ctx.remaining_accounts)
+                .ok_or(FlashLoanError::InvalidCallbackProgram)?;
+            let delegate = ctx
+                .accounts
+                .callback_delegate
+                .as_ref()
+                .ok_or(FlashLoanError::InvalidCallbackDelegate)?
+                .to_account_info();
+            let delegate_bump = ctx
+                .accounts
+                .program_registry
+                .as_ref()
+                .and_then(|registry| registry.delegate_bump(&target))
+                .ok_or(FlashLoanError::CallbackProgramNotRegistered)?;
+            require_keys_eq!(
+                delegate.key(),
+                flash_delegate_address(&target, delegate_bump)?,
+                FlashLoanError::InvalidCallbackDelegate
+            );
+            token_interface::transfer_checked(
+                ctx.accounts.into_escrow_transfer_context(
+                    ctx.accounts.loan_vault.to_account_info(),
+                    escrow.clone(),
+                    &[&pool_seeds[..]],
+                ),
+                disbursement,
+                ctx.accounts.token_mint.decimals,
+            )?;
+            token_interface::approve(
+                ctx.accounts.into_approve_delegate_context(escrow, delegate, &[&pool_seeds[..]]),
+                disbursement,
+            )?;
+        }
+        None => {
+            token_interface::transfer_checked(
+                ctx.accounts.into_transfer_to_borrower_context(&[&pool_seeds[..]]),
+                disbursement,
+                ctx.accounts.token_mint.decimals,
+            )?;
+        }
+    }
+    // Account the callback sees as holding the loan
+    let loan_holder = escrow
+        .clone()
+        .unwrap_or_else(|| ctx.accounts.borrower_account.to_account_info());
+
+    //  Execute the borrower's callback as a Cross-Program Invocation (CPI)
+    let registry = ctx.accounts.program_registry.as_deref();
+    let callback_program = match callback {
+        LoanCallback::Raw(data) => invoke_callback(ctx.remaining_accounts, &[], data, registry)?,
+        LoanCallback::Receiver(data) => {
+            require!(!ctx.remaining_accounts.is_empty(), FlashLoanError::InvalidCallbackProgram);
+            let accounts = &ctx.accounts;
+            let metas = receiver_account_metas(
+                accounts.pool.key(),
+                loan_holder.key(),
+                accounts.borrower.key(),
+                accounts.token_mint.key(),
+                accounts.token_program.key(),
+            );
+            let infos = [
+                accounts.pool.to_account_info(),
+                loan_holder.clone(),
+                accounts.borrower.to_account_info(),
+                accounts.token_mint.to_account_info(),
+                accounts.token_program.to_account_info(),
+            ];
+            let leading: Vec<_> = metas.into_iter().zip(infos).collect();
+            let args = OnFlashLoanArgs {
+                amount: loan_amount,
+                fee: repayment_transfer - loan_amount,
+                data,
+            };
+            invoke_callback(ctx.remaining_accounts, &leading, args.to_ix_data()?, registry)?
+        }
+        LoanCallback::Batch(instructions) => {
+            invoke_callback_batch(ctx.remaining_accounts, &instructions, registry)?
+        }
+    };
+    let callback_success = callback_reported_success(&callback_program);
+
+    //  Borrower repays loan
+    match escrow {
+        Some(escrow) => {
+            token_interface::revoke(ctx.accounts.into_revoke_delegate_context(escrow.clone(), &[&pool_seeds[..]]))?;
+
+            // The escrow must end up holding the loan and fee on top of what it started with
+            let loan_escrow = ctx.accounts.loan_escrow.as_mut().ok_or(FlashLoanError::InvalidCallbackDelegate)?;
+            loan_escrow.reload()?;
+            require!(
+                loan_escrow.amount >= escrow_before + repayment_transfer,
+                FlashLoanError::IncorrectRepayment
+            );
+            token_interface::transfer_checked(
+                ctx.accounts.into_escrow_transfer_context(
+                    escrow.clone(),
+                    ctx.accounts.loan_vault.to_account_info(),
+                    &[&pool_seeds[..]],
+                ),
+                vault_transfer,
+                ctx.accounts.token_mint.decimals,
+            )?;
+            for (account, transfer) in fee_transfers {
+                token_interface::transfer_checked(
+                    ctx.accounts.into_escrow_transfer_context(escrow.clone(), account, &[&pool_seeds[..]]),
+                    transfer,
+                    ctx.accounts.token_mint.decimals,
+                )?;
+            }
+        }
+        None => {
+            let pre_auth_seeds = ctx.accounts.pre_auth.as_ref().map(|pre_auth| pre_auth.seeds());
+            let pre_auth_signer = pre_auth_seeds.as_ref().map(|seeds| [&seeds[..]]);
+            let repay_signer: &[&[&[u8]]] = pre_auth_signer.as_ref().map_or(&[], |signer| &signer[..]);
+            token_interface::transfer_checked(
+                ctx.accounts.into_repay_transfer_context(ctx.accounts.loan_vault.to_account_info(), repay_signer),
+                vault_transfer,
+                ctx.accounts.token_mint.decimals,
+            )?;
+            for (account, transfer) in fee_transfers {
+                token_interface::transfer_checked(
+                    ctx.accounts.into_repay_transfer_context(account, repay_signer),
+                    transfer,
+                    ctx.accounts.token_mint.decimals,
+                )?;
+            }
+        }
+    }
+
+    ctx.accounts.loan_vault.reload()?;
+    let vault_fee = fee - forwarded.total();
+
+    // Pick up anything the callback changed on the pool before settling it
+    ctx.accounts.pool.reload()?;
+    ctx.accounts.pool.write_off_dust(vault_before, ctx.accounts.loan_vault.amount, vault_fee)?;
+    ctx.accounts.pool.open_loan_amount = ctx.accounts.pool.open_loan_amount.saturating_sub(loan_amount);
+    ctx.accounts.pool.accrue_unforwarded_fees(fee, protocol_fee, &forwarded);
+
+    // A pool reporting in USD prices the fee by its accounting oracle
+    let fee_usd_cents = ctx.accounts.pool.usd_cents(ctx.accounts.accounting_price.as_deref(), fee, clock.unix_timestamp);
+
+    // Update loan stats, deferring to the accumulator on the fast path
+    if fast_path {
+        let accumulator = ctx
+            .accounts
+            .stats_accumulator
+            .as_mut()
+            .ok_or(FlashLoanError::StatsAccountMissing)?;
+        accumulator.accumulate(loan_amount, fee);
+        accumulator.pending_fees_usd_cents += fee_usd_cents.unwrap_or(0);
+    } else {
+        let loan_stats = ctx
+            .accounts
+            .loan_stats
+            .as_mut()
+            .ok_or(FlashLoanError::StatsAccountMissing)?;
+        loan_stats.update_stats(loan_amount, fee, clock.unix_timestamp);
+        loan_stats.total_fees_usd_cents += fee_usd_cents.unwrap_or(0);
+
+        #[cfg(feature = "strict-invariants")]
+        crate::invariants::check_loan_stats(loan_stats)?;
+    }
+
+    if let (Some(bucket), Some(stats)) = (experiment_bucket, ctx.accounts.fee_experiment_stats.as_mut()) {
+        stats.sync(&ctx.accounts.pool_config, clock.unix_timestamp);
+        stats.record(bucket, loan_amount, fee);
+    }
+
+    if let Some(reputation) = ctx.accounts.borrower_reputation.as_mut() {
+        reputation.record_loan(loan_amount, ctx.accounts.pool_config.reputation_min_loan_amount);
+    }
+
+    // Update loan state to prevent abuse
+    ctx.accounts.loan_state.active = false;
+    ctx.accounts.loan_state.last_loan_timestamp = clock.unix_timestamp; // Update cooldown
+    ctx.accounts.loan_state.loan_count += 1;
+    ctx.accounts.loan_state.last_loan_slot = clock.slot;
+    ctx.accounts.loan_state.same_slot_loan_count = same_slot_loans + 1;
+
+    // Emit loan execution event
+    emit!(FlashLoanExecuted {
+        pool: ctx.accounts.pool.key(),
+        borrower: borrower_key,
+        loan_amount,
+        fee,
+        protocol_fee,
+        fee_deducted: deduct_fee,
+        callback_program,
+        callback_success,
+        sequence: ctx.accounts.pool.next_event_sequence(),
+    });
+    if ctx.accounts.pool.has_accounting_oracle() {
+        let accounting_price = ctx.accounts.accounting_price.as_deref();
+        emit!(FeeRevenueRecorded {
+            pool: ctx.accounts.pool.key(),
+            sequence: ctx.accounts.pool.event_sequence,
+            fee,
+            protocol_fee: protocol_fee,
+            fee_usd_cents,
+            protocol_fee_usd_cents: ctx.accounts.pool.usd_cents(accounting_price, protocol_fee, clock.unix_timestamp),
+        });
+    }
+    if forwarded.total() > 0 {
+        emit!(FeesForwarded {
+            pool: ctx.accounts.pool.key(),
+            sequence: ctx.accounts.pool.event_sequence,
+            protocol_fee: forwarded.protocol_fee,
+            creator_fee: forwarded.creator_fee,
+        });
+    }
+
+    ctx.accounts.share_mint.reload()?;
+    #[cfg(feature = "strict-invariants")]
+    crate::invariants::check_settlement(
+        &snapshot,
+        &ctx.accounts.pool,
+        ctx.accounts.loan_vault.amount,
+        ctx.accounts.share_mint.supply,
+        vault_fee,
+        0,
+    )?;
+    emit!(SharePriceUpdated {
+        pool: ctx.accounts.pool.key(),
+        price_before,
+        price_after: ctx.accounts.pool.share_price(ctx.accounts.loan_vault.amount, ctx.accounts.share_mint.supply),
+        lp_assets: ctx.accounts.pool.lp_assets(ctx.accounts.loan_vault.amount),
+        share_supply: ctx.accounts.share_mint.supply,
+    });
+
+    Ok(())
+}
+
+// Invoke the borrower callback carried in `remaining_accounts`, returning its
+// program id, or the default key when there is none
+// The first account is the target program, which must be in the pool's
+// registry. It receives the `leading` accounts first, then the rest of
+// `remaining_accounts` as account metas with their signer/writable flags
+pub(crate) fn invoke_callback<'info>(
+    remaining_accounts: &[AccountInfo<'info>],
+    leading: &[(AccountMeta, AccountInfo<'info>)],
+    callback_data: Vec<u8>,
+    registry: Option<&ProgramRegistry>,
+) -> Result<Pubkey> {
+    let Some((program, accounts)) = remaining_accounts.split_first() else {
+        return Ok(Pubkey::default());
+    };
+    require_callback_program(program, registry)?;
+
+    let ix = Instruction {
+        program_id: program.key(),
+        accounts: leading
+            .iter()
+            .map(|(meta, _)| meta.clone())
+            .chain(accounts.iter().map(|account| AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            }))
+            .collect(),
+        data: callback_data,
+    };
+    let account_infos: Vec<AccountInfo<'info>> = leading
+        .iter()
+        .map(|(_, info)| info.clone())
+        .chain(remaining_accounts.iter().cloned())
+        .collect();
+    // Only return data set by this callback may report its success
+    set_return_data(&[]);
+    invoke(&ix, &account_infos)?;
+    Ok(program.key())
+}
+
+// A callback target must be an executable program other than this one and be
+// allowlisted in the pool's registry
+pub(crate) fn require_callback_program(program: &AccountInfo, registry: Option<&ProgramRegistry>) -> Result<()> {
+    require!(program.executable, FlashLoanError::InvalidCallbackProgram);
+    require_keys_neq!(program.key(), crate::ID, FlashLoanError::InvalidCallbackProgram);
+    require!(
+        registry.is_some_and(|registry| registry.is_registered(program.key)),
+        FlashLoanError::CallbackProgramNotRegistered
+    );
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{instruction::{AccountMeta, Instruction}, program::{invoke, set_return_data}};
+
+use crate::errors::FlashLoanError;
+use crate::instructions::execute_flash_loan::{
+    process_flash_loan, require_callback_program, ExecuteFlashLoan, LoanCallback,
+};
+use crate::state::ProgramRegistry;
+
+// One borrower instruction in a callback batch
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct CallbackInstruction {
+    pub program_id: Pubkey,         // Target program; must be among the remaining accounts
+    pub account_indices: Vec<u8>,   // Accounts, as indices into the remaining accounts
+    pub data: Vec<u8>,              // Instruction data passed through as-is
+}
+
+// Flash loan that runs `instructions` in order between disbursal and repayment
+pub(crate) fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteFlashLoan<'info>>,
+    loan_amount: u64,
+    loan_expiration: i64,
+    instructions: Vec<CallbackInstruction>,
+) -> Result<()> {
+    // Batch size and per-instruction lengths are bounded by `LoanCallback::check_len`
+    process_flash_loan(
+        ctx,
+        loan_amount,
+        loan_expiration,
+        false,
+        None,
+        false,
+        LoanCallback::Batch(instructions),
+    )
+}
+
+// Invoke each batched instruction, returning the first program id, or the
+// default key for an empty batch
+// Every target is checked like a single callback; account metas keep the
+// signer/writable flags of the referenced remaining accounts
+pub(crate) fn invoke_callback_batch<'info>(
+    remaining_accounts: &[AccountInfo<'info>],
+    instructions: &[CallbackInstruction],
+    registry: Option<&ProgramRegistry>,
+) -> Result<Pubkey> {
+    // Only return data set during the batch may report its success
+    set_return_data(&[]);
+    for callback in instructions {
+        let program = remaining_accounts
+            .iter()
+            .find(|account| account.key() == callback.program_id)
+            .ok_or(FlashLoanError::InvalidCallbackProgram)?;
+        require_callback_program(program, registry)?;
+
+        let mut metas = Vec::with_capacity(callback.account_indices.len());
+        let mut account_infos = Vec::with_capacity(callback.account_indices.len() + 1);
+        account_infos.push(program.clone());
+        for &index in &callback.account_indices {
+            let account = remaining_accounts
+                .get(index as usize)
+                .ok_or(FlashLoanError::InvalidAccountIndex)?;
+            metas.push(AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            });
+            account_infos.push(account.clone());
+        }
+
+        let ix = Instruction {
+            program_id: program.key(),
+            accounts: metas,
+            data: callback.data.clone(),
+        };
+        invoke(&ix, &account_infos)?;
+    }
+    Ok(instructions
+        .first()
+        .map(|callback| callback.program_id)
+        .unwrap_or_default())
+}
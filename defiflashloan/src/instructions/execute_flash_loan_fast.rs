@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+use crate::instructions::execute_flash_loan::{process_flash_loan, ExecuteFlashLoan, LoanCallback};
+
+// Same loan flow, but stats go to an accumulator instead of the shared LoanStats
+pub(crate) fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteFlashLoan<'info>>,
+    loan_amount: u64,
+    loan_expiration: i64,
+    callback_data: Vec<u8>,
+) -> Result<()> {
+    process_flash_loan(
+        ctx,
+        loan_amount,
+        loan_expiration,
+        true,
+        None,
+        false,
+        LoanCallback::Raw(callback_data),
+    )
+}
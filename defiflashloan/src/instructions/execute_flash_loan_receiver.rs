@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::FlashLoanError;
+use crate::events::ReceiverInterfaceMismatch;
+use crate::instructions::execute_flash_loan::{process_flash_loan, ExecuteFlashLoan, LoanCallback};
+use crate::receiver::RECEIVER_INTERFACE_VERSION;
+
+// Flash loan that calls the standard `on_flash_loan` receiver interface on the
+// first remaining account, passing `data` through; see `receiver`
+// The receiver must be registered with `register_integrator` for an interface
+// version this program still calls compatibly, checked before anything moves
+pub(crate) fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteFlashLoan<'info>>,
+    loan_amount: u64,
+    loan_expiration: i64,
+    data: Vec<u8>,
+) -> Result<()> {
+    let program = ctx.remaining_accounts.first().ok_or(FlashLoanError::InvalidCallbackProgram)?;
+    let integrator = ctx.accounts.integrator.as_ref().ok_or(FlashLoanError::IntegratorNotRegistered)?;
+    require_keys_eq!(integrator.program_id, program.key(), FlashLoanError::IntegratorMismatch);
+    if !integrator.is_compatible() {
+        emit!(ReceiverInterfaceMismatch {
+            pool: ctx.accounts.pool.key(),
+            program_id: integrator.program_id,
+            min_interface_version: integrator.min_interface_version,
+            interface_version: RECEIVER_INTERFACE_VERSION,
+        });
+        return err!(FlashLoanError::IncompatibleReceiverInterface);
+    }
+
+    process_flash_loan(
+        ctx,
+        loan_amount,
+        loan_expiration,
+        false,
+        None,
+        false,
+        LoanCallback::Receiver(data),
+    )
+}
@@ -0,0 +1,337 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{instruction::get_stack_height, sysvar::instructions as sysvar_instructions};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::{FlashLoanExecuted, SharePriceUpdated};
+use crate::fee_forwarding::ForwardedFees;
+use crate::fees::{calculate_dynamic_fee, calculate_same_slot_surcharge};
+use crate::instructions::execute_flash_loan::{invoke_callback, require_callback_program};
+use crate::introspection::require_nonce_policy;
+use crate::pda::loan_state_address;
+use crate::receiver::callback_reported_success;
+use crate::state::{LoanState, Pool, PoolConfig, ProgramRegistry};
+use crate::transfer_fee::gross_for_net;
+
+// One mint borrowed by `execute_multi_flash_loan`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LoanLeg {
+    pub pool: Pubkey,               // Pool lending this leg; must match the leg's accounts
+    pub amount: u64,                // Principal borrowed from the pool
+}
+
+// Context for borrowing several mints at once; each leg's accounts follow in
+// `remaining_accounts`, LOAN_LEG_ACCOUNTS per leg, then the callback
+#[derive(Accounts)]
+pub struct ExecuteMultiFlashLoan<'info> {
+    #[account(mut)]
+    pub borrower: Signer<'info>,                   // Receives and repays every leg
+    /// CHECK: Address is constrained to the Instructions sysvar
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>, // Required by pools refusing durable nonces
+}
+
+// Accounts of one leg, in `remaining_accounts` order:
+// pool, pool_config, loan_vault, borrower_account, token_mint, token_program,
+// program_registry, which is this program's id when the pool has none,
+// share_mint and the borrower's loan_state on the pool
+struct LegAccounts<'info> {
+    pool: Account<'info, Pool>,
+    pool_config: Account<'info, PoolConfig>,
+    loan_vault: InterfaceAccount<'info, TokenAccount>,
+    borrower_account: InterfaceAccount<'info, TokenAccount>,
+    token_mint: InterfaceAccount<'info, Mint>,
+    token_program: Interface<'info, TokenInterface>,
+    program_registry: Option<Account<'info, ProgramRegistry>>,
+    share_mint: InterfaceAccount<'info, Mint>,
+    loan_state: Account<'info, LoanState>,
+}
+
+impl<'info> LegAccounts<'info> {
+    // Deserialize and cross-check the accounts of `leg`
+    fn load(infos: &'info [AccountInfo<'info>], leg: &LoanLeg, borrower: &Pubkey) -> Result<Self> {
+        let [pool, pool_config, loan_vault, borrower_account, token_mint, token_program, program_registry, share_mint, loan_state] = infos else {
+            return err!(FlashLoanError::LoanLegMismatch);
+        };
+        require_keys_eq!(pool.key(), leg.pool, FlashLoanError::LoanLegMismatch);
+
+        let accounts = Self {
+            pool: Account::try_from(pool)?,
+            pool_config: Account::try_from(pool_config)?,
+            loan_vault: InterfaceAccount::try_from(loan_vault)?,
+            borrower_account: InterfaceAccount::try_from(borrower_account)?,
+            token_mint: InterfaceAccount::try_from(token_mint)?,
+            token_program: Interface::try_from(token_program)?,
+            program_registry: if program_registry.key() == crate::ID {
+                None
+            } else {
+                Some(Account::try_from(program_registry)?)
+            },
+            share_mint: InterfaceAccount::try_from(share_mint)?,
+            loan_state: Account::try_from(loan_state)?,
+        };
+
+        require_keys_eq!(accounts.pool_config.pool, leg.pool, FlashLoanError::LoanLegMismatch);
+        require_keys_eq!(accounts.loan_vault.key(), accounts.pool.vault, FlashLoanError::LoanLegMismatch);
+        require_keys_eq!(accounts.token_mint.key(), accounts.pool.token_mint, FlashLoanError::MintMismatch);
+        require_keys_eq!(accounts.share_mint.key(), accounts.pool.share_mint, FlashLoanError::LoanLegMismatch);
+        require_keys_eq!(accounts.borrower_account.mint, accounts.pool.token_mint, FlashLoanError::MintMismatch);
+        require_keys_eq!(*loan_vault.owner, token_program.key(), FlashLoanError::TokenProgramMismatch);
+        require_keys_eq!(*borrower_account.owner, token_program.key(), FlashLoanError::TokenProgramMismatch);
+        if let Some(registry) = accounts.program_registry.as_ref() {
+            require_keys_eq!(registry.pool, leg.pool, FlashLoanError::LoanLegMismatch);
+        }
+        require_keys_eq!(
+            loan_state.key(),
+            loan_state_address(&leg.pool, borrower, accounts.loan_state.bump)?,
+            FlashLoanError::LoanStateMismatch
+        );
+        Ok(accounts)
+    }
+}
+
+// Borrow every leg, run the borrower callback once, then take back each leg's
+// principal plus fee, checking every vault grew by at least its fee
+pub(crate) fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteMultiFlashLoan<'info>>,
+    legs: Vec<LoanLeg>,
+    callback_data: Vec<u8>,
+) -> Result<()> {
+    let leg_accounts_len = legs.len() * LOAN_LEG_ACCOUNTS;
+    require!(
+        ctx.remaining_accounts.len() >= leg_accounts_len,
+        FlashLoanError::LoanLegMismatch
+    );
+    let (leg_infos, callback_accounts) = ctx.remaining_accounts.split_at(leg_accounts_len);
+
+    process_multi_flash_loan(
+        &ctx.accounts.borrower,
+        ctx.accounts.instructions_sysvar.as_deref(),
+        leg_infos,
+        callback_accounts,
+        &legs,
+        callback_data,
+        false,
+    )?;
+    Ok(())
+}
+
+// Settlement of one leg, as returned by `process_multi_flash_loan`
+pub(crate) struct SettledLeg {
+    pub protocol_fee: u64,          // Protocol share of the leg's fee, in the leg's mint
+    pub sequence: u64,              // Sequence of the leg's `FlashLoanExecuted` on its pool
+}
+
+// Shared multi-mint loan flow, returning each leg's settlement; every leg's
+// pool guards the loan with the borrower's own loan state on that pool
+// With `fees_settled` the legs repay principal plus the LP and creator shares
+// only, and the caller collects the protocol fees some other way
+pub(crate) fn process_multi_flash_loan<'info>(
+    borrower: &Signer<'info>,
+    instructions_sysvar: Option<&AccountInfo<'info>>,
+    leg_infos: &'info [AccountInfo<'info>],
+    callback_accounts: &'info [AccountInfo<'info>],
+    legs: &[LoanLeg],
+    callback_data: Vec<u8>,
+    fees_settled: bool,
+) -> Result<Vec<SettledLeg>> {
+    let clock = Clock::get()?;
+
+    require!(
+        !legs.is_empty() && legs.len() <= MAX_LOAN_LEGS,
+        FlashLoanError::TooManyLoanLegs
+    );
+    require!(callback_data.len() <= MAX_CALLBACK_DATA_LEN, FlashLoanError::CallbackTooLarge);
+
+    // Resolve and check every leg before anything moves
+    #[cfg(feature = "strict-invariants")]
+    let mut snapshots = Vec::with_capacity(legs.len());
+    let mut loaded: Vec<(LegAccounts, u64, u64, u64)> = Vec::with_capacity(legs.len());
+    for (leg, infos) in legs.iter().zip(leg_infos.chunks(LOAN_LEG_ACCOUNTS)) {
+        require!(
+            loaded.iter().all(|(accounts, _, _, _)| accounts.pool.key() != leg.pool),
+            FlashLoanError::DuplicateLoanLeg
+        );
+        let accounts = LegAccounts::load(infos, leg, &borrower.key())?;
+        let config = &accounts.pool_config;
+        let loan_state = &accounts.loan_state;
+        require!(!loan_state.active, FlashLoanError::Reentrancy);
+
+        // Every lending pool's invoke depth limit must hold
+        require!(
+            config.allows_invoke_depth(get_stack_height()),
+            FlashLoanError::InvokeDepthExceeded
+        );
+        require!(!accounts.pool.is_paused(PAUSE_BORROW), FlashLoanError::PoolPaused);
+        require_nonce_policy(config, instructions_sysvar)?;
+        require!(leg.amount > 0, FlashLoanError::InvalidAmount);
+        require!(leg.amount <= config.max_loan_amount, FlashLoanError::LoanAmountTooLarge);
+        // Legs carry no experiment stats account, so a pool mid-experiment
+        // cannot price or record the leg by bucket
+        require!(config.experiment_share_bps == 0, FlashLoanError::FeeExperimentRunning);
+        require!(
+            config.allows_probation(leg.amount, loan_state.loan_count),
+            FlashLoanError::ProbationLoanTooLarge
+        );
+        require!(accounts.loan_vault.amount >= leg.amount, FlashLoanError::InsufficientFunds);
+        require!(
+            clock.unix_timestamp >= loan_state.last_loan_timestamp + config.loan_cooldown,
+            FlashLoanError::CooldownPeriodNotOver
+        );
+
+        let same_slot_loans = if loan_state.last_loan_slot == clock.slot {
+            loan_state.same_slot_loan_count
+        } else {
+            0
+        };
+        let fee = calculate_dynamic_fee(
+            leg.amount,
+            accounts.loan_vault.amount,
+            // Legs carry no accounting price, so USD tiers charge the first tier
+            accounts.pool.base_fee_bps(leg.amount, config.fee_bps, None, clock.unix_timestamp),
+            &config.fee_curve,
+        )
+            + calculate_same_slot_surcharge(leg.amount, same_slot_loans, config.same_slot_surcharge_bps);
+        let vault_before = accounts.loan_vault.amount;
+        let price_before = accounts.pool.share_price(vault_before, accounts.share_mint.supply);
+        #[cfg(feature = "strict-invariants")]
+        snapshots.push(crate::invariants::SettlementSnapshot::capture(
+            &accounts.pool,
+            vault_before,
+            accounts.share_mint.supply,
+        ));
+        loaded.push((accounts, fee, vault_before, price_before));
+    }
+
+    //  Disburse every leg, signed by its pool PDA
+    for (leg, (accounts, _, vault_before, _)) in legs.iter().zip(loaded.iter_mut()) {
+        accounts.loan_state.active = true;
+        accounts.loan_state.active_since = clock.unix_timestamp;
+        // Persist the flag before the callback can observe the loan state
+        accounts.loan_state.exit(&crate::ID)?;
+
+        accounts.pool.note_utilization(leg.amount, *vault_before, clock.slot);
+        let pool_seeds = accounts.pool.seeds();
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: accounts.loan_vault.to_account_info(),
+                    mint: accounts.token_mint.to_account_info(),
+                    to: accounts.borrower_account.to_account_info(),
+                    authority: accounts.pool.to_account_info(),
+                },
+                &[&pool_seeds[..]],
+            ),
+            leg.amount,
+            accounts.token_mint.decimals,
+        )?;
+
+        // The leg still belongs to the pool's LPs while it is out
+        accounts.pool.open_loan_amount += leg.amount;
+        accounts.pool.exit(&crate::ID)?;
+    }
+
+    //  The callback touches every leg's funds, so every lending pool must allow it
+    if let Some(program) = callback_accounts.first() {
+        for (accounts, _, _, _) in &loaded {
+            require_callback_program(program, accounts.program_registry.as_deref())?;
+        }
+    }
+    let callback_program = invoke_callback(
+        callback_accounts,
+        &[],
+        callback_data,
+        loaded[0].0.program_registry.as_deref(),
+    )?;
+    let callback_success = callback_reported_success(&callback_program);
+
+    //  Borrower repays every leg
+    let mut settled = Vec::with_capacity(legs.len());
+    #[cfg(feature = "strict-invariants")]
+    let mut snapshots = snapshots.into_iter();
+    for (leg, (accounts, fee, vault_before, price_before)) in legs.iter().zip(loaded.iter_mut()) {
+        let protocol_fee = accounts.pool.protocol_fee(*fee);
+        let owed = if fees_settled { *fee - protocol_fee } else { *fee };
+        let repayment_transfer = gross_for_net(&accounts.token_mint.to_account_info(), leg.amount + owed)?;
+        token_interface::transfer_checked(
+            CpiContext::new(
+                accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: accounts.borrower_account.to_account_info(),
+                    mint: accounts.token_mint.to_account_info(),
+                    to: accounts.loan_vault.to_account_info(),
+                    authority: borrower.to_account_info(),
+                },
+            ),
+            repayment_transfer,
+            accounts.token_mint.decimals,
+        )?;
+
+        accounts.loan_vault.reload()?;
+
+        // Pick up anything the callback changed on the pool before settling it
+        accounts.pool.reload()?;
+        accounts.pool.write_off_dust(*vault_before, accounts.loan_vault.amount, owed)?;
+        accounts.pool.open_loan_amount = accounts.pool.open_loan_amount.saturating_sub(leg.amount);
+
+        // A protocol share settled elsewhere is not reserved in the vault
+        let settled_elsewhere = ForwardedFees {
+            protocol_fee: if fees_settled { protocol_fee } else { 0 },
+            creator_fee: 0,
+        };
+        accounts.pool.accrue_unforwarded_fees(*fee, protocol_fee, &settled_elsewhere);
+        let sequence = accounts.pool.next_event_sequence();
+        settled.push(SettledLeg { protocol_fee, sequence });
+        // Legs are not part of the Accounts struct, so persist the pool and
+        // the loan state by hand
+        accounts.pool.exit(&crate::ID)?;
+        let loan_state = &mut accounts.loan_state;
+        loan_state.same_slot_loan_count = if loan_state.last_loan_slot == clock.slot {
+            loan_state.same_slot_loan_count + 1
+        } else {
+            1
+        };
+        loan_state.active = false;
+        loan_state.last_loan_timestamp = clock.unix_timestamp;
+        loan_state.loan_count += 1;
+        loan_state.last_loan_slot = clock.slot;
+        loan_state.exit(&crate::ID)?;
+
+        emit!(FlashLoanExecuted {
+            pool: leg.pool,
+            borrower: borrower.key(),
+            loan_amount: leg.amount,
+            fee: *fee,
+            protocol_fee,
+            fee_deducted: false,
+            callback_program,
+            callback_success,
+            sequence,
+        });
+
+        accounts.share_mint.reload()?;
+        #[cfg(feature = "strict-invariants")]
+        {
+            let snapshot = snapshots.next().ok_or(FlashLoanError::InvariantViolated)?;
+            crate::invariants::check_settlement(
+                &snapshot,
+                &accounts.pool,
+                accounts.loan_vault.amount,
+                accounts.share_mint.supply,
+                owed,
+                0,
+            )?;
+        }
+        emit!(SharePriceUpdated {
+            pool: leg.pool,
+            price_before: *price_before,
+            price_after: accounts.pool.share_price(accounts.loan_vault.amount, accounts.share_mint.supply),
+            lp_assets: accounts.pool.lp_assets(accounts.loan_vault.amount),
+            share_supply: accounts.share_mint.supply,
+        });
+    }
+
+    Ok(settled)
+}
@@ -0,0 +1,289 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{instruction::get_stack_height, sysvar::instructions as sysvar_instructions};
+use anchor_lang::system_program::{self, System, Transfer as SystemTransfer};
+use anchor_spl::token::{self, spl_token::native_mint, CloseAccount, Mint, SyncNative, Token, TokenAccount, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::{FeeRevenueRecorded, FlashLoanExecuted, RateLimitExhausted, SharePriceUpdated};
+use crate::fees::{calculate_dynamic_fee, calculate_same_slot_surcharge};
+use crate::introspection::require_nonce_policy;
+use crate::instructions::execute_flash_loan::invoke_callback;
+use crate::receiver::callback_reported_success;
+use crate::state::{FeeExperimentStats, FeePrice, LoanState, LoanStats, Pool, PoolConfig, ProgramRegistry};
+
+// Context for a WSOL pool flash loan settled in native SOL
+#[derive(Accounts)]
+pub struct ExecuteNativeFlashLoan<'info> {
+    #[account(
+        mut,
+        seeds = [POOL_SEED, token_mint.key().as_ref(), pool.index.as_ref()],
+        bump = pool.bump,
+        has_one = token_mint
+    )]
+    pub pool: Account<'info, Pool>,                // Pool PDA, signs for the vault
+    #[account(seeds = [POOL_CONFIG_SEED, pool.key().as_ref()], bump = pool_config.bump)]
+    pub pool_config: Account<'info, PoolConfig>,   // Pool risk parameters
+    #[account(
+        mut,
+        address = pool.vault,
+        constraint = loan_vault.mint == token_mint.key() @ FlashLoanError::MintMismatch
+    )]
+    pub loan_vault: Account<'info, TokenAccount>,  // WSOL vault
+    #[account(
+        init,
+        payer = borrower,
+        seeds = [UNWRAP_SEED, pool.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = pool
+    )]
+    pub unwrap_account: Account<'info, TokenAccount>, // Temporary WSOL account closed to the borrower
+    #[account(mut)]
+    pub borrower: Signer<'info>,                   // Receives and repays lamports
+    #[account(
+        mut,
+        seeds = [LOAN_STATE_SEED, pool.key().as_ref(), borrower.key().as_ref()],
+        bump = loan_state.bump
+    )]
+    pub loan_state: Account<'info, LoanState>,     // Reentrancy check and state
+    #[account(mut)]
+    pub loan_stats: Option<Account<'info, LoanStats>>, // Loan statistics account
+    #[account(address = native_mint::ID @ FlashLoanError::NotNativeMint)]
+    pub token_mint: Account<'info, Mint>,          // WSOL mint
+    #[account(address = pool.share_mint)]
+    pub share_mint: Account<'info, Mint>,          // LP share mint, read to report the share price
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    #[account(seeds = [PROGRAM_REGISTRY_SEED, pool.key().as_ref()], bump = program_registry.bump)]
+    pub program_registry: Option<Account<'info, ProgramRegistry>>, // Required for callbacks
+    #[account(
+        mut,
+        seeds = [FEE_EXPERIMENT_SEED, pool.key().as_ref()],
+        bump = fee_experiment_stats.bump
+    )]
+    pub fee_experiment_stats: Option<Account<'info, FeeExperimentStats>>, // Required while the pool runs a fee experiment
+    /// CHECK: Address is constrained to the Instructions sysvar
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>, // Required by pools refusing durable nonces
+    #[account(address = pool.accounting_oracle)]
+    pub accounting_price: Option<Account<'info, FeePrice>>, // Required while the pool reports fees in USD
+}
+
+impl<'info> ExecuteNativeFlashLoan<'info> {
+    // Context for moving the loan into the unwrap account, signed by the pool PDA
+    pub fn into_transfer_to_unwrap_context<'a, 'b, 'c>(
+        &self,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
+            from: self.loan_vault.to_account_info().clone(),
+            mint: self.token_mint.to_account_info().clone(),
+            to: self.unwrap_account.to_account_info().clone(),
+            authority: self.pool.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+
+    // Context for closing the unwrap account, releasing its lamports to the borrower
+    pub fn into_close_unwrap_context<'a, 'b, 'c>(
+        &self,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, CloseAccount<'info>> {
+        let cpi_accounts = CloseAccount {
+            account: self.unwrap_account.to_account_info().clone(),
+            destination: self.borrower.to_account_info().clone(),
+            authority: self.pool.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+
+    // Context for the borrower paying lamports straight into the vault
+    pub fn into_repay_lamports_context(&self) -> CpiContext<'_, '_, '_, 'info, SystemTransfer<'info>> {
+        let cpi_accounts = SystemTransfer {
+            from: self.borrower.to_account_info().clone(),
+            to: self.loan_vault.to_account_info().clone(),
+        };
+        CpiContext::new(self.system_program.to_account_info().clone(), cpi_accounts)
+    }
+
+    // Context for re-syncing the vault's WSOL balance with its lamports
+    pub fn into_sync_vault_context(&self) -> CpiContext<'_, '_, '_, 'info, SyncNative<'info>> {
+        let cpi_accounts = SyncNative {
+            account: self.loan_vault.to_account_info().clone(),
+        };
+        CpiContext::new(self.token_program.to_account_info().clone(), cpi_accounts)
+    }
+}
+
+// Lend from a WSOL pool as native SOL: the loan is unwrapped to the borrower's
+// wallet, and principal plus fee are repaid in lamports and wrapped back into
+// the vault. The borrower callback is carried in `remaining_accounts`
+pub(crate) fn handler(
+    ctx: Context<ExecuteNativeFlashLoan>,
+    loan_amount: u64,
+    callback_data: Vec<u8>,
+) -> Result<()> {
+    let config = &ctx.accounts.pool_config;
+    let clock = Clock::get()?;
+
+    require!(!ctx.accounts.pool.is_paused(PAUSE_BORROW), FlashLoanError::PoolPaused);
+    require!(
+        config.allows_invoke_depth(get_stack_height()),
+        FlashLoanError::InvokeDepthExceeded
+    );
+    require_nonce_policy(config, ctx.accounts.instructions_sysvar.as_deref())?;
+    ctx.accounts.pool.require_accounting_price(ctx.accounts.accounting_price.as_deref())?;
+    require!(loan_amount > 0, FlashLoanError::InvalidAmount);
+    require!(callback_data.len() <= MAX_CALLBACK_DATA_LEN, FlashLoanError::CallbackTooLarge);
+    require!(loan_amount <= config.max_loan_amount, FlashLoanError::LoanAmountTooLarge);
+    require!(
+        config.allows_probation(loan_amount, ctx.accounts.loan_state.loan_count),
+        FlashLoanError::ProbationLoanTooLarge
+    );
+    require!(ctx.accounts.loan_vault.amount >= loan_amount, FlashLoanError::InsufficientFunds);
+
+    let cooldown_ends_at = ctx.accounts.loan_state.last_loan_timestamp + config.loan_cooldown;
+    if clock.unix_timestamp < cooldown_ends_at {
+        emit!(RateLimitExhausted {
+            pool: ctx.accounts.pool.key(),
+            borrower: ctx.accounts.borrower.key(),
+            loan_amount,
+            retry_at: cooldown_ends_at,
+            ops_tag: ctx.accounts.pool.ops_tag,
+        });
+        ctx.accounts.pool.log_ops_tag();
+        return err!(FlashLoanError::CooldownPeriodNotOver);
+    }
+
+    require!(!ctx.accounts.loan_state.active, FlashLoanError::Reentrancy);
+    ctx.accounts.loan_state.active = true;
+    ctx.accounts.loan_state.active_since = clock.unix_timestamp;
+    // Persist the flag before the callback can observe the loan state
+    ctx.accounts.loan_state.exit(&crate::ID)?;
+
+    let same_slot_loans = if ctx.accounts.loan_state.last_loan_slot == clock.slot {
+        ctx.accounts.loan_state.same_slot_loan_count
+    } else {
+        0
+    };
+    let fee_bps = ctx.accounts.pool.base_fee_bps(
+        loan_amount,
+        config.fee_bps,
+        ctx.accounts.accounting_price.as_deref(),
+        clock.unix_timestamp,
+    );
+    // Borrowers in a running fee experiment are priced by their bucket, and the
+    // experiment's stats must come along to record the loan
+    let experiment_bucket = config.fee_experiment_bucket(&ctx.accounts.borrower.key());
+    require!(
+        experiment_bucket.is_none() || ctx.accounts.fee_experiment_stats.is_some(),
+        FlashLoanError::FeeExperimentStatsMissing
+    );
+    let fee_bps = if experiment_bucket == Some(FEE_EXPERIMENT_VARIANT) { config.experiment_fee_bps } else { fee_bps };
+    let fee = calculate_dynamic_fee(loan_amount, ctx.accounts.loan_vault.amount, fee_bps, &config.fee_curve)
+        + calculate_same_slot_surcharge(loan_amount, same_slot_loans, config.same_slot_surcharge_bps);
+    let protocol_fee = ctx.accounts.pool.protocol_fee(fee);
+    let vault_before = ctx.accounts.loan_vault.amount;
+    let price_before = ctx.accounts.pool.share_price(vault_before, ctx.accounts.share_mint.supply);
+    #[cfg(feature = "strict-invariants")]
+    let snapshot = crate::invariants::SettlementSnapshot::capture(&ctx.accounts.pool, vault_before, ctx.accounts.share_mint.supply);
+
+    // The loan still belongs to LPs while it is out; persist that before any
+    // CPI so shares minted or burned from the callback are priced correctly
+    ctx.accounts.pool.open_loan_amount += loan_amount;
+    ctx.accounts.pool.exit(&crate::ID)?;
+
+    // Unwrap: the closed account pays the loan plus the borrower's own rent back
+    let pool_seeds = ctx.accounts.pool.seeds();
+    token::transfer_checked(
+        ctx.accounts.into_transfer_to_unwrap_context(&[&pool_seeds[..]]),
+        loan_amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+    token::close_account(ctx.accounts.into_close_unwrap_context(&[&pool_seeds[..]]))?;
+
+    let callback_program = invoke_callback(
+        ctx.remaining_accounts,
+        &[],
+        callback_data,
+        ctx.accounts.program_registry.as_deref(),
+    )?;
+    let callback_success = callback_reported_success(&callback_program);
+
+    // Wrap on the fly: lamports go straight into the vault, then sync its balance
+    system_program::transfer(ctx.accounts.into_repay_lamports_context(), loan_amount + fee)?;
+    token::sync_native(ctx.accounts.into_sync_vault_context())?;
+
+    ctx.accounts.loan_vault.reload()?;
+    require!(
+        vault_before.checked_add(fee) == Some(ctx.accounts.loan_vault.amount),
+        FlashLoanError::IncorrectRepayment
+    );
+
+    // Pick up anything the callback changed on the pool before settling it
+    ctx.accounts.pool.reload()?;
+    ctx.accounts.pool.open_loan_amount = ctx.accounts.pool.open_loan_amount.saturating_sub(loan_amount);
+    ctx.accounts.pool.accrue_fees(fee, protocol_fee);
+
+    // A pool reporting in USD prices the fee by its accounting oracle
+    let fee_usd_cents = ctx.accounts.pool.usd_cents(ctx.accounts.accounting_price.as_deref(), fee, clock.unix_timestamp);
+    if let Some(loan_stats) = ctx.accounts.loan_stats.as_mut() {
+        loan_stats.update_stats(loan_amount, fee, clock.unix_timestamp);
+        loan_stats.total_fees_usd_cents += fee_usd_cents.unwrap_or(0);
+    }
+
+    if let (Some(bucket), Some(stats)) = (experiment_bucket, ctx.accounts.fee_experiment_stats.as_mut()) {
+        stats.sync(&ctx.accounts.pool_config, clock.unix_timestamp);
+        stats.record(bucket, loan_amount, fee);
+    }
+
+    ctx.accounts.loan_state.active = false;
+    ctx.accounts.loan_state.last_loan_timestamp = clock.unix_timestamp;
+    ctx.accounts.loan_state.loan_count += 1;
+    ctx.accounts.loan_state.last_loan_slot = clock.slot;
+    ctx.accounts.loan_state.same_slot_loan_count = same_slot_loans + 1;
+
+    emit!(FlashLoanExecuted {
+        pool: ctx.accounts.pool.key(),
+        borrower: ctx.accounts.borrower.key(),
+        loan_amount,
+        fee,
+        protocol_fee,
+        fee_deducted: false,
+        callback_program,
+        callback_success,
+        sequence: ctx.accounts.pool.next_event_sequence(),
+    });
+    if ctx.accounts.pool.has_accounting_oracle() {
+        let accounting_price = ctx.accounts.accounting_price.as_deref();
+        emit!(FeeRevenueRecorded {
+            pool: ctx.accounts.pool.key(),
+            sequence: ctx.accounts.pool.event_sequence,
+            fee,
+            protocol_fee,
+            fee_usd_cents,
+            protocol_fee_usd_cents: ctx.accounts.pool.usd_cents(accounting_price, protocol_fee, clock.unix_timestamp),
+        });
+    }
+
+    ctx.accounts.share_mint.reload()?;
+    #[cfg(feature = "strict-invariants")]
+    crate::invariants::check_settlement(
+        &snapshot,
+        &ctx.accounts.pool,
+        ctx.accounts.loan_vault.amount,
+        ctx.accounts.share_mint.supply,
+        fee,
+        0,
+    )?;
+    emit!(SharePriceUpdated {
+        pool: ctx.accounts.pool.key(),
+        price_before,
+        price_after: ctx.accounts.pool.share_price(ctx.accounts.loan_vault.amount, ctx.accounts.share_mint.supply),
+        lp_assets: ctx.accounts.pool.lp_assets(ctx.accounts.loan_vault.amount),
+        share_supply: ctx.accounts.share_mint.supply,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,276 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_spl::token_interface::{self, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::{FeeRevenueRecorded, FlashLoanExecuted, LiquidityDeposited, LoanNetted, SharePriceUpdated};
+use crate::fees::{calculate_dynamic_fee, calculate_same_slot_surcharge};
+use crate::introspection::require_nonce_policy;
+use crate::shares::shares_for_deposit;
+use crate::state::{FeePrice, LoanState, LpFeeAccumulator, LpPosition, Pool, PoolConfig};
+use crate::transfer_fee::{gross_for_net, transfer_fee};
+
+// Context for a loan from one pool netted against a deposit into another
+// pool of the same mint
+#[derive(Accounts)]
+pub struct ExecuteNettedFlashLoan<'info> {
+    #[account(
+        mut,
+        seeds = [POOL_SEED, token_mint.key().as_ref(), source_pool.index.as_ref()],
+        bump = source_pool.bump
+    )]
+    pub source_pool: Account<'info, Pool>,          // Pool lending the loan leg
+    #[account(seeds = [POOL_CONFIG_SEED, source_pool.key().as_ref()], bump = source_config.bump)]
+    pub source_config: Account<'info, PoolConfig>,  // Risk parameters of the lending pool
+    #[account(mut, address = source_pool.vault)]
+    pub source_vault: InterfaceAccount<'info, TokenAccount>, // Receives only the loan fee
+    #[account(address = source_pool.share_mint)]
+    pub source_share_mint: InterfaceAccount<'info, Mint>, // Read to report the lending pool's share price
+    #[account(
+        init_if_needed,
+        payer = borrower,
+        space = LoanState::LEN,
+        seeds = [LOAN_STATE_SEED, source_pool.key().as_ref(), borrower.key().as_ref()],
+        bump
+    )]
+    pub loan_state: Account<'info, LoanState>,      // Per-borrower reentrancy check and cooldown on the lending pool
+    #[account(
+        mut,
+        seeds = [POOL_SEED, token_mint.key().as_ref(), target_pool.index.as_ref()],
+        bump = target_pool.bump,
+        constraint = target_pool.key() != source_pool.key() @ FlashLoanError::InvalidNettingPools
+    )]
+    pub target_pool: Account<'info, Pool>,          // Pool receiving the deposit leg
+    #[account(mut, address = target_pool.vault)]
+    pub target_vault: InterfaceAccount<'info, TokenAccount>, // Receives the deposit
+    #[account(mut, address = target_pool.share_mint)]
+    pub target_share_mint: InterfaceAccount<'info, Mint>, // LP shares minted for the deposit
+    #[account(
+        init_if_needed,
+        payer = borrower,
+        space = LpPosition::LEN,
+        seeds = [LP_POSITION_SEED, target_pool.key().as_ref(), borrower.key().as_ref()],
+        bump
+    )]
+    pub lp_position: Account<'info, LpPosition>,    // Borrower's position in the receiving pool
+    #[account(mut, seeds = [LP_FEE_ACCUMULATOR_SEED, target_pool.key().as_ref()], bump = lp_fee_accumulator.bump)]
+    pub lp_fee_accumulator: Option<Account<'info, LpFeeAccumulator>>, // Required when the receiving pool time-weights LP fees
+    #[account(mut, token::mint = token_mint, token::authority = borrower)]
+    pub borrower_account: InterfaceAccount<'info, TokenAccount>, // Pays the fee and the deposit
+    #[account(mut, token::mint = target_share_mint)]
+    pub borrower_share_account: InterfaceAccount<'info, TokenAccount>, // Receives the minted shares
+    #[account(mut)]
+    pub borrower: Signer<'info>,
+    #[account(address = source_pool.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,  // Mint shared by both pools
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: Address is constrained to the Instructions sysvar
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>, // Required by pools refusing durable nonces
+    #[account(address = source_pool.accounting_oracle)]
+    pub accounting_price: Option<Account<'info, FeePrice>>, // Required while the lending pool reports fees in USD
+}
+
+impl<'info> ExecuteNettedFlashLoan<'info> {
+    // Context for the borrower paying `to`, one of the two vaults
+    pub fn into_transfer_context(
+        &self,
+        to: &InterfaceAccount<'info, TokenAccount>,
+    ) -> CpiContext<'_, '_, '_, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
+            from: self.borrower_account.to_account_info().clone(),
+            mint: self.token_mint.to_account_info().clone(),
+            to: to.to_account_info().clone(),
+            authority: self.borrower.to_account_info().clone(),
+        };
+        CpiContext::new(self.token_program.to_account_info().clone(), cpi_accounts)
+    }
+
+    // Context for minting the receiving pool's LP shares, signed by that pool
+    pub fn into_mint_shares_context<'a, 'b, 'c>(
+        &self,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, MintTo<'info>> {
+        let cpi_accounts = MintTo {
+            mint: self.target_share_mint.to_account_info().clone(),
+            to: self.borrower_share_account.to_account_info().clone(),
+            authority: self.target_pool.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+}
+
+// Borrow `loan_amount` from the source pool and deposit `deposit_amount` into
+// the target pool, settling only the net transfers. The loan's principal
+// would leave the source vault and come straight back, so it never moves:
+// the borrower pays the source vault the fee and the target vault the
+// deposit. Rebalancing bots shuttling liquidity between pools of a mint save
+// the borrow and repay CPIs, while the loan is priced, capped and recorded
+// exactly like a `simple_flash_loan`
+pub(crate) fn handler(ctx: Context<ExecuteNettedFlashLoan>, loan_amount: u64, deposit_amount: u64, min_shares_out: u64) -> Result<()> {
+    let config = &ctx.accounts.source_config;
+    let clock = Clock::get()?;
+
+    require!(!ctx.accounts.source_pool.is_paused(PAUSE_BORROW), FlashLoanError::PoolPaused);
+    require!(!ctx.accounts.target_pool.is_paused(PAUSE_DEPOSIT), FlashLoanError::PoolPaused);
+    require_nonce_policy(config, ctx.accounts.instructions_sysvar.as_deref())?;
+    ctx.accounts.source_pool.require_accounting_price(ctx.accounts.accounting_price.as_deref())?;
+    // The loan state carries no experiment stats, so bucket pricing cannot apply
+    require!(config.experiment_share_bps == 0, FlashLoanError::FeeExperimentRunning);
+    require!(loan_amount > 0 && deposit_amount > 0, FlashLoanError::InvalidAmount);
+    require!(loan_amount <= config.max_loan_amount, FlashLoanError::LoanAmountTooLarge);
+    require!(
+        config.allows_probation(loan_amount, ctx.accounts.loan_state.loan_count),
+        FlashLoanError::ProbationLoanTooLarge
+    );
+    require!(ctx.accounts.source_vault.amount >= loan_amount, FlashLoanError::InsufficientFunds);
+    if clock.unix_timestamp < ctx.accounts.loan_state.last_loan_timestamp + config.loan_cooldown {
+        ctx.accounts.source_pool.log_ops_tag();
+        return err!(FlashLoanError::CooldownPeriodNotOver);
+    }
+    require!(!ctx.accounts.loan_state.active, FlashLoanError::Reentrancy);
+
+    let same_slot_loans = if ctx.accounts.loan_state.last_loan_slot == clock.slot {
+        ctx.accounts.loan_state.same_slot_loan_count
+    } else {
+        0
+    };
+    let vault_before = ctx.accounts.source_vault.amount;
+    let source_price_before = ctx.accounts.source_pool.share_price(vault_before, ctx.accounts.source_share_mint.supply);
+    #[cfg(feature = "strict-invariants")]
+    let snapshot = crate::invariants::SettlementSnapshot::capture(&ctx.accounts.source_pool, vault_before, ctx.accounts.source_share_mint.supply);
+    let fee_bps = ctx.accounts.source_pool.base_fee_bps(
+        loan_amount,
+        config.fee_bps,
+        ctx.accounts.accounting_price.as_deref(),
+        clock.unix_timestamp,
+    );
+    let fee = calculate_dynamic_fee(loan_amount, vault_before, fee_bps, &config.fee_curve)
+        + calculate_same_slot_surcharge(loan_amount, same_slot_loans, config.same_slot_surcharge_bps);
+    let protocol_fee = ctx.accounts.source_pool.protocol_fee(fee);
+
+    // Price the deposit before the receiving vault balance changes; only what
+    // reaches the vault after any Token-2022 transfer fee is credited
+    let received = deposit_amount - transfer_fee(&ctx.accounts.token_mint.to_account_info(), deposit_amount)?;
+    let target_price_before = ctx.accounts.target_pool.share_price(ctx.accounts.target_vault.amount, ctx.accounts.target_share_mint.supply);
+    let shares = shares_for_deposit(
+        received,
+        ctx.accounts.target_pool.lp_assets(ctx.accounts.target_vault.amount),
+        ctx.accounts.target_share_mint.supply,
+    );
+    require!(shares > 0, FlashLoanError::InvalidAmount);
+    require!(shares >= min_shares_out, FlashLoanError::SlippageExceeded);
+
+    if fee > 0 {
+        let payment = gross_for_net(&ctx.accounts.token_mint.to_account_info(), fee)?;
+        token_interface::transfer_checked(
+            ctx.accounts.into_transfer_context(&ctx.accounts.source_vault),
+            payment,
+            ctx.accounts.token_mint.decimals,
+        )?;
+    }
+    token_interface::transfer_checked(
+        ctx.accounts.into_transfer_context(&ctx.accounts.target_vault),
+        deposit_amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+    let target_seeds = ctx.accounts.target_pool.seeds();
+    token_interface::mint_to(ctx.accounts.into_mint_shares_context(&[&target_seeds[..]]), shares)?;
+
+    ctx.accounts.source_vault.reload()?;
+    ctx.accounts.source_pool.write_off_dust(vault_before, ctx.accounts.source_vault.amount, fee)?;
+    ctx.accounts.source_pool.accrue_fees(fee, protocol_fee);
+
+    let loan_state = &mut ctx.accounts.loan_state;
+    loan_state.pool = ctx.accounts.source_pool.key();
+    loan_state.borrower = ctx.accounts.borrower.key();
+    loan_state.bump = ctx.bumps.loan_state;
+    loan_state.last_loan_timestamp = clock.unix_timestamp;
+    loan_state.loan_count += 1;
+    loan_state.last_loan_slot = clock.slot;
+    loan_state.same_slot_loan_count = same_slot_loans + 1;
+
+    let lp_position = &mut ctx.accounts.lp_position;
+    crate::lp_fees::track_position_shares(
+        &ctx.accounts.target_pool,
+        ctx.accounts.lp_fee_accumulator.as_mut(),
+        lp_position,
+        shares,
+        0,
+        clock.unix_timestamp,
+    )?;
+    lp_position.owner = ctx.accounts.borrower.key();
+    lp_position.pool = ctx.accounts.target_pool.key();
+    lp_position.deposited_amount += received;
+    lp_position.record_deposit(received, shares, clock.slot);
+    lp_position.bump = ctx.bumps.lp_position;
+    ctx.accounts.target_pool.total_deposits += received;
+
+    emit!(FlashLoanExecuted {
+        pool: ctx.accounts.source_pool.key(),
+        borrower: ctx.accounts.borrower.key(),
+        loan_amount,
+        fee,
+        protocol_fee,
+        fee_deducted: false,
+        callback_program: Pubkey::default(), // No callback runs
+        callback_success: false,
+        sequence: ctx.accounts.source_pool.next_event_sequence(),
+    });
+    if ctx.accounts.source_pool.has_accounting_oracle() {
+        let accounting_price = ctx.accounts.accounting_price.as_deref();
+        emit!(FeeRevenueRecorded {
+            pool: ctx.accounts.source_pool.key(),
+            sequence: ctx.accounts.source_pool.event_sequence,
+            fee,
+            protocol_fee,
+            fee_usd_cents: ctx.accounts.source_pool.usd_cents(accounting_price, fee, clock.unix_timestamp),
+            protocol_fee_usd_cents: ctx.accounts.source_pool.usd_cents(accounting_price, protocol_fee, clock.unix_timestamp),
+        });
+    }
+    emit!(LiquidityDeposited {
+        pool: ctx.accounts.target_pool.key(),
+        lp: ctx.accounts.borrower.key(),
+        amount: received,
+        shares,
+    });
+    emit!(LoanNetted {
+        source_pool: ctx.accounts.source_pool.key(),
+        target_pool: ctx.accounts.target_pool.key(),
+        borrower: ctx.accounts.borrower.key(),
+        loan_amount,
+        deposit_amount: received,
+        fee,
+        sequence: ctx.accounts.source_pool.event_sequence,
+    });
+
+    #[cfg(feature = "strict-invariants")]
+    crate::invariants::check_settlement(
+        &snapshot,
+        &ctx.accounts.source_pool,
+        ctx.accounts.source_vault.amount,
+        ctx.accounts.source_share_mint.supply,
+        fee,
+        0,
+    )?;
+    emit!(SharePriceUpdated {
+        pool: ctx.accounts.source_pool.key(),
+        price_before: source_price_before,
+        price_after: ctx.accounts.source_pool.share_price(ctx.accounts.source_vault.amount, ctx.accounts.source_share_mint.supply),
+        lp_assets: ctx.accounts.source_pool.lp_assets(ctx.accounts.source_vault.amount),
+        share_supply: ctx.accounts.source_share_mint.supply,
+    });
+    ctx.accounts.target_vault.reload()?;
+    ctx.accounts.target_share_mint.reload()?;
+    emit!(SharePriceUpdated {
+        pool: ctx.accounts.target_pool.key(),
+        price_before: target_price_before,
+        price_after: ctx.accounts.target_pool.share_price(ctx.accounts.target_vault.amount, ctx.accounts.target_share_mint.supply),
+        lp_assets: ctx.accounts.target_pool.lp_assets(ctx.accounts.target_vault.amount),
+        share_supply: ctx.accounts.target_share_mint.supply,
+    });
+
+    Ok(())
+}
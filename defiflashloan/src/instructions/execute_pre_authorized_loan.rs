@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::FlashLoanError;
+use crate::instructions::execute_flash_loan::{process_flash_loan, ExecuteFlashLoan, LoanCallback};
+
+// Keeper runs the loan a borrower pre-authorized; the shared flow checks the
+// window, amount, fee cap and callback against the `pre_auth` account
+pub(crate) fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteFlashLoan<'info>>,
+    callback_data: Vec<u8>,
+) -> Result<()> {
+    let pre_auth = ctx.accounts.pre_auth.as_ref().ok_or(FlashLoanError::PreAuthMismatch)?;
+    let (loan_amount, window_end) = (pre_auth.loan_amount, pre_auth.window_end);
+    process_flash_loan(
+        ctx,
+        loan_amount,
+        window_end,
+        false,
+        None,
+        false,
+        LoanCallback::Raw(callback_data),
+    )
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+
+use crate::errors::FlashLoanError;
+use crate::instructions::execute_flash_loan::{process_flash_loan, ExecuteFlashLoan, LoanCallback};
+use crate::sigverify::verify_ed25519_ix;
+
+// Quote terms signed off-chain by the pool's quote signer
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LoanQuote {
+    pub pool: Pubkey,
+    pub borrower: Pubkey,
+    pub loan_amount: u64,
+    pub fee: u64,
+    pub expires_at: i64,
+}
+
+// Execute a flash loan at a fee quoted off-chain (RFQ mode)
+// The transaction must carry an Ed25519 program instruction right before this
+// one, verifying the borsh-serialized `LoanQuote` signed by `pool.quote_signer`
+pub(crate) fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteFlashLoan<'info>>,
+    loan_amount: u64,
+    fee: u64,
+    expires_at: i64,
+    callback_data: Vec<u8>,
+) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+    require_keys_neq!(pool.quote_signer, Pubkey::default(), FlashLoanError::QuoteSignerNotSet);
+
+    let clock = Clock::get()?;
+    require!(clock.unix_timestamp <= expires_at, FlashLoanError::QuoteExpired);
+
+    let instructions_sysvar = ctx
+        .accounts
+        .instructions_sysvar
+        .as_ref()
+        .ok_or(FlashLoanError::QuoteSignatureMissing)?
+        .to_account_info();
+    let current_index = load_current_index_checked(&instructions_sysvar)?;
+    require!(current_index > 0, FlashLoanError::QuoteSignatureMissing);
+    let signature_ix = load_instruction_at_checked((current_index - 1) as usize, &instructions_sysvar)?;
+
+    let quote = LoanQuote {
+        pool: pool.key(),
+        borrower: ctx.accounts.borrower.key(),
+        loan_amount,
+        fee,
+        expires_at,
+    };
+    verify_ed25519_ix(&signature_ix, &pool.quote_signer, &quote.try_to_vec()?)?;
+
+    process_flash_loan(
+        ctx,
+        loan_amount,
+        expires_at,
+        false,
+        Some(fee),
+        false,
+        LoanCallback::Raw(callback_data),
+    )
+}
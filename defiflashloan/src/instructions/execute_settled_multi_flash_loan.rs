@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::FeesSettled;
+use crate::instructions::execute_multi_flash_loan::{process_multi_flash_loan, LoanLeg};
+use crate::state::{FeePrice, FeeSettlement, ProtocolConfig};
+
+// Context for a multi-mint loan whose fees are paid once in the settlement
+// mint; `remaining_accounts` hold the legs as for `execute_multi_flash_loan`,
+// then one FeePrice per leg, then the callback
+#[derive(Accounts)]
+pub struct ExecuteSettledMultiFlashLoan<'info> {
+    #[account(mut)]
+    pub borrower: Signer<'info>,                   // Receives and repays every leg, pays the fee
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(seeds = [FEE_SETTLEMENT_SEED], bump = fee_settlement.bump, has_one = settlement_mint)]
+    pub fee_settlement: Account<'info, FeeSettlement>,
+    pub settlement_mint: InterfaceAccount<'info, Mint>, // Mint the aggregate fee is paid in
+    #[account(mut, token::mint = settlement_mint, token::authority = borrower)]
+    pub borrower_settlement_account: InterfaceAccount<'info, TokenAccount>, // Pays the aggregate fee
+    #[account(
+        mut,
+        token::mint = settlement_mint,
+        token::authority = protocol_config.treasury
+    )]
+    pub treasury_settlement_account: InterfaceAccount<'info, TokenAccount>, // Receives the aggregate fee
+    pub settlement_token_program: Interface<'info, TokenInterface>,
+    /// CHECK: Address is constrained to the Instructions sysvar
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>, // Required by pools refusing durable nonces
+}
+
+impl<'info> ExecuteSettledMultiFlashLoan<'info> {
+    // Context for paying the aggregate fee to the treasury
+    pub fn into_transfer_fee_context(&self) -> CpiContext<'_, '_, '_, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
+            from: self.borrower_settlement_account.to_account_info().clone(),
+            mint: self.settlement_mint.to_account_info().clone(),
+            to: self.treasury_settlement_account.to_account_info().clone(),
+            authority: self.borrower.to_account_info().clone(),
+        };
+        CpiContext::new(self.settlement_token_program.to_account_info().clone(), cpi_accounts)
+    }
+}
+
+// Borrow every leg and repay principal plus the LP and creator fee shares in
+// kind, then pay every leg's protocol fee, converted at the posted FeePrice,
+// as one transfer to the treasury
+pub(crate) fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteSettledMultiFlashLoan<'info>>,
+    legs: Vec<LoanLeg>,
+    callback_data: Vec<u8>,
+) -> Result<()> {
+    let leg_accounts_len = legs.len() * LOAN_LEG_ACCOUNTS;
+    require!(
+        ctx.remaining_accounts.len() >= leg_accounts_len + legs.len(),
+        FlashLoanError::LoanLegMismatch
+    );
+    let (leg_infos, rest) = ctx.remaining_accounts.split_at(leg_accounts_len);
+    let (price_infos, callback_accounts) = rest.split_at(legs.len());
+
+    let settled = process_multi_flash_loan(
+        &ctx.accounts.borrower,
+        ctx.accounts.instructions_sysvar.as_deref(),
+        leg_infos,
+        callback_accounts,
+        &legs,
+        callback_data,
+        true,
+    )?;
+
+    // Each leg's mint is the fifth account of its group
+    let now = Clock::get()?.unix_timestamp;
+    let max_price_age = ctx.accounts.fee_settlement.max_price_age;
+    let mut total_fee: u64 = 0;
+    for ((leg, price_info), leg_infos) in settled.iter().zip(price_infos).zip(leg_infos.chunks(LOAN_LEG_ACCOUNTS)) {
+        let fee_price = Account::<FeePrice>::try_from(price_info)?;
+        require_keys_eq!(fee_price.token_mint, leg_infos[4].key(), FlashLoanError::FeePriceMismatch);
+        total_fee = total_fee
+            .checked_add(fee_price.convert(leg.protocol_fee, now, max_price_age)?)
+            .ok_or(FlashLoanError::StaleFeePrice)?;
+    }
+
+    token_interface::transfer_checked(
+        ctx.accounts.into_transfer_fee_context(),
+        total_fee,
+        ctx.accounts.settlement_mint.decimals,
+    )?;
+
+    emit!(FeesSettled {
+        borrower: ctx.accounts.borrower.key(),
+        settlement_mint: ctx.accounts.settlement_mint.key(),
+        amount: total_fee,
+        pools: legs.iter().map(|leg| leg.pool).collect(),
+        sequences: settled.iter().map(|leg| leg.sequence).collect(),
+    });
+
+    Ok(())
+}
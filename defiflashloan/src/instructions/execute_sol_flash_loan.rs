@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::get_stack_height;
+use anchor_lang::system_program::{self, Transfer as SystemTransfer};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::FlashLoanExecuted;
+use crate::fees::calculate_dynamic_fee;
+use crate::instructions::execute_flash_loan::invoke_callback;
+use crate::receiver::callback_reported_success;
+use crate::state::{FeeCurve, ProgramRegistry, SolPool};
+
+// Context for a lamport flash loan
+#[derive(Accounts)]
+pub struct ExecuteSolFlashLoan<'info> {
+    #[account(mut, seeds = [SOL_POOL_SEED], bump = sol_pool.bump)]
+    pub sol_pool: Account<'info, SolPool>,
+    #[account(mut, seeds = [SOL_VAULT_SEED], bump = sol_pool.vault_bump)]
+    pub sol_vault: SystemAccount<'info>,           // Lamport vault
+    #[account(mut)]
+    pub borrower: Signer<'info>,                   // Receives and repays lamports
+    pub system_program: Program<'info, System>,
+    #[account(seeds = [PROGRAM_REGISTRY_SEED, sol_pool.key().as_ref()], bump = program_registry.bump)]
+    pub program_registry: Option<Account<'info, ProgramRegistry>>, // Required for callbacks
+}
+
+impl<'info> ExecuteSolFlashLoan<'info> {
+    // Context for lending lamports to the borrower, signed by the vault PDA
+    pub fn into_transfer_to_borrower_context<'a, 'b, 'c>(
+        &self,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, SystemTransfer<'info>> {
+        let cpi_accounts = SystemTransfer {
+            from: self.sol_vault.to_account_info().clone(),
+            to: self.borrower.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.system_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+
+    // Context for the borrower repaying lamports into the vault
+    pub fn into_transfer_to_vault_context(&self) -> CpiContext<'_, '_, '_, 'info, SystemTransfer<'info>> {
+        let cpi_accounts = SystemTransfer {
+            from: self.borrower.to_account_info().clone(),
+            to: self.sol_vault.to_account_info().clone(),
+        };
+        CpiContext::new(self.system_program.to_account_info().clone(), cpi_accounts)
+    }
+}
+
+// Lend lamports from the vault, run the borrower callback carried in
+// `remaining_accounts`, then take back principal plus fee and check the
+// vault's lamport balance grew by at least the fee
+pub(crate) fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteSolFlashLoan<'info>>,
+    loan_amount: u64,
+    callback_data: Vec<u8>,
+) -> Result<()> {
+    require!(
+        get_stack_height() <= DEFAULT_MAX_INVOKE_DEPTH as usize,
+        FlashLoanError::InvokeDepthExceeded
+    );
+    require!(!ctx.accounts.sol_pool.active, FlashLoanError::Reentrancy);
+    require!(loan_amount > 0, FlashLoanError::InvalidAmount);
+    require!(callback_data.len() <= MAX_CALLBACK_DATA_LEN, FlashLoanError::CallbackTooLarge);
+    require!(
+        loan_amount <= ctx.accounts.sol_pool.max_loan_amount,
+        FlashLoanError::LoanAmountTooLarge
+    );
+
+    // The vault must stay rent exempt while the loan is out
+    let vault_before = ctx.accounts.sol_vault.lamports();
+    let available = vault_before.saturating_sub(Rent::get()?.minimum_balance(0));
+    require!(loan_amount <= available, FlashLoanError::InsufficientFunds);
+
+    let fee = calculate_dynamic_fee(loan_amount, available, ctx.accounts.sol_pool.fee_bps, &FeeCurve::DEFAULT);
+    // Persist the flag before any CPI, so nothing the callback reaches sees
+    // the pool as idle while the loan is out
+    ctx.accounts.sol_pool.active = true;
+    ctx.accounts.sol_pool.exit(&crate::ID)?;
+
+    let vault_seeds = ctx.accounts.sol_pool.vault_seeds();
+    system_program::transfer(
+        ctx.accounts.into_transfer_to_borrower_context(&[&vault_seeds[..]]),
+        loan_amount,
+    )?;
+
+    let callback_program = invoke_callback(
+        ctx.remaining_accounts,
+        &[],
+        callback_data,
+        ctx.accounts.program_registry.as_deref(),
+    )?;
+    let callback_success = callback_reported_success(&callback_program);
+
+    system_program::transfer(ctx.accounts.into_transfer_to_vault_context(), loan_amount + fee)?;
+    require!(
+        ctx.accounts.sol_vault.lamports() >= vault_before + fee,
+        FlashLoanError::IncorrectRepayment
+    );
+
+    // Pick up anything the callback changed on the pool before settling it
+    ctx.accounts.sol_pool.reload()?;
+    let sol_pool = &mut ctx.accounts.sol_pool;
+    sol_pool.fees_accrued += fee;
+    sol_pool.active = false;
+
+    emit!(FlashLoanExecuted {
+        pool: sol_pool.key(),
+        borrower: ctx.accounts.borrower.key(),
+        loan_amount,
+        fee,
+        protocol_fee: fee,              // Lamport pool fees all accrue to its admin
+        fee_deducted: false,
+        callback_program,
+        callback_success,
+        sequence: sol_pool.next_event_sequence(),
+    });
+
+    Ok(())
+}
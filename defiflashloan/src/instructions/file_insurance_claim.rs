@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::InsuranceClaimFiled;
+use crate::state::{ClaimStatus, InsuranceClaim, InsuranceFund, InsurancePolicy};
+
+// Context for claiming a refund on an insured loan
+#[derive(Accounts)]
+#[instruction(loan_slot: u64)]
+pub struct FileInsuranceClaim<'info> {
+    #[account(mut)]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+    #[account(
+        has_one = insurance_fund,
+        has_one = borrower,
+        seeds = [INSURANCE_POLICY_SEED, insurance_fund.key().as_ref(), borrower.key().as_ref()],
+        bump = insurance_policy.bump
+    )]
+    pub insurance_policy: Account<'info, InsurancePolicy>,
+    #[account(
+        init,
+        payer = borrower,
+        space = InsuranceClaim::LEN,
+        seeds = [INSURANCE_CLAIM_SEED, insurance_policy.key().as_ref(), loan_slot.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub insurance_claim: Account<'info, InsuranceClaim>,
+    #[account(mut)]
+    pub borrower: Signer<'info>,                   // Insured borrower
+    pub system_program: Program<'info, System>,
+}
+
+// File one claim for `requested_amount` against the insured loan borrowed in
+// `loan_slot`, naming the hash of the evidence that a protocol bug broke its
+// settlement; the request is capped by the loan's principal and by the fund
+pub(crate) fn handler(
+    ctx: Context<FileInsuranceClaim>,
+    loan_slot: u64,
+    requested_amount: u64,
+    evidence: [u8; 32],
+) -> Result<()> {
+    let loan = ctx
+        .accounts
+        .insurance_policy
+        .insured_loan(loan_slot)
+        .ok_or(FlashLoanError::LoanNotInsured)?;
+    require!(
+        requested_amount > 0
+            && requested_amount <= loan.loan_amount
+            && requested_amount <= ctx.accounts.insurance_fund.max_claim_amount,
+        FlashLoanError::InvalidInsuranceClaim
+    );
+
+    let claim = &mut ctx.accounts.insurance_claim;
+    claim.insurance_fund = ctx.accounts.insurance_fund.key();
+    claim.claimant = ctx.accounts.borrower.key();
+    claim.loan_slot = loan_slot;
+    claim.requested_amount = requested_amount;
+    claim.evidence = evidence;
+    claim.status = ClaimStatus::Pending;
+    claim.filed_at = Clock::get()?.unix_timestamp;
+    claim.bump = ctx.bumps.insurance_claim;
+    ctx.accounts.insurance_fund.pending_claims += 1;
+
+    emit!(InsuranceClaimFiled {
+        insurance_fund: claim.insurance_fund,
+        claim: claim.key(),
+        claimant: claim.claimant,
+        loan_slot,
+        requested_amount,
+        evidence,
+    });
+
+    Ok(())
+}
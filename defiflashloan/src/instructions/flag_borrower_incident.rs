@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::events::BorrowerIncidentFlagged;
+use crate::state::{BorrowerReputation, ProtocolConfig};
+
+// Context for recording an incident against a borrower
+#[derive(Accounts)]
+pub struct FlagBorrowerIncident<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump, has_one = admin)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, borrower.key().as_ref()],
+        bump = borrower_reputation.bump
+    )]
+    pub borrower_reputation: Account<'info, BorrowerReputation>,
+    /// CHECK: Only keys the reputation PDA
+    pub borrower: UncheckedAccount<'info>,         // Borrower being flagged
+    pub admin: Signer<'info>,                      // Protocol admin
+}
+
+// Record an incident, which zeroes the borrower's score from now on
+pub(crate) fn handler(ctx: Context<FlagBorrowerIncident>) -> Result<()> {
+    let reputation = &mut ctx.accounts.borrower_reputation;
+    reputation.incidents += 1;
+
+    emit!(BorrowerIncidentFlagged {
+        borrower: reputation.borrower,
+        incidents: reputation.incidents,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,310 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT}, sysvar::instructions as sysvar_instructions};
+use anchor_lang::system_program::{self, Transfer as SystemTransfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::{LoanInsured, PoolSaturated, PublicGoodsLoanCharged, RateLimitExhausted};
+use crate::fees::{calculate_dynamic_fee, calculate_same_slot_surcharge};
+use crate::introspection::{compute_unit_price, require_flash_repay, require_nonce_policy};
+use crate::state::{FeeExperimentStats, FeePrice, InsuranceFund, InsurancePolicy, LoanReceipt, LoanState, Pool, PoolConfig};
+use crate::transfer_fee::gross_for_net;
+
+// Context for the borrow half of a split flash loan
+#[derive(Accounts)]
+pub struct FlashBorrow<'info> {
+    #[account(mut, seeds = [POOL_SEED, pool.token_mint.as_ref(), pool.index.as_ref()], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,                // Pool PDA, signs for the vault
+    #[account(seeds = [POOL_CONFIG_SEED, pool.key().as_ref()], bump = pool_config.bump)]
+    pub pool_config: Account<'info, PoolConfig>,   // Pool risk parameters
+    #[account(
+        mut,
+        address = pool.vault,
+        constraint = loan_vault.mint == pool.token_mint @ FlashLoanError::MintMismatch
+    )]
+    pub loan_vault: InterfaceAccount<'info, TokenAccount>, // Flash loan pool
+    #[account(
+        mut,
+        constraint = borrower_account.mint == pool.token_mint @ FlashLoanError::MintMismatch
+    )]
+    pub borrower_account: InterfaceAccount<'info, TokenAccount>, // Receives the loan
+    #[account(mut)]
+    pub borrower: Signer<'info>,                   // Borrower signing the transaction
+    #[account(
+        mut,
+        seeds = [LOAN_STATE_SEED, pool.key().as_ref(), borrower.key().as_ref()],
+        bump = loan_state.bump
+    )]
+    pub loan_state: Account<'info, LoanState>,     // Stays active until `flash_repay`
+    #[account(
+        init,
+        payer = borrower,
+        space = LoanReceipt::LEN,
+        seeds = [LOAN_RECEIPT_SEED, loan_state.key().as_ref()],
+        bump
+    )]
+    pub loan_receipt: Account<'info, LoanReceipt>, // Must be closed by `flash_repay` in the same transaction
+    #[account(address = pool.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>, // Mint lent out by the pool, for checked transfers
+    pub token_program: Interface<'info, TokenInterface>,
+    /// CHECK: Address is constrained to the Instructions sysvar
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>, // Used to find the matching repay
+    pub system_program: Program<'info, System>,
+    #[account(
+        mut,
+        seeds = [FEE_EXPERIMENT_SEED, pool.key().as_ref()],
+        bump = fee_experiment_stats.bump
+    )]
+    pub fee_experiment_stats: Option<Account<'info, FeeExperimentStats>>, // Required while the pool runs a fee experiment
+    /// CHECK: Address is constrained to the pool admin, which only receives lamports
+    #[account(mut, address = pool.admin)]
+    pub pool_admin: Option<UncheckedAccount<'info>>, // Required by public goods pools, receives the anti-spam charge
+    #[account(address = pool.accounting_oracle)]
+    pub accounting_price: Option<Account<'info, FeePrice>>, // Pass to apply USD fee tiers, which otherwise charge the first tier
+    #[account(mut, seeds = [INSURANCE_FUND_SEED, pool.key().as_ref()], bump = insurance_fund.bump)]
+    pub insurance_fund: Option<Account<'info, InsuranceFund>>, // Passed with the two below to insure the loan
+    #[account(mut)]
+    pub insurance_vault: Option<InterfaceAccount<'info, TokenAccount>>, // Fund vault receiving the premium
+    #[account(mut)]
+    pub insurance_policy: Option<Account<'info, InsurancePolicy>>, // Borrower's policy with the fund
+}
+
+impl<'info> FlashBorrow<'info> {
+    // Context for transferring tokens to borrower, signed by the pool PDA
+    pub fn into_transfer_to_borrower_context<'a, 'b, 'c>(
+        &self,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
+            from: self.loan_vault.to_account_info().clone(),
+            mint: self.token_mint.to_account_info().clone(),
+            to: self.borrower_account.to_account_info().clone(),
+            authority: self.pool.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+
+    // Context for the borrower paying a public goods pool's anti-spam charge
+    pub fn into_pay_charge_context(&self, pool_admin: AccountInfo<'info>) -> CpiContext<'_, '_, '_, 'info, SystemTransfer<'info>> {
+        let cpi_accounts = SystemTransfer {
+            from: self.borrower.to_account_info().clone(),
+            to: pool_admin,
+        };
+        CpiContext::new(self.system_program.to_account_info().clone(), cpi_accounts)
+    }
+
+    // Context for the borrower paying the insurance premium into the fund vault
+    pub fn into_pay_premium_context(&self, insurance_vault: AccountInfo<'info>) -> CpiContext<'_, '_, '_, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
+            from: self.borrower_account.to_account_info().clone(),
+            mint: self.token_mint.to_account_info().clone(),
+            to: insurance_vault,
+            authority: self.borrower.to_account_info().clone(),
+        };
+        CpiContext::new(self.token_program.to_account_info().clone(), cpi_accounts)
+    }
+}
+
+// Lend `amount` to the borrower and open a receipt fixing the fee; the
+// transaction must later call `flash_repay` for this pool and amount that
+// closes the receipt, or it is rejected here
+// A `client_id` is kept on the receipt and in the loan state's ring of recent
+// ids, so a bot retrying a borrow whose status it could not confirm is
+// refused once the first attempt has landed
+// On a public goods pool the loan is fee-free, each borrower is limited to
+// the pool's daily loan count, and every loan pays the pool's lamport charge
+// to its admin, who runs the utility
+// Passing the pool's insurance fund, its vault and the borrower's policy
+// insures the loan: the premium is paid out of the disbursed funds and the
+// loan is recorded on the policy, open to a claim if its settlement breaks
+pub(crate) fn handler(ctx: Context<FlashBorrow>, amount: u64, client_id: Option<[u8; 16]>) -> Result<()> {
+    let config = &ctx.accounts.pool_config;
+    let clock = Clock::get()?;
+
+    require!(!ctx.accounts.pool.is_split_borrow_paused(), FlashLoanError::PoolPaused);
+
+    // Introspection only sees top-level instructions, so refuse to run under CPI
+    require!(
+        get_stack_height() == TRANSACTION_LEVEL_STACK_HEIGHT,
+        FlashLoanError::InvokeDepthExceeded
+    );
+    require_nonce_policy(config, Some(&ctx.accounts.instructions_sysvar.to_account_info()))?;
+    // Checked before the cooldown so a retry is reported as the duplicate it is
+    if let Some(client_id) = client_id {
+        require!(!ctx.accounts.loan_state.has_client_id(&client_id), FlashLoanError::DuplicateClientId);
+    }
+
+    require!(amount > 0, FlashLoanError::InvalidAmount);
+    require!(amount <= config.max_loan_amount, FlashLoanError::LoanAmountTooLarge);
+    require!(
+        config.allows_probation(amount, ctx.accounts.loan_state.loan_count),
+        FlashLoanError::ProbationLoanTooLarge
+    );
+    require!(ctx.accounts.loan_vault.amount >= amount, FlashLoanError::InsufficientFunds);
+
+    let cooldown_ends_at = ctx.accounts.loan_state.last_loan_timestamp + config.loan_cooldown;
+    if clock.unix_timestamp < cooldown_ends_at {
+        emit!(RateLimitExhausted {
+            pool: ctx.accounts.pool.key(),
+            borrower: ctx.accounts.borrower.key(),
+            loan_amount: amount,
+            retry_at: cooldown_ends_at,
+            ops_tag: ctx.accounts.pool.ops_tag,
+        });
+        ctx.accounts.pool.log_ops_tag();
+        return err!(FlashLoanError::CooldownPeriodNotOver);
+    }
+
+    require!(!ctx.accounts.loan_state.active, FlashLoanError::Reentrancy);
+
+    // Each open receipt is a distinct borrower, since the receipt is keyed by loan state
+    let max_borrowers = config.max_concurrent_borrowers;
+    if max_borrowers > 0 && ctx.accounts.pool.open_receipts >= max_borrowers {
+        emit!(PoolSaturated {
+            pool: ctx.accounts.pool.key(),
+            borrower: ctx.accounts.borrower.key(),
+            loan_amount: amount,
+            open_borrowers: ctx.accounts.pool.open_receipts,
+            max_concurrent_borrowers: max_borrowers,
+            ops_tag: ctx.accounts.pool.ops_tag,
+        });
+        ctx.accounts.pool.log_ops_tag();
+        return err!(FlashLoanError::PoolSaturated);
+    }
+
+    let public_goods = ctx.accounts.pool.is_public_goods();
+    if public_goods {
+        let daily_limit = ctx.accounts.pool.public_goods_daily_loans;
+        require!(
+            ctx.accounts.loan_state.record_daily_loan(clock.unix_timestamp, daily_limit),
+            FlashLoanError::DailyLoanLimitReached
+        );
+        let pool_admin = ctx.accounts.pool_admin.as_ref().ok_or(FlashLoanError::ChargeRecipientMissing)?;
+        let charge = ctx.accounts.pool.public_goods_charge;
+        system_program::transfer(ctx.accounts.into_pay_charge_context(pool_admin.to_account_info()), charge)?;
+        emit!(PublicGoodsLoanCharged {
+            pool: ctx.accounts.pool.key(),
+            borrower: ctx.accounts.borrower.key(),
+            charge_lamports: charge,
+            day_loan_count: ctx.accounts.loan_state.day_loan_count,
+        });
+    }
+
+    require_flash_repay(
+        &ctx.accounts.instructions_sysvar.to_account_info(),
+        &ctx.accounts.pool.key(),
+        &ctx.accounts.loan_state.key(),
+        &ctx.accounts.loan_receipt.key(),
+        amount,
+    )?;
+
+    let same_slot_loans = if ctx.accounts.loan_state.last_loan_slot == clock.slot {
+        ctx.accounts.loan_state.same_slot_loan_count
+    } else {
+        0
+    };
+    let fee_bps = ctx.accounts.pool.base_fee_bps(
+        amount,
+        config.fee_bps,
+        ctx.accounts.accounting_price.as_deref(),
+        clock.unix_timestamp,
+    );
+    // Borrowers in a running fee experiment are priced by their bucket, and the
+    // experiment's stats must come along to record the loan; public goods
+    // loans are never priced
+    let experiment_bucket = config.fee_experiment_bucket(&ctx.accounts.borrower.key()).filter(|_| !public_goods);
+    require!(
+        experiment_bucket.is_none() || ctx.accounts.fee_experiment_stats.is_some(),
+        FlashLoanError::FeeExperimentStatsMissing
+    );
+    let fee_bps = if public_goods {
+        0
+    } else if experiment_bucket == Some(FEE_EXPERIMENT_VARIANT) {
+        config.experiment_fee_bps
+    } else {
+        fee_bps
+    };
+    let loan_receipt = &mut ctx.accounts.loan_receipt;
+    loan_receipt.pool = ctx.accounts.pool.key();
+    loan_receipt.borrower = ctx.accounts.borrower.key();
+    loan_receipt.token_mint = ctx.accounts.pool.token_mint;
+    loan_receipt.amount = amount;
+    loan_receipt.fee = if public_goods {
+        0
+    } else {
+        calculate_dynamic_fee(amount, ctx.accounts.loan_vault.amount, fee_bps, &config.fee_curve)
+            + calculate_same_slot_surcharge(amount, same_slot_loans, config.same_slot_surcharge_bps)
+    };
+    // A Token-2022 transfer fee is withheld on the way back too
+    loan_receipt.repayment = gross_for_net(&ctx.accounts.token_mint.to_account_info(), amount + loan_receipt.fee)?;
+    loan_receipt.fee_bps = fee_bps;
+    loan_receipt.max_loan_amount = config.max_loan_amount;
+    loan_receipt.reserve_factor_bps = ctx.accounts.pool.reserve_factor_bps;
+    loan_receipt.client_id = client_id.unwrap_or_default();
+    loan_receipt.compute_unit_price = compute_unit_price(&ctx.accounts.instructions_sysvar)?;
+    let premium = match (&ctx.accounts.insurance_fund, &ctx.accounts.insurance_vault, &ctx.accounts.insurance_policy) {
+        (None, None, None) => None,
+        (Some(fund), Some(vault), Some(policy)) => {
+            require_keys_eq!(vault.key(), fund.vault, FlashLoanError::InsuranceAccountsMissing);
+            require_keys_eq!(policy.insurance_fund, fund.key(), FlashLoanError::InsuranceAccountsMissing);
+            require_keys_eq!(policy.borrower, ctx.accounts.borrower.key(), FlashLoanError::InsuranceAccountsMissing);
+            require!(fund.premium_bps > 0, FlashLoanError::InvalidInsuranceConfig);
+            Some(fund.premium(amount))
+        }
+        _ => return err!(FlashLoanError::InsuranceAccountsMissing),
+    };
+    loan_receipt.insured = premium.is_some();
+    loan_receipt.premium = premium.unwrap_or(0);
+    loan_receipt.bump = ctx.bumps.loan_receipt;
+    let fee = loan_receipt.fee;
+
+    // The repay is guaranteed by introspection, so the loan is recorded now
+    if let (Some(bucket), Some(stats)) = (experiment_bucket, ctx.accounts.fee_experiment_stats.as_mut()) {
+        stats.sync(&ctx.accounts.pool_config, clock.unix_timestamp);
+        stats.record(bucket, amount, fee);
+    }
+
+    let vault_amount = ctx.accounts.loan_vault.amount;
+    ctx.accounts.pool.note_utilization(amount, vault_amount, clock.slot);
+    ctx.accounts.pool.open_loan_amount += amount;
+    ctx.accounts.pool.open_receipts += 1;
+
+    ctx.accounts.loan_state.active = true;
+    ctx.accounts.loan_state.active_since = clock.unix_timestamp;
+    if let Some(client_id) = client_id {
+        ctx.accounts.loan_state.record_client_id(client_id);
+    }
+
+    let pool_seeds = ctx.accounts.pool.seeds();
+    token_interface::transfer_checked(
+        ctx.accounts.into_transfer_to_borrower_context(&[&pool_seeds[..]]),
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    if let (Some(premium), Some(vault)) = (premium.filter(|&premium| premium > 0), &ctx.accounts.insurance_vault) {
+        token_interface::transfer_checked(
+            ctx.accounts.into_pay_premium_context(vault.to_account_info()),
+            premium,
+            ctx.accounts.token_mint.decimals,
+        )?;
+    }
+    if let (Some(premium), Some(fund), Some(policy)) =
+        (premium, ctx.accounts.insurance_fund.as_mut(), ctx.accounts.insurance_policy.as_mut())
+    {
+        fund.total_premiums += premium;
+        policy.record_loan(clock.slot, amount, premium);
+        emit!(LoanInsured {
+            pool: ctx.accounts.pool.key(),
+            borrower: ctx.accounts.borrower.key(),
+            insurance_fund: fund.key(),
+            loan_amount: amount,
+            premium,
+            loan_slot: clock.slot,
+        });
+    }
+
+    Ok(())
+}
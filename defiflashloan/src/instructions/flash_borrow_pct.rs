@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::instructions::execute_flash_loan::{process_flash_loan, ExecuteFlashLoan, LoanCallback};
+
+// Borrow a share of the live vault balance, expressed in basis points
+pub(crate) fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteFlashLoan<'info>>,
+    loan_bps: u16,
+    loan_expiration: i64,
+    callback_data: Vec<u8>,
+) -> Result<()> {
+    require!(
+        loan_bps > 0 && u64::from(loan_bps) <= BPS_DENOMINATOR,
+        FlashLoanError::InvalidLoanBps
+    );
+
+    // Resolve the amount on-chain so it always matches the current pool depth
+    let loan_amount = (ctx.accounts.loan_vault.amount as u128 * loan_bps as u128
+        / BPS_DENOMINATOR as u128) as u64;
+    require!(loan_amount > 0, FlashLoanError::InvalidLoanBps);
+
+    process_flash_loan(
+        ctx,
+        loan_amount,
+        loan_expiration,
+        false,
+        None,
+        false,
+        LoanCallback::Raw(callback_data),
+    )
+}
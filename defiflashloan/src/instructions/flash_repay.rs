@@ -0,0 +1,240 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::{FeeRevenueRecorded, FeesForwarded, FlashLoanExecuted, SharePriceUpdated};
+use crate::fee_forwarding::ForwardedFees;
+use crate::fees::calculate_protocol_fee;
+use crate::state::{FeePrice, LoanReceipt, LoanState, LoanStats, Pool, ProtocolConfig};
+use crate::transfer_fee::{gross_for_net, transfer_fee};
+
+// Context for the repay half of a split flash loan
+// `flash_borrow` finds `pool`, `loan_state` and `loan_receipt` by position, keep the order
+// Up to MAX_REPAY_SOURCES more borrower token accounts may follow in
+// `remaining_accounts`, drawn on in order once `borrower_account` runs dry
+#[derive(Accounts)]
+pub struct FlashRepay<'info> {
+    #[account(
+        mut,
+        seeds = [POOL_SEED, pool.token_mint.as_ref(), pool.index.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        address = pool.vault,
+        constraint = loan_vault.mint == pool.token_mint @ FlashLoanError::MintMismatch
+    )]
+    pub loan_vault: InterfaceAccount<'info, TokenAccount>, // Flash loan pool
+    #[account(
+        mut,
+        constraint = borrower_account.mint == pool.token_mint @ FlashLoanError::MintMismatch
+    )]
+    pub borrower_account: InterfaceAccount<'info, TokenAccount>, // Pays back principal and fee
+    #[account(mut)]
+    pub borrower: Signer<'info>,                   // Borrower signing the transaction
+    #[account(
+        mut,
+        seeds = [LOAN_STATE_SEED, pool.key().as_ref(), borrower.key().as_ref()],
+        bump = loan_state.bump
+    )]
+    pub loan_state: Account<'info, LoanState>,     // Set active by `flash_borrow`
+    #[account(mut)]
+    pub loan_stats: Option<Account<'info, LoanStats>>, // Loan statistics account
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(
+        mut,
+        close = borrower,
+        has_one = pool,
+        has_one = borrower,
+        seeds = [LOAN_RECEIPT_SEED, loan_state.key().as_ref()],
+        bump = loan_receipt.bump
+    )]
+    pub loan_receipt: Account<'info, LoanReceipt>, // Opened by `flash_borrow`, closed here
+    #[account(address = pool.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>, // Mint lent out by the pool, for checked transfers
+    #[account(address = pool.share_mint)]
+    pub share_mint: InterfaceAccount<'info, Mint>, // LP share mint, read to report the share price
+    #[account(address = pool.accounting_oracle)]
+    pub accounting_price: Option<Account<'info, FeePrice>>, // Required while the pool reports fees in USD
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Option<Account<'info, ProtocolConfig>>, // Names the treasury, with `treasury_account`
+    #[account(mut)]
+    pub treasury_account: Option<InterfaceAccount<'info, TokenAccount>>, // Set to forward the protocol fee share
+    #[account(mut)]
+    pub creator_fee_account: Option<InterfaceAccount<'info, TokenAccount>>, // Set to forward the creator fee share
+}
+
+impl<'info> FlashRepay<'info> {
+    // Context for the borrower paying `to`, the vault or a fee account, out of `from`
+    pub fn into_repay_transfer_context(
+        &self,
+        from: AccountInfo<'info>,
+        to: AccountInfo<'info>,
+    ) -> CpiContext<'_, '_, '_, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
+            from,
+            mint: self.token_mint.to_account_info().clone(),
+            to,
+            authority: self.borrower.to_account_info().clone(),
+        };
+        CpiContext::new(self.token_program.to_account_info().clone(), cpi_accounts)
+    }
+
+    // Pull `owed` into `to` from each source in order, grossing up every
+    // transfer for any transfer fee; returns what is still owed
+    fn pull(
+        &self,
+        sources: &[InterfaceAccount<'info, TokenAccount>],
+        to: AccountInfo<'info>,
+        mut owed: u64,
+    ) -> Result<u64> {
+        let mint = self.token_mint.to_account_info();
+        for source in sources {
+            if owed == 0 {
+                break;
+            }
+            let transfer = gross_for_net(&mint, owed)?.min(source.amount);
+            if transfer == 0 {
+                continue;
+            }
+            token_interface::transfer_checked(
+                self.into_repay_transfer_context(source.to_account_info(), to.clone()),
+                transfer,
+                self.token_mint.decimals,
+            )?;
+            owed = owed.saturating_sub(transfer - transfer_fee(&mint, transfer)?);
+        }
+        Ok(owed)
+    }
+}
+
+// Repay `amount` plus the receipt's fee and close out the loan opened by `flash_borrow`
+pub(crate) fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, FlashRepay<'info>>, amount: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(ctx.accounts.loan_state.active, FlashLoanError::LoanNotRepaid);
+    require!(ctx.accounts.loan_receipt.amount == amount, FlashLoanError::LoanReceiptMismatch);
+    ctx.accounts.pool.require_accounting_price(ctx.accounts.accounting_price.as_deref())?;
+
+    let same_slot_loans = if ctx.accounts.loan_state.last_loan_slot == clock.slot {
+        ctx.accounts.loan_state.same_slot_loan_count
+    } else {
+        0
+    };
+    // Settle with the fee and reserve factor pinned at borrow time
+    let fee = ctx.accounts.loan_receipt.fee;
+    let protocol_fee = calculate_protocol_fee(fee, ctx.accounts.loan_receipt.reserve_factor_bps);
+
+    require!(
+        ctx.remaining_accounts.len() <= MAX_REPAY_SOURCES,
+        FlashLoanError::TooManyRepaySources
+    );
+    let mut sources = vec![ctx.accounts.borrower_account.clone()];
+    for info in ctx.remaining_accounts {
+        let source = InterfaceAccount::<TokenAccount>::try_from(info)?;
+        require_keys_eq!(source.mint, ctx.accounts.pool.token_mint, FlashLoanError::MintMismatch);
+        require_keys_eq!(source.owner, ctx.accounts.borrower.key(), FlashLoanError::InvalidRepaySource);
+        sources.push(source);
+    }
+
+    // Fee shares with a fee account passed skip the vault
+    let forwarded = ForwardedFees::resolve(
+        &ctx.accounts.pool,
+        ctx.accounts.protocol_config.as_deref(),
+        ctx.accounts.treasury_account.as_deref(),
+        ctx.accounts.creator_fee_account.as_deref(),
+        fee,
+        protocol_fee,
+    )?;
+
+    // Pull from the sources until the vault is owed nothing, and judge it by
+    // what the vault gains
+    let vault_before = ctx.accounts.loan_vault.amount;
+    let price_before = ctx.accounts.pool.share_price(vault_before, ctx.accounts.share_mint.supply);
+    #[cfg(feature = "strict-invariants")]
+    let snapshot = crate::invariants::SettlementSnapshot::capture(&ctx.accounts.pool, vault_before, ctx.accounts.share_mint.supply);
+    let vault_owed = amount + fee - forwarded.total();
+    ctx.accounts.pull(&sources, ctx.accounts.loan_vault.to_account_info(), vault_owed)?;
+    ctx.accounts.loan_vault.reload()?;
+    ctx.accounts.pool.write_off_dust(vault_before, ctx.accounts.loan_vault.amount, vault_owed)?;
+    let fee_accounts = [
+        (ctx.accounts.treasury_account.as_ref(), forwarded.protocol_fee),
+        (ctx.accounts.creator_fee_account.as_ref(), forwarded.creator_fee),
+    ];
+    for (account, share) in fee_accounts {
+        if let Some(account) = account.filter(|_| share > 0) {
+            let unpaid = ctx.accounts.pull(&sources, account.to_account_info(), share)?;
+            require!(unpaid == 0, FlashLoanError::IncorrectRepayment);
+        }
+    }
+
+    ctx.accounts.pool.accrue_unforwarded_fees(fee, protocol_fee, &forwarded);
+    ctx.accounts.pool.open_loan_amount = ctx.accounts.pool.open_loan_amount.saturating_sub(amount);
+    ctx.accounts.pool.open_receipts = ctx.accounts.pool.open_receipts.saturating_sub(1);
+
+    // A pool reporting in USD prices the fee by its accounting oracle
+    let fee_usd_cents = ctx.accounts.pool.usd_cents(ctx.accounts.accounting_price.as_deref(), fee, clock.unix_timestamp);
+    if let Some(loan_stats) = ctx.accounts.loan_stats.as_mut() {
+        loan_stats.update_stats(amount, fee, clock.unix_timestamp);
+        loan_stats.total_fees_usd_cents += fee_usd_cents.unwrap_or(0);
+        loan_stats.record_compute_unit_price(ctx.accounts.loan_receipt.compute_unit_price);
+    }
+
+    ctx.accounts.loan_state.active = false;
+    ctx.accounts.loan_state.last_loan_timestamp = clock.unix_timestamp;
+    ctx.accounts.loan_state.loan_count += 1;
+    ctx.accounts.loan_state.last_loan_slot = clock.slot;
+    ctx.accounts.loan_state.same_slot_loan_count = same_slot_loans + 1;
+
+    emit!(FlashLoanExecuted {
+        pool: ctx.accounts.pool.key(),
+        borrower: ctx.accounts.borrower.key(),
+        loan_amount: amount,
+        fee,
+        protocol_fee,
+        fee_deducted: false,
+        callback_program: Pubkey::default(), // Borrower logic runs in its own instructions
+        callback_success: false,
+        sequence: ctx.accounts.pool.next_event_sequence(),
+    });
+    if ctx.accounts.pool.has_accounting_oracle() {
+        let accounting_price = ctx.accounts.accounting_price.as_deref();
+        emit!(FeeRevenueRecorded {
+            pool: ctx.accounts.pool.key(),
+            sequence: ctx.accounts.pool.event_sequence,
+            fee,
+            protocol_fee,
+            fee_usd_cents,
+            protocol_fee_usd_cents: ctx.accounts.pool.usd_cents(accounting_price, protocol_fee, clock.unix_timestamp),
+        });
+    }
+    if forwarded.total() > 0 {
+        emit!(FeesForwarded {
+            pool: ctx.accounts.pool.key(),
+            sequence: ctx.accounts.pool.event_sequence,
+            protocol_fee: forwarded.protocol_fee,
+            creator_fee: forwarded.creator_fee,
+        });
+    }
+
+    ctx.accounts.share_mint.reload()?;
+    #[cfg(feature = "strict-invariants")]
+    crate::invariants::check_settlement(
+        &snapshot,
+        &ctx.accounts.pool,
+        ctx.accounts.loan_vault.amount,
+        ctx.accounts.share_mint.supply,
+        vault_owed,
+        amount,
+    )?;
+    emit!(SharePriceUpdated {
+        pool: ctx.accounts.pool.key(),
+        price_before,
+        price_after: ctx.accounts.pool.share_price(ctx.accounts.loan_vault.amount, ctx.accounts.share_mint.supply),
+        lp_assets: ctx.accounts.pool.lp_assets(ctx.accounts.loan_vault.amount),
+        share_supply: ctx.accounts.share_mint.supply,
+    });
+
+    Ok(())
+}
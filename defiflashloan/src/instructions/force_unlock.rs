@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::UnlockForced;
+use crate::state::{Keeper, KeeperCrank, KeeperRegistry, LoanState, ProtocolConfig};
+
+// Context for clearing a stuck reentrancy flag
+#[derive(Accounts)]
+pub struct ForceUnlock<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [LOAN_STATE_SEED, pool.key().as_ref(), borrower.key().as_ref()],
+        bump = loan_state.bump
+    )]
+    pub loan_state: Account<'info, LoanState>,     // Loan state left active
+    /// CHECK: Only keys the loan state PDA
+    pub pool: UncheckedAccount<'info>,             // Pool the loan state guards
+    /// CHECK: Only keys the loan state PDA
+    pub borrower: UncheckedAccount<'info>,         // Borrower the loan state belongs to
+    pub authority: Signer<'info>,                  // Protocol admin, or anyone after the timeout
+    #[account(mut, seeds = [KEEPER_REGISTRY_SEED], bump = keeper_registry.bump)]
+    pub keeper_registry: Option<Account<'info, KeeperRegistry>>, // Passed with the keeper accounts to pay the keeper running the crank
+    #[account(mut)]
+    pub keeper: Option<Account<'info, Keeper>>,   // Registered keeper credited for the crank
+    pub keeper_authority: Option<Signer<'info>>,   // Keeper's authority
+}
+
+// Clear `active` on a loan state; the protocol admin may do so at once,
+// anyone else only once FORCE_UNLOCK_TIMEOUT has passed since it was set,
+// and a keeper sweeping a stale state that way is paid for it
+pub(crate) fn handler(ctx: Context<ForceUnlock>) -> Result<()> {
+    let loan_state = &mut ctx.accounts.loan_state;
+    require!(loan_state.active, FlashLoanError::LoanStateNotLocked);
+
+    let authority = ctx.accounts.authority.key();
+    if authority != ctx.accounts.protocol_config.admin {
+        require!(
+            Clock::get()?.unix_timestamp >= loan_state.active_since + FORCE_UNLOCK_TIMEOUT,
+            FlashLoanError::UnlockTimeoutNotReached
+        );
+        crate::keeper_rewards::credit_crank(
+            ctx.accounts.keeper_registry.as_mut(),
+            ctx.accounts.keeper.as_mut(),
+            ctx.accounts.keeper_authority.as_ref(),
+            KeeperCrank::SweepLoanState,
+        )?;
+    }
+
+    loan_state.active = false;
+
+    emit!(UnlockForced {
+        loan_state: loan_state.key(),
+        authority,
+        active_since: loan_state.active_since,
+    });
+
+    Ok(())
+}
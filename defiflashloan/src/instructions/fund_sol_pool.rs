@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer as SystemTransfer};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::state::SolPool;
+
+// Context for adding lamports to the lamport pool
+#[derive(Accounts)]
+pub struct FundSolPool<'info> {
+    #[account(mut, seeds = [SOL_POOL_SEED], bump = sol_pool.bump)]
+    pub sol_pool: Account<'info, SolPool>,
+    #[account(mut, seeds = [SOL_VAULT_SEED], bump = sol_pool.vault_bump)]
+    pub sol_vault: SystemAccount<'info>,           // Lamport vault
+    #[account(mut)]
+    pub funder: Signer<'info>,                     // Anyone may add liquidity
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FundSolPool<'info> {
+    // Context for moving the funder's lamports into the vault
+    pub fn into_transfer_to_vault_context(&self) -> CpiContext<'_, '_, '_, 'info, SystemTransfer<'info>> {
+        let cpi_accounts = SystemTransfer {
+            from: self.funder.to_account_info().clone(),
+            to: self.sol_vault.to_account_info().clone(),
+        };
+        CpiContext::new(self.system_program.to_account_info().clone(), cpi_accounts)
+    }
+}
+
+// Fund the vault; the first deposit must cover the vault's rent exemption
+pub(crate) fn handler(ctx: Context<FundSolPool>, amount: u64) -> Result<()> {
+    require!(amount > 0, FlashLoanError::InvalidAmount);
+    system_program::transfer(ctx.accounts.into_transfer_to_vault_context(), amount)?;
+    ctx.accounts.sol_pool.total_deposits += amount;
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::constants::*;
+use crate::events::LpStatementGenerated;
+use crate::shares::assets_for_shares;
+use crate::state::{LpPosition, Pool};
+
+// Statement returned by `generate_lp_statement`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LpStatement {
+    pub pool: Pubkey,
+    pub lp: Pubkey,
+    pub slot: u64,                  // Slot the statement was taken in
+    pub total_deposited: u64,       // Underlying ever deposited through the position
+    pub deposit_count: u32,
+    pub total_withdrawn: u64,       // Underlying ever paid out through the position
+    pub withdrawal_count: u32,
+    pub principal: u64,             // Deposits not yet drawn down by withdrawals
+    pub share_balance: u64,         // LP shares the LP holds now
+    pub share_price: u64,           // Underlying per share, scaled by SHARE_PRICE_SCALE
+    pub current_value: u64,         // Underlying the shares redeem for now
+    pub fee_income: i64,            // Current value plus withdrawals less deposits; negative after a loss
+}
+
+// Context for an LP statement
+#[derive(Accounts)]
+pub struct GenerateLpStatement<'info> {
+    #[account(seeds = [POOL_SEED, pool.token_mint.as_ref(), pool.index.as_ref()], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+    #[account(address = pool.vault)]
+    pub loan_vault: InterfaceAccount<'info, TokenAccount>, // Pool vault, for the share price
+    #[account(address = pool.share_mint)]
+    pub share_mint: InterfaceAccount<'info, Mint>, // LP share mint, for the share price
+    #[account(
+        has_one = pool,
+        seeds = [LP_POSITION_SEED, pool.key().as_ref(), lp.key().as_ref()],
+        bump = lp_position.bump
+    )]
+    pub lp_position: Account<'info, LpPosition>,
+    #[account(token::mint = share_mint, token::authority = lp)]
+    pub lp_share_account: InterfaceAccount<'info, TokenAccount>, // Shares the LP holds
+    /// CHECK: Only keys the position and owns the share account
+    pub lp: UncheckedAccount<'info>,               // LP the statement is for
+}
+
+// Permissionless: aggregate an LP's deposits, withdrawals, fee income and
+// current value, returned and emitted so custodians can build client
+// statements from one call. Shares moved in or out by transfer count in the
+// current value but not in the deposit and withdrawal totals
+pub(crate) fn handler(ctx: Context<GenerateLpStatement>) -> Result<LpStatement> {
+    let pool = &ctx.accounts.pool;
+    let position = &ctx.accounts.lp_position;
+    let lp_assets = pool.lp_assets(ctx.accounts.loan_vault.amount);
+    let share_supply = ctx.accounts.share_mint.supply;
+    let share_balance = ctx.accounts.lp_share_account.amount;
+    let current_value = assets_for_shares(share_balance, lp_assets, share_supply);
+    // Time-weighted fees count once settled; claimed ones are in `total_withdrawn`
+    let fee_income = current_value as i128 + position.pending_lp_fees as i128 + position.total_withdrawn as i128
+        - position.total_deposited as i128;
+
+    let statement = LpStatement {
+        pool: pool.key(),
+        lp: ctx.accounts.lp.key(),
+        slot: Clock::get()?.slot,
+        total_deposited: position.total_deposited,
+        deposit_count: position.deposit_count,
+        total_withdrawn: position.total_withdrawn,
+        withdrawal_count: position.withdrawal_count,
+        principal: position.deposited_amount,
+        share_balance,
+        share_price: pool.share_price(ctx.accounts.loan_vault.amount, share_supply),
+        current_value,
+        fee_income: fee_income.clamp(i64::MIN as i128, i64::MAX as i128) as i64,
+    };
+
+    emit!(LpStatementGenerated {
+        pool: statement.pool,
+        lp: statement.lp,
+        slot: statement.slot,
+        total_deposited: statement.total_deposited,
+        total_withdrawn: statement.total_withdrawn,
+        principal: statement.principal,
+        share_balance: statement.share_balance,
+        current_value: statement.current_value,
+        fee_income: statement.fee_income,
+    });
+
+    Ok(statement)
+}
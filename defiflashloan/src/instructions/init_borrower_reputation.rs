@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::state::BorrowerReputation;
+
+// Context for opening a borrower's reputation record
+#[derive(Accounts)]
+pub struct InitBorrowerReputation<'info> {
+    #[account(
+        init,
+        payer = borrower,
+        space = BorrowerReputation::LEN,
+        seeds = [REPUTATION_SEED, borrower.key().as_ref()],
+        bump
+    )]
+    pub borrower_reputation: Account<'info, BorrowerReputation>,
+    #[account(mut)]
+    pub borrower: Signer<'info>,                   // Borrower the record tracks
+    pub system_program: Program<'info, System>,
+}
+
+// Open an empty record; its age counts from now
+pub(crate) fn handler(ctx: Context<InitBorrowerReputation>) -> Result<()> {
+    let reputation = &mut ctx.accounts.borrower_reputation;
+    reputation.borrower = ctx.accounts.borrower.key();
+    reputation.created_at = Clock::get()?.unix_timestamp;
+    reputation.bump = ctx.bumps.borrower_reputation;
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::state::{FeeExperimentStats, Pool};
+
+// Context for opening a pool's fee experiment stats
+#[derive(Accounts)]
+pub struct InitFeeExperimentStats<'info> {
+    #[account(seeds = [POOL_SEED, pool.token_mint.as_ref(), pool.index.as_ref()], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        init,
+        payer = payer,
+        space = FeeExperimentStats::LEN,
+        seeds = [FEE_EXPERIMENT_SEED, pool.key().as_ref()],
+        bump
+    )]
+    pub fee_experiment_stats: Account<'info, FeeExperimentStats>,
+    #[account(mut)]
+    pub payer: Signer<'info>,                      // Anyone; usually the pool admin before an experiment starts
+    pub system_program: Program<'info, System>,
+}
+
+// Open empty per-bucket stats; loans sync them to the pool's experiment
+// parameters as they record into them
+pub(crate) fn handler(ctx: Context<InitFeeExperimentStats>) -> Result<()> {
+    let stats = &mut ctx.accounts.fee_experiment_stats;
+    stats.pool = ctx.accounts.pool.key();
+    stats.bump = ctx.bumps.fee_experiment_stats;
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::state::{LoanState, Pool};
+
+// Context for opening a borrower's loan state on a pool
+#[derive(Accounts)]
+pub struct InitLoanState<'info> {
+    #[account(seeds = [POOL_SEED, pool.token_mint.as_ref(), pool.index.as_ref()], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        init,
+        payer = borrower,
+        space = LoanState::LEN,
+        seeds = [LOAN_STATE_SEED, pool.key().as_ref(), borrower.key().as_ref()],
+        bump
+    )]
+    pub loan_state: Account<'info, LoanState>,
+    #[account(mut)]
+    pub borrower: Signer<'info>,                   // Borrower the loan state guards
+    pub system_program: Program<'info, System>,
+}
+
+// Open an idle loan state; every loan on `pool` by the borrower goes through it
+pub(crate) fn handler(ctx: Context<InitLoanState>) -> Result<()> {
+    let loan_state = &mut ctx.accounts.loan_state;
+    loan_state.pool = ctx.accounts.pool.key();
+    loan_state.borrower = ctx.accounts.borrower.key();
+    loan_state.bump = ctx.bumps.loan_state;
+    Ok(())
+}
@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+use crate::state::LoanStats;
+
+// Context for creating a loan statistics account
+#[derive(Accounts)]
+pub struct InitLoanStats<'info> {
+    #[account(init, payer = payer, space = LoanStats::LEN)]
+    pub loan_stats: Account<'info, LoanStats>,     // Fresh keypair account, zeroed
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// Create an empty LoanStats account for loans to record into
+pub(crate) fn handler(_ctx: Context<InitLoanStats>) -> Result<()> {
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::state::{LoanStats, Pool, StatsAccumulator};
+
+// Context for opening a pool's fast path stats accumulator
+#[derive(Accounts)]
+pub struct InitStatsAccumulator<'info> {
+    #[account(
+        seeds = [POOL_SEED, pool.token_mint.as_ref(), pool.index.as_ref()],
+        bump = pool.bump,
+        has_one = admin
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        init,
+        payer = admin,
+        space = StatsAccumulator::LEN,
+        seeds = [STATS_ACCUMULATOR_SEED, pool.key().as_ref()],
+        bump
+    )]
+    pub stats_accumulator: Account<'info, StatsAccumulator>,
+    pub loan_stats: Account<'info, LoanStats>,     // Stats the accumulator is synced into
+    #[account(mut)]
+    pub admin: Signer<'info>,                      // Pool admin, pays for the account
+    pub system_program: Program<'info, System>,
+}
+
+// Open an empty accumulator for `execute_flash_loan_fast`, bound to the
+// LoanStats account `sync_stats` folds it into
+pub(crate) fn handler(ctx: Context<InitStatsAccumulator>) -> Result<()> {
+    let accumulator = &mut ctx.accounts.stats_accumulator;
+    accumulator.pool = ctx.accounts.pool.key();
+    accumulator.loan_stats = ctx.accounts.loan_stats.key();
+    accumulator.bump = ctx.bumps.stats_accumulator;
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint as InterfaceMint, TokenAccount as InterfaceTokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::state::{FeeLocker, Pool, ProtocolConfig};
+
+// Context for creating a pool's fee locker and its vaults
+#[derive(Accounts)]
+pub struct InitializeFeeLocker<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump, has_one = admin)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(seeds = [POOL_SEED, pool.token_mint.as_ref(), pool.index.as_ref()], bump = pool.bump, has_one = token_mint)]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        init,
+        payer = admin,
+        space = FeeLocker::LEN,
+        seeds = [FEE_LOCKER_SEED, pool.key().as_ref()],
+        bump
+    )]
+    pub fee_locker: Account<'info, FeeLocker>,
+    #[account(
+        init,
+        payer = admin,
+        seeds = [FEE_LOCKER_VAULT_SEED, fee_locker.key().as_ref()],
+        bump,
+        token::mint = lock_mint,
+        token::authority = fee_locker
+    )]
+    pub lock_vault: Account<'info, TokenAccount>,  // Holds the locked protocol tokens
+    #[account(
+        init,
+        payer = admin,
+        seeds = [FEE_LOCKER_REWARDS_SEED, fee_locker.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = fee_locker,
+        token::token_program = reward_token_program
+    )]
+    pub reward_vault: InterfaceAccount<'info, InterfaceTokenAccount>, // Holds streamed fees until claimed
+    pub lock_mint: Account<'info, Mint>,           // Protocol token
+    pub token_mint: InterfaceAccount<'info, InterfaceMint>, // Pool mint the fees are paid in
+    #[account(mut)]
+    pub admin: Signer<'info>,                      // Protocol admin
+    pub token_program: Program<'info, Token>,      // Token program of the protocol token
+    pub reward_token_program: Interface<'info, TokenInterface>, // Token or Token-2022 program of the pool mint
+    pub system_program: Program<'info, System>,
+}
+
+// Create a fee locker that streams the pool's protocol fees every `epoch_duration` seconds
+pub(crate) fn handler(ctx: Context<InitializeFeeLocker>, epoch_duration: i64) -> Result<()> {
+    require!(epoch_duration > 0, FlashLoanError::InvalidPoolConfig);
+
+    let fee_locker = &mut ctx.accounts.fee_locker;
+    fee_locker.pool = ctx.accounts.pool.key();
+    fee_locker.lock_mint = ctx.accounts.lock_mint.key();
+    fee_locker.lock_vault = ctx.accounts.lock_vault.key();
+    fee_locker.reward_vault = ctx.accounts.reward_vault.key();
+    fee_locker.epoch_duration = epoch_duration;
+    fee_locker.last_stream_at = Clock::get()?.unix_timestamp;
+    fee_locker.bump = ctx.bumps.fee_locker;
+    Ok(())
+}
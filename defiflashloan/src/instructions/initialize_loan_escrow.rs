@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::state::Pool;
+
+// Context for creating a pool's loan escrow account
+#[derive(Accounts)]
+pub struct InitializeLoanEscrow<'info> {
+    #[account(mut, seeds = [POOL_SEED, token_mint.key().as_ref(), pool.index.as_ref()], bump = pool.bump, has_one = token_mint)]
+    pub pool: Account<'info, Pool>,                // Records the escrow bump
+    #[account(
+        init,
+        payer = payer,
+        seeds = [LOAN_ESCROW_SEED, pool.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = pool,
+        token::token_program = token_program
+    )]
+    pub loan_escrow: InterfaceAccount<'info, TokenAccount>, // Holds escrowed loans, owned by the pool PDA
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub payer: Signer<'info>,                      // Anyone may pay for the escrow
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+// Create the escrow that escrowed flash loans disburse into
+pub(crate) fn handler(ctx: Context<InitializeLoanEscrow>) -> Result<()> {
+    ctx.accounts.pool.escrow_bump = ctx.bumps.loan_escrow;
+    Ok(())
+}
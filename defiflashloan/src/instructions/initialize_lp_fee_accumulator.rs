@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::state::{LpFeeAccumulator, Pool};
+
+// Context for switching a pool to time-weighted LP fees
+#[derive(Accounts)]
+pub struct InitializeLpFeeAccumulator<'info> {
+    #[account(mut, has_one = admin, has_one = share_mint)]
+    pub pool: Account<'info, Pool>,
+    pub share_mint: InterfaceAccount<'info, Mint>, // Read to check no LP already holds shares
+    #[account(
+        init,
+        payer = admin,
+        space = LpFeeAccumulator::LEN,
+        seeds = [LP_FEE_ACCUMULATOR_SEED, pool.key().as_ref()],
+        bump
+    )]
+    pub lp_fee_accumulator: Account<'info, LpFeeAccumulator>,
+    #[account(mut)]
+    pub admin: Signer<'info>,                      // Pool admin
+    pub system_program: Program<'info, System>,
+}
+
+// Withhold the pool's LP fees from the share price and split them by
+// share-seconds every `epoch_duration` seconds. Only a pool with no LP shares
+// outstanding may switch, so every share is weighed from its deposit onwards
+pub(crate) fn handler(ctx: Context<InitializeLpFeeAccumulator>, epoch_duration: i64) -> Result<()> {
+    require!(
+        (MIN_LP_FEE_EPOCH_DURATION..=MAX_LP_FEE_EPOCH_DURATION).contains(&epoch_duration),
+        FlashLoanError::InvalidLpFeeConfig
+    );
+    require!(
+        !ctx.accounts.pool.time_weighted_lp_fees && ctx.accounts.share_mint.supply == 0,
+        FlashLoanError::InvalidLpFeeConfig
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let accumulator = &mut ctx.accounts.lp_fee_accumulator;
+    accumulator.pool = ctx.accounts.pool.key();
+    accumulator.epoch_duration = epoch_duration;
+    accumulator.epoch_started_at = now;
+    accumulator.updated_at = now;
+    accumulator.bump = ctx.bumps.lp_fee_accumulator;
+    ctx.accounts.pool.time_weighted_lp_fees = true;
+    Ok(())
+}
@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer as SystemTransfer};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::PoolInitialized;
+use crate::state::{ConfigHistory, Pool, PoolConfig, ProtocolConfig, Registry, RegistryEntry, RegistryPage};
+
+// Context for creating a pool, its vault and its LP share mint; open to any signer
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>, // Sets the creation deposit
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = Registry::LEN,
+        seeds = [REGISTRY_SEED],
+        bump
+    )]
+    pub registry: Account<'info, Registry>,        // Global pool registry, created with the first pool
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = RegistryPage::LEN,
+        seeds = [REGISTRY_PAGE_SEED, registry.open_page().to_le_bytes().as_ref()],
+        bump
+    )]
+    pub registry_page: Account<'info, RegistryPage>, // Registry page the pool is listed on
+    #[account(
+        init,
+        payer = admin,
+        space = Pool::LEN,
+        seeds = [POOL_SEED, token_mint.key().as_ref(), registry.next_slot.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        init,
+        payer = admin,
+        space = PoolConfig::LEN,
+        seeds = [POOL_CONFIG_SEED, pool.key().as_ref()],
+        bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,   // Risk parameters, seeded with the defaults
+    #[account(
+        init,
+        payer = admin,
+        space = ConfigHistory::LEN,
+        seeds = [CONFIG_HISTORY_SEED, pool.key().as_ref()],
+        bump
+    )]
+    pub config_history: Account<'info, ConfigHistory>, // Log of later parameter changes
+    #[account(
+        init,
+        payer = admin,
+        associated_token::mint = token_mint,
+        associated_token::authority = pool,
+        associated_token::token_program = token_program
+    )]
+    pub loan_vault: InterfaceAccount<'info, TokenAccount>, // Vault owned by the pool PDA
+    #[account(
+        init,
+        payer = admin,
+        seeds = [SHARE_MINT_SEED, pool.key().as_ref()],
+        bump,
+        mint::decimals = token_mint.decimals,
+        mint::authority = pool,
+        mint::token_program = token_program
+    )]
+    pub share_mint: InterfaceAccount<'info, Mint>, // LP share mint controlled by the pool PDA
+    pub token_mint: InterfaceAccount<'info, Mint>, // Mint lent out by the pool
+    #[account(mut)]
+    pub admin: Signer<'info>,                      // Pool creator and first admin, pays for the accounts
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializePool<'info> {
+    // Context for locking the creation deposit in the pool account
+    pub fn into_deposit_context(&self) -> CpiContext<'_, '_, '_, 'info, SystemTransfer<'info>> {
+        let cpi_accounts = SystemTransfer {
+            from: self.admin.to_account_info().clone(),
+            to: self.pool.to_account_info().clone(),
+        };
+        CpiContext::new(self.system_program.to_account_info().clone(), cpi_accounts)
+    }
+}
+
+// Create the pool PDA, its config and an associated token vault it controls
+// The creator locks the protocol's creation deposit in the pool account and
+// earns `creator_fee_bps` of every loan fee
+pub(crate) fn handler(ctx: Context<InitializePool>, creator_fee_bps: u16) -> Result<()> {
+    require!(creator_fee_bps <= MAX_CREATOR_FEE_BPS, FlashLoanError::InvalidCreatorFee);
+
+    let deposit = ctx.accounts.protocol_config.pool_creation_deposit;
+    if deposit > 0 {
+        system_program::transfer(ctx.accounts.into_deposit_context(), deposit)?;
+    }
+
+    ctx.accounts
+        .pool_config
+        .init_defaults(ctx.accounts.pool.key(), ctx.bumps.pool_config);
+    ctx.accounts.config_history.pool = ctx.accounts.pool.key();
+    ctx.accounts.config_history.bump = ctx.bumps.config_history;
+
+    // List the pool in the registry's open page
+    let registry = &mut ctx.accounts.registry;
+    let registry_page = &mut ctx.accounts.registry_page;
+    registry_page.index = registry.open_page();
+    registry_page.bump = ctx.bumps.registry_page;
+    registry_page.entries.push(RegistryEntry {
+        token_mint: ctx.accounts.token_mint.key(),
+        pool: ctx.accounts.pool.key(),
+    });
+    let index = registry.next_slot;
+    registry.pool_count += 1;
+    registry.next_slot += 1;
+    registry.bump = ctx.bumps.registry;
+
+    let pool = &mut ctx.accounts.pool;
+    pool.admin = ctx.accounts.admin.key();
+    pool.token_mint = ctx.accounts.token_mint.key();
+    pool.index = index.to_le_bytes();
+    pool.vault = ctx.accounts.loan_vault.key();
+    pool.share_mint = ctx.accounts.share_mint.key();
+    pool.creator = ctx.accounts.admin.key();
+    pool.creator_fee_bps = creator_fee_bps;
+    pool.bump = ctx.bumps.pool;
+
+    emit!(PoolInitialized {
+        pool: pool.key(),
+        admin: pool.admin,
+        token_mint: pool.token_mint,
+        vault: pool.vault,
+        share_mint: pool.share_mint,
+        index,
+        creator_fee_bps,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::state::{ParamEnvelope, ProtocolConfig};
+
+// Context for creating the protocol config singleton
+#[derive(Accounts)]
+pub struct InitializeProtocol<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = ProtocolConfig::LEN,
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(mut)]
+    pub admin: Signer<'info>,                      // Becomes the protocol admin
+    pub system_program: Program<'info, System>,
+}
+
+// Create the protocol config with the signer as protocol admin
+pub(crate) fn handler(ctx: Context<InitializeProtocol>, treasury: Pubkey) -> Result<()> {
+    let protocol_config = &mut ctx.accounts.protocol_config;
+    protocol_config.admin = ctx.accounts.admin.key();
+    protocol_config.treasury = treasury;
+    protocol_config.envelope = ParamEnvelope::OPEN;
+    protocol_config.bump = ctx.bumps.protocol_config;
+    Ok(())
+}
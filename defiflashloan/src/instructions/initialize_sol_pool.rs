@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::state::{ProtocolConfig, SolPool};
+
+// Context for creating the lamport pool
+#[derive(Accounts)]
+pub struct InitializeSolPool<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump, has_one = admin)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        init,
+        payer = admin,
+        space = SolPool::LEN,
+        seeds = [SOL_POOL_SEED],
+        bump
+    )]
+    pub sol_pool: Account<'info, SolPool>,
+    #[account(seeds = [SOL_VAULT_SEED], bump)]
+    pub sol_vault: SystemAccount<'info>,           // Lamport vault, funded by `fund_sol_pool`
+    #[account(mut)]
+    pub admin: Signer<'info>,                      // Protocol admin
+    pub system_program: Program<'info, System>,
+}
+
+// Create the lamport pool with its fee and loan size limit
+pub(crate) fn handler(ctx: Context<InitializeSolPool>, fee_bps: u64, max_loan_amount: u64) -> Result<()> {
+    require!(fee_bps <= BPS_DENOMINATOR, FlashLoanError::InvalidPoolConfig);
+
+    let sol_pool = &mut ctx.accounts.sol_pool;
+    sol_pool.admin = ctx.accounts.admin.key();
+    sol_pool.fee_bps = fee_bps;
+    sol_pool.max_loan_amount = max_loan_amount;
+    sol_pool.vault_bump = ctx.bumps.sol_vault;
+    sol_pool.bump = ctx.bumps.sol_pool;
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::constants::*;
+use crate::state::{PoolDirectory, ProtocolConfig};
+
+// Context for listing an alternate pool in a mint's directory
+#[derive(Accounts)]
+pub struct ListDirectoryPool<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump, has_one = admin)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = PoolDirectory::LEN,
+        seeds = [POOL_DIRECTORY_SEED, token_mint.key().as_ref()],
+        bump
+    )]
+    pub pool_directory: Account<'info, PoolDirectory>,
+    pub token_mint: InterfaceAccount<'info, Mint>, // Mint the listed pool lends
+    #[account(mut)]
+    pub admin: Signer<'info>,                      // Protocol admin
+    pub system_program: Program<'info, System>,
+}
+
+// List `pool`, which may belong to another deployment, as an alternate source
+// of `token_mint` liquidity
+pub(crate) fn handler(ctx: Context<ListDirectoryPool>, pool: Pubkey) -> Result<()> {
+    ctx.accounts.pool_directory.list(
+        ctx.accounts.token_mint.key(),
+        ctx.bumps.pool_directory,
+        pool,
+    )
+}
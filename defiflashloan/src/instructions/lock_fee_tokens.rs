@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::FeeTokensLocked;
+use crate::state::{FeeLocker, LockerPosition};
+
+// Context for locking protocol tokens in a fee locker
+#[derive(Accounts)]
+pub struct LockFeeTokens<'info> {
+    /// CHECK: Only keys the fee locker PDA
+    pub pool: UncheckedAccount<'info>,             // Pool the fee locker streams from
+    #[account(mut, seeds = [FEE_LOCKER_SEED, pool.key().as_ref()], bump = fee_locker.bump)]
+    pub fee_locker: Account<'info, FeeLocker>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = LockerPosition::LEN,
+        seeds = [LOCKER_POSITION_SEED, fee_locker.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, LockerPosition>,
+    #[account(mut, address = fee_locker.lock_vault)]
+    pub lock_vault: Account<'info, TokenAccount>,  // Holds the locked protocol tokens
+    #[account(mut, token::mint = fee_locker.lock_mint, token::authority = owner)]
+    pub owner_account: Account<'info, TokenAccount>, // Owner's protocol tokens
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> LockFeeTokens<'info> {
+    // Context for moving the owner's tokens into the lock vault
+    pub fn into_transfer_to_vault_context(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.owner_account.to_account_info().clone(),
+            to: self.lock_vault.to_account_info().clone(),
+            authority: self.owner.to_account_info().clone(),
+        };
+        CpiContext::new(self.token_program.to_account_info().clone(), cpi_accounts)
+    }
+}
+
+// Lock `amount` more tokens until at least `duration` seconds from now; the
+// whole position is reweighted for its remaining lock time
+pub(crate) fn handler(ctx: Context<LockFeeTokens>, amount: u64, duration: i64) -> Result<()> {
+    require!(amount > 0, FlashLoanError::InvalidAmount);
+    require!(
+        duration > 0 && duration <= MAX_FEE_LOCK_DURATION,
+        FlashLoanError::InvalidLockDuration
+    );
+    let now = Clock::get()?.unix_timestamp;
+
+    token::transfer(ctx.accounts.into_transfer_to_vault_context(), amount)?;
+
+    let fee_locker = &mut ctx.accounts.fee_locker;
+    let position = &mut ctx.accounts.position;
+    position.owner = ctx.accounts.owner.key();
+    position.fee_locker = fee_locker.key();
+    position.bump = ctx.bumps.position;
+
+    position.settle(fee_locker.reward_per_weight);
+    fee_locker.total_weight -= position.weight;
+    position.amount += amount;
+    position.unlock_at = position.unlock_at.max(now + duration);
+    position.reweight(now, fee_locker.reward_per_weight);
+    fee_locker.total_weight += position.weight;
+
+    emit!(FeeTokensLocked {
+        fee_locker: position.fee_locker,
+        owner: position.owner,
+        amount: position.amount,
+        weight: position.weight,
+        unlock_at: position.unlock_at,
+    });
+
+    Ok(())
+}
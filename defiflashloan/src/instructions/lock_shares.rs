@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::SharesLocked;
+use crate::state::{Pool, ShareLock};
+
+// Context for escrowing LP shares for an external protocol
+#[derive(Accounts)]
+#[instruction(shares: u64, locker: Pubkey)]
+pub struct LockShares<'info> {
+    #[account(seeds = [POOL_SEED, pool.token_mint.as_ref(), pool.index.as_ref()], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+    #[account(address = pool.share_mint)]
+    pub share_mint: Account<'info, Mint>,          // LP share mint
+    #[account(
+        init,
+        payer = owner,
+        space = ShareLock::LEN,
+        seeds = [SHARE_LOCK_SEED, pool.key().as_ref(), owner.key().as_ref(), locker.as_ref()],
+        bump
+    )]
+    pub share_lock: Account<'info, ShareLock>,
+    #[account(
+        init,
+        payer = owner,
+        seeds = [SHARE_ESCROW_SEED, share_lock.key().as_ref()],
+        bump,
+        token::mint = share_mint,
+        token::authority = share_lock
+    )]
+    pub escrow_share_account: Account<'info, TokenAccount>, // Holds the shares while locked
+    #[account(mut, token::mint = share_mint, token::authority = owner)]
+    pub owner_share_account: Account<'info, TokenAccount>, // LP's shares to lock
+    #[account(mut)]
+    pub owner: Signer<'info>,                      // LP locking the shares
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> LockShares<'info> {
+    // Context for moving the LP's shares into escrow
+    pub fn into_transfer_to_escrow_context(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.owner_share_account.to_account_info().clone(),
+            to: self.escrow_share_account.to_account_info().clone(),
+            authority: self.owner.to_account_info().clone(),
+        };
+        CpiContext::new(self.token_program.to_account_info().clone(), cpi_accounts)
+    }
+}
+
+// Escrow LP shares until `unlock_at`; escrowed shares cannot be withdrawn
+pub(crate) fn handler(ctx: Context<LockShares>, shares: u64, locker: Pubkey, unlock_at: i64) -> Result<()> {
+    require!(shares > 0, FlashLoanError::InvalidAmount);
+    require!(
+        ctx.accounts.owner_share_account.amount >= shares,
+        FlashLoanError::InsufficientLpBalance
+    );
+
+    token::transfer(ctx.accounts.into_transfer_to_escrow_context(), shares)?;
+
+    let share_lock = &mut ctx.accounts.share_lock;
+    share_lock.owner = ctx.accounts.owner.key();
+    share_lock.pool = ctx.accounts.pool.key();
+    share_lock.locker = locker;
+    share_lock.shares = shares;
+    share_lock.unlock_at = unlock_at;
+    share_lock.bump = ctx.bumps.share_lock;
+
+    emit!(SharesLocked {
+        pool: share_lock.pool,
+        owner: share_lock.owner,
+        locker,
+        shares,
+        unlock_at,
+    });
+
+    Ok(())
+}
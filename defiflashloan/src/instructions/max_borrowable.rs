@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::constants::*;
+use crate::state::{BorrowerReputation, FeePrice, LoanState, Pool, PoolConfig};
+
+// Context for the borrowing cap view
+#[derive(Accounts)]
+pub struct MaxBorrowable<'info> {
+    #[account(seeds = [POOL_SEED, pool.token_mint.as_ref(), pool.index.as_ref()], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+    #[account(seeds = [POOL_CONFIG_SEED, pool.key().as_ref()], bump = pool_config.bump)]
+    pub pool_config: Account<'info, PoolConfig>,   // Pool risk parameters
+    #[account(address = pool.vault)]
+    pub loan_vault: InterfaceAccount<'info, TokenAccount>, // Flash loan pool
+    /// CHECK: Only keys the loan state and reputation PDAs
+    pub borrower: UncheckedAccount<'info>,         // Borrower the view is for
+    #[account(
+        seeds = [LOAN_STATE_SEED, pool.key().as_ref(), borrower.key().as_ref()],
+        bump = loan_state.bump
+    )]
+    pub loan_state: Account<'info, LoanState>,     // Reentrancy check and state
+    #[account(
+        seeds = [REPUTATION_SEED, borrower.key().as_ref()],
+        bump = borrower_reputation.bump
+    )]
+    pub borrower_reputation: Option<Account<'info, BorrowerReputation>>, // Pass to price in the trusted tier
+    #[account(address = pool.accounting_oracle)]
+    pub accounting_price: Option<Account<'info, FeePrice>>, // Pass to apply USD fee tiers, which otherwise charge the first tier
+}
+
+impl<'info> MaxBorrowable<'info> {
+    // Whether the borrower's reputation currently unlocks the trusted tier
+    pub fn is_trusted(&self, now: i64) -> bool {
+        self.borrower_reputation
+            .as_ref()
+            .is_some_and(|reputation| self.pool_config.is_trusted(reputation.score(now)))
+    }
+}
+
+// Largest loan the pool, vault and loan state currently allow, at the
+// trusted tier's cap when `trusted` and at most the probation amount until
+// the borrower's first loan from the pool
+pub fn borrowable_amount(
+    pool: &Pool,
+    vault_amount: u64,
+    loan_state: &LoanState,
+    config: &PoolConfig,
+    trusted: bool,
+    now: i64,
+) -> u64 {
+    // A borrow pause, an active loan or a running cooldown blocks borrowing entirely
+    if pool.is_paused(PAUSE_BORROW)
+        || loan_state.active
+        || now < loan_state.last_loan_timestamp + config.loan_cooldown
+    {
+        return 0;
+    }
+
+    let max_loan_amount = vault_amount.min(config.limits(trusted).0);
+    if config.allows_probation(max_loan_amount, loan_state.loan_count) {
+        max_loan_amount
+    } else {
+        config.probation_loan_amount
+    }
+}
+
+// View: largest amount that would currently pass the borrow checks
+pub(crate) fn handler(ctx: Context<MaxBorrowable>) -> Result<u64> {
+    let now = Clock::get()?.unix_timestamp;
+    let accounts = &ctx.accounts;
+    Ok(borrowable_amount(
+        &accounts.pool,
+        accounts.loan_vault.amount,
+        &accounts.loan_state,
+        &accounts.pool_config,
+        accounts.is_trusted(now),
+        now,
+    ))
+}
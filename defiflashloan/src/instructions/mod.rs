@@ -0,0 +1,251 @@
+pub mod accept_admin;
+pub mod adjudicate_insurance_claim;
+pub mod admin_heartbeat;
+#[cfg(feature = "devnet-faucet")]
+pub mod airdrop_test_liquidity;
+pub mod apply_pool_config;
+pub mod batch_admin;
+pub mod begin_keeper_exit;
+pub mod begin_wind_down;
+pub mod cancel_pre_auth;
+pub mod checkpoint_fee_lock;
+pub mod checkpoint_lp_fees;
+pub mod claim_fee_rewards;
+pub mod claim_keeper_rewards;
+pub mod claim_lp_fees;
+pub mod clone_pool_config;
+pub mod close_pool;
+pub mod collect_creator_fees;
+pub mod collect_protocol_fees;
+pub mod complete_admin_recovery;
+pub mod configure_buyback;
+pub mod configure_fee_settlement;
+pub mod configure_insurance_fund;
+pub mod configure_keeper_registry;
+pub mod configure_rebalance_route;
+pub mod create_pre_auth;
+pub mod create_session_key;
+pub mod deposit_liquidity;
+pub mod emergency_withdraw;
+pub mod execute_buyback;
+pub mod execute_flash_loan;
+pub mod execute_flash_loan_batch;
+pub mod execute_flash_loan_fast;
+pub mod execute_flash_loan_receiver;
+pub mod execute_multi_flash_loan;
+pub mod execute_native_flash_loan;
+pub mod execute_netted_flash_loan;
+pub mod execute_pre_authorized_loan;
+pub mod execute_quoted_loan;
+pub mod execute_settled_multi_flash_loan;
+pub mod execute_sol_flash_loan;
+pub mod file_insurance_claim;
+pub mod flag_borrower_incident;
+pub mod flash_borrow;
+pub mod flash_borrow_pct;
+pub mod flash_repay;
+pub mod force_unlock;
+pub mod fund_sol_pool;
+pub mod generate_lp_statement;
+pub mod init_borrower_reputation;
+pub mod init_fee_experiment_stats;
+pub mod init_loan_state;
+pub mod init_loan_stats;
+pub mod init_stats_accumulator;
+pub mod initialize_fee_locker;
+pub mod initialize_loan_escrow;
+pub mod initialize_lp_fee_accumulator;
+pub mod initialize_pool;
+pub mod initialize_protocol;
+pub mod initialize_sol_pool;
+pub mod list_directory_pool;
+pub mod lock_fee_tokens;
+pub mod lock_shares;
+pub mod max_borrowable;
+pub mod open_insurance_policy;
+pub mod override_pool_param;
+pub mod pause_pool;
+pub mod post_fee_price;
+pub mod propose_admin;
+pub mod purchase_borrow_credits;
+pub mod rebalance_liquidity;
+pub mod reconcile_dust;
+pub mod refresh_rate_card;
+pub mod register_integrator;
+pub mod register_keeper;
+pub mod register_sol_target_program;
+pub mod register_target_program;
+pub mod relock_fee_tokens;
+pub mod remove_target_program;
+pub mod revoke_session_key;
+pub mod route_borrow;
+pub mod seed_pool_from_treasury;
+pub mod set_accounting_oracle;
+pub mod set_canonical_pool;
+pub mod set_durable_nonce_policy;
+pub mod set_dust_threshold;
+pub mod set_emergency_withdraw_delay;
+pub mod set_fee_curve;
+pub mod set_fee_experiment;
+pub mod set_fee_tiers;
+pub mod set_max_concurrent_borrowers;
+pub mod set_max_invoke_depth;
+pub mod set_ops_tag;
+pub mod set_param_envelope;
+pub mod set_pool_creation_deposit;
+pub mod set_probation_loan_amount;
+pub mod set_public_goods_mode;
+pub mod set_quote_signer;
+pub mod set_recovery_key;
+pub mod set_reserve_factor;
+pub mod set_same_slot_surcharge;
+pub mod set_trusted_tier;
+pub mod simple_flash_loan;
+pub mod slash_keeper;
+pub mod stake_reputation;
+pub mod start_admin_recovery;
+pub mod stream_protocol_fees;
+pub mod sync_stats;
+pub mod transfer_borrow_credits;
+pub mod unlock_fee_tokens;
+pub mod unlock_shares;
+pub mod unpause_pool;
+pub mod unstake_reputation;
+pub mod unwind_rebalance;
+pub mod update_pool_config;
+pub mod view_borrower_limits;
+pub mod view_fee;
+pub mod view_snapshot_shares;
+pub mod view_ui_amounts;
+pub mod view_utilization;
+pub mod withdraw_and_unwrap_sol;
+pub mod withdraw_keeper_stake;
+pub mod withdraw_liquidity;
+pub mod withdraw_sol_pool;
+pub mod wrap_sol_and_deposit;
+
+pub use accept_admin::*;
+pub use adjudicate_insurance_claim::*;
+pub use admin_heartbeat::*;
+#[cfg(feature = "devnet-faucet")]
+pub use airdrop_test_liquidity::*;
+pub use apply_pool_config::*;
+pub use batch_admin::*;
+pub use begin_keeper_exit::*;
+pub use begin_wind_down::*;
+pub use cancel_pre_auth::*;
+pub use checkpoint_fee_lock::*;
+pub use checkpoint_lp_fees::*;
+pub use claim_fee_rewards::*;
+pub use claim_keeper_rewards::*;
+pub use claim_lp_fees::*;
+pub use clone_pool_config::*;
+pub use close_pool::*;
+pub use collect_creator_fees::*;
+pub use collect_protocol_fees::*;
+pub use complete_admin_recovery::*;
+pub use configure_buyback::*;
+pub use configure_fee_settlement::*;
+pub use configure_insurance_fund::*;
+pub use configure_keeper_registry::*;
+pub use configure_rebalance_route::*;
+pub use create_pre_auth::*;
+pub use create_session_key::*;
+pub use deposit_liquidity::*;
+pub use emergency_withdraw::*;
+pub use execute_buyback::*;
+pub use execute_flash_loan::*;
+pub use execute_flash_loan_batch::*;
+pub use execute_flash_loan_fast::*;
+pub use execute_flash_loan_receiver::*;
+pub use execute_multi_flash_loan::*;
+pub use execute_native_flash_loan::*;
+pub use execute_netted_flash_loan::*;
+pub use execute_pre_authorized_loan::*;
+pub use execute_quoted_loan::*;
+pub use execute_settled_multi_flash_loan::*;
+pub use execute_sol_flash_loan::*;
+pub use file_insurance_claim::*;
+pub use flag_borrower_incident::*;
+pub use flash_borrow::*;
+pub use flash_borrow_pct::*;
+pub use flash_repay::*;
+pub use force_unlock::*;
+pub use fund_sol_pool::*;
+pub use generate_lp_statement::*;
+pub use init_borrower_reputation::*;
+pub use init_fee_experiment_stats::*;
+pub use init_loan_state::*;
+pub use init_loan_stats::*;
+pub use init_stats_accumulator::*;
+pub use initialize_fee_locker::*;
+pub use initialize_loan_escrow::*;
+pub use initialize_lp_fee_accumulator::*;
+pub use initialize_pool::*;
+pub use initialize_protocol::*;
+pub use initialize_sol_pool::*;
+pub use list_directory_pool::*;
+pub use lock_fee_tokens::*;
+pub use lock_shares::*;
+pub use max_borrowable::*;
+pub use open_insurance_policy::*;
+pub use override_pool_param::*;
+pub use pause_pool::*;
+pub use post_fee_price::*;
+pub use propose_admin::*;
+pub use purchase_borrow_credits::*;
+pub use rebalance_liquidity::*;
+pub use reconcile_dust::*;
+pub use refresh_rate_card::*;
+pub use register_integrator::*;
+pub use register_keeper::*;
+pub use register_sol_target_program::*;
+pub use register_target_program::*;
+pub use relock_fee_tokens::*;
+pub use remove_target_program::*;
+pub use revoke_session_key::*;
+pub use route_borrow::*;
+pub use seed_pool_from_treasury::*;
+pub use set_accounting_oracle::*;
+pub use set_canonical_pool::*;
+pub use set_durable_nonce_policy::*;
+pub use set_dust_threshold::*;
+pub use set_emergency_withdraw_delay::*;
+pub use set_fee_curve::*;
+pub use set_fee_experiment::*;
+pub use set_fee_tiers::*;
+pub use set_max_concurrent_borrowers::*;
+pub use set_max_invoke_depth::*;
+pub use set_ops_tag::*;
+pub use set_param_envelope::*;
+pub use set_pool_creation_deposit::*;
+pub use set_probation_loan_amount::*;
+pub use set_public_goods_mode::*;
+pub use set_quote_signer::*;
+pub use set_recovery_key::*;
+pub use set_reserve_factor::*;
+pub use set_same_slot_surcharge::*;
+pub use set_trusted_tier::*;
+pub use simple_flash_loan::*;
+pub use slash_keeper::*;
+pub use stake_reputation::*;
+pub use start_admin_recovery::*;
+pub use stream_protocol_fees::*;
+pub use sync_stats::*;
+pub use transfer_borrow_credits::*;
+pub use unlock_fee_tokens::*;
+pub use unlock_shares::*;
+pub use unpause_pool::*;
+pub use unstake_reputation::*;
+pub use unwind_rebalance::*;
+pub use update_pool_config::*;
+pub use view_borrower_limits::*;
+pub use view_fee::*;
+pub use view_snapshot_shares::*;
+pub use view_ui_amounts::*;
+pub use view_utilization::*;
+pub use withdraw_and_unwrap_sol::*;
+pub use withdraw_keeper_stake::*;
+pub use withdraw_liquidity::*;
+pub use withdraw_sol_pool::*;
+pub use wrap_sol_and_deposit::*;
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::state::{InsuranceFund, InsurancePolicy};
+
+// Context for a borrower opening cover with a pool's insurance fund
+#[derive(Accounts)]
+pub struct OpenInsurancePolicy<'info> {
+    pub insurance_fund: Account<'info, InsuranceFund>,
+    #[account(
+        init,
+        payer = borrower,
+        space = InsurancePolicy::LEN,
+        seeds = [INSURANCE_POLICY_SEED, insurance_fund.key().as_ref(), borrower.key().as_ref()],
+        bump
+    )]
+    pub insurance_policy: Account<'info, InsurancePolicy>,
+    #[account(mut)]
+    pub borrower: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// Open the policy `flash_borrow` records insured loans on
+pub(crate) fn handler(ctx: Context<OpenInsurancePolicy>) -> Result<()> {
+    let policy = &mut ctx.accounts.insurance_policy;
+    policy.insurance_fund = ctx.accounts.insurance_fund.key();
+    policy.borrower = ctx.accounts.borrower.key();
+    policy.bump = ctx.bumps.insurance_policy;
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::PoolParamOverridden;
+use crate::state::{ConfigField, ConfigHistory, Pool, PoolConfig, PoolParam, ProtocolConfig};
+
+// Context for the protocol admin overriding one of a pool's parameters
+#[derive(Accounts)]
+pub struct OverridePoolParam<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump, has_one = admin)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [POOL_CONFIG_SEED, pool.key().as_ref()],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+    #[account(
+        mut,
+        seeds = [CONFIG_HISTORY_SEED, pool.key().as_ref()],
+        bump = config_history.bump
+    )]
+    pub config_history: Account<'info, ConfigHistory>,
+    pub admin: Signer<'info>,                      // Protocol admin
+}
+
+// Set `param` live without the timelock, within the envelope, and freeze or
+// unfreeze it against the curator; both are recorded in the pool's history
+// under the protocol admin
+pub(crate) fn handler(ctx: Context<OverridePoolParam>, param: PoolParam, value: u64, freeze: bool) -> Result<()> {
+    require!(
+        ctx.accounts.protocol_config.envelope.allows(param, value),
+        FlashLoanError::OutsideParamEnvelope
+    );
+
+    let config = &mut ctx.accounts.pool_config;
+    let mut params = config.params();
+    let old_value = param.get(&params);
+    param.set(&mut params, value);
+    config.fee_bps = params.fee_bps;
+    config.max_loan_amount = params.max_loan_amount;
+    config.loan_cooldown = params.loan_cooldown;
+    config.timelock = params.timelock;
+
+    let old_frozen = config.frozen_params;
+    if freeze {
+        config.frozen_params |= param.bit();
+    } else {
+        config.frozen_params &= !param.bit();
+    }
+
+    let authority = ctx.accounts.admin.key();
+    let slot = Clock::get()?.slot;
+    let history = &mut ctx.accounts.config_history;
+    history.record(param.config_field(), old_value, value, authority, slot);
+    history.record(ConfigField::FrozenParams, old_frozen as u64, config.frozen_params as u64, authority, slot);
+
+    emit!(PoolParamOverridden {
+        pool: config.pool,
+        param: param as u8,
+        old_value,
+        new_value: value,
+        frozen: freeze,
+        authority,
+    });
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::PoolPauseUpdated;
+use crate::state::{ConfigField, ConfigHistory, Pool};
+
+// Context for changing the pool's pause flags
+#[derive(Accounts)]
+pub struct PausePool<'info> {
+    #[account(mut, has_one = admin)]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [CONFIG_HISTORY_SEED, pool.key().as_ref()],
+        bump = config_history.bump
+    )]
+    pub config_history: Account<'info, ConfigHistory>,
+    pub admin: Signer<'info>,                      // Pool admin
+}
+
+// Store the new pause flags and announce the change
+pub(crate) fn set_pause_flags(ctx: Context<PausePool>, pause_flags: u8) -> Result<()> {
+    let admin = ctx.accounts.admin.key();
+    apply_pause_flags(&mut ctx.accounts.pool, &mut ctx.accounts.config_history, admin, pause_flags)
+}
+
+// `set_pause_flags` on the accounts directly, shared with `batch_admin`
+pub(crate) fn apply_pause_flags(
+    pool: &mut Account<Pool>,
+    config_history: &mut ConfigHistory,
+    admin: Pubkey,
+    pause_flags: u8,
+) -> Result<()> {
+    require!(pause_flags & !PAUSE_ALL == 0, FlashLoanError::InvalidPauseFlags);
+    let clock = Clock::get()?;
+    config_history.record(ConfigField::PauseFlags, pool.pause_flags.into(), pause_flags.into(), admin, clock.slot);
+
+    // Start the emergency withdrawal clock when withdrawals are first halted
+    if pause_flags & PAUSE_WITHDRAW == 0 {
+        pool.withdraw_paused_since = 0;
+    } else if !pool.is_paused(PAUSE_WITHDRAW) {
+        pool.withdraw_paused_since = clock.unix_timestamp;
+    }
+    pool.pause_flags = pause_flags;
+
+    emit!(PoolPauseUpdated {
+        pool: pool.key(),
+        pause_flags,
+        ops_tag: pool.ops_tag,
+    });
+    pool.log_ops_tag();
+
+    Ok(())
+}
+
+// Halt the operations in `flags` (PAUSE_BORROW, PAUSE_DEPOSIT, PAUSE_WITHDRAW)
+pub(crate) fn handler(ctx: Context<PausePool>, flags: u8) -> Result<()> {
+    let pause_flags = ctx.accounts.pool.pause_flags | flags;
+    set_pause_flags(ctx, pause_flags)
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::state::{FeePrice, FeeSettlement};
+
+// Context for posting a pool mint's price in the settlement mint
+#[derive(Accounts)]
+pub struct PostFeePrice<'info> {
+    #[account(seeds = [FEE_SETTLEMENT_SEED], bump = fee_settlement.bump, has_one = price_authority)]
+    pub fee_settlement: Account<'info, FeeSettlement>,
+    #[account(
+        init_if_needed,
+        payer = price_authority,
+        space = FeePrice::LEN,
+        seeds = [FEE_PRICE_SEED, token_mint.key().as_ref()],
+        bump
+    )]
+    pub fee_price: Account<'info, FeePrice>,
+    pub token_mint: InterfaceAccount<'info, Mint>, // Mint being priced
+    #[account(mut)]
+    pub price_authority: Signer<'info>,            // Oracle key set in the fee settlement
+    pub system_program: Program<'info, System>,
+}
+
+// Record `price` settlement base units per base unit of the mint, scaled by FEE_PRICE_SCALE
+pub(crate) fn handler(ctx: Context<PostFeePrice>, price: u64) -> Result<()> {
+    require!(price > 0, FlashLoanError::StaleFeePrice);
+
+    let fee_price = &mut ctx.accounts.fee_price;
+    fee_price.token_mint = ctx.accounts.token_mint.key();
+    fee_price.price = price;
+    fee_price.updated_at = Clock::get()?.unix_timestamp;
+    fee_price.bump = ctx.bumps.fee_price;
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::events::AdminProposed;
+use crate::state::Pool;
+
+// Context for proposing a new pool admin
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    #[account(mut, has_one = admin)]
+    pub pool: Account<'info, Pool>,
+    pub admin: Signer<'info>,                      // Current pool admin
+}
+
+// Nominate `new_admin`; nothing changes until it signs `accept_admin`
+// Proposing the default key cancels a pending transfer
+pub(crate) fn handler(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+    ctx.accounts.pool.pending_admin = new_admin;
+
+    emit!(AdminProposed {
+        pool: ctx.accounts.pool.key(),
+        admin: ctx.accounts.admin.key(),
+        pending_admin: new_admin,
+    });
+
+    Ok(())
+}
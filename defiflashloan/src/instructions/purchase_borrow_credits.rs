@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::{BorrowCreditsPurchased, SharePriceUpdated};
+use crate::fees::calculate_dynamic_fee;
+use crate::state::{BorrowCredit, LoanStats, Pool, PoolConfig};
+use crate::transfer_fee::gross_for_net;
+
+// Context for buying prepaid borrow credits
+#[derive(Accounts)]
+pub struct PurchaseBorrowCredits<'info> {
+    #[account(
+        mut,
+        seeds = [POOL_SEED, token_mint.key().as_ref(), pool.index.as_ref()],
+        bump = pool.bump,
+        has_one = token_mint
+    )]
+    pub pool: Account<'info, Pool>,                // Pool the credits are spent on
+    #[account(init, payer = buyer, space = BorrowCredit::LEN)]
+    pub borrow_credit: Account<'info, BorrowCredit>,
+    #[account(mut)]
+    pub buyer: Signer<'info>,                      // Buyer paying for the credits
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = buyer
+    )]
+    pub buyer_account: InterfaceAccount<'info, TokenAccount>, // Buyer's token account
+    #[account(mut, address = pool.vault)]
+    pub loan_vault: InterfaceAccount<'info, TokenAccount>, // Flash loan pool
+    #[account(mut)]
+    pub loan_stats: Account<'info, LoanStats>,     // Loan statistics account
+    #[account(seeds = [POOL_CONFIG_SEED, pool.key().as_ref()], bump = pool_config.bump)]
+    pub pool_config: Account<'info, PoolConfig>,   // Pool risk parameters pricing the credits
+    pub token_mint: InterfaceAccount<'info, Mint>, // Mint lent out by the pool, for checked transfers
+    #[account(address = pool.share_mint)]
+    pub share_mint: InterfaceAccount<'info, Mint>, // LP share mint, read to report the share price
+    pub token_program: Interface<'info, TokenInterface>, // Token or Token-2022 program
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PurchaseBorrowCredits<'info> {
+    // Context for paying prepaid fees into the vault
+    pub fn into_transfer_to_vault_context(&self) -> CpiContext<'_, '_, '_, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
+            from: self.buyer_account.to_account_info().clone(),
+            mint: self.token_mint.to_account_info().clone(),
+            to: self.loan_vault.to_account_info().clone(),
+            authority: self.buyer.to_account_info().clone(),
+        };
+        CpiContext::new(self.token_program.to_account_info().clone(), cpi_accounts)
+    }
+}
+
+// Prepay the fees for a bundle of loans at the rate locked for `max_loan_size`
+pub(crate) fn handler(
+    ctx: Context<PurchaseBorrowCredits>,
+    loan_count: u64,
+    max_loan_size: u64,
+) -> Result<()> {
+    require!(loan_count > 0, FlashLoanError::InvalidBorrowCredit);
+    require!(
+        max_loan_size > 0 && max_loan_size <= ctx.accounts.pool_config.max_loan_amount,
+        FlashLoanError::InvalidBorrowCredit
+    );
+
+    let config = &ctx.accounts.pool_config;
+    // Credits carry no accounting price, so USD tiers sell at the first tier
+    let fee_bps = ctx.accounts.pool.base_fee_bps(max_loan_size, config.fee_bps, None, 0);
+    let fee_per_loan = calculate_dynamic_fee(max_loan_size, ctx.accounts.loan_vault.amount, fee_bps, &config.fee_curve);
+    let total_price = fee_per_loan
+        .checked_mul(loan_count)
+        .ok_or(FlashLoanError::InvalidBorrowCredit)?;
+
+    // Prepaid fees go straight into the vault, grossed up for any transfer fee
+    let price_before = ctx.accounts.pool.share_price(ctx.accounts.loan_vault.amount, ctx.accounts.share_mint.supply);
+    let transfer_amount = gross_for_net(&ctx.accounts.token_mint.to_account_info(), total_price)?;
+    token_interface::transfer_checked(
+        ctx.accounts.into_transfer_to_vault_context(),
+        transfer_amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    // They are split like any loan fee; loans covered by the credit pay none
+    let protocol_fee = ctx.accounts.pool.protocol_fee(total_price);
+    ctx.accounts.pool.accrue_fees(total_price, protocol_fee);
+
+    let credit = &mut ctx.accounts.borrow_credit;
+    credit.owner = ctx.accounts.buyer.key();
+    credit.pool = ctx.accounts.pool.key();
+    credit.remaining_loans = loan_count;
+    credit.max_loan_size = max_loan_size;
+    credit.fee_per_loan = fee_per_loan;
+
+    ctx.accounts.loan_stats.total_fees_collected += total_price;
+
+    emit!(BorrowCreditsPurchased {
+        owner: credit.owner,
+        pool: credit.pool,
+        loan_count,
+        max_loan_size,
+        total_price,
+    });
+
+    ctx.accounts.loan_vault.reload()?;
+    ctx.accounts.share_mint.reload()?;
+    emit!(SharePriceUpdated {
+        pool: ctx.accounts.pool.key(),
+        price_before,
+        price_after: ctx.accounts.pool.share_price(ctx.accounts.loan_vault.amount, ctx.accounts.share_mint.supply),
+        lp_assets: ctx.accounts.pool.lp_assets(ctx.accounts.loan_vault.amount),
+        share_supply: ctx.accounts.share_mint.supply,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::LiquidityRebalanced;
+use crate::shares::shares_for_deposit;
+use crate::state::{Pool, ProtocolConfig, RebalanceRoute};
+use crate::transfer_fee::transfer_fee;
+
+// Context for moving liquidity along a rebalance route
+#[derive(Accounts)]
+pub struct RebalanceLiquidity<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        has_one = source_pool,
+        has_one = target_pool,
+        has_one = iou_share_account,
+        seeds = [REBALANCE_ROUTE_SEED, source_pool.key().as_ref(), target_pool.key().as_ref()],
+        bump = rebalance_route.bump,
+        constraint = operator.key() == rebalance_route.operator || operator.key() == protocol_config.admin
+            @ FlashLoanError::RebalanceNotAuthorized
+    )]
+    pub rebalance_route: Account<'info, RebalanceRoute>,
+    #[account(
+        mut,
+        seeds = [POOL_SEED, token_mint.key().as_ref(), source_pool.index.as_ref()],
+        bump = source_pool.bump
+    )]
+    pub source_pool: Account<'info, Pool>,
+    #[account(mut, address = source_pool.vault)]
+    pub source_vault: InterfaceAccount<'info, TokenAccount>, // Pays the moved liquidity
+    #[account(address = source_pool.share_mint)]
+    pub source_share_mint: InterfaceAccount<'info, Mint>, // Read to check the source share price
+    #[account(
+        mut,
+        seeds = [POOL_SEED, token_mint.key().as_ref(), target_pool.index.as_ref()],
+        bump = target_pool.bump
+    )]
+    pub target_pool: Account<'info, Pool>,
+    #[account(mut, address = target_pool.vault)]
+    pub target_vault: InterfaceAccount<'info, TokenAccount>, // Receives the moved liquidity
+    #[account(mut, address = target_pool.share_mint)]
+    pub target_share_mint: InterfaceAccount<'info, Mint>, // IOU shares are minted from it
+    #[account(mut)]
+    pub iou_share_account: InterfaceAccount<'info, TokenAccount>, // Source pool's target shares
+    pub token_mint: InterfaceAccount<'info, Mint>, // Mint both pools lend out
+    pub operator: Signer<'info>,                   // Route operator or protocol admin
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> RebalanceLiquidity<'info> {
+    // Context for moving the liquidity between vaults, signed by the source pool PDA
+    pub fn into_transfer_to_target_context<'a, 'b, 'c>(
+        &self,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
+            from: self.source_vault.to_account_info().clone(),
+            mint: self.token_mint.to_account_info().clone(),
+            to: self.target_vault.to_account_info().clone(),
+            authority: self.source_pool.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+
+    // Context for minting the IOU shares, signed by the target pool PDA
+    pub fn into_mint_iou_shares_context<'a, 'b, 'c>(
+        &self,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, MintTo<'info>> {
+        let cpi_accounts = MintTo {
+            mint: self.target_share_mint.to_account_info().clone(),
+            to: self.iou_share_account.to_account_info().clone(),
+            authority: self.target_pool.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+}
+
+// Move `amount` from the source vault into the target, minting the source
+// pool target shares at the target's price. The source keeps counting the
+// principal as an LP asset, so both share prices must hold across the move
+pub(crate) fn handler(ctx: Context<RebalanceLiquidity>, amount: u64) -> Result<()> {
+    require!(amount > 0, FlashLoanError::InvalidAmount);
+    let route = &ctx.accounts.rebalance_route;
+    require!(
+        amount <= route.max_move_amount && route.outstanding_principal + amount <= route.max_outstanding,
+        FlashLoanError::RebalanceLimitExceeded
+    );
+    require!(!ctx.accounts.source_pool.is_paused(PAUSE_WITHDRAW), FlashLoanError::PoolPaused);
+    require!(!ctx.accounts.target_pool.is_paused(PAUSE_DEPOSIT), FlashLoanError::PoolPaused);
+    require!(!ctx.accounts.target_pool.is_winding_down(), FlashLoanError::PoolWindingDown);
+    require!(
+        ctx.accounts.source_vault.amount.saturating_sub(ctx.accounts.source_pool.fees_reserved()) >= amount,
+        FlashLoanError::InsufficientFunds
+    );
+    // A transfer fee would be a loss the source pool books at cost
+    require!(
+        transfer_fee(&ctx.accounts.token_mint.to_account_info(), amount)? == 0,
+        FlashLoanError::InvalidRebalanceRoute
+    );
+
+    let source_price_before = ctx
+        .accounts
+        .source_pool
+        .share_price(ctx.accounts.source_vault.amount, ctx.accounts.source_share_mint.supply);
+    let target_price_before = ctx
+        .accounts
+        .target_pool
+        .share_price(ctx.accounts.target_vault.amount, ctx.accounts.target_share_mint.supply);
+    let shares = shares_for_deposit(
+        amount,
+        ctx.accounts.target_pool.lp_assets(ctx.accounts.target_vault.amount),
+        ctx.accounts.target_share_mint.supply,
+    );
+    require!(shares > 0, FlashLoanError::InvalidAmount);
+
+    let source_seeds = ctx.accounts.source_pool.seeds();
+    token_interface::transfer_checked(
+        ctx.accounts.into_transfer_to_target_context(&[&source_seeds[..]]),
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+    let target_seeds = ctx.accounts.target_pool.seeds();
+    token_interface::mint_to(ctx.accounts.into_mint_iou_shares_context(&[&target_seeds[..]]), shares)?;
+
+    ctx.accounts.source_pool.rebalanced_out += amount;
+    ctx.accounts.target_pool.total_deposits += amount;
+    let route = &mut ctx.accounts.rebalance_route;
+    route.outstanding_principal += amount;
+    route.iou_shares += shares;
+
+    ctx.accounts.source_vault.reload()?;
+    ctx.accounts.target_vault.reload()?;
+    ctx.accounts.target_share_mint.reload()?;
+    let source_price_after = ctx
+        .accounts
+        .source_pool
+        .share_price(ctx.accounts.source_vault.amount, ctx.accounts.source_share_mint.supply);
+    let target_price_after = ctx
+        .accounts
+        .target_pool
+        .share_price(ctx.accounts.target_vault.amount, ctx.accounts.target_share_mint.supply);
+    require!(
+        source_price_after >= source_price_before && target_price_after >= target_price_before,
+        FlashLoanError::SharePriceMoved
+    );
+
+    emit!(LiquidityRebalanced {
+        route: ctx.accounts.rebalance_route.key(),
+        source_pool: ctx.accounts.source_pool.key(),
+        target_pool: ctx.accounts.target_pool.key(),
+        amount,
+        iou_shares: shares,
+        outstanding_principal: ctx.accounts.rebalance_route.outstanding_principal,
+    });
+
+    Ok(())
+}
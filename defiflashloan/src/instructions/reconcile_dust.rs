@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::events::DustReconciled;
+use crate::state::Pool;
+
+// Context for settling a pool's written-off dust
+#[derive(Accounts)]
+pub struct ReconcileDust<'info> {
+    #[account(mut, seeds = [POOL_SEED, pool.token_mint.as_ref(), pool.index.as_ref()], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+}
+
+// Permissionless: cover the dust owed to LPs out of the protocol fees held in
+// the vault, which stay there and simply stop being reserved for the
+// treasury. Dust beyond the accrued fees waits for the next reconciliation
+pub(crate) fn handler(ctx: Context<ReconcileDust>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let amount = pool.dust_owed.min(pool.protocol_fees_accrued);
+    if amount == 0 {
+        return Ok(());
+    }
+    pool.dust_owed -= amount;
+    pool.protocol_fees_accrued -= amount;
+    emit!(DustReconciled {
+        pool: pool.key(),
+        amount,
+        dust_owed: pool.dust_owed,
+    });
+    Ok(())
+}
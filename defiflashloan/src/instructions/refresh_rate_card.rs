@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::constants::*;
+use crate::fees::calculate_dynamic_fee;
+use crate::state::{FeePrice, Pool, PoolConfig, RateCard, RateCardEntry};
+
+// Context for the rate card crank
+#[derive(Accounts)]
+pub struct RefreshRateCard<'info> {
+    #[account(seeds = [POOL_SEED, pool.token_mint.as_ref(), pool.index.as_ref()], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+    #[account(seeds = [POOL_CONFIG_SEED, pool.key().as_ref()], bump = pool_config.bump)]
+    pub pool_config: Account<'info, PoolConfig>,   // Pool risk parameters
+    #[account(address = pool.vault)]
+    pub loan_vault: InterfaceAccount<'info, TokenAccount>, // Flash loan pool
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = RateCard::LEN,
+        seeds = [RATE_CARD_SEED, pool.key().as_ref()],
+        bump
+    )]
+    pub rate_card: Account<'info, RateCard>,
+    #[account(mut)]
+    pub payer: Signer<'info>,                      // Any cranker; pays for the card on first refresh
+    #[account(address = pool.accounting_oracle)]
+    pub accounting_price: Option<Account<'info, FeePrice>>, // Pass to apply USD fee tiers, which otherwise charge the first tier
+    pub system_program: Program<'info, System>,
+}
+
+// Permissionless crank: re-quote the representative loan sizes against the
+// pool's current config and liquidity; run it after any config or curve change
+pub(crate) fn handler(ctx: Context<RefreshRateCard>) -> Result<()> {
+    let config = &ctx.accounts.pool_config;
+    let vault_amount = ctx.accounts.loan_vault.amount;
+    let now = Clock::get()?.unix_timestamp;
+    let accounting_price = ctx.accounts.accounting_price.as_deref();
+
+    let rate_card = &mut ctx.accounts.rate_card;
+    rate_card.pool = ctx.accounts.pool.key();
+    rate_card.fee_bps = config.fee_bps;
+    rate_card.fee_curve = config.fee_curve;
+    rate_card.vault_amount = vault_amount;
+    rate_card.updated_at = now;
+    rate_card.bump = ctx.bumps.rate_card;
+
+    for (entry, utilization_bps) in rate_card.entries.iter_mut().zip(RATE_CARD_UTILIZATION_BPS) {
+        let loan_amount = ((vault_amount as u128 * utilization_bps as u128 / BPS_DENOMINATOR as u128) as u64)
+            .min(config.max_loan_amount);
+        *entry = RateCardEntry {
+            utilization_bps,
+            loan_amount,
+            fee: calculate_dynamic_fee(
+                loan_amount,
+                vault_amount,
+                ctx.accounts.pool.base_fee_bps(loan_amount, config.fee_bps, accounting_price, now),
+                &config.fee_curve,
+            ),
+        };
+    }
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::IntegratorRegistered;
+use crate::receiver::RECEIVER_INTERFACE_VERSION;
+use crate::state::{Integrator, ProtocolConfig};
+
+// Context for registering a receiver program's interface version
+#[derive(Accounts)]
+#[instruction(program_id: Pubkey)]
+pub struct RegisterIntegrator<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump, has_one = admin)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = Integrator::LEN,
+        seeds = [INTEGRATOR_SEED, program_id.as_ref()],
+        bump
+    )]
+    pub integrator: Account<'info, Integrator>,
+    #[account(mut)]
+    pub admin: Signer<'info>,                      // Protocol admin
+    pub system_program: Program<'info, System>,
+}
+
+// Record that `program_id` implements the receiver interface from
+// `min_interface_version` on; re-registering after an upgrade overwrites it.
+// Receiver loans check it against the interface they call with
+pub(crate) fn handler(ctx: Context<RegisterIntegrator>, program_id: Pubkey, min_interface_version: u16) -> Result<()> {
+    require!(
+        min_interface_version > 0 && min_interface_version <= RECEIVER_INTERFACE_VERSION,
+        FlashLoanError::InvalidInterfaceVersion
+    );
+
+    let integrator = &mut ctx.accounts.integrator;
+    integrator.program_id = program_id;
+    integrator.min_interface_version = min_interface_version;
+    integrator.registered_at = Clock::get()?.unix_timestamp;
+    integrator.bump = ctx.bumps.integrator;
+
+    emit!(IntegratorRegistered {
+        program_id,
+        min_interface_version,
+        interface_version: RECEIVER_INTERFACE_VERSION,
+    });
+    Ok(())
+}
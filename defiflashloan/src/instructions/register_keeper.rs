@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::KeeperStaked;
+use crate::state::{Keeper, KeeperRegistry};
+
+// Context for bonding tokens to register as, or top up, a keeper
+#[derive(Accounts)]
+pub struct RegisterKeeper<'info> {
+    #[account(mut, seeds = [KEEPER_REGISTRY_SEED], bump = keeper_registry.bump)]
+    pub keeper_registry: Account<'info, KeeperRegistry>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = Keeper::LEN,
+        seeds = [KEEPER_SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub keeper: Account<'info, Keeper>,
+    #[account(mut, address = keeper_registry.stake_vault)]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>, // Receives the bond
+    #[account(mut, token::mint = stake_mint, token::authority = authority)]
+    pub source_account: InterfaceAccount<'info, TokenAccount>, // Keeper's stake tokens
+    #[account(address = keeper_registry.stake_mint)]
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub authority: Signer<'info>,                  // Keeper signing its cranks
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RegisterKeeper<'info> {
+    // Context for moving the bond into the stake vault
+    pub fn into_transfer_to_stake_vault_context(&self) -> CpiContext<'_, '_, '_, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
+            from: self.source_account.to_account_info().clone(),
+            mint: self.stake_mint.to_account_info().clone(),
+            to: self.stake_vault.to_account_info().clone(),
+            authority: self.authority.to_account_info().clone(),
+        };
+        CpiContext::new(self.token_program.to_account_info().clone(), cpi_accounts)
+    }
+}
+
+// Add `amount` to the keeper's bond; the bond must reach the registry minimum,
+// and an exiting keeper must withdraw before bonding again
+pub(crate) fn handler(ctx: Context<RegisterKeeper>, amount: u64) -> Result<()> {
+    require!(amount > 0, FlashLoanError::InvalidAmount);
+    require!(ctx.accounts.keeper.exit_requested_at == 0, FlashLoanError::KeeperExiting);
+
+    // Only what reaches the vault after any transfer fee is bonded
+    let vault_before = ctx.accounts.stake_vault.amount;
+    token_interface::transfer_checked(
+        ctx.accounts.into_transfer_to_stake_vault_context(),
+        amount,
+        ctx.accounts.stake_mint.decimals,
+    )?;
+    ctx.accounts.stake_vault.reload()?;
+    let bonded = ctx.accounts.stake_vault.amount - vault_before;
+
+    let registry = &mut ctx.accounts.keeper_registry;
+    let keeper = &mut ctx.accounts.keeper;
+    if keeper.authority == Pubkey::default() {
+        keeper.authority = ctx.accounts.authority.key();
+        keeper.bump = ctx.bumps.keeper;
+    }
+    if keeper.staked_amount == 0 {
+        registry.keeper_count += 1;
+    }
+    keeper.staked_amount += bonded;
+    require!(keeper.staked_amount >= registry.min_stake, FlashLoanError::KeeperStakeTooLow);
+    registry.total_staked += bonded;
+
+    emit!(KeeperStaked {
+        keeper: keeper.key(),
+        authority: keeper.authority,
+        amount: bonded,
+        staked_amount: keeper.staked_amount,
+    });
+
+    Ok(())
+}
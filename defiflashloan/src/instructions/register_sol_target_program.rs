@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::state::{ProgramRegistry, SolPool};
+
+// Context for approving a callback target program for lamport loans
+#[derive(Accounts)]
+pub struct RegisterSolTargetProgram<'info> {
+    #[account(seeds = [SOL_POOL_SEED], bump = sol_pool.bump, has_one = admin)]
+    pub sol_pool: Account<'info, SolPool>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = ProgramRegistry::LEN,
+        seeds = [PROGRAM_REGISTRY_SEED, sol_pool.key().as_ref()],
+        bump
+    )]
+    pub program_registry: Account<'info, ProgramRegistry>,
+    #[account(mut)]
+    pub admin: Signer<'info>,                      // Lamport pool admin
+    pub system_program: Program<'info, System>,
+}
+
+// Allow lamport flash loan callbacks to invoke `program_id`
+pub(crate) fn handler(ctx: Context<RegisterSolTargetProgram>, program_id: Pubkey) -> Result<()> {
+    ctx.accounts.program_registry.register(
+        ctx.accounts.sol_pool.key(),
+        ctx.bumps.program_registry,
+        program_id,
+    )
+}
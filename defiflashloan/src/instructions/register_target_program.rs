@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::state::{Pool, ProgramRegistry};
+
+// Context for approving a callback target program
+#[derive(Accounts)]
+pub struct RegisterTargetProgram<'info> {
+    #[account(has_one = admin)]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = ProgramRegistry::LEN,
+        seeds = [PROGRAM_REGISTRY_SEED, pool.key().as_ref()],
+        bump
+    )]
+    pub program_registry: Account<'info, ProgramRegistry>,
+    #[account(mut)]
+    pub admin: Signer<'info>,                      // Pool admin
+    pub system_program: Program<'info, System>,
+}
+
+// Allow flash loan callbacks from this pool to invoke `program_id`
+pub(crate) fn handler(ctx: Context<RegisterTargetProgram>, program_id: Pubkey) -> Result<()> {
+    ctx.accounts.program_registry.register(
+        ctx.accounts.pool.key(),
+        ctx.bumps.program_registry,
+        program_id,
+    )
+}
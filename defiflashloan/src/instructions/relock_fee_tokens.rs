@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::FeeTokensLocked;
+use crate::state::{FeeLocker, LockerPosition};
+
+// Context for extending a fee lock
+#[derive(Accounts)]
+pub struct RelockFeeTokens<'info> {
+    /// CHECK: Only keys the fee locker PDA
+    pub pool: UncheckedAccount<'info>,             // Pool the fee locker streams from
+    #[account(mut, seeds = [FEE_LOCKER_SEED, pool.key().as_ref()], bump = fee_locker.bump)]
+    pub fee_locker: Account<'info, FeeLocker>,
+    #[account(
+        mut,
+        has_one = owner,
+        has_one = fee_locker,
+        seeds = [LOCKER_POSITION_SEED, fee_locker.key().as_ref(), owner.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, LockerPosition>,
+    pub owner: Signer<'info>,
+}
+
+// Extend the lock to at least `duration` seconds from now and reweight the
+// position for its new remaining lock time
+pub(crate) fn handler(ctx: Context<RelockFeeTokens>, duration: i64) -> Result<()> {
+    require!(
+        duration > 0 && duration <= MAX_FEE_LOCK_DURATION,
+        FlashLoanError::InvalidLockDuration
+    );
+    require!(ctx.accounts.position.amount > 0, FlashLoanError::InvalidAmount);
+    let now = Clock::get()?.unix_timestamp;
+
+    let fee_locker = &mut ctx.accounts.fee_locker;
+    let position = &mut ctx.accounts.position;
+    position.settle(fee_locker.reward_per_weight);
+    fee_locker.total_weight -= position.weight;
+    position.unlock_at = position.unlock_at.max(now + duration);
+    position.reweight(now, fee_locker.reward_per_weight);
+    fee_locker.total_weight += position.weight;
+
+    emit!(FeeTokensLocked {
+        fee_locker: position.fee_locker,
+        owner: position.owner,
+        amount: position.amount,
+        weight: position.weight,
+        unlock_at: position.unlock_at,
+    });
+
+    Ok(())
+}
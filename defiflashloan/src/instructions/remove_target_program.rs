@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::state::{Pool, ProgramRegistry};
+
+// Context for revoking a callback target program
+#[derive(Accounts)]
+pub struct RemoveTargetProgram<'info> {
+    #[account(has_one = admin)]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [PROGRAM_REGISTRY_SEED, pool.key().as_ref()],
+        bump = program_registry.bump
+    )]
+    pub program_registry: Account<'info, ProgramRegistry>,
+    pub admin: Signer<'info>,                      // Pool admin
+}
+
+// Stop flash loan callbacks from this pool invoking `program_id`
+pub(crate) fn handler(ctx: Context<RemoveTargetProgram>, program_id: Pubkey) -> Result<()> {
+    ctx.accounts.program_registry.remove(&program_id)
+}
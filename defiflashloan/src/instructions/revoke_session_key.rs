@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::state::SessionKey;
+
+// Context for revoking a session key
+#[derive(Accounts)]
+pub struct RevokeSessionKey<'info> {
+    #[account(
+        mut,
+        close = borrower,
+        has_one = borrower @ FlashLoanError::SessionKeyMismatch,
+        seeds = [SESSION_KEY_SEED, borrower.key().as_ref(), session_signer.key().as_ref()],
+        bump = session_key.bump
+    )]
+    pub session_key: Account<'info, SessionKey>,
+    /// CHECK: Only keys the session key PDA
+    pub session_signer: UncheckedAccount<'info>,   // Key the session was granted to
+    #[account(mut)]
+    pub borrower: Signer<'info>,                   // Borrower that created the session key
+}
+
+// Revoke a session key and reclaim its rent
+pub(crate) fn handler(_ctx: Context<RevokeSessionKey>) -> Result<()> {
+    Ok(())
+}
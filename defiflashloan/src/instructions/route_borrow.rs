@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::constants::*;
+use crate::instructions::max_borrowable::borrowable_amount;
+use crate::state::{LoanState, Pool, PoolConfig, PoolDirectory};
+
+// Context for the routing hint view
+#[derive(Accounts)]
+pub struct RouteBorrow<'info> {
+    #[account(seeds = [POOL_SEED, pool.token_mint.as_ref(), pool.index.as_ref()], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+    #[account(seeds = [POOL_CONFIG_SEED, pool.key().as_ref()], bump = pool_config.bump)]
+    pub pool_config: Account<'info, PoolConfig>,   // Pool risk parameters
+    #[account(address = pool.vault)]
+    pub loan_vault: InterfaceAccount<'info, TokenAccount>, // Flash loan pool
+    /// CHECK: Only keys the loan state and reputation PDAs
+    pub borrower: UncheckedAccount<'info>,         // Borrower the view is for
+    #[account(
+        seeds = [LOAN_STATE_SEED, pool.key().as_ref(), borrower.key().as_ref()],
+        bump = loan_state.bump
+    )]
+    pub loan_state: Account<'info, LoanState>,     // Reentrancy check and state
+    #[account(seeds = [POOL_DIRECTORY_SEED, pool.token_mint.as_ref()], bump = pool_directory.bump)]
+    pub pool_directory: Option<Account<'info, PoolDirectory>>, // Source of alternate pools, if listed
+}
+
+// Pool-specific borrow constraint that would reject a loan
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BorrowConstraint {
+    Paused,                         // Borrowing is paused on the pool
+    LoanTooLarge,                   // Above the pool's max loan amount
+    InsufficientLiquidity,          // Above the vault balance
+    LoanActive,                     // A loan is already in flight
+    Cooldown,                       // The loan state's cooldown is still running
+}
+
+// Routing hint returned by `route_borrow`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct RoutingHint {
+    pub constraint: Option<BorrowConstraint>, // First failing check, none when the loan would pass
+    pub max_loan_amount: u64,       // Largest loan this pool would accept now
+    pub alternates: Vec<Pubkey>,    // Other pools for the mint, empty when the loan would pass
+}
+
+// First check, in the order `execute_flash_loan` applies them, that a loan of
+// `loan_amount` would fail
+pub fn failing_constraint(
+    pool: &Pool,
+    config: &PoolConfig,
+    loan_state: &LoanState,
+    vault_amount: u64,
+    loan_amount: u64,
+    now: i64,
+) -> Option<BorrowConstraint> {
+    if pool.is_paused(PAUSE_BORROW) {
+        Some(BorrowConstraint::Paused)
+    } else if loan_amount > config.max_loan_amount {
+        Some(BorrowConstraint::LoanTooLarge)
+    } else if loan_amount > vault_amount {
+        Some(BorrowConstraint::InsufficientLiquidity)
+    } else if now < loan_state.last_loan_timestamp + config.loan_cooldown {
+        Some(BorrowConstraint::Cooldown)
+    } else if loan_state.active {
+        Some(BorrowConstraint::LoanActive)
+    } else {
+        None
+    }
+}
+
+// View: whether a loan of `loan_amount` would pass and, if not, why and
+// which other pools a router could fail over to
+pub(crate) fn handler(ctx: Context<RouteBorrow>, loan_amount: u64) -> Result<RoutingHint> {
+    let clock = Clock::get()?;
+    let accounts = &ctx.accounts;
+    let vault_amount = accounts.loan_vault.amount;
+    let constraint = failing_constraint(
+        &accounts.pool,
+        &accounts.pool_config,
+        &accounts.loan_state,
+        vault_amount,
+        loan_amount,
+        clock.unix_timestamp,
+    );
+
+    let max_loan_amount = borrowable_amount(
+        &accounts.pool,
+        vault_amount,
+        &accounts.loan_state,
+        &accounts.pool_config,
+        false,
+        clock.unix_timestamp,
+    );
+
+    // The canonical pool, when there is one, is suggested first
+    let alternates = match (constraint, accounts.pool_directory.as_ref()) {
+        (Some(_), Some(directory)) => std::iter::once(&directory.canonical)
+            .chain(directory.pools.iter().filter(|pool| **pool != directory.canonical))
+            .filter(|pool| **pool != accounts.pool.key() && **pool != Pubkey::default())
+            .copied()
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    Ok(RoutingHint { constraint, max_loan_amount, alternates })
+}
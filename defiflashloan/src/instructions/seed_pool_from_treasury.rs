@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::{ProtocolLiquiditySeeded, SharePriceUpdated};
+use crate::shares::shares_for_deposit;
+use crate::transfer_fee::transfer_fee;
+use crate::state::{Pool, ProtocolConfig};
+
+// Context for seeding a pool with protocol-owned liquidity from the treasury
+#[derive(Accounts)]
+pub struct SeedPoolFromTreasury<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump, has_one = admin, has_one = treasury)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [POOL_SEED, pool.token_mint.as_ref(), pool.index.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(mut, address = pool.vault)]
+    pub loan_vault: InterfaceAccount<'info, TokenAccount>, // Pool vault receiving the liquidity
+    #[account(mut, address = pool.share_mint)]
+    pub share_mint: InterfaceAccount<'info, Mint>, // LP share mint
+    #[account(
+        init_if_needed,
+        payer = admin,
+        seeds = [PROTOCOL_LIQUIDITY_SEED, pool.key().as_ref()],
+        bump,
+        token::mint = share_mint,
+        token::authority = protocol_config,
+        token::token_program = token_program
+    )]
+    pub protocol_share_account: InterfaceAccount<'info, TokenAccount>, // Protocol-owned shares; no instruction releases them
+    #[account(mut, token::mint = pool.token_mint, token::authority = treasury)]
+    pub treasury_account: InterfaceAccount<'info, TokenAccount>, // Treasury token account funding the seed
+    pub treasury: Signer<'info>,                   // Treasury owner
+    #[account(mut)]
+    pub admin: Signer<'info>,                      // Protocol admin
+    #[account(address = pool.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>, // Mint lent out by the pool
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SeedPoolFromTreasury<'info> {
+    // Context for moving treasury tokens into the vault
+    pub fn into_transfer_to_vault_context(&self) -> CpiContext<'_, '_, '_, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
+            from: self.treasury_account.to_account_info().clone(),
+            mint: self.token_mint.to_account_info().clone(),
+            to: self.loan_vault.to_account_info().clone(),
+            authority: self.treasury.to_account_info().clone(),
+        };
+        CpiContext::new(self.token_program.to_account_info().clone(), cpi_accounts)
+    }
+
+    // Context for minting the protocol's shares, signed by the pool PDA
+    pub fn into_mint_shares_context<'a, 'b, 'c>(
+        &self,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, MintTo<'info>> {
+        let cpi_accounts = MintTo {
+            mint: self.share_mint.to_account_info().clone(),
+            to: self.protocol_share_account.to_account_info().clone(),
+            authority: self.pool.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+}
+
+// Deposit treasury tokens as protocol-owned liquidity; the shares are minted to
+// a token account owned by the protocol config PDA, which never signs a
+// withdrawal, so the depth stays until governance adds a release path
+pub(crate) fn handler(ctx: Context<SeedPoolFromTreasury>, amount: u64) -> Result<()> {
+    require!(amount > 0, FlashLoanError::InvalidAmount);
+    require!(!ctx.accounts.pool.is_winding_down(), FlashLoanError::PoolWindingDown);
+
+    // Price the seed like any other deposit so existing LPs are not diluted,
+    // crediting only what arrives after any Token-2022 transfer fee
+    let received = amount - transfer_fee(&ctx.accounts.token_mint.to_account_info(), amount)?;
+    let price_before = ctx.accounts.pool.share_price(ctx.accounts.loan_vault.amount, ctx.accounts.share_mint.supply);
+    let shares = shares_for_deposit(
+        received,
+        ctx.accounts.pool.lp_assets(ctx.accounts.loan_vault.amount),
+        ctx.accounts.share_mint.supply,
+    );
+    require!(shares > 0, FlashLoanError::InvalidAmount);
+
+    token_interface::transfer_checked(
+        ctx.accounts.into_transfer_to_vault_context(),
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    let pool_seeds = ctx.accounts.pool.seeds();
+    token_interface::mint_to(
+        ctx.accounts.into_mint_shares_context(&[&pool_seeds[..]]),
+        shares,
+    )?;
+
+    ctx.accounts.pool.total_deposits += received;
+
+    emit!(ProtocolLiquiditySeeded {
+        pool: ctx.accounts.pool.key(),
+        treasury_account: ctx.accounts.treasury_account.key(),
+        amount: received,
+        shares,
+    });
+
+    ctx.accounts.loan_vault.reload()?;
+    ctx.accounts.share_mint.reload()?;
+    emit!(SharePriceUpdated {
+        pool: ctx.accounts.pool.key(),
+        price_before,
+        price_after: ctx.accounts.pool.share_price(ctx.accounts.loan_vault.amount, ctx.accounts.share_mint.supply),
+        lp_assets: ctx.accounts.pool.lp_assets(ctx.accounts.loan_vault.amount),
+        share_supply: ctx.accounts.share_mint.supply,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::constants::*;
+use crate::events::AccountingOracleSet;
+use crate::state::{FeePrice, FeeSettlement, Pool};
+
+// Context for choosing the price a pool reports its fee revenue in USD by
+#[derive(Accounts)]
+pub struct SetAccountingOracle<'info> {
+    #[account(mut, has_one = admin)]
+    pub pool: Account<'info, Pool>,
+    #[account(seeds = [FEE_SETTLEMENT_SEED], bump = fee_settlement.bump, has_one = settlement_mint)]
+    pub fee_settlement: Account<'info, FeeSettlement>,
+    pub settlement_mint: InterfaceAccount<'info, Mint>, // Dollar stablecoin the oracle prices in
+    #[account(seeds = [FEE_PRICE_SEED, pool.token_mint.as_ref()], bump = fee_price.bump)]
+    pub fee_price: Option<Account<'info, FeePrice>>, // Posted price of the pool's mint; omit to stop reporting
+    pub admin: Signer<'info>,                      // Pool admin
+}
+
+// Report the pool's settled fees in USD cents as well as token units, using
+// the oracle price `post_fee_price` keeps for its mint. Reporting only, so
+// it applies at once; omitting `fee_price` turns it off
+pub(crate) fn handler(ctx: Context<SetAccountingOracle>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    match ctx.accounts.fee_price.as_ref() {
+        Some(fee_price) => {
+            pool.accounting_oracle = fee_price.key();
+            pool.accounting_decimals = ctx.accounts.settlement_mint.decimals;
+            pool.accounting_max_price_age = ctx.accounts.fee_settlement.max_price_age;
+        }
+        None => {
+            pool.accounting_oracle = Pubkey::default();
+            pool.accounting_decimals = 0;
+            pool.accounting_max_price_age = 0;
+        }
+    }
+
+    emit!(AccountingOracleSet {
+        pool: pool.key(),
+        accounting_oracle: pool.accounting_oracle,
+    });
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::constants::*;
+use crate::events::CanonicalPoolSet;
+use crate::state::{Pool, PoolDirectory, ProtocolConfig};
+
+// Context for designating the canonical pool of a mint
+#[derive(Accounts)]
+pub struct SetCanonicalPool<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump, has_one = admin)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = PoolDirectory::LEN,
+        seeds = [POOL_DIRECTORY_SEED, token_mint.key().as_ref()],
+        bump
+    )]
+    pub pool_directory: Account<'info, PoolDirectory>,
+    #[account(
+        seeds = [POOL_SEED, token_mint.key().as_ref(), pool.index.as_ref()],
+        bump = pool.bump,
+        has_one = token_mint
+    )]
+    pub pool: Account<'info, Pool>,                // This program's pool to designate
+    pub token_mint: InterfaceAccount<'info, Mint>, // Mint the pool lends
+    #[account(mut)]
+    pub admin: Signer<'info>,                      // Protocol admin
+    pub system_program: Program<'info, System>,
+}
+
+// Make `pool` the mint's canonical pool and list it; pool creation is open to
+// anyone, so only this designation tells clients which of a mint's pools the
+// protocol stands behind
+pub(crate) fn handler(ctx: Context<SetCanonicalPool>) -> Result<()> {
+    let pool = ctx.accounts.pool.key();
+    let directory = &mut ctx.accounts.pool_directory;
+    directory.list(ctx.accounts.token_mint.key(), ctx.bumps.pool_directory, pool)?;
+    directory.canonical = pool;
+
+    emit!(CanonicalPoolSet {
+        token_mint: directory.token_mint,
+        pool,
+    });
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::events::PoolConfigQueued;
+use crate::state::{Pool, PoolConfig};
+
+// Context for queueing a pool's durable nonce policy
+#[derive(Accounts)]
+pub struct SetDurableNoncePolicy<'info> {
+    #[account(has_one = admin)]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [POOL_CONFIG_SEED, pool.key().as_ref()],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+    pub admin: Signer<'info>,                      // Pool admin
+}
+
+// Queue whether the pool refuses loans from durable nonce transactions, which
+// can be held and landed long after signing; loans then need the Instructions
+// sysvar so the transaction's first instruction can be inspected
+pub(crate) fn handler(ctx: Context<SetDurableNoncePolicy>, reject_durable_nonce: bool) -> Result<()> {
+    let config = &mut ctx.accounts.pool_config;
+    let mut pending = config.queued_or_live(&ctx.accounts.pool);
+    pending.reject_durable_nonce = reject_durable_nonce;
+    let activates_at = config.queue(pending, ctx.accounts.admin.key(), Clock::get()?.unix_timestamp);
+
+    emit!(PoolConfigQueued {
+        pool: config.pool,
+        fee_bps: pending.params.fee_bps,
+        max_loan_amount: pending.params.max_loan_amount,
+        loan_cooldown: pending.params.loan_cooldown,
+        timelock: pending.params.timelock,
+        activates_at,
+    });
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::DustThresholdSet;
+use crate::state::{Pool, ProtocolConfig};
+
+// Context for setting how much settlement dust a pool writes off
+#[derive(Accounts)]
+pub struct SetDustThreshold<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump, has_one = admin)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(mut, seeds = [POOL_SEED, pool.token_mint.as_ref(), pool.index.as_ref()], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+    pub admin: Signer<'info>,                      // Protocol admin, since the treasury covers the dust
+}
+
+// Let settlements on the pool fall short by up to `dust_threshold` raw units
+// of its mint; zero makes every shortfall fail again. The threshold is per
+// pool because a unit of dust is worth a different amount in each mint
+pub(crate) fn handler(ctx: Context<SetDustThreshold>, dust_threshold: u64) -> Result<()> {
+    require!(dust_threshold <= MAX_DUST_THRESHOLD, FlashLoanError::InvalidDustThreshold);
+    ctx.accounts.pool.dust_threshold = dust_threshold;
+    emit!(DustThresholdSet {
+        pool: ctx.accounts.pool.key(),
+        dust_threshold,
+    });
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::PoolConfigQueued;
+use crate::state::{Pool, PoolConfig};
+
+// Context for queueing a pool's emergency withdrawal delay
+#[derive(Accounts)]
+pub struct SetEmergencyWithdrawDelay<'info> {
+    #[account(has_one = admin)]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [POOL_CONFIG_SEED, pool.key().as_ref()],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+    pub admin: Signer<'info>,                      // Pool admin
+}
+
+// Queue how long, in seconds, withdrawals must stay paused before LPs may
+// take out their principal with `emergency_withdraw`
+pub(crate) fn handler(ctx: Context<SetEmergencyWithdrawDelay>, delay: i64) -> Result<()> {
+    require!(delay >= MIN_EMERGENCY_WITHDRAW_DELAY, FlashLoanError::InvalidPoolConfig);
+
+    let config = &mut ctx.accounts.pool_config;
+    let mut pending = config.queued_or_live(&ctx.accounts.pool);
+    pending.emergency_withdraw_delay = delay;
+    let activates_at = config.queue(pending, ctx.accounts.admin.key(), Clock::get()?.unix_timestamp);
+
+    emit!(PoolConfigQueued {
+        pool: config.pool,
+        fee_bps: pending.params.fee_bps,
+        max_loan_amount: pending.params.max_loan_amount,
+        loan_cooldown: pending.params.loan_cooldown,
+        timelock: pending.params.timelock,
+        activates_at,
+    });
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::PoolConfigQueued;
+use crate::state::{FeeCurve, Pool, PoolConfig};
+
+// Context for queueing a pool's utilization fee curve
+#[derive(Accounts)]
+pub struct SetFeeCurve<'info> {
+    #[account(has_one = admin)]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [POOL_CONFIG_SEED, pool.key().as_ref()],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+    pub admin: Signer<'info>,                      // Pool admin
+}
+
+// Queue the curve scaling the base fee with loan utilization; it is recorded
+// in the config history when `apply_pool_config` makes it live
+pub(crate) fn handler(ctx: Context<SetFeeCurve>, curve: FeeCurve) -> Result<()> {
+    require!(curve.is_valid(), FlashLoanError::InvalidFeeCurve);
+
+    let config = &mut ctx.accounts.pool_config;
+    let mut pending = config.queued_or_live(&ctx.accounts.pool);
+    pending.fee_curve = curve;
+    let activates_at = config.queue(pending, ctx.accounts.admin.key(), Clock::get()?.unix_timestamp);
+
+    emit!(PoolConfigQueued {
+        pool: config.pool,
+        fee_bps: pending.params.fee_bps,
+        max_loan_amount: pending.params.max_loan_amount,
+        loan_cooldown: pending.params.loan_cooldown,
+        timelock: pending.params.timelock,
+        activates_at,
+    });
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::PoolConfigQueued;
+use crate::state::{Pool, PoolConfig};
+
+// Context for queueing a pool's A/B fee experiment
+#[derive(Accounts)]
+pub struct SetFeeExperiment<'info> {
+    #[account(has_one = admin)]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [POOL_CONFIG_SEED, pool.key().as_ref()],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+    pub admin: Signer<'info>,                      // Pool admin
+}
+
+// Queue an experiment charging `variant_fee_bps` as the base fee to the
+// `variant_share_bps` of standard borrowers hashed into the variant bucket,
+// while the rest keep the pool's usual fee; a zero share ends the experiment
+pub(crate) fn handler(ctx: Context<SetFeeExperiment>, variant_fee_bps: u64, variant_share_bps: u64) -> Result<()> {
+    require!(
+        variant_fee_bps <= BPS_DENOMINATOR && variant_share_bps <= BPS_DENOMINATOR,
+        FlashLoanError::InvalidPoolConfig
+    );
+
+    let config = &mut ctx.accounts.pool_config;
+    let mut pending = config.queued_or_live(&ctx.accounts.pool);
+    pending.experiment_fee_bps = variant_fee_bps;
+    pending.experiment_share_bps = variant_share_bps;
+    let activates_at = config.queue(pending, ctx.accounts.admin.key(), Clock::get()?.unix_timestamp);
+
+    emit!(PoolConfigQueued {
+        pool: config.pool,
+        fee_bps: pending.params.fee_bps,
+        max_loan_amount: pending.params.max_loan_amount,
+        loan_cooldown: pending.params.loan_cooldown,
+        timelock: pending.params.timelock,
+        activates_at,
+    });
+    Ok(())
+}
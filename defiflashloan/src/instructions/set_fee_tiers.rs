@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::PoolConfigQueued;
+use crate::state::{FeeTier, Pool, PoolConfig};
+
+// Context for queueing a new fee tier table for a pool
+#[derive(Accounts)]
+pub struct SetFeeTiers<'info> {
+    #[account(has_one = admin)]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [POOL_CONFIG_SEED, pool.key().as_ref()],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+    pub admin: Signer<'info>,                      // Pool admin
+}
+
+// Tiers must cover every size from zero, with thresholds rising and the base
+// fee falling so larger loans never pay a higher rate
+fn validate_tiers(tiers: &[FeeTier]) -> bool {
+    let ordered = tiers
+        .windows(2)
+        .all(|pair| pair[1].min_loan_amount > pair[0].min_loan_amount && pair[1].fee_bps < pair[0].fee_bps);
+    let in_range = tiers.iter().all(|tier| tier.fee_bps <= BPS_DENOMINATOR);
+    let covers_zero = tiers.first().map_or(true, |tier| tier.min_loan_amount == 0);
+    ordered && in_range && covers_zero
+}
+
+// Queue the size tiers setting the base fee for standard borrowers behind the
+// pool's timelock; an empty table falls back to the config's `fee_bps`.
+// With `in_usd` the thresholds are USD cents, valued by the pool's accounting
+// oracle, so one tier table can serve mints of very different prices
+pub(crate) fn handler(ctx: Context<SetFeeTiers>, tiers: Vec<FeeTier>, in_usd: bool) -> Result<()> {
+    require!(tiers.len() <= MAX_FEE_TIERS, FlashLoanError::InvalidFeeTiers);
+    require!(validate_tiers(&tiers), FlashLoanError::InvalidFeeTiers);
+    require!(!in_usd || ctx.accounts.pool.has_accounting_oracle(), FlashLoanError::InvalidFeeTiers);
+
+    let config = &mut ctx.accounts.pool_config;
+    let mut pending = config.queued_or_live(&ctx.accounts.pool);
+    pending.fee_tiers = [FeeTier::default(); MAX_FEE_TIERS];
+    pending.fee_tiers[..tiers.len()].copy_from_slice(&tiers);
+    pending.fee_tier_count = tiers.len() as u8;
+    pending.fee_tiers_in_usd = in_usd;
+    let activates_at = config.queue(pending, ctx.accounts.admin.key(), Clock::get()?.unix_timestamp);
+
+    emit!(PoolConfigQueued {
+        pool: config.pool,
+        fee_bps: pending.params.fee_bps,
+        max_loan_amount: pending.params.max_loan_amount,
+        loan_cooldown: pending.params.loan_cooldown,
+        timelock: pending.params.timelock,
+        activates_at,
+    });
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::events::PoolConfigQueued;
+use crate::state::{Pool, PoolConfig};
+
+// Context for queueing a pool's concurrent borrower cap
+#[derive(Accounts)]
+pub struct SetMaxConcurrentBorrowers<'info> {
+    #[account(has_one = admin)]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [POOL_CONFIG_SEED, pool.key().as_ref()],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+    pub admin: Signer<'info>,                      // Pool admin
+}
+
+// Queue the number of distinct borrowers that may hold an open `flash_borrow`
+// receipt on the pool at once; zero removes the cap
+pub(crate) fn handler(ctx: Context<SetMaxConcurrentBorrowers>, max_concurrent_borrowers: u32) -> Result<()> {
+    let config = &mut ctx.accounts.pool_config;
+    let mut pending = config.queued_or_live(&ctx.accounts.pool);
+    pending.max_concurrent_borrowers = max_concurrent_borrowers;
+    let activates_at = config.queue(pending, ctx.accounts.admin.key(), Clock::get()?.unix_timestamp);
+
+    emit!(PoolConfigQueued {
+        pool: config.pool,
+        fee_bps: pending.params.fee_bps,
+        max_loan_amount: pending.params.max_loan_amount,
+        loan_cooldown: pending.params.loan_cooldown,
+        timelock: pending.params.timelock,
+        activates_at,
+    });
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::TRANSACTION_LEVEL_STACK_HEIGHT;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::PoolConfigQueued;
+use crate::state::{Pool, PoolConfig};
+
+// Context for queueing a pool's maximum invoke depth
+#[derive(Accounts)]
+pub struct SetMaxInvokeDepth<'info> {
+    #[account(has_one = admin)]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [POOL_CONFIG_SEED, pool.key().as_ref()],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+    pub admin: Signer<'info>,                      // Pool admin
+}
+
+// Queue the deepest stack height loans may be taken from: 1 allows only
+// top-level instructions, each step above allows one more CPI layer in front
+pub(crate) fn handler(ctx: Context<SetMaxInvokeDepth>, max_invoke_depth: u8) -> Result<()> {
+    check_max_invoke_depth(max_invoke_depth)?;
+
+    let config = &mut ctx.accounts.pool_config;
+    let mut pending = config.queued_or_live(&ctx.accounts.pool);
+    pending.max_invoke_depth = max_invoke_depth;
+    let activates_at = config.queue(pending, ctx.accounts.admin.key(), Clock::get()?.unix_timestamp);
+
+    emit!(PoolConfigQueued {
+        pool: config.pool,
+        fee_bps: pending.params.fee_bps,
+        max_loan_amount: pending.params.max_loan_amount,
+        loan_cooldown: pending.params.loan_cooldown,
+        timelock: pending.params.timelock,
+        activates_at,
+    });
+    Ok(())
+}
+
+// Whether `max_invoke_depth` may be queued, shared with `batch_admin`
+pub(crate) fn check_max_invoke_depth(max_invoke_depth: u8) -> Result<()> {
+    require!(
+        max_invoke_depth as usize >= TRANSACTION_LEVEL_STACK_HEIGHT && max_invoke_depth <= MAX_INVOKE_DEPTH_LIMIT,
+        FlashLoanError::InvalidPoolConfig
+    );
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::events::OpsTagSet;
+use crate::state::Pool;
+
+// Context for changing the pool's alert routing tag
+#[derive(Accounts)]
+pub struct SetOpsTag<'info> {
+    #[account(mut, has_one = admin)]
+    pub pool: Account<'info, Pool>,
+    pub admin: Signer<'info>,                      // Pool admin
+}
+
+// Set the opaque tag logged with the pool's pauses, rate limits, saturation,
+// throttled withdrawals and invariant failures; it changes no loan terms, so
+// it skips the timelock. All zero clears it
+pub(crate) fn handler(ctx: Context<SetOpsTag>, ops_tag: [u8; OPS_TAG_LEN]) -> Result<()> {
+    ctx.accounts.pool.ops_tag = ops_tag;
+    emit!(OpsTagSet {
+        pool: ctx.accounts.pool.key(),
+        ops_tag,
+    });
+    Ok(())
+}
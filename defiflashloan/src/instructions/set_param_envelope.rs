@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::ParamEnvelopeSet;
+use crate::state::{ParamEnvelope, ProtocolConfig};
+
+// Context for changing the bounds on curator-set pool parameters
+#[derive(Accounts)]
+pub struct SetParamEnvelope<'info> {
+    #[account(mut, seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump, has_one = admin)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    pub admin: Signer<'info>,                      // Protocol admin
+}
+
+// Replace the envelope; live pool parameters outside it stay until the
+// curator's next change, or until the protocol admin overrides them
+pub(crate) fn handler(ctx: Context<SetParamEnvelope>, envelope: ParamEnvelope) -> Result<()> {
+    require!(envelope.is_valid(), FlashLoanError::InvalidParamEnvelope);
+    ctx.accounts.protocol_config.envelope = envelope;
+
+    emit!(ParamEnvelopeSet {
+        max_fee_bps: envelope.max_fee_bps,
+        max_loan_amount: envelope.max_loan_amount,
+        min_loan_cooldown: envelope.min_loan_cooldown,
+        min_timelock: envelope.min_timelock,
+    });
+    Ok(())
+}
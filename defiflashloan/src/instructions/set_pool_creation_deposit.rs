@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::state::ProtocolConfig;
+
+// Context for setting the pool creation deposit
+#[derive(Accounts)]
+pub struct SetPoolCreationDeposit<'info> {
+    #[account(mut, seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump, has_one = admin)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    pub admin: Signer<'info>,                      // Protocol admin
+}
+
+// Set the lamports each new pool's creator must lock; existing pools keep theirs
+pub(crate) fn handler(ctx: Context<SetPoolCreationDeposit>, pool_creation_deposit: u64) -> Result<()> {
+    ctx.accounts.protocol_config.pool_creation_deposit = pool_creation_deposit;
+    Ok(())
+}
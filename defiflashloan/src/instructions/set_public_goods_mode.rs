@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::PublicGoodsModeSet;
+use crate::state::Pool;
+
+// Context for turning a pool into a fee-free public goods pool, or back
+#[derive(Accounts)]
+pub struct SetPublicGoodsMode<'info> {
+    #[account(mut, has_one = admin, has_one = share_mint)]
+    pub pool: Account<'info, Pool>,
+    pub share_mint: InterfaceAccount<'info, Mint>, // Read to check no LP is relying on fees
+    pub admin: Signer<'info>,                      // Pool admin
+}
+
+// Lend fee-free through `flash_borrow` only, limited to `daily_loan_limit`
+// loans per borrower per UTC day, each paying the admin `charge_lamports`
+// against spam. A pool only becomes fee-free while it has no LP shares outstanding,
+// since its LPs would stop earning; once fee-free, the limits can be retuned
+// at any time. A zero limit (with a zero charge) makes it a standard pool again
+pub(crate) fn handler(ctx: Context<SetPublicGoodsMode>, daily_loan_limit: u32, charge_lamports: u64) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    if daily_loan_limit == 0 {
+        require!(charge_lamports == 0, FlashLoanError::InvalidPublicGoodsMode);
+    } else {
+        require!(
+            charge_lamports > 0 && charge_lamports <= MAX_PUBLIC_GOODS_CHARGE,
+            FlashLoanError::InvalidPublicGoodsMode
+        );
+        require!(
+            pool.is_public_goods() || ctx.accounts.share_mint.supply == 0,
+            FlashLoanError::InvalidPublicGoodsMode
+        );
+    }
+
+    pool.public_goods_daily_loans = daily_loan_limit;
+    pool.public_goods_charge = charge_lamports;
+    emit!(PublicGoodsModeSet {
+        pool: pool.key(),
+        daily_loan_limit,
+        charge_lamports,
+    });
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::events::QuoteSignerUpdated;
+use crate::state::{key_fingerprint, ConfigField, ConfigHistory, Pool};
+
+// Context for configuring the pool's RFQ quote signer
+#[derive(Accounts)]
+pub struct SetQuoteSigner<'info> {
+    #[account(mut, has_one = admin)]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [CONFIG_HISTORY_SEED, pool.key().as_ref()],
+        bump = config_history.bump
+    )]
+    pub config_history: Account<'info, ConfigHistory>,
+    pub admin: Signer<'info>,                      // Pool admin
+}
+
+// Set the key whose signed quotes `execute_quoted_loan` accepts; default disables RFQ
+pub(crate) fn handler(ctx: Context<SetQuoteSigner>, quote_signer: Pubkey) -> Result<()> {
+    let old_quote_signer = ctx.accounts.pool.quote_signer;
+    ctx.accounts.config_history.record(
+        ConfigField::QuoteSigner,
+        key_fingerprint(&old_quote_signer),
+        key_fingerprint(&quote_signer),
+        ctx.accounts.admin.key(),
+        Clock::get()?.slot,
+    );
+    ctx.accounts.pool.quote_signer = quote_signer;
+
+    emit!(QuoteSignerUpdated {
+        pool: ctx.accounts.pool.key(),
+        old_quote_signer,
+        quote_signer,
+    });
+
+    Ok(())
+}
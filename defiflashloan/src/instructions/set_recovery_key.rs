@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::state::{AdminRecovery, Pool};
+
+// Context for configuring a pool's dead-man's switch
+#[derive(Accounts)]
+pub struct SetRecoveryKey<'info> {
+    #[account(has_one = admin)]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = AdminRecovery::LEN,
+        seeds = [ADMIN_RECOVERY_SEED, pool.key().as_ref()],
+        bump
+    )]
+    pub admin_recovery: Account<'info, AdminRecovery>,
+    #[account(mut)]
+    pub admin: Signer<'info>,                      // Current pool admin
+    pub system_program: Program<'info, System>,
+}
+
+// Set the recovery key and inactivity period; counts as a heartbeat and
+// cancels any pending recovery
+pub(crate) fn handler(ctx: Context<SetRecoveryKey>, recovery_key: Pubkey, inactivity_epochs: u64) -> Result<()> {
+    require!(
+        inactivity_epochs >= MIN_RECOVERY_INACTIVITY_EPOCHS,
+        FlashLoanError::InvalidRecoveryPeriod
+    );
+
+    let admin_recovery = &mut ctx.accounts.admin_recovery;
+    admin_recovery.pool = ctx.accounts.pool.key();
+    admin_recovery.recovery_key = recovery_key;
+    admin_recovery.inactivity_epochs = inactivity_epochs;
+    admin_recovery.last_heartbeat_epoch = Clock::get()?.epoch;
+    admin_recovery.recovery_started_at = 0;
+    admin_recovery.bump = ctx.bumps.admin_recovery;
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::state::{ConfigField, ConfigHistory, Pool, ProtocolConfig};
+
+// Context for setting a pool's protocol fee share
+#[derive(Accounts)]
+pub struct SetReserveFactor<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump, has_one = admin)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(mut)]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [CONFIG_HISTORY_SEED, pool.key().as_ref()],
+        bump = config_history.bump
+    )]
+    pub config_history: Account<'info, ConfigHistory>,
+    pub admin: Signer<'info>,                      // Protocol admin
+}
+
+// Set the share of each loan fee, in basis points, reserved for the protocol
+pub(crate) fn handler(ctx: Context<SetReserveFactor>, reserve_factor_bps: u16) -> Result<()> {
+    require!(
+        u64::from(reserve_factor_bps) + u64::from(ctx.accounts.pool.creator_fee_bps) <= BPS_DENOMINATOR,
+        FlashLoanError::InvalidReserveFactor
+    );
+    ctx.accounts.config_history.record(
+        ConfigField::ReserveFactorBps,
+        ctx.accounts.pool.reserve_factor_bps.into(),
+        reserve_factor_bps.into(),
+        ctx.accounts.admin.key(),
+        Clock::get()?.slot,
+    );
+    ctx.accounts.pool.reserve_factor_bps = reserve_factor_bps;
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::PoolConfigQueued;
+use crate::state::{Pool, PoolConfig};
+
+// Context for queueing a pool's trusted borrower tier
+#[derive(Accounts)]
+pub struct SetTrustedTier<'info> {
+    #[account(has_one = admin)]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [POOL_CONFIG_SEED, pool.key().as_ref()],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+    pub admin: Signer<'info>,                      // Pool admin
+}
+
+// Queue giving borrowers scoring at least `min_score` their own loan cap and
+// base fee; a zero `min_score` disables the tier once applied. Only loans of
+// at least `min_counted_loan` build reputation on this pool
+pub(crate) fn handler(
+    ctx: Context<SetTrustedTier>,
+    min_score: u64,
+    max_loan_amount: u64,
+    fee_bps: u64,
+    min_counted_loan: u64,
+) -> Result<()> {
+    require!(fee_bps <= BPS_DENOMINATOR, FlashLoanError::InvalidPoolConfig);
+
+    let config = &mut ctx.accounts.pool_config;
+    let mut pending = config.queued_or_live(&ctx.accounts.pool);
+    pending.trusted_min_score = min_score;
+    pending.trusted_max_loan_amount = max_loan_amount;
+    pending.trusted_fee_bps = fee_bps;
+    pending.reputation_min_loan_amount = min_counted_loan;
+    let activates_at = config.queue(pending, ctx.accounts.admin.key(), Clock::get()?.unix_timestamp);
+
+    emit!(PoolConfigQueued {
+        pool: config.pool,
+        fee_bps: pending.params.fee_bps,
+        max_loan_amount: pending.params.max_loan_amount,
+        loan_cooldown: pending.params.loan_cooldown,
+        timelock: pending.params.timelock,
+        activates_at,
+    });
+    Ok(())
+}
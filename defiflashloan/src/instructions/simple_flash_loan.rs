@@ -0,0 +1,218 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::{FeeRevenueRecorded, FlashLoanExecuted, SharePriceUpdated};
+use crate::fees::{calculate_dynamic_fee, calculate_same_slot_surcharge};
+use crate::introspection::require_nonce_policy;
+use crate::state::{FeeExperimentStats, FeePrice, LoanState, Pool, PoolConfig};
+use crate::transfer_fee::gross_for_net;
+
+// Context for a callback-free flash loan; besides the pool and the borrower's
+// token account, every account is a PDA or follows from the pool, so clients
+// can resolve them automatically
+#[derive(Accounts)]
+pub struct SimpleFlashLoan<'info> {
+    #[account(
+        mut,
+        seeds = [POOL_SEED, token_mint.key().as_ref(), pool.index.as_ref()],
+        bump = pool.bump,
+        has_one = vault,
+        has_one = token_mint,
+        has_one = share_mint
+    )]
+    pub pool: Account<'info, Pool>,                // Pool PDA, signs for the vault
+    #[account(seeds = [POOL_CONFIG_SEED, pool.key().as_ref()], bump = pool_config.bump)]
+    pub pool_config: Account<'info, PoolConfig>,   // Pool risk parameters
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>, // Flash loan pool
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = borrower
+    )]
+    pub borrower_account: InterfaceAccount<'info, TokenAccount>, // Receives the loan and repays it
+    #[account(
+        init_if_needed,
+        payer = borrower,
+        space = LoanState::LEN,
+        seeds = [LOAN_STATE_SEED, pool.key().as_ref(), borrower.key().as_ref()],
+        bump
+    )]
+    pub loan_state: Account<'info, LoanState>,     // Per-borrower reentrancy check and cooldown
+    #[account(mut)]
+    pub borrower: Signer<'info>,                   // Borrower signing the transaction
+    pub token_mint: InterfaceAccount<'info, Mint>, // Mint lent out by the pool, for checked transfers
+    pub share_mint: InterfaceAccount<'info, Mint>, // LP share mint, read to report the share price
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        mut,
+        seeds = [FEE_EXPERIMENT_SEED, pool.key().as_ref()],
+        bump = fee_experiment_stats.bump
+    )]
+    pub fee_experiment_stats: Option<Account<'info, FeeExperimentStats>>, // Required while the pool runs a fee experiment
+    /// CHECK: Address is constrained to the Instructions sysvar
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>, // Required by pools refusing durable nonces
+    #[account(address = pool.accounting_oracle)]
+    pub accounting_price: Option<Account<'info, FeePrice>>, // Required while the pool reports fees in USD
+}
+
+impl<'info> SimpleFlashLoan<'info> {
+    // Context for transferring tokens to borrower, signed by the pool PDA
+    pub fn into_transfer_to_borrower_context<'a, 'b, 'c>(
+        &self,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
+            from: self.vault.to_account_info().clone(),
+            mint: self.token_mint.to_account_info().clone(),
+            to: self.borrower_account.to_account_info().clone(),
+            authority: self.pool.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+
+    // Context for the borrower repaying the vault
+    pub fn into_transfer_to_vault_context(&self) -> CpiContext<'_, '_, '_, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
+            from: self.borrower_account.to_account_info().clone(),
+            mint: self.token_mint.to_account_info().clone(),
+            to: self.vault.to_account_info().clone(),
+            authority: self.borrower.to_account_info().clone(),
+        };
+        CpiContext::new(self.token_program.to_account_info().clone(), cpi_accounts)
+    }
+}
+
+// Lend `loan_amount` and take it back with the fee in the same instruction,
+// with no callback. Meant for low-code integrations, transaction requests and
+// teaching; anything that uses the funds needs the full `execute_flash_loan`
+// or the `flash_borrow`/`flash_repay` pair
+pub(crate) fn handler(ctx: Context<SimpleFlashLoan>, loan_amount: u64) -> Result<()> {
+    let config = &ctx.accounts.pool_config;
+    let clock = Clock::get()?;
+
+    require!(!ctx.accounts.pool.is_paused(PAUSE_BORROW), FlashLoanError::PoolPaused);
+    require_nonce_policy(config, ctx.accounts.instructions_sysvar.as_deref())?;
+    ctx.accounts.pool.require_accounting_price(ctx.accounts.accounting_price.as_deref())?;
+    require!(loan_amount > 0, FlashLoanError::InvalidAmount);
+    require!(loan_amount <= config.max_loan_amount, FlashLoanError::LoanAmountTooLarge);
+    require!(
+        config.allows_probation(loan_amount, ctx.accounts.loan_state.loan_count),
+        FlashLoanError::ProbationLoanTooLarge
+    );
+    require!(ctx.accounts.vault.amount >= loan_amount, FlashLoanError::InsufficientFunds);
+    require!(
+        clock.unix_timestamp >= ctx.accounts.loan_state.last_loan_timestamp + config.loan_cooldown,
+        FlashLoanError::CooldownPeriodNotOver
+    );
+    require!(!ctx.accounts.loan_state.active, FlashLoanError::Reentrancy);
+
+    let same_slot_loans = if ctx.accounts.loan_state.last_loan_slot == clock.slot {
+        ctx.accounts.loan_state.same_slot_loan_count
+    } else {
+        0
+    };
+    let vault_before = ctx.accounts.vault.amount;
+    let price_before = ctx.accounts.pool.share_price(vault_before, ctx.accounts.share_mint.supply);
+    #[cfg(feature = "strict-invariants")]
+    let snapshot = crate::invariants::SettlementSnapshot::capture(&ctx.accounts.pool, vault_before, ctx.accounts.share_mint.supply);
+    let fee_bps = ctx.accounts.pool.base_fee_bps(
+        loan_amount,
+        config.fee_bps,
+        ctx.accounts.accounting_price.as_deref(),
+        clock.unix_timestamp,
+    );
+    // Borrowers in a running fee experiment are priced by their bucket, and the
+    // experiment's stats must come along to record the loan
+    let experiment_bucket = config.fee_experiment_bucket(&ctx.accounts.borrower.key());
+    require!(
+        experiment_bucket.is_none() || ctx.accounts.fee_experiment_stats.is_some(),
+        FlashLoanError::FeeExperimentStatsMissing
+    );
+    let fee_bps = if experiment_bucket == Some(FEE_EXPERIMENT_VARIANT) { config.experiment_fee_bps } else { fee_bps };
+    let fee = calculate_dynamic_fee(loan_amount, vault_before, fee_bps, &config.fee_curve)
+        + calculate_same_slot_surcharge(loan_amount, same_slot_loans, config.same_slot_surcharge_bps);
+    let protocol_fee = ctx.accounts.pool.protocol_fee(fee);
+
+    let pool_seeds = ctx.accounts.pool.seeds();
+    token_interface::transfer_checked(
+        ctx.accounts.into_transfer_to_borrower_context(&[&pool_seeds[..]]),
+        loan_amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    // Gross up the repayment so the vault nets principal plus fee
+    let repayment = gross_for_net(&ctx.accounts.token_mint.to_account_info(), loan_amount + fee)?;
+    token_interface::transfer_checked(
+        ctx.accounts.into_transfer_to_vault_context(),
+        repayment,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    ctx.accounts.vault.reload()?;
+    ctx.accounts.pool.write_off_dust(vault_before, ctx.accounts.vault.amount, fee)?;
+
+    ctx.accounts.pool.accrue_fees(fee, protocol_fee);
+
+    if let (Some(bucket), Some(stats)) = (experiment_bucket, ctx.accounts.fee_experiment_stats.as_mut()) {
+        stats.sync(&ctx.accounts.pool_config, clock.unix_timestamp);
+        stats.record(bucket, loan_amount, fee);
+    }
+
+    let loan_state = &mut ctx.accounts.loan_state;
+    loan_state.pool = ctx.accounts.pool.key();
+    loan_state.borrower = ctx.accounts.borrower.key();
+    loan_state.bump = ctx.bumps.loan_state;
+    loan_state.last_loan_timestamp = clock.unix_timestamp;
+    loan_state.loan_count += 1;
+    loan_state.last_loan_slot = clock.slot;
+    loan_state.same_slot_loan_count = same_slot_loans + 1;
+
+    emit!(FlashLoanExecuted {
+        pool: ctx.accounts.pool.key(),
+        borrower: ctx.accounts.borrower.key(),
+        loan_amount,
+        fee,
+        protocol_fee,
+        fee_deducted: false,
+        callback_program: Pubkey::default(), // No callback runs
+        callback_success: false,
+        sequence: ctx.accounts.pool.next_event_sequence(),
+    });
+    if ctx.accounts.pool.has_accounting_oracle() {
+        let accounting_price = ctx.accounts.accounting_price.as_deref();
+        emit!(FeeRevenueRecorded {
+            pool: ctx.accounts.pool.key(),
+            sequence: ctx.accounts.pool.event_sequence,
+            fee,
+            protocol_fee,
+            fee_usd_cents: ctx.accounts.pool.usd_cents(accounting_price, fee, clock.unix_timestamp),
+            protocol_fee_usd_cents: ctx.accounts.pool.usd_cents(accounting_price, protocol_fee, clock.unix_timestamp),
+        });
+    }
+
+    ctx.accounts.share_mint.reload()?;
+    #[cfg(feature = "strict-invariants")]
+    crate::invariants::check_settlement(
+        &snapshot,
+        &ctx.accounts.pool,
+        ctx.accounts.vault.amount,
+        ctx.accounts.share_mint.supply,
+        fee,
+        0,
+    )?;
+    emit!(SharePriceUpdated {
+        pool: ctx.accounts.pool.key(),
+        price_before,
+        price_after: ctx.accounts.pool.share_price(ctx.accounts.vault.amount, ctx.accounts.share_mint.supply),
+        lp_assets: ctx.accounts.pool.lp_assets(ctx.accounts.vault.amount),
+        share_supply: ctx.accounts.share_mint.supply,
+    });
+
+    Ok(())
+}
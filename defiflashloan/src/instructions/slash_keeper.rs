@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::KeeperSlashed;
+use crate::state::{Keeper, KeeperRegistry, ProtocolConfig};
+
+// Context for slashing a misbehaving keeper's bond
+#[derive(Accounts)]
+pub struct SlashKeeper<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump, has_one = admin)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(mut, seeds = [KEEPER_REGISTRY_SEED], bump = keeper_registry.bump)]
+    pub keeper_registry: Account<'info, KeeperRegistry>,
+    #[account(mut, seeds = [KEEPER_SEED, authority.key().as_ref()], bump = keeper.bump, has_one = authority)]
+    pub keeper: Account<'info, Keeper>,
+    /// CHECK: Only keys the keeper PDA
+    pub authority: UncheckedAccount<'info>,        // Keeper being slashed
+    #[account(mut, address = keeper_registry.stake_vault)]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>, // Holds the bond
+    #[account(mut, address = keeper_registry.reward_vault)]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>, // Receives the slashed bond
+    #[account(address = keeper_registry.stake_mint)]
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+    pub admin: Signer<'info>,                      // Protocol admin
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> SlashKeeper<'info> {
+    // Context for moving the slashed bond to the reward vault, signed by the registry PDA
+    pub fn into_transfer_to_rewards_context<'a, 'b, 'c>(
+        &self,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
+            from: self.stake_vault.to_account_info().clone(),
+            mint: self.stake_mint.to_account_info().clone(),
+            to: self.reward_vault.to_account_info().clone(),
+            authority: self.keeper_registry.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+}
+
+// Protocol admin: take `amount` of a keeper's bond, including one that is
+// unbonding, into the reward vault to fund honest keepers; `evidence` is the
+// hash of the misbehavior proof, published so the slash can be audited
+pub(crate) fn handler(ctx: Context<SlashKeeper>, amount: u64, evidence: [u8; 32]) -> Result<()> {
+    require!(amount > 0, FlashLoanError::InvalidAmount);
+    require!(amount <= ctx.accounts.keeper.staked_amount, FlashLoanError::KeeperStakeTooLow);
+
+    let registry_seeds = ctx.accounts.keeper_registry.seeds();
+    token_interface::transfer_checked(
+        ctx.accounts.into_transfer_to_rewards_context(&[&registry_seeds[..]]),
+        amount,
+        ctx.accounts.stake_mint.decimals,
+    )?;
+
+    let keeper = &mut ctx.accounts.keeper;
+    keeper.staked_amount -= amount;
+    keeper.total_slashed += amount;
+    let registry = &mut ctx.accounts.keeper_registry;
+    registry.total_staked -= amount;
+    registry.total_slashed += amount;
+    if keeper.staked_amount == 0 {
+        registry.keeper_count -= 1;
+    }
+
+    emit!(KeeperSlashed {
+        keeper: keeper.key(),
+        authority: keeper.authority,
+        amount,
+        staked_amount: keeper.staked_amount,
+        evidence,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer as SystemTransfer};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::ReputationStaked;
+use crate::state::BorrowerReputation;
+
+// Context for locking lamports behind a borrower reputation
+#[derive(Accounts)]
+pub struct StakeReputation<'info> {
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, borrower.key().as_ref()],
+        bump = borrower_reputation.bump
+    )]
+    pub borrower_reputation: Account<'info, BorrowerReputation>, // Holds the stake
+    #[account(mut)]
+    pub borrower: Signer<'info>,                   // Borrower the record tracks
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> StakeReputation<'info> {
+    // Context for moving the stake into the reputation account
+    pub fn into_transfer_to_reputation_context(&self) -> CpiContext<'_, '_, '_, 'info, SystemTransfer<'info>> {
+        let cpi_accounts = SystemTransfer {
+            from: self.borrower.to_account_info().clone(),
+            to: self.borrower_reputation.to_account_info().clone(),
+        };
+        CpiContext::new(self.system_program.to_account_info().clone(), cpi_accounts)
+    }
+}
+
+// Add `amount` lamports to the stake; every top-up restarts the lock
+pub(crate) fn handler(ctx: Context<StakeReputation>, amount: u64) -> Result<()> {
+    require!(amount > 0, FlashLoanError::InvalidAmount);
+    system_program::transfer(ctx.accounts.into_transfer_to_reputation_context(), amount)?;
+
+    let reputation = &mut ctx.accounts.borrower_reputation;
+    reputation.staked_lamports += amount;
+    reputation.stake_unlocks_at = Clock::get()?.unix_timestamp + REPUTATION_STAKE_LOCK;
+
+    emit!(ReputationStaked {
+        borrower: reputation.borrower,
+        amount,
+        staked_lamports: reputation.staked_lamports,
+        unlocks_at: reputation.stake_unlocks_at,
+    });
+
+    Ok(())
+}
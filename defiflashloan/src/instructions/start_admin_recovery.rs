@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::AdminRecoveryStarted;
+use crate::state::{AdminRecovery, Pool};
+
+// Context for starting a dead-man's-switch recovery
+#[derive(Accounts)]
+pub struct StartAdminRecovery<'info> {
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [ADMIN_RECOVERY_SEED, pool.key().as_ref()],
+        bump = admin_recovery.bump,
+        has_one = recovery_key @ FlashLoanError::NotRecoveryKey
+    )]
+    pub admin_recovery: Account<'info, AdminRecovery>,
+    pub recovery_key: Signer<'info>,               // Designated recovery key
+}
+
+// Open the public delay window after the admin has gone silent
+pub(crate) fn handler(ctx: Context<StartAdminRecovery>) -> Result<()> {
+    let clock = Clock::get()?;
+    let admin_recovery = &mut ctx.accounts.admin_recovery;
+    require!(
+        admin_recovery.is_admin_inactive(clock.epoch),
+        FlashLoanError::AdminStillActive
+    );
+
+    // Restarting would only push the window back, so keep the first start
+    if !admin_recovery.is_pending() {
+        admin_recovery.recovery_started_at = clock.unix_timestamp;
+    }
+
+    emit!(AdminRecoveryStarted {
+        pool: ctx.accounts.pool.key(),
+        recovery_key: admin_recovery.recovery_key,
+        executable_at: admin_recovery.recovery_started_at + RECOVERY_DELAY,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::ProtocolFeesStreamed;
+use crate::state::{FeeLocker, Keeper, KeeperCrank, KeeperRegistry, Pool};
+
+// Context for streaming a pool's accrued protocol fees to its fee locker
+#[derive(Accounts)]
+pub struct StreamProtocolFees<'info> {
+    #[account(
+        mut,
+        seeds = [POOL_SEED, pool.token_mint.as_ref(), pool.index.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(mut, address = pool.vault)]
+    pub loan_vault: InterfaceAccount<'info, TokenAccount>, // Pool vault holding the accrued fees
+    #[account(mut, has_one = pool, seeds = [FEE_LOCKER_SEED, pool.key().as_ref()], bump = fee_locker.bump)]
+    pub fee_locker: Account<'info, FeeLocker>,
+    #[account(mut, address = fee_locker.reward_vault)]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>, // Receives the streamed fees
+    #[account(address = pool.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>, // Pool mint, for checked transfers
+    pub token_program: Interface<'info, TokenInterface>, // Token or Token-2022 program
+    #[account(mut, seeds = [KEEPER_REGISTRY_SEED], bump = keeper_registry.bump)]
+    pub keeper_registry: Option<Account<'info, KeeperRegistry>>, // Passed with the keeper accounts to pay the keeper running the crank
+    #[account(mut)]
+    pub keeper: Option<Account<'info, Keeper>>,   // Registered keeper credited for the crank
+    pub keeper_authority: Option<Signer<'info>>,   // Keeper's authority
+}
+
+impl<'info> StreamProtocolFees<'info> {
+    // Context for paying the locker out of the vault, signed by the pool PDA
+    pub fn into_transfer_to_rewards_context<'a, 'b, 'c>(
+        &self,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
+            from: self.loan_vault.to_account_info().clone(),
+            mint: self.token_mint.to_account_info().clone(),
+            to: self.reward_vault.to_account_info().clone(),
+            authority: self.pool.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+}
+
+// Permissionless, once per epoch: move the accrued protocol fees to the locker
+// and split them across the current lock weight; fees stay accrued while
+// nothing is locked, and a keeper is paid only for epochs it archives
+pub(crate) fn handler(ctx: Context<StreamProtocolFees>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let fee_locker = &ctx.accounts.fee_locker;
+    require!(
+        now >= fee_locker.last_stream_at + fee_locker.epoch_duration,
+        FlashLoanError::EpochNotElapsed
+    );
+    if fee_locker.total_weight == 0 {
+        return Ok(());
+    }
+
+    let amount = ctx
+        .accounts
+        .pool
+        .protocol_fees_accrued
+        .min(ctx.accounts.loan_vault.amount);
+    // Only what reaches the reward vault after any transfer fee is streamed
+    let rewards_before = ctx.accounts.reward_vault.amount;
+    if amount > 0 {
+        let pool_seeds = ctx.accounts.pool.seeds();
+        token_interface::transfer_checked(
+            ctx.accounts.into_transfer_to_rewards_context(&[&pool_seeds[..]]),
+            amount,
+            ctx.accounts.token_mint.decimals,
+        )?;
+    }
+    ctx.accounts.pool.protocol_fees_accrued -= amount;
+    ctx.accounts.reward_vault.reload()?;
+    let streamed = ctx.accounts.reward_vault.amount - rewards_before;
+
+    let fee_locker = &mut ctx.accounts.fee_locker;
+    fee_locker.stream(streamed);
+    fee_locker.epoch += 1;
+    fee_locker.last_stream_at = now;
+
+    emit!(ProtocolFeesStreamed {
+        fee_locker: fee_locker.key(),
+        epoch: fee_locker.epoch,
+        amount: streamed,
+        total_weight: fee_locker.total_weight,
+    });
+
+    crate::keeper_rewards::credit_crank(
+        ctx.accounts.keeper_registry.as_mut(),
+        ctx.accounts.keeper.as_mut(),
+        ctx.accounts.keeper_authority.as_ref(),
+        KeeperCrank::StreamProtocolFees,
+    )?;
+
+    Ok(())
+}
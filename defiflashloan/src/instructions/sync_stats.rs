@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::state::{Keeper, KeeperCrank, KeeperRegistry, LoanStats, Pool, StatsAccumulator};
+
+// Context for folding a pool's deferred stats into its LoanStats
+#[derive(Accounts)]
+pub struct SyncStats<'info> {
+    #[account(seeds = [POOL_SEED, pool.token_mint.as_ref(), pool.index.as_ref()], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+    #[account(mut)]
+    pub loan_stats: Account<'info, LoanStats>,     // Loan statistics account bound to the accumulator
+    #[account(
+        mut,
+        seeds = [STATS_ACCUMULATOR_SEED, pool.key().as_ref()],
+        bump = stats_accumulator.bump,
+        has_one = pool,
+        has_one = loan_stats
+    )]
+    pub stats_accumulator: Account<'info, StatsAccumulator>, // Deferred fast path stats
+    #[account(mut, seeds = [KEEPER_REGISTRY_SEED], bump = keeper_registry.bump)]
+    pub keeper_registry: Option<Account<'info, KeeperRegistry>>, // Passed with the keeper accounts to pay the keeper running the crank
+    #[account(mut)]
+    pub keeper: Option<Account<'info, Keeper>>,   // Registered keeper credited for the crank
+    pub keeper_authority: Option<Signer<'info>>,   // Keeper's authority
+}
+
+// Crank: fold accumulated fast path stats into LoanStats; a keeper is paid
+// only when there was something to fold
+pub(crate) fn handler(ctx: Context<SyncStats>) -> Result<()> {
+    if ctx.accounts.stats_accumulator.pending_loan_count > 0 {
+        crate::keeper_rewards::credit_crank(
+            ctx.accounts.keeper_registry.as_mut(),
+            ctx.accounts.keeper.as_mut(),
+            ctx.accounts.keeper_authority.as_ref(),
+            KeeperCrank::SyncStats,
+        )?;
+    }
+
+    let accumulator = &mut ctx.accounts.stats_accumulator;
+    ctx.accounts.loan_stats.apply_pending(
+        accumulator.pending_loans,
+        accumulator.pending_fees,
+        accumulator.pending_loan_count,
+        Clock::get()?.unix_timestamp,
+    );
+
+    ctx.accounts.loan_stats.total_fees_usd_cents += accumulator.pending_fees_usd_cents;
+
+    accumulator.pending_loans = 0;
+    accumulator.pending_fees = 0;
+    accumulator.pending_loan_count = 0;
+    accumulator.pending_fees_usd_cents = 0;
+
+    #[cfg(feature = "strict-invariants")]
+    crate::invariants::check_loan_stats(&ctx.accounts.loan_stats)?;
+
+    Ok(())
+}
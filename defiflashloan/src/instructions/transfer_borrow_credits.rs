@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::FlashLoanError;
+use crate::state::BorrowCredit;
+
+// Context for handing borrow credits to a new owner
+#[derive(Accounts)]
+pub struct TransferBorrowCredits<'info> {
+    #[account(mut, has_one = owner @ FlashLoanError::BorrowCreditOwnerMismatch)]
+    pub borrow_credit: Account<'info, BorrowCredit>,
+    pub owner: Signer<'info>,                      // Current credit owner
+}
+
+// Hand the remaining credits over to another borrower
+pub(crate) fn handler(ctx: Context<TransferBorrowCredits>, new_owner: Pubkey) -> Result<()> {
+    ctx.accounts.borrow_credit.owner = new_owner;
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::FeeTokensUnlocked;
+use crate::state::{FeeLocker, LockerPosition};
+
+// Context for withdrawing an expired fee lock
+#[derive(Accounts)]
+pub struct UnlockFeeTokens<'info> {
+    /// CHECK: Only keys the fee locker PDA
+    pub pool: UncheckedAccount<'info>,             // Pool the fee locker streams from
+    #[account(mut, seeds = [FEE_LOCKER_SEED, pool.key().as_ref()], bump = fee_locker.bump)]
+    pub fee_locker: Account<'info, FeeLocker>,
+    #[account(
+        mut,
+        has_one = owner,
+        has_one = fee_locker,
+        seeds = [LOCKER_POSITION_SEED, fee_locker.key().as_ref(), owner.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, LockerPosition>,
+    #[account(mut, address = fee_locker.lock_vault)]
+    pub lock_vault: Account<'info, TokenAccount>,  // Holds the locked protocol tokens
+    #[account(mut, token::mint = fee_locker.lock_mint)]
+    pub destination_account: Account<'info, TokenAccount>, // Receives the unlocked tokens
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> UnlockFeeTokens<'info> {
+    // Context for releasing the locked tokens, signed by the locker PDA
+    pub fn into_transfer_from_vault_context<'a, 'b, 'c>(
+        &self,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.lock_vault.to_account_info().clone(),
+            to: self.destination_account.to_account_info().clone(),
+            authority: self.fee_locker.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+}
+
+// Withdraw the locked tokens once the lock has expired; rewards earned so far
+// stay claimable on the position
+pub(crate) fn handler(ctx: Context<UnlockFeeTokens>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(now >= ctx.accounts.position.unlock_at, FlashLoanError::FeeLockActive);
+
+    let fee_locker = &mut ctx.accounts.fee_locker;
+    let position = &mut ctx.accounts.position;
+    let amount = position.amount;
+    require!(amount > 0, FlashLoanError::InvalidAmount);
+
+    position.settle(fee_locker.reward_per_weight);
+    fee_locker.total_weight -= position.weight;
+    position.amount = 0;
+    position.reweight(now, fee_locker.reward_per_weight);
+
+    let locker_seeds = ctx.accounts.fee_locker.seeds();
+    token::transfer(
+        ctx.accounts.into_transfer_from_vault_context(&[&locker_seeds[..]]),
+        amount,
+    )?;
+
+    emit!(FeeTokensUnlocked {
+        fee_locker: ctx.accounts.fee_locker.key(),
+        owner: ctx.accounts.owner.key(),
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount, Transfer};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::SharesUnlocked;
+use crate::state::ShareLock;
+
+// Context for releasing escrowed LP shares
+#[derive(Accounts)]
+pub struct UnlockShares<'info> {
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner,
+        seeds = [SHARE_LOCK_SEED, pool.key().as_ref(), owner.key().as_ref(), locker.key().as_ref()],
+        bump = share_lock.bump
+    )]
+    pub share_lock: Account<'info, ShareLock>,
+    /// CHECK: Only keys the share lock PDA
+    pub pool: UncheckedAccount<'info>,             // Pool whose shares are locked
+    /// CHECK: Only keys the share lock PDA
+    pub locker: UncheckedAccount<'info>,           // Locker the shares were escrowed for
+    #[account(
+        mut,
+        seeds = [SHARE_ESCROW_SEED, share_lock.key().as_ref()],
+        bump
+    )]
+    pub escrow_share_account: Account<'info, TokenAccount>, // Holds the shares while locked
+    #[account(mut, token::mint = escrow_share_account.mint)]
+    pub destination_share_account: Account<'info, TokenAccount>, // Receives the released shares
+    /// CHECK: Only receives the lock and escrow rent
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,            // LP that locked the shares
+    pub authority: Signer<'info>,                  // The locker, or the owner once expired
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> UnlockShares<'info> {
+    // Context for releasing the escrowed shares, signed by the lock PDA
+    pub fn into_transfer_from_escrow_context<'a, 'b, 'c>(
+        &self,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.escrow_share_account.to_account_info().clone(),
+            to: self.destination_share_account.to_account_info().clone(),
+            authority: self.share_lock.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+
+    // Context for closing the emptied escrow account, signed by the lock PDA
+    pub fn into_close_escrow_context<'a, 'b, 'c>(
+        &self,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, CloseAccount<'info>> {
+        let cpi_accounts = CloseAccount {
+            account: self.escrow_share_account.to_account_info().clone(),
+            destination: self.owner.to_account_info().clone(),
+            authority: self.share_lock.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+}
+
+// Release escrowed shares: the locker may send them anywhere at any time (e.g.
+// on liquidation), the owner may reclaim them once the lock has expired
+pub(crate) fn handler(ctx: Context<UnlockShares>) -> Result<()> {
+    let share_lock = &ctx.accounts.share_lock;
+    let authority = ctx.accounts.authority.key();
+
+    if authority != share_lock.locker {
+        require_keys_eq!(authority, share_lock.owner, FlashLoanError::UnlockNotAuthorized);
+        require!(
+            Clock::get()?.unix_timestamp >= share_lock.unlock_at,
+            FlashLoanError::SharesLocked
+        );
+        require_keys_eq!(
+            ctx.accounts.destination_share_account.owner,
+            share_lock.owner,
+            FlashLoanError::UnlockNotAuthorized
+        );
+    }
+
+    let shares = ctx.accounts.escrow_share_account.amount;
+    let lock_seeds = share_lock.seeds();
+    token::transfer(
+        ctx.accounts.into_transfer_from_escrow_context(&[&lock_seeds[..]]),
+        shares,
+    )?;
+    token::close_account(ctx.accounts.into_close_escrow_context(&[&lock_seeds[..]]))?;
+
+    emit!(SharesUnlocked {
+        pool: share_lock.pool,
+        owner: share_lock.owner,
+        authority,
+        destination: ctx.accounts.destination_share_account.key(),
+        shares,
+    });
+
+    Ok(())
+}
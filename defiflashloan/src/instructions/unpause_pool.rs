@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+use crate::instructions::pause_pool::{set_pause_flags, PausePool};
+
+// Resume the operations in `flags`
+pub(crate) fn handler(ctx: Context<PausePool>, flags: u8) -> Result<()> {
+    let pause_flags = ctx.accounts.pool.pause_flags & !flags;
+    set_pause_flags(ctx, pause_flags)
+}
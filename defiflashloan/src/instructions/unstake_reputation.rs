@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::ReputationUnstaked;
+use crate::state::BorrowerReputation;
+
+// Context for withdrawing reputation stake
+#[derive(Accounts)]
+pub struct UnstakeReputation<'info> {
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, borrower.key().as_ref()],
+        bump = borrower_reputation.bump,
+        has_one = borrower
+    )]
+    pub borrower_reputation: Account<'info, BorrowerReputation>, // Holds the stake
+    #[account(mut)]
+    pub borrower: Signer<'info>,                   // Receives the lamports
+}
+
+// Return `amount` lamports of unlocked stake; the score drops with it
+pub(crate) fn handler(ctx: Context<UnstakeReputation>, amount: u64) -> Result<()> {
+    let reputation = &mut ctx.accounts.borrower_reputation;
+    require!(amount > 0, FlashLoanError::InvalidAmount);
+    require!(amount <= reputation.staked_lamports, FlashLoanError::InsufficientStake);
+    require!(
+        Clock::get()?.unix_timestamp >= reputation.stake_unlocks_at,
+        FlashLoanError::StakeLocked
+    );
+
+    reputation.staked_lamports -= amount;
+    reputation.sub_lamports(amount)?;
+    ctx.accounts.borrower.add_lamports(amount)?;
+
+    emit!(ReputationUnstaked {
+        borrower: reputation.borrower,
+        amount,
+        staked_lamports: reputation.staked_lamports,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Burn, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::RebalanceUnwound;
+use crate::shares::assets_for_shares;
+use crate::state::{Pool, ProtocolConfig, RebalanceRoute};
+
+// Context for returning rebalanced liquidity to its source pool
+#[derive(Accounts)]
+pub struct UnwindRebalance<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        has_one = source_pool,
+        has_one = target_pool,
+        has_one = iou_share_account,
+        seeds = [REBALANCE_ROUTE_SEED, source_pool.key().as_ref(), target_pool.key().as_ref()],
+        bump = rebalance_route.bump,
+        constraint = operator.key() == rebalance_route.operator || operator.key() == protocol_config.admin
+            @ FlashLoanError::RebalanceNotAuthorized
+    )]
+    pub rebalance_route: Account<'info, RebalanceRoute>,
+    #[account(
+        mut,
+        seeds = [POOL_SEED, token_mint.key().as_ref(), source_pool.index.as_ref()],
+        bump = source_pool.bump
+    )]
+    pub source_pool: Account<'info, Pool>,
+    #[account(mut, address = source_pool.vault)]
+    pub source_vault: InterfaceAccount<'info, TokenAccount>, // Receives the returned liquidity
+    #[account(
+        mut,
+        seeds = [POOL_SEED, token_mint.key().as_ref(), target_pool.index.as_ref()],
+        bump = target_pool.bump
+    )]
+    pub target_pool: Account<'info, Pool>,
+    #[account(mut, address = target_pool.vault)]
+    pub target_vault: InterfaceAccount<'info, TokenAccount>, // Pays the returned liquidity
+    #[account(mut, address = target_pool.share_mint)]
+    pub target_share_mint: InterfaceAccount<'info, Mint>, // IOU shares are burned from it
+    #[account(mut)]
+    pub iou_share_account: InterfaceAccount<'info, TokenAccount>, // Source pool's target shares
+    pub token_mint: InterfaceAccount<'info, Mint>, // Mint both pools lend out
+    pub operator: Signer<'info>,                   // Route operator or protocol admin
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> UnwindRebalance<'info> {
+    // Context for burning IOU shares, signed by the source pool PDA that owns them
+    pub fn into_burn_iou_shares_context<'a, 'b, 'c>(
+        &self,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, Burn<'info>> {
+        let cpi_accounts = Burn {
+            mint: self.target_share_mint.to_account_info().clone(),
+            from: self.iou_share_account.to_account_info().clone(),
+            authority: self.source_pool.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+
+    // Context for returning the liquidity, signed by the target pool PDA
+    pub fn into_transfer_to_source_context<'a, 'b, 'c>(
+        &self,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
+            from: self.target_vault.to_account_info().clone(),
+            mint: self.token_mint.to_account_info().clone(),
+            to: self.source_vault.to_account_info().clone(),
+            authority: self.target_pool.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+}
+
+// Redeem `shares` of the source pool's IOU shares at the target's price. The
+// matching principal leaves `rebalanced_out`; whatever the target earned on
+// it on top lands in the source vault as fee yield for its LPs. Redemption
+// rounds in the target's favour, so a round trip with no fees in between can
+// cost the source a unit of dust, and only the target's price is enforced
+pub(crate) fn handler(ctx: Context<UnwindRebalance>, shares: u64) -> Result<()> {
+    require!(
+        shares > 0 && shares <= ctx.accounts.rebalance_route.iou_shares,
+        FlashLoanError::InvalidAmount
+    );
+    let amount = assets_for_shares(
+        shares,
+        ctx.accounts.target_pool.lp_assets(ctx.accounts.target_vault.amount),
+        ctx.accounts.target_share_mint.supply,
+    );
+    require!(
+        ctx.accounts.target_vault.amount.saturating_sub(ctx.accounts.target_pool.fees_reserved()) >= amount,
+        FlashLoanError::InsufficientFunds
+    );
+    let principal = ctx.accounts.rebalance_route.principal_for_shares(shares);
+
+    let target_price_before = ctx
+        .accounts
+        .target_pool
+        .share_price(ctx.accounts.target_vault.amount, ctx.accounts.target_share_mint.supply);
+
+    let source_seeds = ctx.accounts.source_pool.seeds();
+    token_interface::burn(ctx.accounts.into_burn_iou_shares_context(&[&source_seeds[..]]), shares)?;
+    if amount > 0 {
+        let target_seeds = ctx.accounts.target_pool.seeds();
+        token_interface::transfer_checked(
+            ctx.accounts.into_transfer_to_source_context(&[&target_seeds[..]]),
+            amount,
+            ctx.accounts.token_mint.decimals,
+        )?;
+    }
+
+    ctx.accounts.source_pool.rebalanced_out -= principal;
+    ctx.accounts.target_pool.total_deposits = ctx.accounts.target_pool.total_deposits.saturating_sub(principal);
+    let route = &mut ctx.accounts.rebalance_route;
+    route.outstanding_principal -= principal;
+    route.iou_shares -= shares;
+
+    ctx.accounts.target_vault.reload()?;
+    ctx.accounts.target_share_mint.reload()?;
+    let target_price_after = ctx
+        .accounts
+        .target_pool
+        .share_price(ctx.accounts.target_vault.amount, ctx.accounts.target_share_mint.supply);
+    require!(target_price_after >= target_price_before, FlashLoanError::SharePriceMoved);
+
+    emit!(RebalanceUnwound {
+        route: ctx.accounts.rebalance_route.key(),
+        source_pool: ctx.accounts.source_pool.key(),
+        target_pool: ctx.accounts.target_pool.key(),
+        iou_shares: shares,
+        amount,
+        principal,
+        outstanding_principal: ctx.accounts.rebalance_route.outstanding_principal,
+    });
+
+    Ok(())
+}
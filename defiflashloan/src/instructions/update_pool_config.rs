@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::PoolConfigQueued;
+use crate::state::{ParamEnvelope, Pool, PoolConfig, PoolConfigParams, PoolParam, ProtocolConfig};
+
+// Context for queueing a pool parameter change
+#[derive(Accounts)]
+pub struct UpdatePoolConfig<'info> {
+    #[account(has_one = admin)]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [POOL_CONFIG_SEED, pool.key().as_ref()],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>, // Holds the parameter envelope
+    pub admin: Signer<'info>,                      // Pool admin
+}
+
+// Queue new parameters behind the pool's current timelock, replacing any
+// queued values for them; they must lie within the protocol's envelope and
+// leave frozen parameters at their live values
+pub(crate) fn handler(ctx: Context<UpdatePoolConfig>, params: PoolConfigParams) -> Result<()> {
+    check_params(&ctx.accounts.pool_config, &ctx.accounts.protocol_config.envelope, &params)?;
+
+    let config = &mut ctx.accounts.pool_config;
+    let mut pending = config.queued_or_live(&ctx.accounts.pool);
+    pending.params = params;
+    let activates_at = config.queue(pending, ctx.accounts.admin.key(), Clock::get()?.unix_timestamp);
+
+    emit!(PoolConfigQueued {
+        pool: config.pool,
+        fee_bps: params.fee_bps,
+        max_loan_amount: params.max_loan_amount,
+        loan_cooldown: params.loan_cooldown,
+        timelock: params.timelock,
+        activates_at,
+    });
+
+    Ok(())
+}
+
+// Whether `params` may be queued on `config`, shared with `batch_admin`
+pub(crate) fn check_params(config: &PoolConfig, envelope: &ParamEnvelope, params: &PoolConfigParams) -> Result<()> {
+    require!(
+        params.fee_bps <= BPS_DENOMINATOR
+            && params.max_loan_amount > 0
+            && params.loan_cooldown >= 0
+            && params.timelock >= 0,
+        FlashLoanError::InvalidPoolConfig
+    );
+    envelope.check(params)?;
+
+    let live = config.params();
+    for param in PoolParam::ALL {
+        require!(
+            !config.is_frozen(param) || param.get(params) == param.get(&live),
+            FlashLoanError::ParamFrozen
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::fees::calculate_dynamic_fee;
+use crate::instructions::max_borrowable::{borrowable_amount, MaxBorrowable};
+
+// Borrowing limits returned by `view_borrower_limits`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct BorrowerLimits {
+    pub max_loan_amount: u64,       // Largest loan that would pass today's checks
+    pub cooldown_remaining: i64,    // Seconds until the cooldown is over
+    pub loan_active: bool,          // Whether a loan is currently in flight
+    pub borrow_paused: bool,        // Whether the pool has borrowing paused
+    pub reputation_score: u64,      // Stake-weighted score, zero without a reputation account
+    pub trusted: bool,              // Whether the score unlocks the trusted tier
+    pub base_fee_bps: u64,          // Base fee on a `max_loan_amount` loan, from the fee tier or trusted tier
+    pub reputation_discount_bps: u64, // How far the trusted tier undercuts the standard base fee
+    pub max_loan_fee: u64,          // Fee a `max_loan_amount` loan would pay now, before any same-slot surcharge
+}
+
+// View: current borrowing limits and pricing for the given loan state
+pub(crate) fn handler(ctx: Context<MaxBorrowable>) -> Result<BorrowerLimits> {
+    let accounts = &ctx.accounts;
+    let loan_state = &accounts.loan_state;
+    let config = &accounts.pool_config;
+    let pool = &accounts.pool;
+    let vault_amount = accounts.loan_vault.amount;
+    let now = Clock::get()?.unix_timestamp;
+    let cooldown_ends_at = loan_state.last_loan_timestamp + config.loan_cooldown;
+
+    let reputation_score = accounts
+        .borrower_reputation
+        .as_ref()
+        .map_or(0, |reputation| reputation.score(now));
+    let trusted = accounts.is_trusted(now);
+    let max_loan_amount = borrowable_amount(pool, vault_amount, loan_state, config, trusted, now);
+
+    // Priced the way `execute_flash_loan` prices it: size tiers for standard
+    // borrowers, the trusted tier's own fee otherwise
+    let standard_fee_bps = pool.base_fee_bps(max_loan_amount, config.fee_bps, accounts.accounting_price.as_deref(), now);
+    let base_fee_bps = if trusted { config.limits(true).1 } else { standard_fee_bps };
+
+    Ok(BorrowerLimits {
+        max_loan_amount,
+        cooldown_remaining: (cooldown_ends_at - now).max(0),
+        loan_active: loan_state.active,
+        borrow_paused: pool.is_paused(PAUSE_BORROW),
+        reputation_score,
+        trusted,
+        base_fee_bps,
+        reputation_discount_bps: standard_fee_bps.saturating_sub(base_fee_bps),
+        max_loan_fee: calculate_dynamic_fee(max_loan_amount, vault_amount, base_fee_bps, &config.fee_curve),
+    })
+}
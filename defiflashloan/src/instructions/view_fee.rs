@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::constants::*;
+use crate::fees::calculate_dynamic_fee;
+use crate::state::{FeePrice, Pool, PoolConfig};
+
+// Context for fee quotes
+#[derive(Accounts)]
+pub struct ViewFee<'info> {
+    #[account(seeds = [POOL_SEED, pool.token_mint.as_ref(), pool.index.as_ref()], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+    #[account(seeds = [POOL_CONFIG_SEED, pool.key().as_ref()], bump = pool_config.bump)]
+    pub pool_config: Account<'info, PoolConfig>,   // Pool risk parameters
+    #[account(address = pool.vault)]
+    pub loan_vault: InterfaceAccount<'info, TokenAccount>, // Flash loan pool
+    #[account(address = pool.accounting_oracle)]
+    pub accounting_price: Option<Account<'info, FeePrice>>, // Pass to apply USD fee tiers, which otherwise charge the first tier
+}
+
+// View: fee charged for a loan of `loan_amount` at the vault's current liquidity
+pub(crate) fn handler(ctx: Context<ViewFee>, loan_amount: u64) -> Result<u64> {
+    let config = &ctx.accounts.pool_config;
+    let accounting_price = ctx.accounts.accounting_price.as_deref();
+    let fee_bps = ctx.accounts.pool.base_fee_bps(loan_amount, config.fee_bps, accounting_price, Clock::get()?.unix_timestamp);
+    Ok(calculate_dynamic_fee(loan_amount, ctx.accounts.loan_vault.amount, fee_bps, &config.fee_curve))
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::constants::*;
+use crate::state::{LpPosition, Pool};
+
+// Context for the governance snapshot weight view
+#[derive(Accounts)]
+pub struct ViewSnapshotShares<'info> {
+    #[account(seeds = [POOL_SEED, pool.token_mint.as_ref(), pool.index.as_ref()], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        seeds = [LP_POSITION_SEED, pool.key().as_ref(), owner.key().as_ref()],
+        bump = lp_position.bump
+    )]
+    pub lp_position: Account<'info, LpPosition>,
+    #[account(token::mint = pool.share_mint, token::authority = owner)]
+    pub owner_share_account: InterfaceAccount<'info, TokenAccount>, // Shares the owner holds
+    /// CHECK: Only keys the position and owns the share account
+    pub owner: UncheckedAccount<'info>,            // LP being snapshotted
+}
+
+// View: LP shares of `owner` that count in a governance snapshot taken now,
+// for share-weighted voting programs to read or CPI into
+pub(crate) fn handler(ctx: Context<ViewSnapshotShares>) -> Result<u64> {
+    Ok(ctx
+        .accounts
+        .lp_position
+        .snapshot_shares(ctx.accounts.owner_share_account.amount, Clock::get()?.slot))
+}
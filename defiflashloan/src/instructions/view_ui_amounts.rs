@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::constants::*;
+use crate::interest_bearing::{amount_to_ui_amount, is_interest_bearing, ui_amount_to_amount};
+use crate::state::{Pool, PoolConfig};
+
+// Pool balances as wallets display them, returned by `view_ui_amounts`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PoolUiAmounts {
+    pub interest_bearing: bool,     // Mint has the Token-2022 interest-bearing extension
+    pub vault_liquidity: String,    // Tokens in the vault right now
+    pub lp_assets: String,          // Assets backing LP shares
+    pub max_loan_amount: String,    // Standard loan cap
+    pub amount: u64,                // Raw amount `ui_amount` stands for, zero when none was given
+}
+
+// Context for UI amount reads
+#[derive(Accounts)]
+pub struct ViewUiAmounts<'info> {
+    #[account(seeds = [POOL_SEED, pool.token_mint.as_ref(), pool.index.as_ref()], bump = pool.bump, has_one = token_mint)]
+    pub pool: Account<'info, Pool>,
+    #[account(seeds = [POOL_CONFIG_SEED, pool.key().as_ref()], bump = pool_config.bump)]
+    pub pool_config: Account<'info, PoolConfig>,
+    #[account(address = pool.vault)]
+    pub loan_vault: InterfaceAccount<'info, TokenAccount>, // Flash loan pool
+    pub token_mint: InterfaceAccount<'info, Mint>, // Mint lent out by the pool
+}
+
+// View: the pool's raw balances converted to UI amounts, with interest
+// accrued to now for interest-bearing mints, plus the raw amount for an
+// optional `ui_amount` so clients can size loans in displayed terms
+pub(crate) fn handler(ctx: Context<ViewUiAmounts>, ui_amount: Option<String>) -> Result<PoolUiAmounts> {
+    let mint = ctx.accounts.token_mint.to_account_info();
+    let vault_liquidity = ctx.accounts.loan_vault.amount;
+
+    Ok(PoolUiAmounts {
+        interest_bearing: is_interest_bearing(&mint)?,
+        vault_liquidity: amount_to_ui_amount(&mint, vault_liquidity)?,
+        lp_assets: amount_to_ui_amount(&mint, ctx.accounts.pool.lp_assets(vault_liquidity))?,
+        max_loan_amount: amount_to_ui_amount(&mint, ctx.accounts.pool_config.max_loan_amount)?,
+        amount: ui_amount.map_or(Ok(0), |ui_amount| ui_amount_to_amount(&mint, &ui_amount))?,
+    })
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::constants::*;
+use crate::state::Pool;
+
+// Pool utilization returned by `view_utilization`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PoolUtilization {
+    pub vault_liquidity: u64,       // Tokens in the vault right now
+    pub open_loan_amount: u64,      // Principal of loans still out
+    pub lp_assets: u64,             // Assets backing LP shares, net of accrued protocol and creator fees
+    pub utilization_bps: u64,       // Share of the pool's liquidity currently lent out
+    pub withdrawals_throttled: bool, // Whether large withdrawals must wait for a later slot
+}
+
+// Context for utilization reads
+#[derive(Accounts)]
+pub struct ViewUtilization<'info> {
+    #[account(seeds = [POOL_SEED, pool.token_mint.as_ref(), pool.index.as_ref()], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+    #[account(address = pool.vault)]
+    pub loan_vault: InterfaceAccount<'info, TokenAccount>, // Flash loan pool
+}
+
+// View: how much of the pool is lent out and whether withdrawals are throttled
+pub(crate) fn handler(ctx: Context<ViewUtilization>) -> Result<PoolUtilization> {
+    let pool = &ctx.accounts.pool;
+    let vault_liquidity = ctx.accounts.loan_vault.amount;
+    let total = pool.open_loan_amount as u128 + vault_liquidity as u128;
+    let utilization_bps = if total == 0 {
+        0
+    } else {
+        (pool.open_loan_amount as u128 * BPS_DENOMINATOR as u128 / total) as u64
+    };
+
+    Ok(PoolUtilization {
+        vault_liquidity,
+        open_loan_amount: pool.open_loan_amount,
+        lp_assets: pool.lp_assets(vault_liquidity),
+        utilization_bps,
+        withdrawals_throttled: pool.is_throttled(Clock::get()?.slot),
+    })
+}
@@ -0,0 +1,154 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, spl_token::native_mint, Burn, CloseAccount, Mint, Token, TokenAccount, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::{LiquidityWithdrawn, SharePriceUpdated};
+use crate::shares::assets_for_shares;
+use crate::state::{LpFeeAccumulator, LpPosition, Pool};
+
+// Context for withdrawing from a WSOL pool as native SOL
+#[derive(Accounts)]
+pub struct WithdrawAndUnwrapSol<'info> {
+    #[account(
+        mut,
+        seeds = [POOL_SEED, token_mint.key().as_ref(), pool.index.as_ref()],
+        bump = pool.bump,
+        has_one = token_mint
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(mut, address = pool.vault)]
+    pub loan_vault: Account<'info, TokenAccount>,  // WSOL vault paying out the withdrawal
+    #[account(mut, address = pool.share_mint)]
+    pub share_mint: Account<'info, Mint>,          // LP share mint
+    #[account(
+        mut,
+        has_one = owner,
+        has_one = pool,
+        seeds = [LP_POSITION_SEED, pool.key().as_ref(), owner.key().as_ref()],
+        bump = lp_position.bump
+    )]
+    pub lp_position: Account<'info, LpPosition>,
+    #[account(mut, seeds = [LP_FEE_ACCUMULATOR_SEED, pool.key().as_ref()], bump = lp_fee_accumulator.bump)]
+    pub lp_fee_accumulator: Option<Account<'info, LpFeeAccumulator>>, // Required by pools with time-weighted LP fees
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = token_mint,
+        associated_token::authority = owner
+    )]
+    pub wsol_account: Account<'info, TokenAccount>, // Owner's WSOL account, closed to unwrap the withdrawal
+    #[account(mut, token::mint = share_mint, token::authority = owner)]
+    pub owner_share_account: Account<'info, TokenAccount>, // LP's shares to burn
+    #[account(mut)]
+    pub owner: Signer<'info>,                      // LP withdrawing, receives the lamports
+    #[account(address = native_mint::ID @ FlashLoanError::NotNativeMint)]
+    pub token_mint: Account<'info, Mint>,          // WSOL mint
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WithdrawAndUnwrapSol<'info> {
+    // Context for paying the LP's WSOL account out of the vault, signed by the pool PDA
+    pub fn into_transfer_to_wsol_context<'a, 'b, 'c>(
+        &self,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
+            from: self.loan_vault.to_account_info().clone(),
+            mint: self.token_mint.to_account_info().clone(),
+            to: self.wsol_account.to_account_info().clone(),
+            authority: self.pool.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+
+    // Context for closing the WSOL account, releasing its lamports to the owner
+    pub fn into_close_wsol_context(&self) -> CpiContext<'_, '_, '_, 'info, CloseAccount<'info>> {
+        let cpi_accounts = CloseAccount {
+            account: self.wsol_account.to_account_info().clone(),
+            destination: self.owner.to_account_info().clone(),
+            authority: self.owner.to_account_info().clone(),
+        };
+        CpiContext::new(self.token_program.to_account_info().clone(), cpi_accounts)
+    }
+
+    // Context for burning the LP's shares
+    pub fn into_burn_shares_context(&self) -> CpiContext<'_, '_, '_, 'info, Burn<'info>> {
+        let cpi_accounts = Burn {
+            mint: self.share_mint.to_account_info().clone(),
+            from: self.owner_share_account.to_account_info().clone(),
+            authority: self.owner.to_account_info().clone(),
+        };
+        CpiContext::new(self.token_program.to_account_info().clone(), cpi_accounts)
+    }
+}
+
+// Burn LP shares, withdraw their WSOL into the owner's WSOL account and close
+// it, so the owner receives native SOL in one instruction
+pub(crate) fn handler(ctx: Context<WithdrawAndUnwrapSol>, shares: u64) -> Result<()> {
+    require!(!ctx.accounts.pool.is_paused(PAUSE_WITHDRAW), FlashLoanError::PoolPaused);
+    require!(shares > 0, FlashLoanError::InvalidAmount);
+    require!(
+        ctx.accounts.owner_share_account.amount >= shares,
+        FlashLoanError::InsufficientLpBalance
+    );
+
+    let price_before = ctx.accounts.pool.share_price(ctx.accounts.loan_vault.amount, ctx.accounts.share_mint.supply);
+    let amount = assets_for_shares(
+        shares,
+        ctx.accounts.pool.lp_assets(ctx.accounts.loan_vault.amount),
+        ctx.accounts.share_mint.supply,
+    );
+    require!(amount > 0, FlashLoanError::InvalidAmount);
+    require!(ctx.accounts.loan_vault.amount >= amount, FlashLoanError::InsufficientFunds);
+    if ctx.accounts.pool.is_withdrawal_throttled(amount, ctx.accounts.loan_vault.amount, Clock::get()?.slot) {
+        ctx.accounts.pool.log_ops_tag();
+        return err!(FlashLoanError::WithdrawalThrottled);
+    }
+
+    token::burn(ctx.accounts.into_burn_shares_context(), shares)?;
+
+    let pool_seeds = ctx.accounts.pool.seeds();
+    token::transfer_checked(
+        ctx.accounts.into_transfer_to_wsol_context(&[&pool_seeds[..]]),
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+    token::close_account(ctx.accounts.into_close_wsol_context())?;
+
+    // Principal is drawn down first; anything above it is fee yield
+    let principal = amount.min(ctx.accounts.lp_position.deposited_amount);
+    ctx.accounts.lp_position.deposited_amount -= principal;
+    crate::lp_fees::track_position_shares(
+        &ctx.accounts.pool,
+        ctx.accounts.lp_fee_accumulator.as_mut(),
+        &mut ctx.accounts.lp_position,
+        0,
+        shares,
+        Clock::get()?.unix_timestamp,
+    )?;
+    ctx.accounts.lp_position.record_withdrawal(amount, shares);
+    ctx.accounts.pool.total_deposits = ctx.accounts.pool.total_deposits.saturating_sub(principal);
+
+    emit!(LiquidityWithdrawn {
+        pool: ctx.accounts.pool.key(),
+        lp: ctx.accounts.owner.key(),
+        amount,
+        shares,
+    });
+
+    ctx.accounts.loan_vault.reload()?;
+    ctx.accounts.share_mint.reload()?;
+    emit!(SharePriceUpdated {
+        pool: ctx.accounts.pool.key(),
+        price_before,
+        price_after: ctx.accounts.pool.share_price(ctx.accounts.loan_vault.amount, ctx.accounts.share_mint.supply),
+        lp_assets: ctx.accounts.pool.lp_assets(ctx.accounts.loan_vault.amount),
+        share_supply: ctx.accounts.share_mint.supply,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::KeeperStakeWithdrawn;
+use crate::state::{Keeper, KeeperRegistry};
+
+// Context for taking back an unbonded keeper bond
+#[derive(Accounts)]
+pub struct WithdrawKeeperStake<'info> {
+    #[account(mut, seeds = [KEEPER_REGISTRY_SEED], bump = keeper_registry.bump)]
+    pub keeper_registry: Account<'info, KeeperRegistry>,
+    #[account(mut, seeds = [KEEPER_SEED, authority.key().as_ref()], bump = keeper.bump, has_one = authority)]
+    pub keeper: Account<'info, Keeper>,
+    #[account(mut, address = keeper_registry.stake_vault)]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>, // Holds the bond
+    #[account(mut, token::mint = stake_mint)]
+    pub destination_account: InterfaceAccount<'info, TokenAccount>, // Receives the bond
+    #[account(address = keeper_registry.stake_mint)]
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+    pub authority: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> WithdrawKeeperStake<'info> {
+    // Context for paying the bond out of the stake vault, signed by the registry PDA
+    pub fn into_transfer_from_stake_vault_context<'a, 'b, 'c>(
+        &self,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
+            from: self.stake_vault.to_account_info().clone(),
+            mint: self.stake_mint.to_account_info().clone(),
+            to: self.destination_account.to_account_info().clone(),
+            authority: self.keeper_registry.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+}
+
+// Return what is left of the bond once the unbonding period has passed; the
+// keeper account stays so unclaimed rewards can still be claimed, and bonding
+// again re-registers it
+pub(crate) fn handler(ctx: Context<WithdrawKeeperStake>) -> Result<()> {
+    let keeper = &ctx.accounts.keeper;
+    require!(keeper.exit_requested_at != 0, FlashLoanError::KeeperNotExiting);
+    require!(
+        Clock::get()?.unix_timestamp >= keeper.exit_requested_at + ctx.accounts.keeper_registry.unbonding_period,
+        FlashLoanError::KeeperStillUnbonding
+    );
+
+    let amount = keeper.staked_amount;
+    if amount > 0 {
+        let registry_seeds = ctx.accounts.keeper_registry.seeds();
+        token_interface::transfer_checked(
+            ctx.accounts.into_transfer_from_stake_vault_context(&[&registry_seeds[..]]),
+            amount,
+            ctx.accounts.stake_mint.decimals,
+        )?;
+        let registry = &mut ctx.accounts.keeper_registry;
+        registry.total_staked -= amount;
+        registry.keeper_count -= 1;
+    }
+
+    let keeper = &mut ctx.accounts.keeper;
+    keeper.staked_amount = 0;
+    keeper.exit_requested_at = 0;
+
+    emit!(KeeperStakeWithdrawn {
+        keeper: keeper.key(),
+        authority: keeper.authority,
+        amount,
+    });
+
+    Ok(())
+}
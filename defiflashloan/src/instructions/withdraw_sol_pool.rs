@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer as SystemTransfer};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::state::SolPool;
+
+// Context for taking lamports out of the lamport pool
+#[derive(Accounts)]
+pub struct WithdrawSolPool<'info> {
+    #[account(mut, seeds = [SOL_POOL_SEED], bump = sol_pool.bump, has_one = admin)]
+    pub sol_pool: Account<'info, SolPool>,
+    #[account(mut, seeds = [SOL_VAULT_SEED], bump = sol_pool.vault_bump)]
+    pub sol_vault: SystemAccount<'info>,           // Lamport vault
+    #[account(mut)]
+    pub admin: Signer<'info>,                      // Lamport pool admin, receives the lamports
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WithdrawSolPool<'info> {
+    // Context for paying the admin out of the vault, signed by the vault PDA
+    pub fn into_transfer_to_admin_context<'a, 'b, 'c>(
+        &self,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, SystemTransfer<'info>> {
+        let cpi_accounts = SystemTransfer {
+            from: self.sol_vault.to_account_info().clone(),
+            to: self.admin.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.system_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+}
+
+// Withdraw lamports, principal first and then earned fees, keeping the vault
+// rent exempt
+pub(crate) fn handler(ctx: Context<WithdrawSolPool>, amount: u64) -> Result<()> {
+    require!(amount > 0, FlashLoanError::InvalidAmount);
+    let available = ctx
+        .accounts
+        .sol_vault
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(0));
+    require!(amount <= available, FlashLoanError::InsufficientFunds);
+
+    let vault_seeds = ctx.accounts.sol_pool.vault_seeds();
+    system_program::transfer(
+        ctx.accounts.into_transfer_to_admin_context(&[&vault_seeds[..]]),
+        amount,
+    )?;
+
+    let sol_pool = &mut ctx.accounts.sol_pool;
+    let principal = amount.min(sol_pool.total_deposits);
+    sol_pool.total_deposits -= principal;
+    sol_pool.fees_accrued = sol_pool.fees_accrued.saturating_sub(amount - principal);
+    Ok(())
+}
@@ -0,0 +1,173 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer as SystemTransfer};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, spl_token::native_mint, CloseAccount, Mint, MintTo, SyncNative, Token, TokenAccount, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::events::{LiquidityDeposited, SharePriceUpdated};
+use crate::shares::shares_for_deposit;
+use crate::state::{LpFeeAccumulator, LpPosition, Pool};
+
+// Context for depositing native SOL into a WSOL pool
+#[derive(Accounts)]
+pub struct WrapSolAndDeposit<'info> {
+    #[account(
+        mut,
+        seeds = [POOL_SEED, token_mint.key().as_ref(), pool.index.as_ref()],
+        bump = pool.bump,
+        has_one = token_mint
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(mut, address = pool.vault)]
+    pub loan_vault: Account<'info, TokenAccount>,  // WSOL vault receiving the deposit
+    #[account(mut, address = pool.share_mint)]
+    pub share_mint: Account<'info, Mint>,          // LP share mint
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        space = LpPosition::LEN,
+        seeds = [LP_POSITION_SEED, pool.key().as_ref(), depositor.key().as_ref()],
+        bump
+    )]
+    pub lp_position: Account<'info, LpPosition>,
+    #[account(mut, seeds = [LP_FEE_ACCUMULATOR_SEED, pool.key().as_ref()], bump = lp_fee_accumulator.bump)]
+    pub lp_fee_accumulator: Option<Account<'info, LpFeeAccumulator>>, // Required by pools with time-weighted LP fees
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        associated_token::mint = token_mint,
+        associated_token::authority = depositor
+    )]
+    pub wsol_account: Account<'info, TokenAccount>, // Depositor's WSOL account, closed once the deposit is made
+    #[account(mut, token::mint = share_mint)]
+    pub depositor_share_account: Account<'info, TokenAccount>, // Receives the minted shares
+    #[account(mut)]
+    pub depositor: Signer<'info>,                  // LP providing the lamports
+    #[account(address = native_mint::ID @ FlashLoanError::NotNativeMint)]
+    pub token_mint: Account<'info, Mint>,          // WSOL mint
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WrapSolAndDeposit<'info> {
+    // Context for funding the WSOL account with the depositor's lamports
+    pub fn into_wrap_lamports_context(&self) -> CpiContext<'_, '_, '_, 'info, SystemTransfer<'info>> {
+        let cpi_accounts = SystemTransfer {
+            from: self.depositor.to_account_info().clone(),
+            to: self.wsol_account.to_account_info().clone(),
+        };
+        CpiContext::new(self.system_program.to_account_info().clone(), cpi_accounts)
+    }
+
+    // Context for re-syncing the WSOL account's balance with its lamports
+    pub fn into_sync_wsol_context(&self) -> CpiContext<'_, '_, '_, 'info, SyncNative<'info>> {
+        let cpi_accounts = SyncNative {
+            account: self.wsol_account.to_account_info().clone(),
+        };
+        CpiContext::new(self.token_program.to_account_info().clone(), cpi_accounts)
+    }
+
+    // Context for moving the wrapped deposit into the vault
+    pub fn into_transfer_to_vault_context(&self) -> CpiContext<'_, '_, '_, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
+            from: self.wsol_account.to_account_info().clone(),
+            mint: self.token_mint.to_account_info().clone(),
+            to: self.loan_vault.to_account_info().clone(),
+            authority: self.depositor.to_account_info().clone(),
+        };
+        CpiContext::new(self.token_program.to_account_info().clone(), cpi_accounts)
+    }
+
+    // Context for closing the WSOL account, refunding its rent to the depositor
+    pub fn into_close_wsol_context(&self) -> CpiContext<'_, '_, '_, 'info, CloseAccount<'info>> {
+        let cpi_accounts = CloseAccount {
+            account: self.wsol_account.to_account_info().clone(),
+            destination: self.depositor.to_account_info().clone(),
+            authority: self.depositor.to_account_info().clone(),
+        };
+        CpiContext::new(self.token_program.to_account_info().clone(), cpi_accounts)
+    }
+
+    // Context for minting LP shares, signed by the pool PDA
+    pub fn into_mint_shares_context<'a, 'b, 'c>(
+        &self,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+    ) -> CpiContext<'a, 'b, 'c, 'info, MintTo<'info>> {
+        let cpi_accounts = MintTo {
+            mint: self.share_mint.to_account_info().clone(),
+            to: self.depositor_share_account.to_account_info().clone(),
+            authority: self.pool.to_account_info().clone(),
+        };
+        CpiContext::new_with_signer(self.token_program.to_account_info().clone(), cpi_accounts, signer_seeds)
+    }
+}
+
+// Wrap `amount` lamports in the depositor's WSOL account, deposit them and
+// close the account again, all in one instruction
+// Closing also unwraps any WSOL the account already held
+pub(crate) fn handler(ctx: Context<WrapSolAndDeposit>, amount: u64) -> Result<()> {
+    require!(!ctx.accounts.pool.is_paused(PAUSE_DEPOSIT), FlashLoanError::PoolPaused);
+    require!(amount > 0, FlashLoanError::InvalidAmount);
+
+    // Price the deposit before the vault balance changes
+    let price_before = ctx.accounts.pool.share_price(ctx.accounts.loan_vault.amount, ctx.accounts.share_mint.supply);
+    let shares = shares_for_deposit(
+        amount,
+        ctx.accounts.pool.lp_assets(ctx.accounts.loan_vault.amount),
+        ctx.accounts.share_mint.supply,
+    );
+    require!(shares > 0, FlashLoanError::InvalidAmount);
+
+    system_program::transfer(ctx.accounts.into_wrap_lamports_context(), amount)?;
+    token::sync_native(ctx.accounts.into_sync_wsol_context())?;
+    token::transfer_checked(
+        ctx.accounts.into_transfer_to_vault_context(),
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+    token::close_account(ctx.accounts.into_close_wsol_context())?;
+
+    let pool_seeds = ctx.accounts.pool.seeds();
+    token::mint_to(
+        ctx.accounts.into_mint_shares_context(&[&pool_seeds[..]]),
+        shares,
+    )?;
+
+    let lp_position = &mut ctx.accounts.lp_position;
+    crate::lp_fees::track_position_shares(
+        &ctx.accounts.pool,
+        ctx.accounts.lp_fee_accumulator.as_mut(),
+        lp_position,
+        shares,
+        0,
+        Clock::get()?.unix_timestamp,
+    )?;
+    lp_position.owner = ctx.accounts.depositor.key();
+    lp_position.pool = ctx.accounts.pool.key();
+    lp_position.deposited_amount += amount;
+    lp_position.record_deposit(amount, shares, Clock::get()?.slot);
+    lp_position.bump = ctx.bumps.lp_position;
+
+    ctx.accounts.pool.total_deposits += amount;
+
+    emit!(LiquidityDeposited {
+        pool: lp_position.pool,
+        lp: lp_position.owner,
+        amount,
+        shares,
+    });
+
+    ctx.accounts.loan_vault.reload()?;
+    ctx.accounts.share_mint.reload()?;
+    emit!(SharePriceUpdated {
+        pool: ctx.accounts.pool.key(),
+        price_before,
+        price_after: ctx.accounts.pool.share_price(ctx.accounts.loan_vault.amount, ctx.accounts.share_mint.supply),
+        lp_assets: ctx.accounts.pool.lp_assets(ctx.accounts.loan_vault.amount),
+        share_supply: ctx.accounts.share_mint.supply,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+// Token-2022 interest-bearing extension; balances, caps and share prices are
+// all kept in raw amounts, which never accrue, so these helpers only convert
+// for display and pricing happens the same as for any other mint
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{interest_bearing_mint::InterestBearingConfig, BaseStateWithExtensions, StateWithExtensions},
+    state::Mint,
+};
+
+use crate::errors::FlashLoanError;
+
+// Decimals of the mint and its interest-bearing config, if it has one
+fn with_interest_config<T>(mint: &AccountInfo, f: impl FnOnce(u8, Option<&InterestBearingConfig>) -> Option<T>) -> Result<T> {
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<Mint>::unpack(&data)?;
+    let config = if *mint.owner == spl_token_2022::ID {
+        mint_state.get_extension::<InterestBearingConfig>().ok()
+    } else {
+        None
+    };
+    f(mint_state.base.decimals, config).ok_or_else(|| error!(FlashLoanError::InvalidUiAmount))
+}
+
+// Whether the mint displays balances with accrued interest
+pub fn is_interest_bearing(mint: &AccountInfo) -> Result<bool> {
+    with_interest_config(mint, |_, config| Some(config.is_some()))
+}
+
+// Raw `amount` as the wallet-facing UI amount, with interest accrued up to now
+pub fn amount_to_ui_amount(mint: &AccountInfo, amount: u64) -> Result<String> {
+    let now = Clock::get()?.unix_timestamp;
+    with_interest_config(mint, |decimals, config| match config {
+        Some(config) => config.amount_to_ui_amount(amount, decimals, now),
+        None => Some(spl_token_2022::amount_to_ui_amount_string_trimmed(amount, decimals)),
+    })
+}
+
+// Raw amount a wallet-facing `ui_amount` stands for right now
+pub fn ui_amount_to_amount(mint: &AccountInfo, ui_amount: &str) -> Result<u64> {
+    let now = Clock::get()?.unix_timestamp;
+    with_interest_config(mint, |decimals, config| match config {
+        Some(config) => config.try_ui_amount_into_amount(ui_amount, decimals, now).ok(),
+        None => spl_token_2022::try_ui_amount_into_amount(ui_amount.to_string(), decimals).ok(),
+    })
+}
@@ -0,0 +1,97 @@
+// Instructions sysvar checks pairing `flash_borrow` with a later `flash_repay`,
+// spotting durable nonce transactions and reading the priority fee
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{compute_budget, system_program, sysvar::instructions::{load_current_index_checked, load_instruction_at_checked}};
+use anchor_lang::Discriminator;
+
+use crate::errors::FlashLoanError;
+use crate::state::PoolConfig;
+
+const REPAY_POOL_ACCOUNT_INDEX: usize = 0; // Position of `pool` in FlashRepay
+const REPAY_LOAN_STATE_ACCOUNT_INDEX: usize = 4; // Position of `loan_state` in FlashRepay
+const REPAY_LOAN_RECEIPT_ACCOUNT_INDEX: usize = 7; // Position of `loan_receipt` in FlashRepay
+const ADVANCE_NONCE_ACCOUNT: u32 = 4; // SystemInstruction::AdvanceNonceAccount, bincode-encoded as a little-endian u32
+const SET_COMPUTE_UNIT_PRICE: u8 = 3; // ComputeBudgetInstruction::SetComputeUnitPrice, borsh-encoded tag
+
+// Require a `flash_repay` of `amount` for `pool` and `loan_state` that closes
+// `loan_receipt` later in the transaction, with no other `flash_borrow` from
+// this program in between
+pub fn require_flash_repay(
+    instructions_sysvar: &AccountInfo,
+    pool: &Pubkey,
+    loan_state: &Pubkey,
+    loan_receipt: &Pubkey,
+    amount: u64,
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)? as usize;
+
+    let mut index = current_index + 1;
+    while let Ok(ix) = load_instruction_at_checked(index, instructions_sysvar) {
+        index += 1;
+        if ix.program_id != crate::ID || ix.data.len() < 8 {
+            continue;
+        }
+
+        let (discriminator, args) = ix.data.split_at(8);
+        if discriminator == crate::instruction::FlashBorrow::DISCRIMINATOR {
+            return err!(FlashLoanError::MissingFlashRepay);
+        }
+        if discriminator != crate::instruction::FlashRepay::DISCRIMINATOR {
+            continue;
+        }
+
+        let account_is = |index: usize, key: &Pubkey| {
+            ix.accounts.get(index).is_some_and(|meta| meta.pubkey == *key)
+        };
+        let repays_loan = account_is(REPAY_POOL_ACCOUNT_INDEX, pool)
+            && account_is(REPAY_LOAN_STATE_ACCOUNT_INDEX, loan_state)
+            && account_is(REPAY_LOAN_RECEIPT_ACCOUNT_INDEX, loan_receipt);
+        let repay_amount = args
+            .get(..8)
+            .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()));
+        if repays_loan && repay_amount == Some(amount) {
+            return Ok(());
+        }
+    }
+
+    err!(FlashLoanError::MissingFlashRepay)
+}
+
+// Whether this is a durable nonce transaction: the runtime requires those to
+// start by advancing the nonce account
+pub fn is_durable_nonce_transaction(instructions_sysvar: &AccountInfo) -> Result<bool> {
+    let first = load_instruction_at_checked(0, instructions_sysvar)?;
+    Ok(first.program_id == system_program::ID
+        && first.data.get(..4) == Some(&ADVANCE_NONCE_ACCOUNT.to_le_bytes()[..]))
+}
+
+// Compute unit price, in micro-lamports, set by the transaction's
+// ComputeBudget instruction, or zero when it pays no priority fee; the
+// runtime refuses a transaction setting it twice
+pub fn compute_unit_price(instructions_sysvar: &AccountInfo) -> Result<u64> {
+    let mut index = 0;
+    while let Ok(ix) = load_instruction_at_checked(index, instructions_sysvar) {
+        index += 1;
+        if ix.program_id != compute_budget::ID || ix.data.first() != Some(&SET_COMPUTE_UNIT_PRICE) {
+            continue;
+        }
+        if let Some(price) = ix.data.get(1..9) {
+            return Ok(u64::from_le_bytes(price.try_into().unwrap()));
+        }
+    }
+    Ok(0)
+}
+
+// Enforce the pool's durable nonce policy; a pool refusing them needs the
+// Instructions sysvar to tell
+pub fn require_nonce_policy(config: &PoolConfig, instructions_sysvar: Option<&AccountInfo>) -> Result<()> {
+    if !config.reject_durable_nonce {
+        return Ok(());
+    }
+    let instructions_sysvar = instructions_sysvar.ok_or(FlashLoanError::NonceCheckUnavailable)?;
+    require!(
+        !is_durable_nonce_transaction(instructions_sysvar)?,
+        FlashLoanError::DurableNonceRejected
+    );
+    Ok(())
+}
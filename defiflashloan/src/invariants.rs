@@ -0,0 +1,153 @@
+// Accounting invariants checked on testnets built with `strict-invariants`
+use anchor_lang::prelude::*;
+
+use crate::errors::FlashLoanError;
+use crate::state::{LoanStats, Pool};
+
+// Pool accounting read before a loan is disbursed, or before a split loan is
+// repaid, and checked again once it settles
+pub struct SettlementSnapshot {
+    pub vault: u64,
+    pub lp_assets: u64,
+    pub share_price: u64,
+    pub open_loan_amount: u64,
+    pub fees_reserved: u64,
+    pub dust_owed: u64,
+}
+
+impl SettlementSnapshot {
+    pub fn capture(pool: &Pool, vault_amount: u64, share_supply: u64) -> Self {
+        Self {
+            vault: vault_amount,
+            lp_assets: pool.lp_assets(vault_amount),
+            share_price: pool.share_price(vault_amount, share_supply),
+            open_loan_amount: pool.open_loan_amount,
+            fees_reserved: pool.fees_reserved(),
+            dust_owed: pool.dust_owed,
+        }
+    }
+}
+
+// Every settlement path runs this once the pool has been settled and the
+// share mint reloaded. `owed` is what the vault had to gain since the
+// snapshot and `principal` the part of it that was counted as an open loan;
+// dust written off since the snapshot is owed by the treasury instead
+pub fn check_settlement(
+    before: &SettlementSnapshot,
+    pool: &Pool,
+    vault_after: u64,
+    share_supply: u64,
+    owed: u64,
+    principal: u64,
+) -> Result<()> {
+    let owed = owed.saturating_sub(pool.dust_owed.saturating_sub(before.dust_owed));
+    check_vault_reconciled(before.vault, vault_after, owed)
+        .and_then(|_| check_receipt_counters(pool))
+        .and_then(|_| check_lp_assets_reconciled(before, pool, vault_after, owed, principal))
+        .and_then(|_| check_share_backing(before, pool, vault_after, share_supply))
+        .inspect_err(|_| pool.log_ops_tag())
+}
+
+// The vault must close a loan holding at least its opening balance plus what
+// was owed; anything over is a donation to LPs
+pub fn check_vault_reconciled(vault_before: u64, vault_after: u64, owed: u64) -> Result<()> {
+    if vault_before.checked_add(owed).map_or(true, |expected| vault_after < expected) {
+        msg!(
+            "Invariant violated: vault {} -> {}, expected at least {} more",
+            vault_before,
+            vault_after,
+            owed
+        );
+        return err!(FlashLoanError::InvariantViolated);
+    }
+    Ok(())
+}
+
+// Each open split loan carries principal, and no principal is left open once
+// every receipt has been repaid
+pub fn check_receipt_counters(pool: &Pool) -> Result<()> {
+    let consistent = if pool.open_receipts == 0 {
+        pool.open_loan_amount == 0
+    } else {
+        pool.open_loan_amount >= pool.open_receipts as u64
+    };
+    if !consistent {
+        msg!(
+            "Invariant violated: {} open receipts for {} open principal",
+            pool.open_receipts,
+            pool.open_loan_amount
+        );
+        return err!(FlashLoanError::InvariantViolated);
+    }
+    Ok(())
+}
+
+// LP assets must grow by at least the LP share of the fee: the repaid
+// principal leaves `open_loan_amount`, uncollected fees stay backed by the
+// vault, and nothing in `lp_assets` saturates
+pub fn check_lp_assets_reconciled(
+    before: &SettlementSnapshot,
+    pool: &Pool,
+    vault_after: u64,
+    owed: u64,
+    principal: u64,
+) -> Result<()> {
+    let fees_reserved = pool.fees_reserved();
+    let backing = vault_after as u128 + pool.open_loan_amount as u128;
+    let open_restored = before.open_loan_amount.checked_sub(principal) == Some(pool.open_loan_amount);
+    let lp_fee = owed
+        .saturating_sub(principal)
+        .saturating_sub(fees_reserved.saturating_sub(before.fees_reserved));
+    let lp_after = pool.lp_assets(vault_after);
+    if !open_restored || backing < fees_reserved as u128 || (lp_after as u128) < before.lp_assets as u128 + lp_fee as u128 {
+        msg!(
+            "Invariant violated: lp assets {} -> {}, expected {} more; open principal {} -> {}, {} fees reserved",
+            before.lp_assets,
+            lp_after,
+            lp_fee,
+            before.open_loan_amount,
+            pool.open_loan_amount,
+            fees_reserved
+        );
+        return err!(FlashLoanError::InvariantViolated);
+    }
+    Ok(())
+}
+
+// Outstanding shares must be backed by LP assets, and a settlement only ever
+// adds fees, so it can never lower the share price
+pub fn check_share_backing(before: &SettlementSnapshot, pool: &Pool, vault_after: u64, share_supply: u64) -> Result<()> {
+    let lp_assets = pool.lp_assets(vault_after);
+    let price_after = pool.share_price(vault_after, share_supply);
+    if (share_supply > 0 && lp_assets == 0) || price_after < before.share_price {
+        msg!(
+            "Invariant violated: {} shares over {} lp assets, share price {} -> {}",
+            share_supply,
+            lp_assets,
+            before.share_price,
+            price_after
+        );
+        return err!(FlashLoanError::InvariantViolated);
+    }
+    Ok(())
+}
+
+// Aggregate stats must stay consistent with each other
+pub fn check_loan_stats(stats: &LoanStats) -> Result<()> {
+    let window_count: u64 = stats.buckets.iter().map(|b| b.count).sum();
+    let consistent = if stats.total_loan_count == 0 {
+        stats.total_loans == 0 && stats.ewma_loan_size == 0 && window_count == 0
+    } else {
+        window_count <= stats.total_loan_count
+    };
+    if !consistent {
+        msg!(
+            "Invariant violated: {} loans totalling {}, {} in the stats window",
+            stats.total_loan_count,
+            stats.total_loans,
+            window_count
+        );
+        return err!(FlashLoanError::InvariantViolated);
+    }
+    Ok(())
+}
@@ -0,0 +1,43 @@
+// Crank rewards for registered keepers: a permissionless crank pays the
+// keeper that ran it when the registry, keeper and keeper authority accounts
+// are all passed, and behaves as before when none are
+use anchor_lang::prelude::*;
+
+use crate::errors::FlashLoanError;
+use crate::events::KeeperCrankCredited;
+use crate::state::{Keeper, KeeperCrank, KeeperRegistry};
+
+// Credit `crank` to the keeper signing it; cranks run by a keeper that has
+// begun exiting, or whose bond was slashed below the minimum, earn nothing
+pub fn credit_crank(
+    keeper_registry: Option<&mut Account<KeeperRegistry>>,
+    keeper: Option<&mut Account<Keeper>>,
+    keeper_authority: Option<&Signer>,
+    crank: KeeperCrank,
+) -> Result<()> {
+    let (registry, keeper, authority) = match (keeper_registry, keeper, keeper_authority) {
+        (None, None, None) => return Ok(()),
+        (Some(registry), Some(keeper), Some(authority)) => (registry, keeper, authority),
+        _ => return err!(FlashLoanError::KeeperAccountsMissing),
+    };
+    require_keys_eq!(keeper.authority, authority.key(), FlashLoanError::KeeperMismatch);
+    if !keeper.is_active(registry.min_stake) {
+        return Ok(());
+    }
+
+    let reward = registry.reward_per_crank;
+    keeper.pending_rewards += reward;
+    keeper.total_rewards += reward;
+    keeper.cranks_run += 1;
+    registry.rewards_owed += reward;
+
+    emit!(KeeperCrankCredited {
+        keeper: keeper.key(),
+        authority: keeper.authority,
+        crank: crank as u8,
+        reward,
+        pending_rewards: keeper.pending_rewards,
+    });
+
+    Ok(())
+}
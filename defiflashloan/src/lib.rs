@@ -1,210 +1,804 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Transfer, TokenAccount, Token, Mint};
-use anchor_lang::solana_program::{clock::Clock, instruction::{Instruction, AccountMeta}, program::invoke};
 
+pub mod constants;
+pub mod errors;
+pub mod events;
+pub mod fee_forwarding;
+pub mod fees;
+#[cfg(feature = "fee-vectors")]
+pub mod fee_vectors;
+pub mod instructions;
+pub mod interest_bearing;
+pub mod introspection;
+#[cfg(feature = "strict-invariants")]
+pub mod invariants;
+pub mod keeper_rewards;
+pub mod lp_fees;
+pub mod pda;
+pub mod receiver;
+pub mod shares;
+pub mod sigverify;
+pub mod state;
+pub mod transfer_fee;
 
-declare_id!("9o3VbMAbvmXmrj4QJ35voJ3ScpccEATRAsi2zuFyUj2a");
+pub use constants::*;
+pub use errors::*;
+pub use events::*;
+pub use instructions::*;
+pub use state::*;
 
-const FEE_BPS: u64 = 50; // Default fee is 0.5%
-const MAX_LOAN_AMOUNT: u64 = 1_000_000; // Maximum loan amount allowed
-const LOAN_COOLDOWN: i64 = 60; // Cooldown between loans in seconds
-const GRACE_PERIOD: i64 = 30; // Grace period for repayment in seconds
+declare_id!("9o3VbMAbvmXmrj4QJ35voJ3ScpccEATRAsi2zuFyUj2a");
 
 #[program]
 pub mod flash_loan {
     use super::*;
 
-    pub fn execute_flash_loan(
-        ctx: Context<ExecuteFlashLoan>,
+    // Create the protocol config; the signer becomes protocol admin
+    pub fn initialize_protocol(ctx: Context<InitializeProtocol>, treasury: Pubkey) -> Result<()> {
+        initialize_protocol::handler(ctx, treasury)
+    }
+
+    // Set the share of a pool's loan fees reserved for the protocol
+    pub fn set_reserve_factor(ctx: Context<SetReserveFactor>, reserve_factor_bps: u16) -> Result<()> {
+        set_reserve_factor::handler(ctx, reserve_factor_bps)
+    }
+
+    // Sweep a pool's accrued protocol fees to the treasury
+    pub fn collect_protocol_fees(ctx: Context<CollectProtocolFees>) -> Result<()> {
+        collect_protocol_fees::handler(ctx)
+    }
+
+    // Lamports every new pool's creator must lock in the pool until it is closed
+    pub fn set_pool_creation_deposit(ctx: Context<SetPoolCreationDeposit>, pool_creation_deposit: u64) -> Result<()> {
+        set_pool_creation_deposit::handler(ctx, pool_creation_deposit)
+    }
+
+    // Permissionless: create a pool for `token_mint` with a vault owned by the pool PDA,
+    // routing `creator_fee_bps` of each loan fee to the creator
+    pub fn initialize_pool(ctx: Context<InitializePool>, creator_fee_bps: u16) -> Result<()> {
+        initialize_pool::handler(ctx, creator_fee_bps)
+    }
+
+    // Create a pool for `token_mint` with the fee curve, caps and roles of a
+    // pool the signer already administers
+    pub fn clone_pool_config(ctx: Context<ClonePoolConfig>) -> Result<()> {
+        clone_pool_config::handler(ctx)
+    }
+
+    // Sweep a pool's accrued creator fees to the creator's token account
+    pub fn collect_creator_fees(ctx: Context<CollectCreatorFees>) -> Result<()> {
+        collect_creator_fees::handler(ctx)
+    }
+
+    // Close an empty pool and remove it from registry page `page`
+    pub fn close_pool(ctx: Context<ClosePool>, page: u32) -> Result<()> {
+        close_pool::handler(ctx, page)
+    }
+
+    // Allow flash loan callbacks from the pool to invoke `program_id`
+    pub fn register_target_program(ctx: Context<RegisterTargetProgram>, program_id: Pubkey) -> Result<()> {
+        register_target_program::handler(ctx, program_id)
+    }
+
+    // Remove `program_id` from the pool's callback allowlist
+    pub fn remove_target_program(ctx: Context<RemoveTargetProgram>, program_id: Pubkey) -> Result<()> {
+        remove_target_program::handler(ctx, program_id)
+    }
+
+    // Nominate a new pool admin, who must accept before the rotation takes effect
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        propose_admin::handler(ctx, new_admin)
+    }
+
+    // Accept a pending pool admin nomination
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        accept_admin::handler(ctx)
+    }
+
+    // Set the pool's dead-man's-switch recovery key and inactivity period, in epochs
+    pub fn set_recovery_key(ctx: Context<SetRecoveryKey>, recovery_key: Pubkey, inactivity_epochs: u64) -> Result<()> {
+        set_recovery_key::handler(ctx, recovery_key, inactivity_epochs)
+    }
+
+    // Tag the pool's critical failure logs and events for alert routing
+    pub fn set_ops_tag(ctx: Context<SetOpsTag>, ops_tag: [u8; OPS_TAG_LEN]) -> Result<()> {
+        set_ops_tag::handler(ctx, ops_tag)
+    }
+
+    // Make the pool fee-free with per-borrower daily limits and an anti-spam
+    // charge, or standard again
+    pub fn set_public_goods_mode(ctx: Context<SetPublicGoodsMode>, daily_loan_limit: u32, charge_lamports: u64) -> Result<()> {
+        set_public_goods_mode::handler(ctx, daily_loan_limit, charge_lamports)
+    }
+
+    // Pool admin liveness signal; cancels a pending recovery
+    pub fn admin_heartbeat(ctx: Context<AdminHeartbeat>) -> Result<()> {
+        admin_heartbeat::handler(ctx)
+    }
+
+    // Recovery key only: start the public delay window after the admin has gone silent
+    pub fn start_admin_recovery(ctx: Context<StartAdminRecovery>) -> Result<()> {
+        start_admin_recovery::handler(ctx)
+    }
+
+    // Recovery key only: assume the admin role once the delay window has passed
+    pub fn complete_admin_recovery(ctx: Context<CompleteAdminRecovery>) -> Result<()> {
+        complete_admin_recovery::handler(ctx)
+    }
+
+    // Open the signer's borrower reputation record
+    pub fn init_borrower_reputation(ctx: Context<InitBorrowerReputation>) -> Result<()> {
+        init_borrower_reputation::handler(ctx)
+    }
+
+    // Lock lamports behind the signer's reputation; the score scales with the stake
+    pub fn stake_reputation(ctx: Context<StakeReputation>, amount: u64) -> Result<()> {
+        stake_reputation::handler(ctx, amount)
+    }
+
+    // Withdraw reputation stake once its lock has run out
+    pub fn unstake_reputation(ctx: Context<UnstakeReputation>, amount: u64) -> Result<()> {
+        unstake_reputation::handler(ctx, amount)
+    }
+
+    // Open the signer's loan state on a pool
+    pub fn init_loan_state(ctx: Context<InitLoanState>) -> Result<()> {
+        init_loan_state::handler(ctx)
+    }
+
+    // Create an empty loan statistics account
+    pub fn init_loan_stats(ctx: Context<InitLoanStats>) -> Result<()> {
+        init_loan_stats::handler(ctx)
+    }
+
+    // Open the per-bucket stats a pool's fee experiment records into
+    pub fn init_fee_experiment_stats(ctx: Context<InitFeeExperimentStats>) -> Result<()> {
+        init_fee_experiment_stats::handler(ctx)
+    }
+
+    // Protocol admin only: record an incident against a borrower, zeroing their score
+    pub fn flag_borrower_incident(ctx: Context<FlagBorrowerIncident>) -> Result<()> {
+        flag_borrower_incident::handler(ctx)
+    }
+
+    // Loan cap and base fee for borrowers whose reputation reaches `min_score`,
+    // queued behind the pool's timelock
+    pub fn set_trusted_tier(
+        ctx: Context<SetTrustedTier>,
+        min_score: u64,
+        max_loan_amount: u64,
+        fee_bps: u64,
+        min_counted_loan: u64,
+    ) -> Result<()> {
+        set_trusted_tier::handler(ctx, min_score, max_loan_amount, fee_bps, min_counted_loan)
+    }
+
+    // Utilization curve scaling the base fee, queued behind the pool's timelock
+    pub fn set_fee_curve(ctx: Context<SetFeeCurve>, curve: FeeCurve) -> Result<()> {
+        set_fee_curve::handler(ctx, curve)
+    }
+
+    // Size tiers setting the base fee for standard borrowers, largest loans
+    // cheapest, sized in raw units or in USD cents by the accounting oracle
+    pub fn set_fee_tiers(ctx: Context<SetFeeTiers>, tiers: Vec<FeeTier>, in_usd: bool) -> Result<()> {
+        set_fee_tiers::handler(ctx, tiers, in_usd)
+    }
+
+    // Deepest stack height loans may be taken from, queued behind the pool's timelock
+    pub fn set_max_invoke_depth(ctx: Context<SetMaxInvokeDepth>, max_invoke_depth: u8) -> Result<()> {
+        set_max_invoke_depth::handler(ctx, max_invoke_depth)
+    }
+
+    // Extra fee per repeated loan in one slot, queued behind the pool's timelock
+    pub fn set_same_slot_surcharge(ctx: Context<SetSameSlotSurcharge>, surcharge_bps: u64) -> Result<()> {
+        set_same_slot_surcharge::handler(ctx, surcharge_bps)
+    }
+
+    // Cap on borrowers holding an open split loan at once, queued behind the pool's timelock
+    pub fn set_max_concurrent_borrowers(ctx: Context<SetMaxConcurrentBorrowers>, max_concurrent_borrowers: u32) -> Result<()> {
+        set_max_concurrent_borrowers::handler(ctx, max_concurrent_borrowers)
+    }
+
+    // A/B fee experiment over borrowers bucketed by key hash, queued behind the pool's timelock
+    pub fn set_fee_experiment(ctx: Context<SetFeeExperiment>, variant_fee_bps: u64, variant_share_bps: u64) -> Result<()> {
+        set_fee_experiment::handler(ctx, variant_fee_bps, variant_share_bps)
+    }
+
+    // Withdrawal pause after which LPs may use `emergency_withdraw`, queued behind the pool's timelock
+    pub fn set_emergency_withdraw_delay(ctx: Context<SetEmergencyWithdrawDelay>, delay: i64) -> Result<()> {
+        set_emergency_withdraw_delay::handler(ctx, delay)
+    }
+
+    // Queue the cap on a borrower's first loan from the pool, zero to disable
+    pub fn set_probation_loan_amount(ctx: Context<SetProbationLoanAmount>, probation_loan_amount: u64) -> Result<()> {
+        set_probation_loan_amount::handler(ctx, probation_loan_amount)
+    }
+
+    // Queue whether the pool refuses loans from durable nonce transactions
+    pub fn set_durable_nonce_policy(ctx: Context<SetDurableNoncePolicy>, reject_durable_nonce: bool) -> Result<()> {
+        set_durable_nonce_policy::handler(ctx, reject_durable_nonce)
+    }
+
+    // Report the pool's fee revenue in USD cents by its mint's posted FeePrice
+    pub fn set_accounting_oracle(ctx: Context<SetAccountingOracle>) -> Result<()> {
+        set_accounting_oracle::handler(ctx)
+    }
+
+    // Emergency stop for borrows, deposits and/or withdrawals, selected by PAUSE_* flags
+    pub fn pause_pool(ctx: Context<PausePool>, flags: u8) -> Result<()> {
+        pause_pool::handler(ctx, flags)
+    }
+
+    // Resume the operations selected by PAUSE_* flags
+    pub fn unpause_pool(ctx: Context<PausePool>, flags: u8) -> Result<()> {
+        unpause_pool::handler(ctx, flags)
+    }
+
+    // Apply up to MAX_ADMIN_OPS pause and queued config changes all-or-nothing
+    pub fn batch_admin(ctx: Context<BatchAdmin>, ops: Vec<AdminOp>) -> Result<()> {
+        batch_admin::handler(ctx, ops)
+    }
+
+    // Retire the pool: stop borrows and deposits for good, reopen withdrawals
+    pub fn begin_wind_down(ctx: Context<PausePool>) -> Result<()> {
+        begin_wind_down::handler(ctx)
+    }
+
+    // Queue new pool parameters behind the pool's timelock
+    pub fn update_pool_config(ctx: Context<UpdatePoolConfig>, params: PoolConfigParams) -> Result<()> {
+        update_pool_config::handler(ctx, params)
+    }
+
+    // Crank: activate queued pool parameters once the timelock has passed
+    pub fn apply_pool_config(ctx: Context<ApplyPoolConfig>) -> Result<()> {
+        apply_pool_config::handler(ctx)
+    }
+
+    // Add liquidity to a pool vault in exchange for LP shares
+    pub fn deposit_liquidity(ctx: Context<DepositLiquidity>, amount: u64, min_shares_out: u64) -> Result<()> {
+        deposit_liquidity::handler(ctx, amount, min_shares_out)
+    }
+
+    // Burn LP shares for their proportional share of the pool vault
+    pub fn withdraw_liquidity(ctx: Context<WithdrawLiquidity>, shares: u64, min_assets_out: u64) -> Result<()> {
+        withdraw_liquidity::handler(ctx, shares, min_assets_out)
+    }
+
+    // Principal-only exit once withdrawals have stayed paused past the pool's emergency delay
+    pub fn emergency_withdraw(ctx: Context<EmergencyWithdraw>, shares: u64) -> Result<()> {
+        emergency_withdraw::handler(ctx, shares)
+    }
+
+    // WSOL pools only: wrap `amount` lamports and deposit them in one step
+    pub fn wrap_sol_and_deposit(ctx: Context<WrapSolAndDeposit>, amount: u64) -> Result<()> {
+        wrap_sol_and_deposit::handler(ctx, amount)
+    }
+
+    // WSOL pools only: burn LP shares and receive the withdrawal as native SOL
+    pub fn withdraw_and_unwrap_sol(ctx: Context<WithdrawAndUnwrapSol>, shares: u64) -> Result<()> {
+        withdraw_and_unwrap_sol::handler(ctx, shares)
+    }
+
+    // Devnet only: mint faucet-controlled test tokens into a pool vault
+    #[cfg(feature = "devnet-faucet")]
+    pub fn airdrop_test_liquidity(ctx: Context<AirdropTestLiquidity>, amount: u64) -> Result<()> {
+        airdrop_test_liquidity::handler(ctx, amount)
+    }
+
+    // Create the pool's escrow for escrowed flash loans
+    pub fn initialize_loan_escrow(ctx: Context<InitializeLoanEscrow>) -> Result<()> {
+        initialize_loan_escrow::handler(ctx)
+    }
+
+    // Seed a pool with protocol-owned liquidity from the treasury
+    pub fn seed_pool_from_treasury(ctx: Context<SeedPoolFromTreasury>, amount: u64) -> Result<()> {
+        seed_pool_from_treasury::handler(ctx, amount)
+    }
+
+    // Create a fee locker streaming the pool's protocol fees to protocol token lockers
+    pub fn initialize_fee_locker(ctx: Context<InitializeFeeLocker>, epoch_duration: i64) -> Result<()> {
+        initialize_fee_locker::handler(ctx, epoch_duration)
+    }
+
+    // Lock protocol tokens for fee weight
+    pub fn lock_fee_tokens(ctx: Context<LockFeeTokens>, amount: u64, duration: i64) -> Result<()> {
+        lock_fee_tokens::handler(ctx, amount, duration)
+    }
+
+    // Extend a fee lock and reweight it
+    pub fn relock_fee_tokens(ctx: Context<RelockFeeTokens>, duration: i64) -> Result<()> {
+        relock_fee_tokens::handler(ctx, duration)
+    }
+
+    // Decay a fee lock position's weight to its remaining lock time
+    pub fn checkpoint_fee_lock(ctx: Context<CheckpointFeeLock>) -> Result<()> {
+        checkpoint_fee_lock::handler(ctx)
+    }
+
+    // Stream the epoch's accrued protocol fees to the fee locker
+    pub fn stream_protocol_fees(ctx: Context<StreamProtocolFees>) -> Result<()> {
+        stream_protocol_fees::handler(ctx)
+    }
+
+    // Claim streamed protocol fees
+    pub fn claim_fee_rewards(ctx: Context<ClaimFeeRewards>) -> Result<()> {
+        claim_fee_rewards::handler(ctx)
+    }
+
+    // Switch a pool with no LP shares yet to LP fees split by share-seconds
+    pub fn initialize_lp_fee_accumulator(ctx: Context<InitializeLpFeeAccumulator>, epoch_duration: i64) -> Result<()> {
+        initialize_lp_fee_accumulator::handler(ctx, epoch_duration)
+    }
+
+    // Close a time-weighted LP fee epoch, spreading its withheld fees
+    pub fn checkpoint_lp_fees(ctx: Context<CheckpointLpFees>) -> Result<()> {
+        checkpoint_lp_fees::handler(ctx)
+    }
+
+    // Claim an LP position's time-weighted fees
+    pub fn claim_lp_fees(ctx: Context<ClaimLpFees>) -> Result<()> {
+        claim_lp_fees::handler(ctx)
+    }
+
+    // Create or retune the limits on moving liquidity between two pools of a mint
+    pub fn configure_rebalance_route(
+        ctx: Context<ConfigureRebalanceRoute>,
+        max_move_amount: u64,
+        max_outstanding: u64,
+        operator: Pubkey,
+    ) -> Result<()> {
+        configure_rebalance_route::handler(ctx, max_move_amount, max_outstanding, operator)
+    }
+
+    // Move liquidity along a rebalance route for IOU shares of the target
+    pub fn rebalance_liquidity(ctx: Context<RebalanceLiquidity>, amount: u64) -> Result<()> {
+        rebalance_liquidity::handler(ctx, amount)
+    }
+
+    // Redeem a route's IOU shares, returning the liquidity to the source pool
+    pub fn unwind_rebalance(ctx: Context<UnwindRebalance>, shares: u64) -> Result<()> {
+        unwind_rebalance::handler(ctx, shares)
+    }
+
+    // Set the settlement shortfall a pool writes off as dust
+    pub fn set_dust_threshold(ctx: Context<SetDustThreshold>, dust_threshold: u64) -> Result<()> {
+        set_dust_threshold::handler(ctx, dust_threshold)
+    }
+
+    // Cover a pool's written-off dust from its accrued protocol fees
+    pub fn reconcile_dust(ctx: Context<ReconcileDust>) -> Result<()> {
+        reconcile_dust::handler(ctx)
+    }
+
+    // Withdraw protocol tokens from an expired fee lock
+    pub fn unlock_fee_tokens(ctx: Context<UnlockFeeTokens>) -> Result<()> {
+        unlock_fee_tokens::handler(ctx)
+    }
+
+    // Escrow LP shares for an external protocol until `unlock_at`
+    pub fn lock_shares(
+        ctx: Context<LockShares>,
+        shares: u64,
+        locker: Pubkey,
+        unlock_at: i64,
+    ) -> Result<()> {
+        lock_shares::handler(ctx, shares, locker, unlock_at)
+    }
+
+    // Release escrowed LP shares, by the locker or by the owner after expiry
+    pub fn unlock_shares(ctx: Context<UnlockShares>) -> Result<()> {
+        unlock_shares::handler(ctx)
+    }
+
+    // With `deduct_fee` the borrower receives `loan_amount - fee` and repays only `loan_amount`
+    // The borrower callback is `callback_data` sent to the first remaining account
+    pub fn execute_flash_loan<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteFlashLoan<'info>>,
         loan_amount: u64,
         loan_expiration: i64,
+        deduct_fee: bool,
+        callback_data: Vec<u8>,
     ) -> Result<()> {
-        let loan = &ctx.accounts.loan_vault;
-        let borrower = &ctx.accounts.borrower_account;
+        execute_flash_loan::handler(ctx, loan_amount, loan_expiration, deduct_fee, callback_data)
+    }
 
-        // Ensure loan does not exceed maximum allowed amount
-        require!(loan_amount <= MAX_LOAN_AMOUNT, FlashLoanError::LoanAmountTooLarge);
+    // Flash loan calling `on_flash_loan(amount, fee, data)` on a receiver program
+    pub fn execute_flash_loan_receiver<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteFlashLoan<'info>>,
+        loan_amount: u64,
+        loan_expiration: i64,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        execute_flash_loan_receiver::handler(ctx, loan_amount, loan_expiration, data)
+    }
 
-        // Ensure the loan vault has enough liquidity
-        require!(loan.amount >= loan_amount, FlashLoanError::InsufficientFunds);
+    // Flash loan running a batch of borrower instructions before repayment
+    pub fn execute_flash_loan_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteFlashLoan<'info>>,
+        loan_amount: u64,
+        loan_expiration: i64,
+        instructions: Vec<CallbackInstruction>,
+    ) -> Result<()> {
+        execute_flash_loan_batch::handler(ctx, loan_amount, loan_expiration, instructions)
+    }
 
-        // Ensure the loan has not expired (with grace period)
-        let clock = Clock::get()?;
-        require!(
-            clock.unix_timestamp <= loan_expiration + GRACE_PERIOD,
-            FlashLoanError::LoanExpired
-        );
+    // Borrow up to four mints at once, one leg per pool, settling every leg after a single callback
+    pub fn execute_multi_flash_loan<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteMultiFlashLoan<'info>>,
+        legs: Vec<LoanLeg>,
+        callback_data: Vec<u8>,
+    ) -> Result<()> {
+        execute_multi_flash_loan::handler(ctx, legs, callback_data)
+    }
 
-        // Cooldown check
-        require!(
-            clock.unix_timestamp >= ctx.accounts.loan_state.last_loan_timestamp + LOAN_COOLDOWN,
-            FlashLoanError::CooldownPeriodNotOver
-        );
+    // Multi-mint loan whose fees are paid to the treasury as one transfer in the settlement mint
+    pub fn execute_settled_multi_flash_loan<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteSettledMultiFlashLoan<'info>>,
+        legs: Vec<LoanLeg>,
+        callback_data: Vec<u8>,
+    ) -> Result<()> {
+        execute_settled_multi_flash_loan::handler(ctx, legs, callback_data)
+    }
 
-        // Reentrancy check
-        require!(!ctx.accounts.loan_state.active, FlashLoanError::Reentrancy);
-        ctx.accounts.loan_state.active = true;
+    // Protocol admin only: set the fee settlement mint and its price oracle key
+    pub fn configure_fee_settlement(
+        ctx: Context<ConfigureFeeSettlement>,
+        price_authority: Pubkey,
+        max_price_age: i64,
+    ) -> Result<()> {
+        configure_fee_settlement::handler(ctx, price_authority, max_price_age)
+    }
 
-        //  Transfer loan amount to borrower
-        token::transfer(
-            ctx.accounts.into_transfer_to_borrower_context(),
-            loan_amount,
-        )?;
-
-        //  Execute a Cross-Program Invocation (CPI)
-        // Assuming you're invoking some external program (e.g., a token swap)
-        // Construct the instruction
-        let ix = Instruction {
-            program_id: ctx.accounts.token_program.key(), // Replace with the actual program ID you are calling
-            accounts: vec![
-                AccountMeta::new(ctx.accounts.loan_vault.key(), false),  // Loan vault
-                AccountMeta::new(ctx.accounts.borrower_account.key(), false), // Borrower account
-                // Add other accounts required by the external program
-            ],
-            data: vec![], // Add the actual instruction data for the external program
-        };
-
-        // Execute the CPI instruction
-        invoke(
-            &ix,
-            &[
-                ctx.accounts.loan_vault.to_account_info(),
-                ctx.accounts.borrower_account.to_account_info(),
-                // Add other account_infos as needed
-            ],
-        )?;
-
-        //  Borrower repays loan
-        let fee = calculate_dynamic_fee(loan_amount); // Calculate fee based on loan size
-        let total_repayment = loan_amount + fee;
-
-        // Ensure borrower repays the correct loan amount and fee
-        let repayment_amount = ctx.accounts.borrower_account.amount;
-        require!(repayment_amount == total_repayment, FlashLoanError::IncorrectRepayment);
-
-        token::transfer(
-            ctx.accounts.into_transfer_to_vault_context(),
-            total_repayment,
-        )?;
-
-        // Update loan stats
-        ctx.accounts.loan_stats.update_stats(loan_amount, fee);
-
-        // Update loan state to prevent abuse
-        ctx.accounts.loan_state.active = false;
-        ctx.accounts.loan_state.last_loan_timestamp = clock.unix_timestamp; // Update cooldown
-
-        // Emit loan execution event
-        emit!(FlashLoanExecuted {
-            borrower: *ctx.accounts.borrower.key,
+    // Oracle only: post a pool mint's price in the settlement mint
+    pub fn post_fee_price(ctx: Context<PostFeePrice>, price: u64) -> Result<()> {
+        post_fee_price::handler(ctx, price)
+    }
+
+    // WSOL pools only: lend and settle in native SOL, wrapping and unwrapping on the fly
+    pub fn execute_native_flash_loan<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteNativeFlashLoan<'info>>,
+        loan_amount: u64,
+        callback_data: Vec<u8>,
+    ) -> Result<()> {
+        execute_native_flash_loan::handler(ctx, loan_amount, callback_data)
+    }
+
+    // Create the lamport pool, lending native SOL from a system-owned vault
+    pub fn initialize_sol_pool(ctx: Context<InitializeSolPool>, fee_bps: u64, max_loan_amount: u64) -> Result<()> {
+        initialize_sol_pool::handler(ctx, fee_bps, max_loan_amount)
+    }
+
+    // Add lamports to the lamport pool's vault
+    pub fn fund_sol_pool(ctx: Context<FundSolPool>, amount: u64) -> Result<()> {
+        fund_sol_pool::handler(ctx, amount)
+    }
+
+    // Admin only: withdraw lamports from the lamport pool's vault
+    pub fn withdraw_sol_pool(ctx: Context<WithdrawSolPool>, amount: u64) -> Result<()> {
+        withdraw_sol_pool::handler(ctx, amount)
+    }
+
+    // Allow lamport flash loan callbacks to invoke `program_id`
+    pub fn register_sol_target_program(ctx: Context<RegisterSolTargetProgram>, program_id: Pubkey) -> Result<()> {
+        register_sol_target_program::handler(ctx, program_id)
+    }
+
+    // Lend lamports, checked by the vault's lamport balance delta on return
+    pub fn execute_sol_flash_loan<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteSolFlashLoan<'info>>,
+        loan_amount: u64,
+        callback_data: Vec<u8>,
+    ) -> Result<()> {
+        execute_sol_flash_loan::handler(ctx, loan_amount, callback_data)
+    }
+
+    // Callback-free loan needing only the pool, the borrower's token account and
+    // the amount; the advanced flows remain for real strategies
+    pub fn simple_flash_loan(ctx: Context<SimpleFlashLoan>, loan_amount: u64) -> Result<()> {
+        simple_flash_loan::handler(ctx, loan_amount)
+    }
+
+    // Loan from one pool netted against a deposit into another pool of the
+    // same mint; only the fee and the deposit are transferred
+    pub fn execute_netted_flash_loan(
+        ctx: Context<ExecuteNettedFlashLoan>,
+        loan_amount: u64,
+        deposit_amount: u64,
+        min_shares_out: u64,
+    ) -> Result<()> {
+        execute_netted_flash_loan::handler(ctx, loan_amount, deposit_amount, min_shares_out)
+    }
+
+    // Split flow: lend `amount`, requiring a matching `flash_repay` later in the transaction
+    pub fn flash_borrow(ctx: Context<FlashBorrow>, amount: u64, client_id: Option<[u8; 16]>) -> Result<()> {
+        flash_borrow::handler(ctx, amount, client_id)
+    }
+
+    // Split flow: repay `amount` plus the pool fee
+    pub fn flash_repay<'info>(ctx: Context<'_, '_, 'info, 'info, FlashRepay<'info>>, amount: u64) -> Result<()> {
+        flash_repay::handler(ctx, amount)
+    }
+
+    // Same loan flow, but stats go to an accumulator instead of the shared LoanStats
+    pub fn execute_flash_loan_fast<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteFlashLoan<'info>>,
+        loan_amount: u64,
+        loan_expiration: i64,
+        callback_data: Vec<u8>,
+    ) -> Result<()> {
+        execute_flash_loan_fast::handler(ctx, loan_amount, loan_expiration, callback_data)
+    }
+
+    // Flash loan at a fee quoted and signed off-chain by the pool's quote signer
+    pub fn execute_quoted_loan<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteFlashLoan<'info>>,
+        loan_amount: u64,
+        fee: u64,
+        expires_at: i64,
+        callback_data: Vec<u8>,
+    ) -> Result<()> {
+        execute_quoted_loan::handler(ctx, loan_amount, fee, expires_at, callback_data)
+    }
+
+    // Configure the key whose quotes `execute_quoted_loan` accepts
+    pub fn set_quote_signer(ctx: Context<SetQuoteSigner>, quote_signer: Pubkey) -> Result<()> {
+        set_quote_signer::handler(ctx, quote_signer)
+    }
+
+    // Clear a loan state left active; protocol admin at once, anyone after the timeout
+    pub fn force_unlock(ctx: Context<ForceUnlock>) -> Result<()> {
+        force_unlock::handler(ctx)
+    }
+
+    // Open a pool's fast path stats accumulator, bound to `loan_stats`
+    pub fn init_stats_accumulator(ctx: Context<InitStatsAccumulator>) -> Result<()> {
+        init_stats_accumulator::handler(ctx)
+    }
+
+    // Crank: fold accumulated fast path stats into LoanStats
+    pub fn sync_stats(ctx: Context<SyncStats>) -> Result<()> {
+        sync_stats::handler(ctx)
+    }
+
+    // Borrow a share of the live vault balance, expressed in basis points
+    pub fn flash_borrow_pct<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteFlashLoan<'info>>,
+        loan_bps: u16,
+        loan_expiration: i64,
+        callback_data: Vec<u8>,
+    ) -> Result<()> {
+        flash_borrow_pct::handler(ctx, loan_bps, loan_expiration, callback_data)
+    }
+
+    // Prepay the fees for a bundle of loans at the rate locked for `max_loan_size`
+    pub fn purchase_borrow_credits(
+        ctx: Context<PurchaseBorrowCredits>,
+        loan_count: u64,
+        max_loan_size: u64,
+    ) -> Result<()> {
+        purchase_borrow_credits::handler(ctx, loan_count, max_loan_size)
+    }
+
+    // Hand the remaining credits over to another borrower
+    pub fn transfer_borrow_credits(ctx: Context<TransferBorrowCredits>, new_owner: Pubkey) -> Result<()> {
+        transfer_borrow_credits::handler(ctx, new_owner)
+    }
+
+    // Authorize an ephemeral key to flash borrow on the borrower's behalf
+    pub fn create_session_key(
+        ctx: Context<CreateSessionKey>,
+        session_signer: Pubkey,
+        max_loan_amount: u64,
+        expires_at: i64,
+    ) -> Result<()> {
+        create_session_key::handler(ctx, session_signer, max_loan_amount, expires_at)
+    }
+
+    // Revoke a session key and reclaim its rent
+    pub fn revoke_session_key(ctx: Context<RevokeSessionKey>) -> Result<()> {
+        revoke_session_key::handler(ctx)
+    }
+
+    // Pre-authorize one loan for any keeper to execute within a window
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_pre_auth(
+        ctx: Context<CreatePreAuth>,
+        nonce: u64,
+        loan_amount: u64,
+        max_fee: u64,
+        callback_program: Pubkey,
+        callback_data_hash: [u8; 32],
+        window_start: i64,
+        window_end: i64,
+    ) -> Result<()> {
+        create_pre_auth::handler(
+            ctx,
+            nonce,
             loan_amount,
-            fee,
-        });
+            max_fee,
+            callback_program,
+            callback_data_hash,
+            window_start,
+            window_end,
+        )
+    }
 
-        Ok(())
+    // Cancel a pre-authorized loan and reclaim its rent
+    pub fn cancel_pre_auth(ctx: Context<CancelPreAuth>) -> Result<()> {
+        cancel_pre_auth::handler(ctx)
     }
-}
 
-// Context for flash loan
-#[derive(Accounts)]
-pub struct ExecuteFlashLoan<'info> {
-    #[account(mut)]
-    pub loan_vault: Account<'info, TokenAccount>,   // Flash loan pool
-    #[account(mut)]
-    pub borrower_account: Account<'info, TokenAccount>,  // Borrower’s token account
-    #[account(mut)]
-    pub borrower: Signer<'info>,                   // Borrower signing the transaction
-    pub token_program: Program<'info, Token>,      // Token program
-    #[account(mut)]
-    pub loan_stats: Account<'info, LoanStats>,     // Loan statistics account
-    #[account(mut)]
-    pub loan_state: Account<'info, LoanState>,     // Reentrancy check and state
-    pub token_mint: Account<'info, Mint>,          // Token mint for multi-token support
-}
+    // Keeper: execute a borrower's pre-authorized loan within its window
+    pub fn execute_pre_authorized_loan<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteFlashLoan<'info>>,
+        callback_data: Vec<u8>,
+    ) -> Result<()> {
+        execute_pre_authorized_loan::handler(ctx, callback_data)
+    }
 
-// Loan statistics account
-#[account]
-pub struct LoanStats {
-    pub total_loans: u64,
-    pub total_fees_collected: u64,
-    pub total_loan_count: u64,      // Number of loans taken
-    pub average_loan_size: u64,     // Average loan size
-}
+    // View: largest amount that would currently pass the borrow checks
+    pub fn max_borrowable(ctx: Context<MaxBorrowable>) -> Result<u64> {
+        max_borrowable::handler(ctx)
+    }
 
-impl LoanStats {
-    pub fn update_stats(&mut self, loan_amount: u64, fee: u64) {
-        self.total_loans += loan_amount;
-        self.total_fees_collected += fee;
-        self.total_loan_count += 1;
-        self.average_loan_size = self.total_loans / self.total_loan_count;
+    // Crank: re-quote the pool's public rate card after a config or curve change
+    pub fn refresh_rate_card(ctx: Context<RefreshRateCard>) -> Result<()> {
+        refresh_rate_card::handler(ctx)
     }
-}
 
-// Loan state for reentrancy guard and cooldown tracking
-#[account]
-pub struct LoanState {
-    pub active: bool,               // Whether a loan is currently active
-    pub last_loan_timestamp: i64,   // Track when the last loan was issued
-}
+    // View: fee charged for a loan of `loan_amount`
+    pub fn view_fee(ctx: Context<ViewFee>, loan_amount: u64) -> Result<u64> {
+        view_fee::handler(ctx, loan_amount)
+    }
 
-impl<'info> ExecuteFlashLoan<'info> {
-    // Context for transferring tokens to borrower
-    pub fn into_transfer_to_borrower_context(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
-        let cpi_accounts = Transfer {
-            from: self.loan_vault.to_account_info().clone(),
-            to: self.borrower_account.to_account_info().clone(),
-            authority: self.loan_vault.to_account_info().clone(),
-        };
-        CpiContext::new(self.token_program.to_account_info().clone(), cpi_accounts)
-    }
-
-    // Context for borrower repaying the loan
-    pub fn into_transfer_to_vault_context(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
-        let cpi_accounts = Transfer {
-            from: self.borrower_account.to_account_info().clone(),
-            to: self.loan_vault.to_account_info().clone(),
-            authority: self.borrower.to_account_info().clone(),
-        };
-        CpiContext::new(self.token_program.to_account_info().clone(), cpi_accounts)
+    // View: share of the pool currently lent out
+    pub fn view_utilization(ctx: Context<ViewUtilization>) -> Result<PoolUtilization> {
+        view_utilization::handler(ctx)
     }
-}
 
-// Calculate a dynamic fee based on loan amount
-fn calculate_dynamic_fee(loan_amount: u64) -> u64 {
-    if loan_amount > 500_000 {
-        (loan_amount * 25) / 10000 // 0.25% for large loans
-    } else if loan_amount > 100_000 {
-        (loan_amount * 50) / 10000 // 0.5% for medium loans
-    } else {
-        (loan_amount * 100) / 10000 // 1% for small loans
+    // View: an LP's shares that count in a governance snapshot, excluding fresh deposits
+    pub fn view_snapshot_shares(ctx: Context<ViewSnapshotShares>) -> Result<u64> {
+        view_snapshot_shares::handler(ctx)
     }
-}
 
-// Error handling
-#[error_code]
-pub enum FlashLoanError {
-    #[msg("Insufficient funds in the loan vault.")]
-    InsufficientFunds,
-    #[msg("Borrower did not repay the loan.")]
-    LoanNotRepaid,
-    #[msg("Invalid fee structure.")]
-    InvalidFeeStructure,
-    #[msg("Reentrancy detected.")]
-    Reentrancy,
-    #[msg("Flash loan expired.")]
-    LoanExpired,
-    #[msg("Loan amount exceeds the maximum allowed.")]
-    LoanAmountTooLarge,
-    #[msg("Borrower repaid an incorrect amount.")]
-    IncorrectRepayment,
-    #[msg("Cooldown period not over.")]
-    CooldownPeriodNotOver,
-}
+    // Statement of an LP's deposits, withdrawals, fee income and current value,
+    // returned and emitted for custodians
+    pub fn generate_lp_statement(ctx: Context<GenerateLpStatement>) -> Result<LpStatement> {
+        generate_lp_statement::handler(ctx)
+    }
+
+    // View: pool balances as UI amounts, with accrued interest for interest-bearing mints
+    pub fn view_ui_amounts(ctx: Context<ViewUiAmounts>, ui_amount: Option<String>) -> Result<PoolUiAmounts> {
+        view_ui_amounts::handler(ctx, ui_amount)
+    }
+
+    // View: current borrowing limits for the given loan state
+    pub fn view_borrower_limits(ctx: Context<MaxBorrowable>) -> Result<BorrowerLimits> {
+        view_borrower_limits::handler(ctx)
+    }
 
-// Flash loan executed event
-#[event]
-pub struct FlashLoanExecuted {
-    pub borrower: Pubkey,
-    pub loan_amount: u64,
-    pub fee: u64,
+    // View: why a loan of `loan_amount` would fail, with alternate pools for the mint
+    pub fn route_borrow(ctx: Context<RouteBorrow>, loan_amount: u64) -> Result<RoutingHint> {
+        route_borrow::handler(ctx, loan_amount)
+    }
+
+    // Protocol admin only: list `pool` as an alternate source of the mint's liquidity
+    pub fn list_directory_pool(ctx: Context<ListDirectoryPool>, pool: Pubkey) -> Result<()> {
+        list_directory_pool::handler(ctx, pool)
+    }
+
+    // Protocol admin: designate the pool clients should default to for its mint
+    pub fn set_canonical_pool(ctx: Context<SetCanonicalPool>) -> Result<()> {
+        set_canonical_pool::handler(ctx)
+    }
+
+    // Protocol admin: record the receiver interface version `program_id` implements
+    pub fn register_integrator(
+        ctx: Context<RegisterIntegrator>,
+        program_id: Pubkey,
+        min_interface_version: u16,
+    ) -> Result<()> {
+        register_integrator::handler(ctx, program_id, min_interface_version)
+    }
+
+    // Protocol admin: route a share of a fee mint's collected protocol fees to
+    // buying back and burning the protocol token
+    pub fn configure_buyback(
+        ctx: Context<ConfigureBuyback>,
+        collect_share_bps: u16,
+        max_slippage_bps: u16,
+        keeper_reward_bps: u16,
+        min_interval: i64,
+    ) -> Result<()> {
+        configure_buyback::handler(ctx, collect_share_bps, max_slippage_bps, keeper_reward_bps, min_interval)
+    }
+
+    // Keeper: swap buyback fees into the protocol token through the configured AMM and burn it
+    pub fn execute_buyback<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteBuyback<'info>>,
+        amount_in: u64,
+        min_amount_out: u64,
+        swap_data: Vec<u8>,
+    ) -> Result<()> {
+        execute_buyback::handler(ctx, amount_in, min_amount_out, swap_data)
+    }
+
+    // Protocol admin: create or retune the keeper registry paying bonded keepers per crank
+    pub fn configure_keeper_registry(
+        ctx: Context<ConfigureKeeperRegistry>,
+        min_stake: u64,
+        reward_per_crank: u64,
+        unbonding_period: i64,
+    ) -> Result<()> {
+        configure_keeper_registry::handler(ctx, min_stake, reward_per_crank, unbonding_period)
+    }
+
+    // Bond stake tokens to register as, or top up, a keeper
+    pub fn register_keeper(ctx: Context<RegisterKeeper>, amount: u64) -> Result<()> {
+        register_keeper::handler(ctx, amount)
+    }
+
+    // Keeper: stop earning crank rewards and start unbonding
+    pub fn begin_keeper_exit(ctx: Context<BeginKeeperExit>) -> Result<()> {
+        begin_keeper_exit::handler(ctx)
+    }
+
+    // Keeper: take back the bond once unbonded
+    pub fn withdraw_keeper_stake(ctx: Context<WithdrawKeeperStake>) -> Result<()> {
+        withdraw_keeper_stake::handler(ctx)
+    }
+
+    // Keeper: claim credited crank rewards
+    pub fn claim_keeper_rewards(ctx: Context<ClaimKeeperRewards>) -> Result<()> {
+        claim_keeper_rewards::handler(ctx)
+    }
+
+    // Protocol admin: slash a keeper's bond for misbehavior proven by `evidence`
+    pub fn slash_keeper(ctx: Context<SlashKeeper>, amount: u64, evidence: [u8; 32]) -> Result<()> {
+        slash_keeper::handler(ctx, amount, evidence)
+    }
+
+    // Protocol admin: create or retune a pool's loan insurance fund
+    pub fn configure_insurance_fund(
+        ctx: Context<ConfigureInsuranceFund>,
+        premium_bps: u16,
+        max_claim_amount: u64,
+    ) -> Result<()> {
+        configure_insurance_fund::handler(ctx, premium_bps, max_claim_amount)
+    }
+
+    // Open the policy a borrower's insured split loans are recorded on
+    pub fn open_insurance_policy(ctx: Context<OpenInsurancePolicy>) -> Result<()> {
+        open_insurance_policy::handler(ctx)
+    }
+
+    // Insured borrower: claim a refund on a loan whose settlement a protocol bug broke
+    pub fn file_insurance_claim(
+        ctx: Context<FileInsuranceClaim>,
+        loan_slot: u64,
+        requested_amount: u64,
+        evidence: [u8; 32],
+    ) -> Result<()> {
+        file_insurance_claim::handler(ctx, loan_slot, requested_amount, evidence)
+    }
+
+    // Protocol admin: pay an insurance claim out of the fund, or reject it with zero
+    pub fn adjudicate_insurance_claim(ctx: Context<AdjudicateInsuranceClaim>, approved_amount: u64) -> Result<()> {
+        adjudicate_insurance_claim::handler(ctx, approved_amount)
+    }
+
+    // Protocol admin: bound the parameters pool curators may set
+    pub fn set_param_envelope(ctx: Context<SetParamEnvelope>, envelope: ParamEnvelope) -> Result<()> {
+        set_param_envelope::handler(ctx, envelope)
+    }
+
+    // Protocol admin: set one of a pool's parameters live and freeze or unfreeze it
+    pub fn override_pool_param(
+        ctx: Context<OverridePoolParam>,
+        param: PoolParam,
+        value: u64,
+        freeze: bool,
+    ) -> Result<()> {
+        override_pool_param::handler(ctx, param, value, freeze)
+    }
 }
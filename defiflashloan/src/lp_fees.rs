@@ -0,0 +1,32 @@
+// Time-weighted LP fees: a pool that opted in through
+// `initialize_lp_fee_accumulator` weighs each LP position by the shares
+// minted to it, so every deposit and withdrawal path settles the position
+// against the pool's accumulator before its shares change
+use anchor_lang::prelude::*;
+
+use crate::errors::FlashLoanError;
+use crate::state::{LpFeeAccumulator, LpPosition, Pool};
+
+// Settle `position` and move its weight by the `minted` and `burned` shares
+// at `now`. Shares redeem only through the position they weigh, so shares
+// moved to another wallet cannot keep earning for the sender once redeemed
+pub fn track_position_shares(
+    pool: &Pool,
+    accumulator: Option<&mut Account<LpFeeAccumulator>>,
+    position: &mut LpPosition,
+    minted: u64,
+    burned: u64,
+    now: i64,
+) -> Result<()> {
+    if !pool.time_weighted_lp_fees {
+        return Ok(());
+    }
+    let accumulator = accumulator.ok_or(FlashLoanError::LpFeeAccumulatorMissing)?;
+    require!(burned <= position.fee_shares, FlashLoanError::SharesNotTracked);
+
+    accumulator.accrue(now);
+    accumulator.settle(position, now);
+    position.fee_shares = position.fee_shares + minted - burned;
+    accumulator.total_shares = accumulator.total_shares + minted - burned;
+    Ok(())
+}
@@ -0,0 +1,182 @@
+// PDA derivation helpers shared by the program and off-chain Rust clients
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+
+// Protocol config singleton PDA
+pub fn derive_protocol_config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PROTOCOL_CONFIG_SEED], &crate::ID)
+}
+
+// Pool PDA for `token_mint` created in registry slot `index`, which also signs
+// for the pool vault
+pub fn derive_pool(token_mint: &Pubkey, index: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POOL_SEED, token_mint.as_ref(), &index.to_le_bytes()], &crate::ID)
+}
+
+// Risk parameter PDA for `pool`
+pub fn derive_pool_config(pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POOL_CONFIG_SEED, pool.as_ref()], &crate::ID)
+}
+
+// Parameter change log PDA for `pool`
+pub fn derive_config_history(pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONFIG_HISTORY_SEED, pool.as_ref()], &crate::ID)
+}
+
+// Callback target allowlist PDA for `pool`
+pub fn derive_program_registry(pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PROGRAM_REGISTRY_SEED, pool.as_ref()], &crate::ID)
+}
+
+// LP share mint PDA for `pool`
+pub fn derive_share_mint(pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SHARE_MINT_SEED, pool.as_ref()], &crate::ID)
+}
+
+// LP position PDA for `owner` in `pool`
+pub fn derive_lp_position(pool: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[LP_POSITION_SEED, pool.as_ref(), owner.as_ref()], &crate::ID)
+}
+
+// Share lock PDA for `owner`'s shares in `pool` escrowed for `locker`
+pub fn derive_share_lock(pool: &Pubkey, owner: &Pubkey, locker: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SHARE_LOCK_SEED, pool.as_ref(), owner.as_ref(), locker.as_ref()],
+        &crate::ID,
+    )
+}
+
+// Escrow token account PDA holding the shares of `share_lock`
+pub fn derive_share_escrow(share_lock: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SHARE_ESCROW_SEED, share_lock.as_ref()], &crate::ID)
+}
+
+// Token account PDA holding the protocol-owned shares of `pool`
+pub fn derive_protocol_liquidity(pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PROTOCOL_LIQUIDITY_SEED, pool.as_ref()], &crate::ID)
+}
+
+// Escrow token account PDA that escrowed loans from `pool` are disbursed into
+pub fn derive_loan_escrow(pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[LOAN_ESCROW_SEED, pool.as_ref()], &crate::ID)
+}
+
+// Delegate PDA of `callback_program` approved over an escrowed loan
+pub fn derive_flash_delegate(callback_program: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FLASH_DELEGATE_SEED], callback_program)
+}
+
+// Delegate PDA of `callback_program` from its known `bump`, without the bump search
+pub fn flash_delegate_address(callback_program: &Pubkey, bump: u8) -> Result<Pubkey> {
+    Pubkey::create_program_address(&[FLASH_DELEGATE_SEED, &[bump]], callback_program)
+        .map_err(|_| error!(FlashLoanError::InvalidCallbackDelegate))
+}
+
+// Fee locker PDA streaming the protocol fees of `pool`
+pub fn derive_fee_locker(pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FEE_LOCKER_SEED, pool.as_ref()], &crate::ID)
+}
+
+// Token account PDA holding the protocol tokens locked in `fee_locker`
+pub fn derive_fee_locker_vault(fee_locker: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FEE_LOCKER_VAULT_SEED, fee_locker.as_ref()], &crate::ID)
+}
+
+// Token account PDA holding the fees streamed to `fee_locker`
+pub fn derive_fee_locker_rewards(fee_locker: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FEE_LOCKER_REWARDS_SEED, fee_locker.as_ref()], &crate::ID)
+}
+
+// Position PDA for `owner`'s lock in `fee_locker`
+pub fn derive_locker_position(fee_locker: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[LOCKER_POSITION_SEED, fee_locker.as_ref(), owner.as_ref()],
+        &crate::ID,
+    )
+}
+
+// Receipt PDA for the split loan open on `loan_state`
+pub fn derive_loan_receipt(loan_state: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[LOAN_RECEIPT_SEED, loan_state.as_ref()], &crate::ID)
+}
+
+// Devnet faucet PDA that test mints hand their mint authority to
+pub fn derive_faucet_authority() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FAUCET_SEED], &crate::ID)
+}
+
+// Lamport pool singleton PDA
+pub fn derive_sol_pool() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SOL_POOL_SEED], &crate::ID)
+}
+
+// System-owned vault PDA holding the lamport pool's liquidity
+pub fn derive_sol_vault() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SOL_VAULT_SEED], &crate::ID)
+}
+
+// Directory of alternate pools for `token_mint`
+pub fn derive_pool_directory(token_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POOL_DIRECTORY_SEED, token_mint.as_ref()], &crate::ID)
+}
+
+// Dead-man's-switch recovery PDA for `pool`
+pub fn derive_admin_recovery(pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ADMIN_RECOVERY_SEED, pool.as_ref()], &crate::ID)
+}
+
+// Reputation record of `borrower`
+pub fn derive_borrower_reputation(borrower: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REPUTATION_SEED, borrower.as_ref()], &crate::ID)
+}
+
+// Global pool registry singleton PDA
+pub fn derive_registry() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REGISTRY_SEED], &crate::ID)
+}
+
+// Registry page PDA for page `index`
+pub fn derive_registry_page(index: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REGISTRY_PAGE_SEED, &index.to_le_bytes()], &crate::ID)
+}
+
+// Fee settlement singleton PDA
+pub fn derive_fee_settlement() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FEE_SETTLEMENT_SEED], &crate::ID)
+}
+
+// Settlement price PDA for `token_mint`
+pub fn derive_fee_price(token_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FEE_PRICE_SEED, token_mint.as_ref()], &crate::ID)
+}
+
+// Rate card PDA for `pool`
+pub fn derive_rate_card(pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RATE_CARD_SEED, pool.as_ref()], &crate::ID)
+}
+
+// Loan state PDA of `borrower` on `pool`
+pub fn derive_loan_state(pool: &Pubkey, borrower: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[LOAN_STATE_SEED, pool.as_ref(), borrower.as_ref()], &crate::ID)
+}
+
+// Loan state PDA of `borrower` on `pool` from its known `bump`, without the bump search
+pub fn loan_state_address(pool: &Pubkey, borrower: &Pubkey, bump: u8) -> Result<Pubkey> {
+    Pubkey::create_program_address(&[LOAN_STATE_SEED, pool.as_ref(), borrower.as_ref(), &[bump]], &crate::ID)
+        .map_err(|_| error!(FlashLoanError::LoanStateMismatch))
+}
+
+// Fast path stats accumulator PDA for `pool`
+pub fn derive_stats_accumulator(pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[STATS_ACCUMULATOR_SEED, pool.as_ref()], &crate::ID)
+}
+
+// Session key PDA for `session_signer` acting on behalf of `borrower`
+pub fn derive_session_key(borrower: &Pubkey, session_signer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SESSION_KEY_SEED, borrower.as_ref(), session_signer.as_ref()],
+        &crate::ID,
+    )
+}
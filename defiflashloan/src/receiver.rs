@@ -0,0 +1,72 @@
+// Standard flash loan receiver interface for `execute_flash_loan_receiver`
+//
+// The receiver program is called with `on_flash_loan(amount: u64, fee: u64, data: Vec<u8>)`:
+// the 8-byte Anchor discriminator of `global:on_flash_loan` followed by the
+// borsh-encoded arguments, so an Anchor program can implement it as an
+// ordinary instruction. Accounts, in order:
+//   0. pool              read-only   Pool the loan was drawn from
+//   1. loan_holder       writable    Borrower token account, or the pool's loan escrow
+//                                    for escrowed loans; must hold `amount + fee` on return
+//   2. borrower          signer      Authority of `borrower_account`
+//   3. token_mint        read-only
+//   4. token_program     read-only
+//   5.. every remaining account passed after the receiver program, as given
+//
+// Receiver programs are registered with `register_integrator` and the oldest
+// interface version they implement. A receiver loan is refused, with a
+// ReceiverInterfaceMismatch event, unless that version lies within
+// MIN_RECEIVER_INTERFACE_VERSION..=RECEIVER_INTERFACE_VERSION.
+//
+// Any callback, receiver or not, may report success by setting
+// ON_FLASH_LOAN_SUCCESS as its return data; settlement events carry the result.
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::AccountMeta;
+use anchor_lang::solana_program::program::get_return_data;
+
+pub const ON_FLASH_LOAN_DISCRIMINATOR: [u8; 8] = [195, 212, 238, 236, 80, 204, 73, 167];
+pub const RECEIVER_INTERFACE_VERSION: u16 = 1; // Interface receivers are called with
+pub const MIN_RECEIVER_INTERFACE_VERSION: u16 = 1; // Oldest interface still called compatibly; raise on a breaking change
+pub const ON_FLASH_LOAN_SUCCESS: [u8; 8] = [233, 111, 8, 85, 115, 252, 246, 25]; // First 8 bytes of sha256("on_flash_loan:success")
+
+// Arguments of `on_flash_loan`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct OnFlashLoanArgs {
+    pub amount: u64,                // Principal lent to the borrower
+    pub fee: u64,                   // Owed on top of the principal, including any Token-2022 transfer fee
+    pub data: Vec<u8>,              // Opaque bytes passed through from the borrower
+}
+
+impl OnFlashLoanArgs {
+    // Instruction data for the receiver call
+    pub fn to_ix_data(&self) -> Result<Vec<u8>> {
+        let mut data = ON_FLASH_LOAN_DISCRIMINATOR.to_vec();
+        data.extend(self.try_to_vec()?);
+        Ok(data)
+    }
+}
+
+// Metas for the fixed receiver accounts, in the published order
+pub fn receiver_account_metas(
+    pool: Pubkey,
+    loan_holder: Pubkey,
+    borrower: Pubkey,
+    token_mint: Pubkey,
+    token_program: Pubkey,
+) -> [AccountMeta; 5] {
+    [
+        AccountMeta::new_readonly(pool, false),
+        AccountMeta::new(loan_holder, false),
+        AccountMeta::new_readonly(borrower, true),
+        AccountMeta::new_readonly(token_mint, false),
+        AccountMeta::new_readonly(token_program, false),
+    ]
+}
+
+// Whether `callback_program` left ON_FLASH_LOAN_SUCCESS as the return data;
+// read right after the callback, before repayment CPIs run
+pub fn callback_reported_success(callback_program: &Pubkey) -> bool {
+    matches!(
+        get_return_data(),
+        Some((program, data)) if program == *callback_program && data == ON_FLASH_LOAN_SUCCESS
+    )
+}
@@ -0,0 +1,28 @@
+// LP share exchange rate math; assets are the vault balance net of protocol fees
+
+use crate::constants::SHARE_PRICE_SCALE;
+
+// Shares minted for depositing `amount` into a vault holding `total_assets`
+pub fn shares_for_deposit(amount: u64, total_assets: u64, total_shares: u64) -> u64 {
+    if total_shares == 0 || total_assets == 0 {
+        return amount; // First deposit sets a 1:1 exchange rate
+    }
+    (amount as u128 * total_shares as u128 / total_assets as u128) as u64
+}
+
+// Underlying returned for burning `shares`
+pub fn assets_for_shares(shares: u64, total_assets: u64, total_shares: u64) -> u64 {
+    if total_shares == 0 {
+        return 0;
+    }
+    (shares as u128 * total_assets as u128 / total_shares as u128) as u64
+}
+
+// Underlying per share scaled by SHARE_PRICE_SCALE, at the 1:1 first deposit
+// rate while there are no shares
+pub fn share_price(total_assets: u64, total_shares: u64) -> u64 {
+    if total_shares == 0 {
+        return SHARE_PRICE_SCALE as u64;
+    }
+    (total_assets as u128 * SHARE_PRICE_SCALE / total_shares as u128).min(u64::MAX as u128) as u64
+}
@@ -0,0 +1,52 @@
+// Ed25519 signature checks against instructions loaded from the Instructions sysvar
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{ed25519_program, instruction::Instruction};
+
+use crate::errors::FlashLoanError;
+
+const SIGNATURE_OFFSETS_START: usize = 2; // After the signature count and padding bytes
+const SIGNATURE_OFFSETS_LEN: usize = 14; // Seven little-endian u16 offsets
+const PUBKEY_LEN: usize = 32;
+const CURRENT_INSTRUCTION: u16 = u16::MAX; // Offsets refer to the Ed25519 instruction itself
+
+// Check that `ix` is an Ed25519 program instruction verifying `message` signed by `signer`
+pub fn verify_ed25519_ix(ix: &Instruction, signer: &Pubkey, message: &[u8]) -> Result<()> {
+    require_keys_eq!(ix.program_id, ed25519_program::ID, FlashLoanError::InvalidQuoteSignature);
+
+    let data = &ix.data;
+    require!(
+        data.len() >= SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_LEN && data[0] == 1,
+        FlashLoanError::InvalidQuoteSignature
+    );
+
+    let offsets = &data[SIGNATURE_OFFSETS_START..SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_LEN];
+    let read_u16 = |i: usize| u16::from_le_bytes([offsets[i], offsets[i + 1]]);
+    let signature_ix_index = read_u16(2);
+    let public_key_offset = read_u16(4) as usize;
+    let public_key_ix_index = read_u16(6);
+    let message_offset = read_u16(8) as usize;
+    let message_size = read_u16(10) as usize;
+    let message_ix_index = read_u16(12);
+
+    // Signature, key and message must all be carried by the Ed25519 instruction
+    require!(
+        signature_ix_index == CURRENT_INSTRUCTION
+            && public_key_ix_index == CURRENT_INSTRUCTION
+            && message_ix_index == CURRENT_INSTRUCTION,
+        FlashLoanError::InvalidQuoteSignature
+    );
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + PUBKEY_LEN)
+        .ok_or(FlashLoanError::InvalidQuoteSignature)?;
+    let signed_message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(FlashLoanError::InvalidQuoteSignature)?;
+
+    require!(
+        public_key == signer.as_ref() && signed_message == message,
+        FlashLoanError::InvalidQuoteSignature
+    );
+
+    Ok(())
+}
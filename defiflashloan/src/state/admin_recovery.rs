@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+// Dead-man's switch for a pool admin: once the admin stops sending heartbeats
+// for `inactivity_epochs`, the recovery key may start a public, cancellable
+// takeover of the admin role
+#[account]
+pub struct AdminRecovery {
+    pub pool: Pubkey,               // Pool the switch guards
+    pub recovery_key: Pubkey,       // Key allowed to assume the admin role
+    pub inactivity_epochs: u64,     // Epochs of admin silence before recovery may start
+    pub last_heartbeat_epoch: u64,  // Epoch of the admin's last heartbeat
+    pub recovery_started_at: i64,   // Unix timestamp recovery started, zero when none is pending
+    pub bump: u8,
+}
+
+impl AdminRecovery {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 8 + 1;
+
+    // Whether the admin has been silent long enough for recovery to start
+    pub fn is_admin_inactive(&self, epoch: u64) -> bool {
+        epoch >= self.last_heartbeat_epoch.saturating_add(self.inactivity_epochs)
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.recovery_started_at != 0
+    }
+}
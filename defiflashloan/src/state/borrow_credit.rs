@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+// Prepaid borrow credits consumed by the fee engine before charging cash fees
+#[account]
+pub struct BorrowCredit {
+    pub owner: Pubkey,              // Borrower allowed to consume the credits
+    pub pool: Pubkey,               // Pool the credits were bought from and are redeemed at
+    pub remaining_loans: u64,       // Loans left on this credit
+    pub max_loan_size: u64,         // Largest loan a credit can cover
+    pub fee_per_loan: u64,          // Fee locked in at purchase time
+}
+
+impl BorrowCredit {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 8;
+}
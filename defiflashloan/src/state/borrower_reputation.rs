@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+
+// On-chain track record of a borrower, shared by every pool and updated on
+// each settled loan
+#[account]
+pub struct BorrowerReputation {
+    pub borrower: Pubkey,           // Borrower the record belongs to
+    pub successful_loans: u64,      // Loans settled in full
+    pub total_volume: u64,          // Principal borrowed across those loans
+    pub created_at: i64,            // Unix timestamp the record was opened
+    pub incidents: u32,             // Incidents flagged by the protocol admin
+    pub staked_lamports: u64,       // Lamports locked in this account backing the score
+    pub stake_unlocks_at: i64,      // Unix timestamp the stake can be withdrawn
+    pub bump: u8,
+}
+
+impl BorrowerReputation {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 4 + 8 + 8 + 1;
+
+    // Score built from loan count, record age in days and the order of
+    // magnitude of volume, each capped, then weighted by the locked stake up
+    // to REPUTATION_FULL_STAKE so fresh keys cannot farm it for free; any
+    // incident zeroes it
+    pub fn score(&self, now: i64) -> u64 {
+        if self.incidents > 0 {
+            return 0;
+        }
+        let age_days = ((now - self.created_at).max(0) / 86_400) as u64;
+        let volume_bits = u64::from(u64::BITS - self.total_volume.leading_zeros());
+
+        let raw = self.successful_loans.min(REPUTATION_MAX_LOAN_POINTS)
+            + age_days.min(REPUTATION_MAX_AGE_POINTS)
+            + volume_bits * REPUTATION_POINTS_PER_VOLUME_BIT;
+        let stake = self.staked_lamports.min(REPUTATION_FULL_STAKE);
+        (raw as u128 * stake as u128 / REPUTATION_FULL_STAKE as u128) as u64
+    }
+
+    // Count a settled loan of `loan_amount`; loans below `min_loan_amount`
+    // are ignored so dust loans cannot build a record
+    pub fn record_loan(&mut self, loan_amount: u64, min_loan_amount: u64) {
+        if loan_amount < min_loan_amount {
+            return;
+        }
+        self.successful_loans += 1;
+        self.total_volume = self.total_volume.saturating_add(loan_amount);
+    }
+}
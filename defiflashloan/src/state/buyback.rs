@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::fees::calculate_protocol_fee;
+use crate::state::FeePrice;
+
+// Protocol fees in one mint set aside to be swapped into the protocol token
+// and burned; filled by `collect_protocol_fees` and spent by `execute_buyback`
+#[account]
+pub struct Buyback {
+    pub fee_mint: Pubkey,           // Mint the protocol fees are collected in
+    pub protocol_mint: Pubkey,      // Protocol token bought and burned
+    pub fee_vault: Pubkey,          // Buyback-owned fee mint account the collected share lands in
+    pub protocol_vault: Pubkey,     // Buyback-owned protocol token account swaps pay into
+    pub amm_program: Pubkey,        // Only program a buyback may swap through
+    pub collect_share_bps: u16,     // Share of each protocol fee collection routed here, zero to pause
+    pub max_slippage_bps: u16,      // Largest shortfall accepted against the oracle price
+    pub keeper_reward_bps: u16,     // Share of each buyback paid to the keeper running it
+    pub min_interval: i64,          // Minimum seconds between buybacks
+    pub last_buyback_at: i64,       // Unix timestamp of the last buyback
+    pub total_spent: u64,           // Fee mint swapped so far
+    pub total_burned: u64,          // Protocol tokens burned so far
+    pub bump: u8,
+}
+
+impl Buyback {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 32 + 2 + 2 + 2 + 8 + 8 + 8 + 8 + 1;
+
+    // Seeds used to sign for the buyback vaults as the buyback PDA
+    pub fn seeds(&self) -> [&[u8]; 3] {
+        [BUYBACK_SEED, self.fee_mint.as_ref(), std::slice::from_ref(&self.bump)]
+    }
+
+    // Part of a `collected` protocol fee sweep routed to the buyback
+    pub fn collect_share(&self, collected: u64) -> u64 {
+        calculate_protocol_fee(collected, self.collect_share_bps)
+    }
+
+    pub fn keeper_reward(&self, amount: u64) -> u64 {
+        calculate_protocol_fee(amount, self.keeper_reward_bps)
+    }
+
+    // Fewest protocol tokens a swap of `amount_in` may return: the oracle
+    // value of the fee mint in the protocol token, less the allowed slippage
+    pub fn min_amount_out(
+        &self,
+        amount_in: u64,
+        fee_price: &FeePrice,
+        protocol_price: &FeePrice,
+        now: i64,
+        max_price_age: i64,
+    ) -> Result<u64> {
+        // Both legs are priced in the settlement mint; rounding the fair
+        // output down keeps the floor from overshooting it
+        let value = fee_price.convert(amount_in, now, max_price_age)?;
+        protocol_price.require_fresh(now, max_price_age)?;
+        let fair_out = value as u128 * FEE_PRICE_SCALE / protocol_price.price as u128;
+        let floor = fair_out * (BPS_DENOMINATOR - self.max_slippage_bps as u64) as u128 / BPS_DENOMINATOR as u128;
+        u64::try_from(floor).map_err(|_| error!(FlashLoanError::BuybackSlippageExceeded))
+    }
+}
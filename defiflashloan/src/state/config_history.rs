@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+
+// Pool parameter touched by a recorded change
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ConfigField {
+    #[default]
+    FeeBps,
+    MaxLoanAmount,
+    LoanCooldown,
+    Timelock,
+    ReserveFactorBps,
+    PauseFlags,
+    TrustedMinScore,
+    TrustedMaxLoanAmount,
+    TrustedFeeBps,
+    FeeCurveKink,
+    FeeCurveMinMultiplier,
+    FeeCurveMaxMultiplier,
+    FeeTierCount,
+    FeeTierMinLoanAmount(u8),       // Threshold of the tier at this index
+    FeeTierFeeBps(u8),              // Base fee of the tier at this index
+    ReputationMinLoanAmount,
+    MaxInvokeDepth,
+    SameSlotSurchargeBps,
+    QuoteSigner,                    // Values are `key_fingerprint`s of the signer keys
+    MaxConcurrentBorrowers,
+    ExperimentFeeBps,
+    ExperimentShareBps,
+    EmergencyWithdrawDelay,
+    ProbationLoanAmount,
+    DurableNoncePolicy,
+    FeeTiersInUsd,
+    FrozenParams,                   // Values are `PoolConfig::frozen_params` bitmasks
+}
+
+// First 8 bytes of `key`, little-endian, so a key change fits a ConfigChange
+pub fn key_fingerprint(key: &Pubkey) -> u64 {
+    let mut prefix = [0u8; 8];
+    prefix.copy_from_slice(&key.as_ref()[..8]);
+    u64::from_le_bytes(prefix)
+}
+
+// One recorded parameter change
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct ConfigChange {
+    pub field: ConfigField,
+    pub old_value: u64,             // Signed parameters are stored as their two's complement bits
+    pub new_value: u64,
+    pub authority: Pubkey,          // Key that made or queued the change
+    pub slot: u64,                  // Slot the change took effect
+}
+
+impl ConfigChange {
+    pub const LEN: usize = (1 + 1) + 8 + 8 + 32 + 8;
+}
+
+// Ring of the most recent parameter changes for a pool
+#[account]
+pub struct ConfigHistory {
+    pub pool: Pubkey,               // Pool whose changes are recorded
+    pub total_changes: u64,         // Changes ever recorded; the next one goes to total_changes % N
+    pub entries: [ConfigChange; CONFIG_HISTORY_LEN],
+    pub bump: u8,
+}
+
+impl ConfigHistory {
+    pub const LEN: usize = 8 + 32 + 8 + ConfigChange::LEN * CONFIG_HISTORY_LEN + 1;
+
+    // Append a change, overwriting the oldest entry once the ring is full
+    // No-op changes are not recorded
+    pub fn record(&mut self, field: ConfigField, old_value: u64, new_value: u64, authority: Pubkey, slot: u64) {
+        if old_value == new_value {
+            return;
+        }
+        let index = (self.total_changes % CONFIG_HISTORY_LEN as u64) as usize;
+        self.entries[index] = ConfigChange {
+            field,
+            old_value,
+            new_value,
+            authority,
+            slot,
+        };
+        self.total_changes += 1;
+    }
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::constants::*;
+use crate::state::PoolConfig;
+
+// Loans settled in one bucket of a fee experiment
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct FeeBucketStats {
+    pub loan_count: u64,
+    pub total_volume: u64,          // Principal lent
+    pub total_fees: u64,            // Fees charged, surcharges included
+}
+
+impl FeeBucketStats {
+    pub const LEN: usize = 8 + 8 + 8;
+}
+
+// Per-bucket results of the pool's A/B fee experiment, reset whenever the
+// experiment parameters applied to the pool change
+#[account]
+pub struct FeeExperimentStats {
+    pub pool: Pubkey,               // Pool running the experiment
+    pub variant_fee_bps: u64,       // Variant base fee these stats were collected under
+    pub variant_share_bps: u64,     // Variant bucket size these stats were collected under
+    pub started_at: i64,            // Unix timestamp of the first loan under these parameters
+    pub buckets: [FeeBucketStats; FEE_EXPERIMENT_BUCKETS], // Control, then variant
+    pub bump: u8,
+}
+
+impl FeeExperimentStats {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + FeeBucketStats::LEN * FEE_EXPERIMENT_BUCKETS + 1;
+
+    // Start over when the pool's experiment parameters no longer match
+    pub fn sync(&mut self, config: &PoolConfig, now: i64) {
+        if self.variant_fee_bps != config.experiment_fee_bps || self.variant_share_bps != config.experiment_share_bps {
+            self.variant_fee_bps = config.experiment_fee_bps;
+            self.variant_share_bps = config.experiment_share_bps;
+            self.started_at = now;
+            self.buckets = [FeeBucketStats::default(); FEE_EXPERIMENT_BUCKETS];
+        }
+    }
+
+    pub fn record(&mut self, bucket: usize, loan_amount: u64, fee: u64) {
+        let stats = &mut self.buckets[bucket];
+        stats.loan_count += 1;
+        stats.total_volume += loan_amount;
+        stats.total_fees += fee;
+    }
+}
+
+// Bucket `borrower` falls in on `pool`: the first 8 bytes of a hash of both
+// keys, little-endian, put the borrower in the variant bucket when their
+// residue mod BPS_DENOMINATOR is under `variant_share_bps`
+pub fn fee_experiment_bucket(pool: &Pubkey, borrower: &Pubkey, variant_share_bps: u64) -> usize {
+    let hash = hashv(&[pool.as_ref(), borrower.as_ref()]);
+    let mut prefix = [0u8; 8];
+    prefix.copy_from_slice(&hash.as_ref()[..8]);
+    if u64::from_le_bytes(prefix) % BPS_DENOMINATOR < variant_share_bps {
+        FEE_EXPERIMENT_VARIANT
+    } else {
+        FEE_EXPERIMENT_CONTROL
+    }
+}
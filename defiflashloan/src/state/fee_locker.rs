@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+
+// Streams a pool's protocol fees to holders of locked protocol tokens
+// Each epoch the accrued fees move into `reward_vault` and are split pro rata
+// by lock weight through the `reward_per_weight` accumulator
+#[account]
+pub struct FeeLocker {
+    pub pool: Pubkey,               // Pool whose protocol fees are streamed
+    pub lock_mint: Pubkey,          // Protocol token mint that is locked for weight
+    pub lock_vault: Pubkey,         // Holds the locked protocol tokens
+    pub reward_vault: Pubkey,       // Holds streamed fees, in the pool mint, until claimed
+    pub epoch_duration: i64,        // Minimum seconds between fee streams
+    pub last_stream_at: i64,        // Unix timestamp of the last fee stream
+    pub epoch: u64,                 // Number of fee streams so far
+    pub total_weight: u64,          // Sum of the weights of all positions
+    pub reward_per_weight: u128,    // Fees per unit of weight, scaled by REWARD_PRECISION
+    pub bump: u8,
+}
+
+impl FeeLocker {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 16 + 1;
+
+    // Seeds used to sign for the locker vaults as the locker PDA
+    pub fn seeds(&self) -> [&[u8]; 3] {
+        [FEE_LOCKER_SEED, self.pool.as_ref(), std::slice::from_ref(&self.bump)]
+    }
+
+    // Spread `amount` of fees over the current weight
+    pub fn stream(&mut self, amount: u64) {
+        if self.total_weight > 0 {
+            self.reward_per_weight += amount as u128 * REWARD_PRECISION / self.total_weight as u128;
+        }
+    }
+}
+
+// A user's locked protocol tokens and their unclaimed fee rewards
+#[account]
+pub struct LockerPosition {
+    pub owner: Pubkey,              // User that locked the tokens
+    pub fee_locker: Pubkey,         // Locker the position belongs to
+    pub amount: u64,                // Protocol tokens locked
+    pub weight: u64,                // Share weight, scaled by the lock duration
+    pub unlock_at: i64,             // Unix timestamp after which the tokens can be withdrawn
+    pub reward_debt: u128,          // `weight * reward_per_weight` already accounted for
+    pub pending_rewards: u64,       // Rewards settled but not yet claimed
+    pub bump: u8,
+}
+
+impl LockerPosition {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 8 + 16 + 8 + 1;
+
+    // Move rewards earned since the last settlement into `pending_rewards`
+    pub fn settle(&mut self, reward_per_weight: u128) {
+        let earned = self.weight as u128 * reward_per_weight / REWARD_PRECISION;
+        self.pending_rewards += (earned - self.reward_debt) as u64;
+        self.reward_debt = earned;
+    }
+
+    // Reset the weight for the tokens still locked at `now`; must follow `settle`
+    pub fn reweight(&mut self, now: i64, reward_per_weight: u128) {
+        self.weight = lock_weight(self.amount, self.unlock_at - now);
+        self.reward_debt = self.weight as u128 * reward_per_weight / REWARD_PRECISION;
+    }
+}
+
+// Weight earned by locking `amount` for `duration` seconds: the full amount at
+// the maximum lock duration, proportionally less for shorter locks
+pub fn lock_weight(amount: u64, duration: i64) -> u64 {
+    let duration = duration.clamp(0, MAX_FEE_LOCK_DURATION) as u128;
+    (amount as u128 * duration / MAX_FEE_LOCK_DURATION as u128) as u64
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+
+// Protocol-wide setup for paying multi-mint loan fees in one settlement mint
+#[account]
+pub struct FeeSettlement {
+    pub settlement_mint: Pubkey,    // Mint aggregate fees are paid in, e.g. USDC
+    pub price_authority: Pubkey,    // Oracle key posting FeePrice updates
+    pub max_price_age: i64,         // Seconds after which a posted price is stale
+    pub bump: u8,
+}
+
+impl FeeSettlement {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 1;
+}
+
+// Oracle price of a pool mint in the settlement mint
+#[account]
+pub struct FeePrice {
+    pub token_mint: Pubkey,         // Mint being priced
+    pub price: u64,                 // Settlement base units per base unit, scaled by FEE_PRICE_SCALE
+    pub updated_at: i64,            // Unix timestamp of the last update
+    pub bump: u8,
+}
+
+impl FeePrice {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 1;
+
+    // Value of `amount` base units in the settlement mint, rounded up so the
+    // conversion never undercharges
+    pub fn convert(&self, amount: u64, now: i64, max_price_age: i64) -> Result<u64> {
+        self.require_fresh(now, max_price_age)?;
+        let value = (amount as u128 * self.price as u128).div_ceil(FEE_PRICE_SCALE);
+        u64::try_from(value).map_err(|_| error!(FlashLoanError::StaleFeePrice))
+    }
+
+    pub fn require_fresh(&self, now: i64, max_price_age: i64) -> Result<()> {
+        require!(now - self.updated_at <= max_price_age, FlashLoanError::StaleFeePrice);
+        Ok(())
+    }
+
+    // Value of `amount` base units in USD cents, reading the settlement mint
+    // as a dollar stablecoin with `decimals`; None once the price is stale
+    pub fn usd_cents(&self, amount: u64, decimals: u8, now: i64, max_price_age: i64) -> Option<u64> {
+        if now - self.updated_at > max_price_age {
+            return None;
+        }
+        let value = (amount as u128)
+            .checked_mul(self.price as u128)?
+            .checked_mul(100)?
+            / FEE_PRICE_SCALE
+            / 10u128.checked_pow(decimals as u32)?;
+        u64::try_from(value).ok()
+    }
+}
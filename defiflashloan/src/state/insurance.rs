@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::fees::calculate_protocol_fee;
+
+// A pool's loan insurance fund; its PDA owns the vault premiums are paid into
+// and refunds come out of
+#[account]
+pub struct InsuranceFund {
+    pub pool: Pubkey,               // Pool whose split loans the fund insures
+    pub vault: Pubkey,              // Fund-owned pool mint account holding the premiums
+    pub premium_bps: u16,           // Premium on an insured loan, charged on the principal
+    pub max_claim_amount: u64,      // Largest refund a single claim may ask for
+    pub total_premiums: u64,        // Premiums paid in so far
+    pub total_paid_out: u64,        // Refunds paid on approved claims
+    pub pending_claims: u32,        // Claims filed and not yet adjudicated
+    pub bump: u8,
+}
+
+impl InsuranceFund {
+    pub const LEN: usize = 8 + 32 + 32 + 2 + 8 + 8 + 8 + 4 + 1;
+
+    // Seeds used to sign for the vault as the fund PDA
+    pub fn seeds(&self) -> [&[u8]; 3] {
+        [INSURANCE_FUND_SEED, self.pool.as_ref(), std::slice::from_ref(&self.bump)]
+    }
+
+    pub fn premium(&self, loan_amount: u64) -> u64 {
+        calculate_protocol_fee(loan_amount, self.premium_bps)
+    }
+}
+
+// One insured loan a claim can be filed against
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct InsuredLoan {
+    pub slot: u64,                  // Slot the loan was borrowed in, naming it in claims
+    pub loan_amount: u64,           // Principal, the most a claim against it may ask for
+}
+
+impl InsuredLoan {
+    pub const LEN: usize = 8 + 8;
+}
+
+// A borrower's cover with a pool's insurance fund, opened with
+// `open_insurance_policy` and passed to `flash_borrow` to insure a loan
+#[account]
+pub struct InsurancePolicy {
+    pub insurance_fund: Pubkey,
+    pub borrower: Pubkey,
+    pub insured_loans: u64,         // Loans insured so far
+    pub premiums_paid: u64,         // Premiums paid so far
+    pub recent_loans: [InsuredLoan; RECENT_INSURED_LOANS], // Ring of the loans still open to claims
+    pub next_loan: u8,              // Ring slot the next insured loan is written to
+    pub bump: u8,
+}
+
+impl InsurancePolicy {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + InsuredLoan::LEN * RECENT_INSURED_LOANS + 1 + 1;
+
+    pub fn record_loan(&mut self, slot: u64, loan_amount: u64, premium: u64) {
+        self.recent_loans[self.next_loan as usize % RECENT_INSURED_LOANS] = InsuredLoan { slot, loan_amount };
+        self.next_loan = ((self.next_loan as usize + 1) % RECENT_INSURED_LOANS) as u8;
+        self.insured_loans += 1;
+        self.premiums_paid += premium;
+    }
+
+    // The insured loan borrowed in `slot`, while it is still in the ring
+    pub fn insured_loan(&self, slot: u64) -> Option<InsuredLoan> {
+        self.recent_loans
+            .iter()
+            .copied()
+            .find(|loan| loan.loan_amount > 0 && loan.slot == slot)
+    }
+}
+
+// Where an insurance claim stands
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClaimStatus {
+    Pending,
+    Paid,
+    Rejected,
+}
+
+// A refund claim against one insured loan, adjudicated by the protocol admin
+#[account]
+pub struct InsuranceClaim {
+    pub insurance_fund: Pubkey,
+    pub claimant: Pubkey,           // Insured borrower, receives the refund
+    pub loan_slot: u64,             // Insured loan the claim is about
+    pub requested_amount: u64,
+    pub paid_amount: u64,           // Refund paid, zero unless approved
+    pub evidence: [u8; 32],         // Hash of the failed settlement's evidence
+    pub status: ClaimStatus,
+    pub filed_at: i64,
+    pub bump: u8,
+}
+
+impl InsuranceClaim {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 8 + 32 + 1 + 8 + 1;
+}
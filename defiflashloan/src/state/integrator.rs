@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::receiver::{MIN_RECEIVER_INTERFACE_VERSION, RECEIVER_INTERFACE_VERSION};
+
+// A receiver program registered with the protocol and the `on_flash_loan`
+// interface version it was built against
+#[account]
+pub struct Integrator {
+    pub program_id: Pubkey,         // Receiver program
+    pub min_interface_version: u16, // Oldest receiver interface version the program implements
+    pub registered_at: i64,         // Unix timestamp of the latest registration
+    pub bump: u8,
+}
+
+impl Integrator {
+    pub const LEN: usize = 8 + 32 + 2 + 8 + 1;
+
+    // Whether the receiver interface this program calls with still matches
+    // what the integrator implements
+    pub fn is_compatible(&self) -> bool {
+        self.min_interface_version >= MIN_RECEIVER_INTERFACE_VERSION
+            && self.min_interface_version <= RECEIVER_INTERFACE_VERSION
+    }
+}
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+
+// Protocol-wide keeper registry; its PDA owns the stake vault, which holds
+// every keeper's bond, and the reward vault cranks are paid out of
+#[account]
+pub struct KeeperRegistry {
+    pub stake_mint: Pubkey,         // Token keepers bond and are paid in
+    pub stake_vault: Pubkey,        // Registry-owned account holding the bonds
+    pub reward_vault: Pubkey,       // Registry-owned account crank rewards are paid from; funded by transfer
+    pub min_stake: u64,             // Bond a keeper needs for its cranks to be paid
+    pub reward_per_crank: u64,      // Credited to a keeper for each paid crank
+    pub unbonding_period: i64,      // Seconds between asking to exit and withdrawing the bond
+    pub total_staked: u64,          // Sum of every keeper's bond
+    pub rewards_owed: u64,          // Credited to keepers and not yet claimed
+    pub total_slashed: u64,         // Bonds moved to the reward vault by slashing
+    pub keeper_count: u32,          // Registered keepers
+    pub bump: u8,
+}
+
+impl KeeperRegistry {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 4 + 1;
+
+    // Seeds used to sign for the stake and reward vaults as the registry PDA
+    pub fn seeds(&self) -> [&[u8]; 2] {
+        [KEEPER_REGISTRY_SEED, std::slice::from_ref(&self.bump)]
+    }
+}
+
+// One registered keeper's bond and earnings, keyed by its authority
+#[account]
+pub struct Keeper {
+    pub authority: Pubkey,          // Signs the cranks credited to this keeper
+    pub staked_amount: u64,         // Bond left after any slashing
+    pub pending_rewards: u64,       // Credited and not yet claimed
+    pub total_rewards: u64,         // Credited over the keeper's lifetime
+    pub cranks_run: u64,            // Paid cranks run
+    pub total_slashed: u64,         // Bond taken by slashing
+    pub exit_requested_at: i64,     // Unix timestamp of `begin_keeper_exit`, zero while active
+    pub bump: u8,
+}
+
+impl Keeper {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 1;
+
+    // Whether cranks the keeper runs are paid: bonded at the registry minimum and not exiting
+    pub fn is_active(&self, min_stake: u64) -> bool {
+        self.exit_requested_at == 0 && self.staked_amount >= min_stake
+    }
+}
+
+// Permissionless cranks a registered keeper is paid for
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeeperCrank {
+    SyncStats,                      // Folding deferred stats into LoanStats
+    SweepLoanState,                 // Force unlocking a stale loan state after the timeout
+    StreamProtocolFees,             // Archiving an epoch's protocol fees to the fee locker
+    ApplyPoolConfig,                // Activating a queued pool config change
+    CheckpointLpFees,               // Closing a time-weighted LP fee epoch
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+// Open split loan, created by `flash_borrow` and closed by the matching `flash_repay`
+// The pool parameters in force at borrow time are pinned here, so a config
+// change landing between the two cannot alter the loan's economics
+#[account]
+pub struct LoanReceipt {
+    pub pool: Pubkey,               // Pool the loan was drawn from
+    pub borrower: Pubkey,           // Borrower that must repay; receives the rent back
+    pub token_mint: Pubkey,         // Mint lent out
+    pub amount: u64,                // Principal lent
+    pub fee: u64,                   // Fee fixed at borrow time
+    pub repayment: u64,             // Transfer that nets `amount + fee` in the vault after any transfer fee
+    pub fee_bps: u64,               // Pool base fee in force at borrow time
+    pub max_loan_amount: u64,       // Pool loan cap in force at borrow time
+    pub reserve_factor_bps: u16,    // Protocol fee share in force at borrow time, applied at repay
+    pub client_id: [u8; 16],        // Borrower's idempotency key, zero when none was given
+    pub compute_unit_price: u64,    // Priority fee of the borrow transaction, in micro-lamports per compute unit
+    pub insured: bool,              // Whether the borrower paid for insurance on the loan
+    pub premium: u64,               // Insurance premium paid, zero when uninsured
+    pub bump: u8,
+}
+
+impl LoanReceipt {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 2 + 16 + 8 + 1 + 8 + 1;
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+
+// Loan state for reentrancy guard and cooldown tracking
+#[account]
+pub struct LoanState {
+    pub pool: Pubkey,               // Pool the loan state guards
+    pub borrower: Pubkey,           // Borrower the loan state belongs to
+    pub active: bool,               // Whether a loan is currently active
+    pub last_loan_timestamp: i64,   // Track when the last loan was issued
+    pub last_loan_slot: u64,        // Slot of the last loan
+    pub same_slot_loan_count: u64,  // Loans already taken in `last_loan_slot`
+    pub active_since: i64,          // When `active` was last set, for `force_unlock`
+    pub loan_count: u64,            // Loans the borrower has settled on the pool, zero while on probation
+    pub recent_client_ids: [[u8; 16]; RECENT_CLIENT_IDS], // Ring of the last `flash_borrow` client ids
+    pub next_client_id: u8,         // Ring slot the next client id is written to
+    pub loan_day: i64,              // UTC day, in days since the epoch, `day_loan_count` counts
+    pub day_loan_count: u32,        // Public goods loans taken on `loan_day`
+    pub bump: u8,
+}
+
+impl LoanState {
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 8 + 8 + 8 + 8 + 8 + 16 * RECENT_CLIENT_IDS + 1 + 8 + 4 + 1;
+
+    // Whether a borrow already landed with `client_id`; the all-zero id is
+    // reserved, since empty ring slots hold it
+    pub fn has_client_id(&self, client_id: &[u8; 16]) -> bool {
+        self.recent_client_ids.contains(client_id)
+    }
+
+    // Count a public goods loan at `now`, or return false once `daily_limit`
+    // loans were already taken that UTC day
+    pub fn record_daily_loan(&mut self, now: i64, daily_limit: u32) -> bool {
+        let day = now.div_euclid(86_400);
+        if self.loan_day != day {
+            self.loan_day = day;
+            self.day_loan_count = 0;
+        }
+        if self.day_loan_count >= daily_limit {
+            return false;
+        }
+        self.day_loan_count += 1;
+        true
+    }
+
+    // Remember `client_id`, overwriting the oldest one kept
+    pub fn record_client_id(&mut self, client_id: [u8; 16]) {
+        self.recent_client_ids[self.next_client_id as usize % RECENT_CLIENT_IDS] = client_id;
+        self.next_client_id = ((self.next_client_id as usize + 1) % RECENT_CLIENT_IDS) as u8;
+    }
+}
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+
+// Loan volume recorded during one hour of the stats window
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct StatsBucket {
+    pub hour: i64,                  // Unix timestamp / 3600 of the hour this bucket covers
+    pub volume: u64,                // Amount lent during the hour
+    pub count: u64,                 // Number of loans during the hour
+}
+
+impl StatsBucket {
+    pub const LEN: usize = 8 + 8 + 8;
+}
+
+// Loan statistics account
+#[account]
+pub struct LoanStats {
+    pub total_loans: u64,
+    pub total_fees_collected: u64,
+    pub total_loan_count: u64,      // Number of loans taken
+    pub ewma_loan_size: u64,        // Exponentially weighted moving average loan size
+    pub window_average_loan_size: u64, // Average loan size over the last 24 hours
+    pub buckets: [StatsBucket; STATS_WINDOW_HOURS], // Hourly ring buffer, indexed by hour
+    pub total_fees_usd_cents: u64,  // Fees priced by the pool's accounting oracle at settlement
+    pub priced_loan_count: u64,     // Split loans whose transaction paid a priority fee
+    pub total_compute_unit_price: u64, // Sum of those loans' compute unit prices, in micro-lamports
+    pub max_compute_unit_price: u64, // Highest compute unit price a split loan paid
+}
+
+impl LoanStats {
+    pub const LEN: usize = 8 + 8 + 8 + 8 + 8 + 8 + StatsBucket::LEN * STATS_WINDOW_HOURS + 8 + 8 + 8 + 8;
+
+    pub fn update_stats(&mut self, loan_amount: u64, fee: u64, now: i64) {
+        self.apply_pending(loan_amount, fee, 1, now);
+    }
+
+    pub fn apply_pending(&mut self, loan_amount: u64, fees: u64, loan_count: u64, now: i64) {
+        if loan_count == 0 {
+            return;
+        }
+        self.total_loans = self.total_loans.saturating_add(loan_amount);
+        self.total_fees_collected = self.total_fees_collected.saturating_add(fees);
+        self.total_loan_count = self.total_loan_count.saturating_add(loan_count);
+
+        // A batch of deferred loans is folded in as a single sample of its average
+        let sample = loan_amount / loan_count;
+        self.ewma_loan_size = if self.total_loan_count == loan_count {
+            sample
+        } else {
+            ewma(self.ewma_loan_size, sample)
+        };
+
+        self.record(loan_amount, loan_count, now);
+    }
+
+    // Record the priority fee a split loan's transaction paid, so curators can
+    // see how hard borrowers bid for inclusion; unpriced loans count only in
+    // `total_loan_count`
+    pub fn record_compute_unit_price(&mut self, compute_unit_price: u64) {
+        if compute_unit_price == 0 {
+            return;
+        }
+        self.priced_loan_count = self.priced_loan_count.saturating_add(1);
+        self.total_compute_unit_price = self.total_compute_unit_price.saturating_add(compute_unit_price);
+        self.max_compute_unit_price = self.max_compute_unit_price.max(compute_unit_price);
+    }
+
+    // Add volume to the current hour's bucket and refresh the windowed average
+    fn record(&mut self, loan_amount: u64, loan_count: u64, now: i64) {
+        let hour = now.div_euclid(3600);
+        let bucket = &mut self.buckets[hour.rem_euclid(STATS_WINDOW_HOURS as i64) as usize];
+        if bucket.hour != hour {
+            *bucket = StatsBucket { hour, ..Default::default() };
+        }
+        bucket.volume = bucket.volume.saturating_add(loan_amount);
+        bucket.count = bucket.count.saturating_add(loan_count);
+
+        let (volume, count) = self
+            .buckets
+            .iter()
+            .filter(|b| hour - b.hour < STATS_WINDOW_HOURS as i64)
+            .fold((0u128, 0u128), |(v, c), b| (v + b.volume as u128, c + b.count as u128));
+        self.window_average_loan_size = if count == 0 { 0 } else { (volume / count) as u64 };
+    }
+}
+
+// Move `current` towards `sample` by EWMA_ALPHA_BPS of the difference
+fn ewma(current: u64, sample: u64) -> u64 {
+    let current = current as i128;
+    let delta = (sample as i128 - current) * EWMA_ALPHA_BPS as i128 / BPS_DENOMINATOR as i128;
+    (current + delta) as u64
+}
@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::state::LpPosition;
+
+// A closed accrual period of a pool's time-weighted LP fees
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct LpFeeEpoch {
+    pub started_at: i64,
+    pub ended_at: i64,
+    pub reward_per_share_second: u128, // Epoch fees over its share-seconds, scaled by SHARE_SECOND_PRECISION
+    pub index_at_end: u128,         // Sum of `reward_per_share_second * duration` over every epoch up to this one
+}
+
+impl LpFeeEpoch {
+    pub const LEN: usize = 8 + 8 + 16 + 16;
+
+    // Cumulative index at `ts`, clamped into the epoch
+    fn index_at(&self, ts: i64) -> u128 {
+        let remaining = self.ended_at - ts.clamp(self.started_at, self.ended_at);
+        self.index_at_end - self.reward_per_share_second * remaining as u128
+    }
+}
+
+// Splits a pool's LP fees by share-seconds instead of by the share balance at
+// the time of each loan. Fees withheld from the share price over an epoch are
+// spread across the share-seconds LP positions held during it, so liquidity
+// present through busy hours earns more than liquidity that arrived later
+#[account]
+pub struct LpFeeAccumulator {
+    pub pool: Pubkey,               // Pool whose LP fees are time-weighted
+    pub epoch_duration: i64,        // Minimum seconds an epoch accrues before it can close
+    pub epoch: u32,                 // Index of the open epoch
+    pub epoch_started_at: i64,      // Unix timestamp the open epoch began at
+    pub total_shares: u64,          // Sum of every position's `fee_shares`
+    pub share_seconds: u128,        // Share-seconds accrued in the open epoch up to `updated_at`
+    pub updated_at: i64,            // Unix timestamp `share_seconds` was last brought up to
+    pub fees_unclaimed: u64,        // Withheld fees assigned to closed epochs and not yet claimed
+    pub epochs: [LpFeeEpoch; LP_FEE_EPOCH_HISTORY], // Closed epochs, epoch `e` at `e % LP_FEE_EPOCH_HISTORY`
+    pub bump: u8,
+}
+
+impl LpFeeAccumulator {
+    pub const LEN: usize = 8 + 32 + 8 + 4 + 8 + 8 + 16 + 8 + 8 + LpFeeEpoch::LEN * LP_FEE_EPOCH_HISTORY + 1;
+
+    // Bring the open epoch's share-seconds up to `now`
+    pub fn accrue(&mut self, now: i64) {
+        self.share_seconds += self.total_shares as u128 * (now - self.updated_at).max(0) as u128;
+        self.updated_at = self.updated_at.max(now);
+    }
+
+    // Close the open epoch at `now`, spreading `fees` over its share-seconds,
+    // and return the amount spread; an epoch no position held shares through
+    // spreads nothing, carrying its fees into the next
+    pub fn close_epoch(&mut self, now: i64, fees: u64) -> u64 {
+        self.accrue(now);
+        let (reward_per_share_second, distributed) = if self.share_seconds > 0 {
+            (fees as u128 * SHARE_SECOND_PRECISION / self.share_seconds, fees)
+        } else {
+            (0, 0)
+        };
+        let index_at_end = self.last_index() + reward_per_share_second * (now - self.epoch_started_at) as u128;
+        self.epochs[self.epoch as usize % LP_FEE_EPOCH_HISTORY] = LpFeeEpoch {
+            started_at: self.epoch_started_at,
+            ended_at: now,
+            reward_per_share_second,
+            index_at_end,
+        };
+        self.epoch += 1;
+        self.epoch_started_at = now;
+        self.share_seconds = 0;
+        self.fees_unclaimed += distributed;
+        distributed
+    }
+
+    // Credit `position` with its fees from every epoch closed since it was
+    // last settled, then bank its share-seconds in the open epoch up to
+    // `now`. A position left unsettled for longer than the history forfeits
+    // the epochs that aged out; the shares weighing them stay in the epoch
+    // totals, so nobody else gains them either
+    pub fn settle(&self, position: &mut LpPosition, now: i64) {
+        let shares = position.fee_shares as u128;
+        if position.fee_epoch < self.epoch {
+            let last_index = self.last_index();
+            let earned = match self.closed_epoch(position.fee_epoch) {
+                Some(epoch) => {
+                    position.fee_share_seconds * epoch.reward_per_share_second
+                        + shares * (last_index - epoch.index_at(position.fee_anchor_ts))
+                }
+                None => {
+                    let oldest = &self.epochs[self.epoch as usize % LP_FEE_EPOCH_HISTORY];
+                    shares * (last_index - oldest.index_at(oldest.started_at))
+                }
+            };
+            position.pending_lp_fees += (earned / SHARE_SECOND_PRECISION) as u64;
+            position.fee_epoch = self.epoch;
+            position.fee_anchor_ts = self.epoch_started_at;
+            position.fee_share_seconds = 0;
+        }
+        position.fee_share_seconds += shares * (now - position.fee_anchor_ts).max(0) as u128;
+        position.fee_anchor_ts = position.fee_anchor_ts.max(now);
+    }
+
+    // Closed epoch `epoch`, while it is still in the history
+    pub fn closed_epoch(&self, epoch: u32) -> Option<&LpFeeEpoch> {
+        (epoch < self.epoch && self.epoch - epoch <= LP_FEE_EPOCH_HISTORY as u32)
+            .then(|| &self.epochs[epoch as usize % LP_FEE_EPOCH_HISTORY])
+    }
+
+    fn last_index(&self) -> u128 {
+        match self.epoch.checked_sub(1) {
+            Some(last) => self.epochs[last as usize % LP_FEE_EPOCH_HISTORY].index_at_end,
+            None => 0,
+        }
+    }
+}
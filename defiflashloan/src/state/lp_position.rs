@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+
+// Liquidity provided to a pool by a single LP
+#[account]
+pub struct LpPosition {
+    pub owner: Pubkey,              // LP that owns the position
+    pub pool: Pubkey,               // Pool the liquidity was provided to
+    pub deposited_amount: u64,      // Principal deposited, net of withdrawals
+    pub last_deposit_slot: u64,     // Slot of the latest deposit, starting its snapshot holding period
+    pub shares: u64,                // Shares minted to the position, less those burned through it
+    pub held_shares: u64,           // Shares of deposits still in their holding period
+    pub total_deposited: u64,       // Underlying ever deposited through the position
+    pub total_withdrawn: u64,       // Underlying ever paid out through the position
+    pub deposit_count: u32,
+    pub withdrawal_count: u32,
+    pub fee_shares: u64,            // Shares weighing the position in time-weighted LP fees
+    pub fee_epoch: u32,             // LP fee epoch `fee_share_seconds` accrued in
+    pub fee_anchor_ts: i64,         // Unix timestamp `fee_share_seconds` was last brought up to
+    pub fee_share_seconds: u128,    // Share-seconds banked in `fee_epoch`
+    pub pending_lp_fees: u64,       // Time-weighted LP fees settled but not yet claimed
+    pub bump: u8,
+}
+
+impl LpPosition {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 4 + 4 + 8 + 4 + 8 + 16 + 8 + 1;
+
+    // Record a deposit of `amount` minting `shares` in `slot`; one landing
+    // while an earlier deposit is still held restarts the holding period for both
+    pub fn record_deposit(&mut self, amount: u64, shares: u64, slot: u64) {
+        self.total_deposited += amount;
+        self.deposit_count += 1;
+        if !self.is_holding(slot) {
+            self.held_shares = 0;
+        }
+        self.held_shares += shares;
+        self.shares += shares;
+        self.last_deposit_slot = slot;
+    }
+
+    // Record `shares` burned by a withdrawal paying out `amount`, drawn from
+    // settled shares first
+    pub fn record_withdrawal(&mut self, amount: u64, shares: u64) {
+        self.total_withdrawn += amount;
+        self.withdrawal_count += 1;
+        self.shares = self.shares.saturating_sub(shares);
+        self.held_shares = self.held_shares.min(self.shares);
+    }
+
+    // Shares a governance snapshot in `slot` may count for an owner holding
+    // `share_balance`: those minted to this position whose deposit is at
+    // least SNAPSHOT_HOLD_SLOTS old. Shares received by transfer never count,
+    // so capital flash-borrowed and deposited cannot swing a vote
+    pub fn snapshot_shares(&self, share_balance: u64, slot: u64) -> u64 {
+        let held = if self.is_holding(slot) { self.held_shares } else { 0 };
+        share_balance.min(self.shares - held)
+    }
+
+    fn is_holding(&self, slot: u64) -> bool {
+        slot < self.last_deposit_slot.saturating_add(SNAPSHOT_HOLD_SLOTS)
+    }
+}
@@ -0,0 +1,59 @@
+pub mod admin_recovery;
+pub mod borrow_credit;
+pub mod borrower_reputation;
+pub mod buyback;
+pub mod config_history;
+pub mod fee_experiment;
+pub mod fee_locker;
+pub mod fee_settlement;
+pub mod insurance;
+pub mod integrator;
+pub mod keeper;
+pub mod loan_receipt;
+pub mod loan_state;
+pub mod loan_stats;
+pub mod lp_fee_accumulator;
+pub mod lp_position;
+pub mod pool;
+pub mod pool_config;
+pub mod pool_directory;
+pub mod pre_auth;
+pub mod program_registry;
+pub mod protocol_config;
+pub mod rate_card;
+pub mod rebalance_route;
+pub mod registry;
+pub mod session_key;
+pub mod share_lock;
+pub mod sol_pool;
+pub mod stats_accumulator;
+
+pub use admin_recovery::*;
+pub use borrow_credit::*;
+pub use borrower_reputation::*;
+pub use buyback::*;
+pub use config_history::*;
+pub use fee_experiment::*;
+pub use fee_locker::*;
+pub use fee_settlement::*;
+pub use insurance::*;
+pub use integrator::*;
+pub use keeper::*;
+pub use loan_receipt::*;
+pub use loan_state::*;
+pub use loan_stats::*;
+pub use lp_fee_accumulator::*;
+pub use lp_position::*;
+pub use pool::*;
+pub use pool_config::*;
+pub use pool_directory::*;
+pub use pre_auth::*;
+pub use program_registry::*;
+pub use protocol_config::*;
+pub use rate_card::*;
+pub use rebalance_route::*;
+pub use registry::*;
+pub use session_key::*;
+pub use share_lock::*;
+pub use sol_pool::*;
+pub use stats_accumulator::*;
@@ -0,0 +1,238 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::fee_forwarding::ForwardedFees;
+use crate::fees::{calculate_protocol_fee, calculate_tiered_fee_bps};
+use crate::shares::share_price;
+use crate::state::FeePrice;
+
+// Base fee for loans of at least `min_loan_amount`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FeeTier {
+    pub min_loan_amount: u64,       // Smallest loan the tier applies to, in raw units or USD cents
+    pub fee_bps: u64,               // Base fee before the utilization curve
+}
+
+impl FeeTier {
+    pub const LEN: usize = 8 + 8;
+}
+
+// Flash loan pool for a single mint; the pool PDA is also the vault authority
+#[account]
+pub struct Pool {
+    pub admin: Pubkey,              // Pool authority, rotated with propose/accept_admin
+    pub token_mint: Pubkey,         // Mint lent out by this pool
+    pub vault: Pubkey,              // Associated token account holding the liquidity
+    pub share_mint: Pubkey,         // LP share mint, authority is the pool PDA
+    pub total_deposits: u64,        // Principal deposited by LPs
+    pub quote_signer: Pubkey,       // Key signing RFQ quotes, default when RFQ is disabled
+    pub reserve_factor_bps: u16,    // Share of each loan fee reserved for the protocol
+    pub protocol_fees_accrued: u64, // Protocol fees held in the vault until collected
+    pub pending_admin: Pubkey,      // Proposed admin, default when no transfer is pending
+    pub pause_flags: u8,            // PAUSE_* bits for the operations currently halted
+    pub open_loan_amount: u64,      // Principal lent and not yet repaid, still counted as LP assets
+    pub throttle_slot: u64,         // Slot in which utilization last crossed the throttle threshold
+    pub creator: Pubkey,            // Signer that created the pool, earns the creator fee share
+    pub creator_fee_bps: u16,       // Share of each loan fee owed to the creator, fixed at creation
+    pub creator_fees_accrued: u64,  // Creator fees held in the vault until collected
+    pub fee_tiers: [FeeTier; MAX_FEE_TIERS], // Size tiers set by `set_fee_tiers`, first `fee_tier_count` used
+    pub fee_tier_count: u8,         // Tiers in use, zero to charge the config's base fee at every size
+    pub escrow_bump: u8,            // Bump of the loan escrow PDA, set by `initialize_loan_escrow`
+    pub event_sequence: u64,        // Sequence of the last loan event, for gap detection
+    pub open_receipts: u32,         // Split loans drawn by `flash_borrow` and not yet repaid, one per borrower
+    pub withdraw_paused_since: i64, // Unix timestamp withdrawals were paused at, zero while they are open
+    pub wind_down_at: i64,          // Unix timestamp `begin_wind_down` ran at, zero while the pool is live
+    pub index: [u8; 8],             // Little-endian registry slot taken at creation, part of the PDA seeds
+    pub accounting_oracle: Pubkey,  // FeePrice reporting fees in USD, default when none
+    pub accounting_decimals: u8,    // Decimals of the dollar stablecoin the oracle prices in
+    pub accounting_max_price_age: i64, // Seconds after which an accounting price goes unreported
+    pub ops_tag: [u8; OPS_TAG_LEN], // Curator's opaque alert routing tag, logged with critical failures
+    pub public_goods_daily_loans: u32, // Fee-free loans per borrower per day, zero for a standard pool
+    pub public_goods_charge: u64,   // Lamports each public goods loan pays the admin against spam
+    pub fee_tiers_in_usd: bool,     // Tier thresholds are USD cents by the accounting price, not raw units
+    pub time_weighted_lp_fees: bool, // LP fees are withheld and split by share-seconds through the LpFeeAccumulator
+    pub lp_fees_withheld: u64,      // Time-weighted LP fees held in the vault until claimed
+    pub rebalanced_out: u64,        // Principal moved to other pools of the mint, still counted as LP assets
+    pub dust_threshold: u64,        // Largest settlement shortfall written off instead of failing, in raw units
+    pub dust_owed: u64,             // Shortfalls written off and not yet reconciled, owed to LPs by the treasury
+    pub total_dust_written_off: u64, // Every shortfall ever written off
+    pub bump: u8,                   // Canonical bump of the pool PDA
+}
+
+impl Pool {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 8 + 32 + 2 + 8 + 32 + 1 + 8 + 8 + 32 + 2 + 8 + FeeTier::LEN * MAX_FEE_TIERS + 1 + 1 + 8 + 4 + 8 + 8 + 8 + 32 + 1 + 8 + OPS_TAG_LEN + 4 + 8 + 1 + 1 + 8 + 8 + 8 + 8 + 8 + 1;
+
+    // Assets that belong to LPs: the vault balance plus principal out on open
+    // loans or rebalanced into other pools and dust the treasury owes,
+    // excluding uncollected protocol and creator fees and the LP fees
+    // withheld for time-weighted distribution
+    pub fn lp_assets(&self, vault_amount: u64) -> u64 {
+        vault_amount
+            .saturating_add(self.open_loan_amount)
+            .saturating_add(self.rebalanced_out)
+            .saturating_add(self.dust_owed)
+            .saturating_sub(self.fees_reserved())
+    }
+
+    // Fees held in the vault that no longer back the share price
+    pub fn fees_reserved(&self) -> u64 {
+        self.protocol_fees_accrued + self.creator_fees_accrued + self.lp_fees_withheld
+    }
+
+    // Underlying per LP share, scaled by SHARE_PRICE_SCALE
+    pub fn share_price(&self, vault_amount: u64, share_supply: u64) -> u64 {
+        share_price(self.lp_assets(vault_amount), share_supply)
+    }
+
+    // Tiers in use, ordered by ascending `min_loan_amount`
+    pub fn fee_tiers(&self) -> &[FeeTier] {
+        &self.fee_tiers[..self.fee_tier_count as usize]
+    }
+
+    // Base fee for a standard loan of `loan_amount`: the largest tier it
+    // reaches, or the config's `fee_bps` when no tiers are set. USD tiers are
+    // compared against the loan's value by the accounting price; a loan with
+    // no fresh price is valued at zero, so it pays the first and dearest tier
+    pub fn base_fee_bps(&self, loan_amount: u64, fee_bps: u64, accounting_price: Option<&FeePrice>, now: i64) -> u64 {
+        let tier_amount = if self.fee_tiers_in_usd {
+            self.usd_cents(accounting_price, loan_amount, now).unwrap_or(0)
+        } else {
+            loan_amount
+        };
+        calculate_tiered_fee_bps(self.fee_tiers(), tier_amount, fee_bps)
+    }
+
+    // Protocol share of a loan fee
+    pub fn protocol_fee(&self, fee: u64) -> u64 {
+        calculate_protocol_fee(fee, self.reserve_factor_bps)
+    }
+
+    // Reserve the protocol's and the creator's shares of a settled loan `fee`;
+    // both come out of the LP share
+    pub fn accrue_fees(&mut self, fee: u64, protocol_fee: u64) {
+        self.accrue_unforwarded_fees(fee, protocol_fee, &ForwardedFees::default());
+    }
+
+    // As `accrue_fees`, less the shares the settlement already paid out. A
+    // time-weighted pool also withholds the LP share from the share price
+    // until `checkpoint_lp_fees` spreads it
+    pub fn accrue_unforwarded_fees(&mut self, fee: u64, protocol_fee: u64, forwarded: &ForwardedFees) {
+        let creator_fee = calculate_protocol_fee(fee, self.creator_fee_bps);
+        self.protocol_fees_accrued += protocol_fee - forwarded.protocol_fee;
+        self.creator_fees_accrued += creator_fee - forwarded.creator_fee;
+        if self.time_weighted_lp_fees {
+            self.lp_fees_withheld += fee.saturating_sub(protocol_fee + creator_fee);
+        }
+    }
+
+    // Check that a settlement raised the vault from `vault_before` to at least
+    // `vault_before + owed`. A shortfall within the dust threshold, such as
+    // transfer fee rounding, is written off to `dust_owed` instead of failing
+    // the loan; `reconcile_dust` later settles it from the protocol's fees
+    pub fn write_off_dust(&mut self, vault_before: u64, vault_after: u64, owed: u64) -> Result<()> {
+        let shortfall = vault_before.saturating_add(owed).saturating_sub(vault_after);
+        require!(shortfall <= self.dust_threshold, FlashLoanError::IncorrectRepayment);
+        self.dust_owed += shortfall;
+        self.total_dust_written_off += shortfall;
+        Ok(())
+    }
+
+    // Whether withdrawals have been paused for at least `delay` seconds
+    pub fn can_emergency_withdraw(&self, now: i64, delay: i64) -> bool {
+        self.is_paused(PAUSE_WITHDRAW) && now >= self.withdraw_paused_since.saturating_add(delay)
+    }
+
+    // Whether `begin_wind_down` has run; irreversible
+    pub fn is_winding_down(&self) -> bool {
+        self.wind_down_at != 0
+    }
+
+    // Whether `set_public_goods_mode` made the pool fee-free
+    pub fn is_public_goods(&self) -> bool {
+        self.public_goods_daily_loans > 0
+    }
+
+    // Whether any of the `flags` operations are paused, counting borrows and
+    // deposits as paused for good once the pool is winding down. A public
+    // goods pool lends only through `flash_borrow`, which enforces its spam
+    // limits, so every other loan path counts as paused
+    pub fn is_paused(&self, flags: u8) -> bool {
+        let public_goods_flags = if self.is_public_goods() { PAUSE_BORROW } else { 0 };
+        (self.halted_flags() | public_goods_flags) & flags != 0
+    }
+
+    // As `is_paused(PAUSE_BORROW)` for `flash_borrow`, which public goods pools lend through
+    pub fn is_split_borrow_paused(&self) -> bool {
+        self.halted_flags() & PAUSE_BORROW != 0
+    }
+
+    // Operations paused by the admin or for good by the wind-down
+    fn halted_flags(&self) -> u8 {
+        let wind_down_flags = if self.is_winding_down() { WIND_DOWN_PAUSED } else { 0 };
+        self.pause_flags | wind_down_flags
+    }
+
+    // Record a loan of `loan_amount` drawn from a vault holding `vault_amount`,
+    // throttling large withdrawals for the rest of `slot` when the open loans
+    // exceed THROTTLE_UTILIZATION_BPS of the pool's liquidity
+    pub fn note_utilization(&mut self, loan_amount: u64, vault_amount: u64, slot: u64) {
+        let open = self.open_loan_amount as u128 + loan_amount as u128;
+        let total = self.open_loan_amount as u128 + vault_amount as u128;
+        if total > 0 && open * BPS_DENOMINATOR as u128 > total * THROTTLE_UTILIZATION_BPS as u128 {
+            self.throttle_slot = slot;
+        }
+    }
+
+    // Whether withdrawals in `slot` are capped, after a large loan or while a split loan is open
+    pub fn is_throttled(&self, slot: u64) -> bool {
+        self.throttle_slot == slot || self.open_loan_amount > 0
+    }
+
+    // Whether a withdrawal of `amount` from a vault holding `vault_amount` must
+    // wait for a later slot
+    pub fn is_withdrawal_throttled(&self, amount: u64, vault_amount: u64, slot: u64) -> bool {
+        self.is_throttled(slot) && amount as u128 * BPS_DENOMINATOR as u128 > vault_amount as u128 * THROTTLED_WITHDRAW_BPS as u128
+    }
+
+    // Log the pool's ops tag, when set, so alert routers can pick up the
+    // failure or incident logged alongside it
+    pub fn log_ops_tag(&self) {
+        if self.ops_tag != [0; OPS_TAG_LEN] {
+            let hex: String = self.ops_tag.iter().map(|byte| format!("{:02x}", byte)).collect();
+            msg!("ops_tag: {}", hex);
+        }
+    }
+
+    // Whether `set_accounting_oracle` has given the pool an accounting price
+    pub fn has_accounting_oracle(&self) -> bool {
+        self.accounting_oracle != Pubkey::default()
+    }
+
+    // Require the accounting price on loans from a pool that reports in USD
+    pub fn require_accounting_price(&self, accounting_price: Option<&FeePrice>) -> Result<()> {
+        require!(
+            !self.has_accounting_oracle() || accounting_price.is_some(),
+            FlashLoanError::AccountingPriceMissing
+        );
+        Ok(())
+    }
+
+    // Value of `amount` in USD cents by the accounting price, or None when
+    // it is missing or stale
+    pub fn usd_cents(&self, accounting_price: Option<&FeePrice>, amount: u64, now: i64) -> Option<u64> {
+        accounting_price?.usd_cents(amount, self.accounting_decimals, now, self.accounting_max_price_age)
+    }
+
+    // Sequence for the next loan or settlement event; consecutive per pool,
+    // starting at 1, so indexers can spot missed events
+    pub fn next_event_sequence(&mut self) -> u64 {
+        self.event_sequence += 1;
+        self.event_sequence
+    }
+
+    // Seeds used to sign for the vault as the pool PDA
+    pub fn seeds(&self) -> [&[u8]; 4] {
+        [POOL_SEED, self.token_mint.as_ref(), self.index.as_ref(), std::slice::from_ref(&self.bump)]
+    }
+}
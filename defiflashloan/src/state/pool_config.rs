@@ -0,0 +1,273 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::state::{fee_experiment_bucket, FeeTier, Pool, PoolParam};
+
+// Parameters the admin can change through `update_pool_config`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct PoolConfigParams {
+    pub fee_bps: u64,
+    pub max_loan_amount: u64,
+    pub loan_cooldown: i64,
+    pub timelock: i64,
+}
+
+impl PoolConfigParams {
+    pub const LEN: usize = 8 + 8 + 8 + 8;
+}
+
+// Change queued behind the timelock, replacing every timelocked parameter at
+// once; admin setters start from `PoolConfig::queued_or_live` so they keep
+// whatever else is already queued
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct PendingPoolConfig {
+    pub params: PoolConfigParams,   // Set by `update_pool_config`
+    pub fee_tiers: [FeeTier; MAX_FEE_TIERS], // Set by `set_fee_tiers`, first `fee_tier_count` used
+    pub fee_tier_count: u8,
+    pub fee_tiers_in_usd: bool,     // Set by `set_fee_tiers` with the tiers
+    pub fee_curve: FeeCurve,        // Set by `set_fee_curve`
+    pub trusted_min_score: u64,     // Set by `set_trusted_tier`, with the two below
+    pub trusted_max_loan_amount: u64,
+    pub trusted_fee_bps: u64,
+    pub reputation_min_loan_amount: u64,
+    pub max_invoke_depth: u8,       // Set by `set_max_invoke_depth`
+    pub same_slot_surcharge_bps: u64, // Set by `set_same_slot_surcharge`
+    pub max_concurrent_borrowers: u32, // Set by `set_max_concurrent_borrowers`
+    pub experiment_fee_bps: u64,    // Set by `set_fee_experiment`
+    pub experiment_share_bps: u64,  // Set by `set_fee_experiment`
+    pub emergency_withdraw_delay: i64, // Set by `set_emergency_withdraw_delay`
+    pub probation_loan_amount: u64, // Set by `set_probation_loan_amount`
+    pub reject_durable_nonce: bool, // Set by `set_durable_nonce_policy`
+}
+
+impl PendingPoolConfig {
+    pub const LEN: usize = PoolConfigParams::LEN + FeeTier::LEN * MAX_FEE_TIERS + 1 + 1 + FeeCurve::LEN + 8 + 8 + 8 + 8 + 1 + 8 + 4 + 8 + 8 + 8 + 8 + 1;
+}
+
+// Kinked fee curve: the base fee is multiplied by `min_multiplier_bps` at zero
+// utilization, rising linearly to 1x at the kink and to `max_multiplier_bps`
+// when the loan takes the whole vault
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeeCurve {
+    pub kink_utilization_bps: u64,  // Utilization at which the base fee applies unscaled
+    pub min_multiplier_bps: u64,    // Base fee multiplier at zero utilization
+    pub max_multiplier_bps: u64,    // Base fee multiplier at full utilization
+}
+
+impl FeeCurve {
+    pub const LEN: usize = 8 + 8 + 8;
+
+    pub const DEFAULT: FeeCurve = FeeCurve {
+        kink_utilization_bps: DEFAULT_KINK_UTILIZATION_BPS,
+        min_multiplier_bps: DEFAULT_MIN_FEE_MULTIPLIER_BPS,
+        max_multiplier_bps: DEFAULT_MAX_FEE_MULTIPLIER_BPS,
+    };
+
+    // Kink inside (0, 100%] and multipliers ordered around 1x
+    pub fn is_valid(&self) -> bool {
+        self.kink_utilization_bps > 0
+            && self.kink_utilization_bps <= BPS_DENOMINATOR
+            && self.min_multiplier_bps <= BPS_DENOMINATOR
+            && self.max_multiplier_bps >= BPS_DENOMINATOR
+            && self.max_multiplier_bps <= MAX_FEE_MULTIPLIER_BPS
+    }
+
+    // Base fee multiplier, in bps, at `utilization_bps`
+    pub fn multiplier_bps(&self, utilization_bps: u64) -> u64 {
+        let utilization_bps = utilization_bps.min(BPS_DENOMINATOR);
+        if utilization_bps <= self.kink_utilization_bps {
+            self.min_multiplier_bps
+                + (BPS_DENOMINATOR - self.min_multiplier_bps) * utilization_bps / self.kink_utilization_bps
+        } else {
+            BPS_DENOMINATOR
+                + (self.max_multiplier_bps - BPS_DENOMINATOR) * (utilization_bps - self.kink_utilization_bps)
+                    / (BPS_DENOMINATOR - self.kink_utilization_bps)
+        }
+    }
+}
+
+// Per-pool risk parameters, created alongside the pool
+#[account]
+pub struct PoolConfig {
+    pub pool: Pubkey,               // Pool these parameters apply to
+    pub fee_bps: u64,               // Base fee, charged unscaled at the fee curve kink
+    pub max_loan_amount: u64,       // Maximum loan amount allowed
+    pub loan_cooldown: i64,         // Cooldown between loans in seconds
+    pub grace_period: i64,          // Grace period for repayment in seconds
+    pub timelock: i64,              // Delay in seconds before queued changes can be applied
+    pub pending: Option<PendingPoolConfig>, // Queued change awaiting `apply_pool_config`
+    pub pending_activates_at: i64,  // Unix timestamp when the queued change can be applied
+    pub pending_queued_by: Pubkey,  // Admin that queued the pending change
+    pub trusted_min_score: u64,     // Reputation score unlocking the trusted tier, zero when disabled
+    pub trusted_max_loan_amount: u64, // Loan cap for trusted borrowers
+    pub trusted_fee_bps: u64,       // Base fee for trusted borrowers
+    pub fee_curve: FeeCurve,        // Utilization scaling applied to the base fee
+    pub reputation_min_loan_amount: u64, // Smallest loan counted towards a borrower's reputation
+    pub max_invoke_depth: u8,       // Deepest stack height a loan may be called from, 1 for top-level only
+    pub same_slot_surcharge_bps: u64, // Extra fee per loan the borrower already took in the same slot
+    pub max_concurrent_borrowers: u32, // Borrowers that may hold an open split loan at once, zero for no cap
+    pub experiment_fee_bps: u64,    // Base fee charged to the experiment's variant bucket
+    pub experiment_share_bps: u64,  // Share of borrowers in the variant bucket, zero when no experiment runs
+    pub emergency_withdraw_delay: i64, // Seconds withdrawals must stay paused before `emergency_withdraw` opens
+    pub probation_loan_amount: u64, // Cap on a borrower's first loan from the pool, zero for no probation
+    pub reject_durable_nonce: bool, // Refuse loans from durable nonce transactions
+    pub frozen_params: u8,          // `PoolParam` bits the protocol admin froze against curator changes
+    pub bump: u8,
+}
+
+impl PoolConfig {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 8 + 8 + (1 + PendingPoolConfig::LEN) + 8 + 32 + 8 + 8 + 8 + FeeCurve::LEN + 8 + 1 + 8 + 4 + 8 + 8 + 8 + 8 + 1 + 1 + 1;
+
+    // Parameters a new pool starts with
+    pub fn init_defaults(&mut self, pool: Pubkey, bump: u8) {
+        self.pool = pool;
+        self.fee_bps = DEFAULT_FEE_BPS;
+        self.max_loan_amount = DEFAULT_MAX_LOAN_AMOUNT;
+        self.loan_cooldown = DEFAULT_LOAN_COOLDOWN;
+        self.grace_period = DEFAULT_GRACE_PERIOD;
+        self.timelock = DEFAULT_CONFIG_TIMELOCK;
+        self.fee_curve = FeeCurve::DEFAULT;
+        self.reputation_min_loan_amount = DEFAULT_REPUTATION_MIN_LOAN_AMOUNT;
+        self.max_invoke_depth = DEFAULT_MAX_INVOKE_DEPTH;
+        self.same_slot_surcharge_bps = DEFAULT_SAME_SLOT_SURCHARGE_BPS;
+        self.emergency_withdraw_delay = DEFAULT_EMERGENCY_WITHDRAW_DELAY;
+        self.bump = bump;
+    }
+
+    // Parameters copied from `source` for a pool created by `clone_pool_config`;
+    // nothing queued carries over and no fee experiment runs, since the new
+    // pool has no stats account for it yet
+    pub fn init_from(&mut self, source: &PoolConfig, pool: Pubkey, bump: u8) {
+        self.pool = pool;
+        self.fee_bps = source.fee_bps;
+        self.max_loan_amount = source.max_loan_amount;
+        self.loan_cooldown = source.loan_cooldown;
+        self.grace_period = source.grace_period;
+        self.timelock = source.timelock;
+        self.trusted_min_score = source.trusted_min_score;
+        self.trusted_max_loan_amount = source.trusted_max_loan_amount;
+        self.trusted_fee_bps = source.trusted_fee_bps;
+        self.fee_curve = source.fee_curve;
+        self.reputation_min_loan_amount = source.reputation_min_loan_amount;
+        self.max_invoke_depth = source.max_invoke_depth;
+        self.same_slot_surcharge_bps = source.same_slot_surcharge_bps;
+        self.max_concurrent_borrowers = source.max_concurrent_borrowers;
+        self.emergency_withdraw_delay = source.emergency_withdraw_delay;
+        self.probation_loan_amount = source.probation_loan_amount;
+        self.reject_durable_nonce = source.reject_durable_nonce;
+        self.bump = bump;
+    }
+
+    // Whether a loan may run at the current stack height
+    pub fn allows_invoke_depth(&self, stack_height: usize) -> bool {
+        stack_height <= self.max_invoke_depth as usize
+    }
+
+    // Whether a loan of `loan_amount` is allowed for a borrower that has taken
+    // `loan_count` loans from the pool; the first must stay within probation
+    pub fn allows_probation(&self, loan_amount: u64, loan_count: u64) -> bool {
+        self.probation_loan_amount == 0 || loan_count > 0 || loan_amount <= self.probation_loan_amount
+    }
+
+    // Whether a borrower with reputation `score` gets the trusted tier
+    pub fn is_trusted(&self, score: u64) -> bool {
+        self.trusted_min_score > 0 && score >= self.trusted_min_score
+    }
+
+    // Fee experiment bucket of `borrower`, or None when no experiment runs
+    pub fn fee_experiment_bucket(&self, borrower: &Pubkey) -> Option<usize> {
+        (self.experiment_share_bps > 0)
+            .then(|| fee_experiment_bucket(&self.pool, borrower, self.experiment_share_bps))
+    }
+
+    // Loan cap and base fee for a trusted or untrusted borrower
+    pub fn limits(&self, trusted: bool) -> (u64, u64) {
+        if trusted {
+            (self.trusted_max_loan_amount, self.trusted_fee_bps)
+        } else {
+            (self.max_loan_amount, self.fee_bps)
+        }
+    }
+
+    // Live values of the curator-set parameters
+    pub fn params(&self) -> PoolConfigParams {
+        PoolConfigParams {
+            fee_bps: self.fee_bps,
+            max_loan_amount: self.max_loan_amount,
+            loan_cooldown: self.loan_cooldown,
+            timelock: self.timelock,
+        }
+    }
+
+    pub fn is_frozen(&self, param: PoolParam) -> bool {
+        self.frozen_params & param.bit() != 0
+    }
+
+    // `params` with every frozen parameter reset to its live value
+    pub fn keep_frozen(&self, mut params: PoolConfigParams) -> PoolConfigParams {
+        let live = self.params();
+        for param in PoolParam::ALL {
+            if self.is_frozen(param) {
+                param.set(&mut params, param.get(&live));
+            }
+        }
+        params
+    }
+
+    // Queued change to build on, or the live parameters when none is queued
+    pub fn queued_or_live(&self, pool: &Pool) -> PendingPoolConfig {
+        self.pending.unwrap_or(PendingPoolConfig {
+            params: self.params(),
+            fee_tiers: pool.fee_tiers,
+            fee_tier_count: pool.fee_tier_count,
+            fee_tiers_in_usd: pool.fee_tiers_in_usd,
+            fee_curve: self.fee_curve,
+            trusted_min_score: self.trusted_min_score,
+            trusted_max_loan_amount: self.trusted_max_loan_amount,
+            trusted_fee_bps: self.trusted_fee_bps,
+            reputation_min_loan_amount: self.reputation_min_loan_amount,
+            max_invoke_depth: self.max_invoke_depth,
+            same_slot_surcharge_bps: self.same_slot_surcharge_bps,
+            max_concurrent_borrowers: self.max_concurrent_borrowers,
+            experiment_fee_bps: self.experiment_fee_bps,
+            experiment_share_bps: self.experiment_share_bps,
+            emergency_withdraw_delay: self.emergency_withdraw_delay,
+            probation_loan_amount: self.probation_loan_amount,
+            reject_durable_nonce: self.reject_durable_nonce,
+        })
+    }
+
+    // Queue `pending` behind the current timelock, replacing any queued
+    // change, and return when it can be applied
+    pub fn queue(&mut self, pending: PendingPoolConfig, queued_by: Pubkey, now: i64) -> i64 {
+        let activates_at = now + self.timelock;
+        self.pending = Some(pending);
+        self.pending_activates_at = activates_at;
+        self.pending_queued_by = queued_by;
+        activates_at
+    }
+
+    // Make queued parameters live; the fee tiers live on the pool
+    pub fn apply(&mut self, pool: &mut Pool, pending: &PendingPoolConfig) {
+        self.fee_bps = pending.params.fee_bps;
+        self.max_loan_amount = pending.params.max_loan_amount;
+        self.loan_cooldown = pending.params.loan_cooldown;
+        self.timelock = pending.params.timelock;
+        self.fee_curve = pending.fee_curve;
+        self.trusted_min_score = pending.trusted_min_score;
+        self.trusted_max_loan_amount = pending.trusted_max_loan_amount;
+        self.trusted_fee_bps = pending.trusted_fee_bps;
+        self.reputation_min_loan_amount = pending.reputation_min_loan_amount;
+        self.max_invoke_depth = pending.max_invoke_depth;
+        self.same_slot_surcharge_bps = pending.same_slot_surcharge_bps;
+        self.max_concurrent_borrowers = pending.max_concurrent_borrowers;
+        self.experiment_fee_bps = pending.experiment_fee_bps;
+        self.experiment_share_bps = pending.experiment_share_bps;
+        self.emergency_withdraw_delay = pending.emergency_withdraw_delay;
+        self.probation_loan_amount = pending.probation_loan_amount;
+        self.reject_durable_nonce = pending.reject_durable_nonce;
+        pool.fee_tiers = pending.fee_tiers;
+        pool.fee_tier_count = pending.fee_tier_count;
+        pool.fee_tiers_in_usd = pending.fee_tiers_in_usd;
+    }
+}
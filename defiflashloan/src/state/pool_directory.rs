@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+
+// Pools lending the same mint, curated by the protocol admin: the canonical
+// pool clients should default to, and alternates suggested to routers when a
+// borrow from one pool would fail
+#[account]
+pub struct PoolDirectory {
+    pub token_mint: Pubkey,         // Mint every listed pool lends
+    pub pools: Vec<Pubkey>,         // Alternate pool addresses, at most MAX_DIRECTORY_POOLS
+    pub canonical: Pubkey,          // This program's pool designated for the mint, default when none is
+    pub bump: u8,
+}
+
+impl PoolDirectory {
+    pub const LEN: usize = 8 + 32 + (4 + 32 * MAX_DIRECTORY_POOLS) + 32 + 1;
+
+    // List `pool` for `token_mint`; listing twice is a no-op
+    pub fn list(&mut self, token_mint: Pubkey, bump: u8, pool: Pubkey) -> Result<()> {
+        self.token_mint = token_mint;
+        self.bump = bump;
+
+        if self.pools.contains(&pool) {
+            return Ok(());
+        }
+        require!(self.pools.len() < MAX_DIRECTORY_POOLS, FlashLoanError::PoolDirectoryFull);
+        self.pools.push(pool);
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+
+// A loan the borrower signed off on in advance, for any keeper to execute
+// within its window while the borrower is offline. The borrower must also
+// approve this PDA as delegate on the borrower token account, since it signs
+// the repayment transfer
+#[account]
+pub struct PreAuth {
+    pub borrower: Pubkey,             // Borrower the loan is taken for
+    pub pool: Pubkey,                 // Pool the loan is drawn from
+    pub nonce: [u8; 8],               // Little-endian nonce chosen by the borrower, part of the PDA seeds
+    pub loan_amount: u64,             // Exact principal of the loan
+    pub max_fee: u64,                 // Most the loan may be charged, so a keeper cannot run it when fees spike
+    pub callback_program: Pubkey,     // Program the callback must start in
+    pub callback_data_hash: [u8; 32], // SHA-256 of the exact callback data
+    pub window_start: i64,            // Unix timestamp the loan may first run at
+    pub window_end: i64,              // Unix timestamp after which it may no longer run
+    pub executed: bool,               // Set once a keeper has run the loan
+    pub bump: u8,
+}
+
+impl PreAuth {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 8 + 32 + 32 + 8 + 8 + 1 + 1;
+
+    // Seeds used to sign the repayment as the pre-authorization PDA
+    pub fn seeds(&self) -> [&[u8]; 5] {
+        [
+            PRE_AUTH_SEED,
+            self.pool.as_ref(),
+            self.borrower.as_ref(),
+            self.nonce.as_ref(),
+            std::slice::from_ref(&self.bump),
+        ]
+    }
+
+    // Check a keeper is running exactly the loan the borrower authorized,
+    // once and within its window
+    pub fn require_executable(&self, pool: Pubkey, now: i64, callback_program: Option<Pubkey>, callback_data: &[u8]) -> Result<()> {
+        require!(!self.executed, FlashLoanError::PreAuthAlreadyExecuted);
+        require_keys_eq!(self.pool, pool, FlashLoanError::PreAuthMismatch);
+        require!(
+            now >= self.window_start && now <= self.window_end,
+            FlashLoanError::PreAuthOutsideWindow
+        );
+        require!(
+            callback_program == Some(self.callback_program),
+            FlashLoanError::PreAuthMismatch
+        );
+        require!(
+            hash(callback_data).to_bytes() == self.callback_data_hash,
+            FlashLoanError::PreAuthMismatch
+        );
+        Ok(())
+    }
+}
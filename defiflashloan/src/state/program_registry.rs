@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::pda::derive_flash_delegate;
+
+// Programs a pool allows flash loan callbacks to invoke
+#[account]
+pub struct ProgramRegistry {
+    pub pool: Pubkey,               // Pool the allowlist applies to
+    pub programs: Vec<Pubkey>,      // Approved callback targets, at most MAX_REGISTERED_PROGRAMS
+    pub delegate_bumps: Vec<u8>,    // Bump of each program's flash delegate PDA, parallel to `programs`
+    pub bump: u8,
+}
+
+impl ProgramRegistry {
+    pub const LEN: usize = 8 + 32 + (4 + 32 * MAX_REGISTERED_PROGRAMS) + (4 + MAX_REGISTERED_PROGRAMS) + 1;
+
+    pub fn is_registered(&self, program: &Pubkey) -> bool {
+        self.programs.contains(program)
+    }
+
+    // Cached flash delegate bump of a registered `program`
+    pub fn delegate_bump(&self, program: &Pubkey) -> Option<u8> {
+        let index = self.programs.iter().position(|registered| registered == program)?;
+        self.delegate_bumps.get(index).copied()
+    }
+
+    // Add `program_id` to the allowlist of `pool`; registering twice is a no-op
+    // The program's flash delegate bump is found once here so loans can use
+    // `create_program_address`
+    pub fn register(&mut self, pool: Pubkey, bump: u8, program_id: Pubkey) -> Result<()> {
+        self.pool = pool;
+        self.bump = bump;
+
+        if self.is_registered(&program_id) {
+            return Ok(());
+        }
+        require!(
+            self.programs.len() < MAX_REGISTERED_PROGRAMS,
+            FlashLoanError::ProgramRegistryFull
+        );
+        self.programs.push(program_id);
+        self.delegate_bumps.push(derive_flash_delegate(&program_id).1);
+        Ok(())
+    }
+
+    // Drop `program_id` and its delegate bump from the allowlist
+    pub fn remove(&mut self, program_id: &Pubkey) -> Result<()> {
+        let index = self
+            .programs
+            .iter()
+            .position(|program| program == program_id)
+            .ok_or(FlashLoanError::CallbackProgramNotRegistered)?;
+        self.programs.swap_remove(index);
+        if index < self.delegate_bumps.len() {
+            self.delegate_bumps.swap_remove(index);
+        }
+        Ok(())
+    }
+}
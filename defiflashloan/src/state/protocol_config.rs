@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+use crate::state::{ConfigField, PoolConfigParams};
+
+// Bounds the protocol admin sets on every pool's curator-set parameters
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParamEnvelope {
+    pub max_fee_bps: u64,           // Highest base fee a curator may set
+    pub max_loan_amount: u64,       // Highest loan cap a curator may set
+    pub min_loan_cooldown: i64,     // Shortest loan cooldown a curator may set
+    pub min_timelock: i64,          // Shortest config timelock a curator may set
+}
+
+impl ParamEnvelope {
+    pub const LEN: usize = 8 + 8 + 8 + 8;
+
+    // Envelope a new protocol starts with, admitting any valid parameters
+    pub const OPEN: ParamEnvelope = ParamEnvelope {
+        max_fee_bps: BPS_DENOMINATOR,
+        max_loan_amount: u64::MAX,
+        min_loan_cooldown: 0,
+        min_timelock: 0,
+    };
+
+    pub fn is_valid(&self) -> bool {
+        self.max_fee_bps <= BPS_DENOMINATOR
+            && self.max_loan_amount > 0
+            && self.min_loan_cooldown >= 0
+            && self.min_timelock >= 0
+    }
+
+    // Whether `value` for `param` lies within the envelope
+    pub fn allows(&self, param: PoolParam, value: u64) -> bool {
+        match param {
+            PoolParam::FeeBps => value <= self.max_fee_bps,
+            PoolParam::MaxLoanAmount => value > 0 && value <= self.max_loan_amount,
+            PoolParam::LoanCooldown => value <= i64::MAX as u64 && value as i64 >= self.min_loan_cooldown,
+            PoolParam::Timelock => value <= i64::MAX as u64 && value as i64 >= self.min_timelock,
+        }
+    }
+
+    // Every parameter of `params` within the envelope
+    pub fn check(&self, params: &PoolConfigParams) -> Result<()> {
+        for param in PoolParam::ALL {
+            require!(self.allows(param, param.get(params)), FlashLoanError::OutsideParamEnvelope);
+        }
+        Ok(())
+    }
+}
+
+// Curator-set parameter the protocol admin can override or freeze
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PoolParam {
+    FeeBps,
+    MaxLoanAmount,
+    LoanCooldown,
+    Timelock,
+}
+
+impl PoolParam {
+    pub const ALL: [PoolParam; 4] = [PoolParam::FeeBps, PoolParam::MaxLoanAmount, PoolParam::LoanCooldown, PoolParam::Timelock];
+
+    // Bit of the parameter in `PoolConfig::frozen_params`
+    pub fn bit(self) -> u8 {
+        1 << self as u8
+    }
+
+    // History field the parameter's changes are recorded under
+    pub fn config_field(self) -> ConfigField {
+        match self {
+            PoolParam::FeeBps => ConfigField::FeeBps,
+            PoolParam::MaxLoanAmount => ConfigField::MaxLoanAmount,
+            PoolParam::LoanCooldown => ConfigField::LoanCooldown,
+            PoolParam::Timelock => ConfigField::Timelock,
+        }
+    }
+
+    pub fn get(self, params: &PoolConfigParams) -> u64 {
+        match self {
+            PoolParam::FeeBps => params.fee_bps,
+            PoolParam::MaxLoanAmount => params.max_loan_amount,
+            PoolParam::LoanCooldown => params.loan_cooldown as u64,
+            PoolParam::Timelock => params.timelock as u64,
+        }
+    }
+
+    pub fn set(self, params: &mut PoolConfigParams, value: u64) {
+        match self {
+            PoolParam::FeeBps => params.fee_bps = value,
+            PoolParam::MaxLoanAmount => params.max_loan_amount = value,
+            PoolParam::LoanCooldown => params.loan_cooldown = value as i64,
+            PoolParam::Timelock => params.timelock = value as i64,
+        }
+    }
+}
+
+// Protocol-wide settings shared by every pool
+#[account]
+pub struct ProtocolConfig {
+    pub admin: Pubkey,              // Protocol admin
+    pub treasury: Pubkey,           // Owner of the treasury token accounts
+    pub pool_creation_deposit: u64, // Lamports a pool creator locks in the pool, refunded on close
+    pub envelope: ParamEnvelope,    // Bounds on curator-set pool parameters
+    pub bump: u8,
+}
+
+impl ProtocolConfig {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + ParamEnvelope::LEN + 1;
+}
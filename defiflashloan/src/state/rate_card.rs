@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::state::FeeCurve;
+
+// Quoted fee for one representative loan size
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct RateCardEntry {
+    pub utilization_bps: u64,       // Loan size as a share of the vault's liquidity
+    pub loan_amount: u64,           // Loan size quoted, capped at the pool's max loan amount
+    pub fee: u64,                   // Fee a standard borrower pays for it
+}
+
+// Public fee disclosure for a pool, refreshed by `refresh_rate_card`
+#[account]
+pub struct RateCard {
+    pub pool: Pubkey,               // Pool the card describes
+    pub fee_bps: u64,               // Base fee the quotes were computed with
+    pub fee_curve: FeeCurve,        // Utilization curve the quotes were computed with
+    pub vault_amount: u64,          // Vault liquidity the quotes were computed against
+    pub updated_at: i64,            // Unix timestamp of the last refresh
+    pub entries: [RateCardEntry; RATE_CARD_ENTRIES], // One quote per RATE_CARD_UTILIZATION_BPS point
+    pub bump: u8,
+}
+
+impl RateCard {
+    pub const LEN: usize = 8 + 32 + 8 + FeeCurve::LEN + 8 + 8 + (8 + 8 + 8) * RATE_CARD_ENTRIES + 1;
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+// Limits on moving liquidity from one pool of a mint into another. The
+// source pool is paid in IOU shares of the target, held by the source pool
+// PDA, so neither pool's share price moves when liquidity changes pools
+#[account]
+pub struct RebalanceRoute {
+    pub source_pool: Pubkey,        // Pool the liquidity is moved out of
+    pub target_pool: Pubkey,        // Pool of the same mint receiving it
+    pub operator: Pubkey,           // Keeper allowed to rebalance besides the protocol admin
+    pub iou_share_account: Pubkey,  // Target share account owned by the source pool
+    pub max_move_amount: u64,       // Largest single move
+    pub max_outstanding: u64,       // Most principal the target may hold for the source at once
+    pub outstanding_principal: u64, // Principal moved and not yet unwound
+    pub iou_shares: u64,            // Target shares minted for the outstanding principal
+    pub bump: u8,
+}
+
+impl RebalanceRoute {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 1;
+
+    // Principal backing `shares` of the outstanding IOU shares
+    pub fn principal_for_shares(&self, shares: u64) -> u64 {
+        if self.iou_shares == 0 {
+            return 0;
+        }
+        (self.outstanding_principal as u128 * shares as u128 / self.iou_shares as u128) as u64
+    }
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::FlashLoanError;
+
+// Global index of every pool, split into fixed-size RegistryPage accounts so
+// clients can discover pools without scanning program accounts
+#[account]
+pub struct Registry {
+    pub pool_count: u64,            // Pools currently listed across all pages
+    pub next_slot: u64,             // Entries ever appended; picks the page new pools go to
+    pub bump: u8,
+}
+
+impl Registry {
+    pub const LEN: usize = 8 + 8 + 8 + 1;
+
+    // Page the next initialized pool is appended to
+    pub fn open_page(&self) -> u32 {
+        (self.next_slot / REGISTRY_PAGE_SIZE as u64) as u32
+    }
+}
+
+// One registry entry
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct RegistryEntry {
+    pub token_mint: Pubkey,         // Mint the pool lends
+    pub pool: Pubkey,               // Pool PDA
+}
+
+// One page of the registry; closed pools leave their page slot empty
+#[account]
+pub struct RegistryPage {
+    pub index: u32,                 // Page number, starting at zero
+    pub entries: Vec<RegistryEntry>, // Listed pools, at most REGISTRY_PAGE_SIZE
+    pub bump: u8,
+}
+
+impl RegistryPage {
+    pub const LEN: usize = 8 + 4 + (4 + 64 * REGISTRY_PAGE_SIZE) + 1;
+
+    // Drop `pool` from the page
+    pub fn remove(&mut self, pool: &Pubkey) -> Result<()> {
+        let position = self
+            .entries
+            .iter()
+            .position(|entry| entry.pool == *pool)
+            .ok_or(FlashLoanError::PoolNotInRegistryPage)?;
+        self.entries.remove(position);
+        Ok(())
+    }
+}
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+// Scoped borrowing permission for an ephemeral key
+// The borrower must also approve the session signer as delegate on the
+// borrower token account, since it signs the repayment transfer
+#[account]
+pub struct SessionKey {
+    pub borrower: Pubkey,           // Borrower the session acts for
+    pub session_signer: Pubkey,     // Ephemeral key allowed to borrow
+    pub max_loan_amount: u64,       // Largest loan the session may take
+    pub expires_at: i64,            // Unix timestamp after which the session is invalid
+    pub bump: u8,
+}
+
+impl SessionKey {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 1;
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+
+// LP shares escrowed for an external protocol, e.g. as money market collateral
+#[account]
+pub struct ShareLock {
+    pub owner: Pubkey,              // LP that locked the shares
+    pub pool: Pubkey,               // Pool the shares belong to
+    pub locker: Pubkey,             // External protocol that may release the shares at any time
+    pub shares: u64,                // Shares held in the escrow account
+    pub unlock_at: i64,             // Unix timestamp after which the owner may unlock
+    pub bump: u8,
+}
+
+impl ShareLock {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 8 + 1;
+
+    // Seeds used to sign for the escrow account as the lock PDA
+    pub fn seeds(&self) -> [&[u8]; 5] {
+        [
+            SHARE_LOCK_SEED,
+            self.pool.as_ref(),
+            self.owner.as_ref(),
+            self.locker.as_ref(),
+            std::slice::from_ref(&self.bump),
+        ]
+    }
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+
+// Lamport flash loan pool singleton; liquidity sits in a system-owned vault
+// PDA so it can be lent with plain system transfers
+#[account]
+pub struct SolPool {
+    pub admin: Pubkey,              // Protocol admin at creation; funds and withdraws liquidity
+    pub fee_bps: u64,               // Base fee fed into the dynamic fee tiers
+    pub max_loan_amount: u64,       // Largest single loan, in lamports
+    pub total_deposits: u64,        // Lamports funded net of withdrawals
+    pub fees_accrued: u64,          // Loan fees earned, in lamports
+    pub active: bool,               // Set while a loan is outstanding
+    pub event_sequence: u64,        // Sequence of the last loan event, for gap detection
+    pub vault_bump: u8,             // Bump of the vault PDA
+    pub bump: u8,
+}
+
+impl SolPool {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 8 + 1 + 8 + 1 + 1;
+
+    // Sequence for the next loan event, consecutive starting at 1
+    pub fn next_event_sequence(&mut self) -> u64 {
+        self.event_sequence += 1;
+        self.event_sequence
+    }
+
+    // Seeds used to sign for the vault PDA
+    pub fn vault_seeds(&self) -> [&[u8]; 2] {
+        [SOL_VAULT_SEED, std::slice::from_ref(&self.vault_bump)]
+    }
+}
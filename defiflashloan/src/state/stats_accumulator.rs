@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+// Stats recorded by a pool's fast path loans until the next sync_stats crank
+#[account]
+pub struct StatsAccumulator {
+    pub pool: Pubkey,               // Pool whose fast path loans record here
+    pub loan_stats: Pubkey,         // LoanStats the crank folds the pending stats into
+    pub pending_loans: u64,         // Loan volume not yet synced
+    pub pending_fees: u64,          // Fees not yet synced
+    pub pending_loan_count: u64,    // Loans not yet synced
+    pub pending_fees_usd_cents: u64, // Oracle-priced fees not yet synced
+    pub bump: u8,
+}
+
+impl StatsAccumulator {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 1;
+
+    pub fn accumulate(&mut self, loan_amount: u64, fee: u64) {
+        self.pending_loans += loan_amount;
+        self.pending_fees += fee;
+        self.pending_loan_count += 1;
+    }
+}
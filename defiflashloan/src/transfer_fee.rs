@@ -0,0 +1,36 @@
+// Token-2022 transfer-fee extension math; legacy mints never withhold a fee
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions},
+    state::Mint,
+};
+
+use crate::errors::FlashLoanError;
+
+// Apply `f` to the mint's transfer fee config for the current epoch, or
+// return 0 when the mint has none
+fn with_fee_config(mint: &AccountInfo, f: impl FnOnce(&TransferFeeConfig, u64) -> Option<u64>) -> Result<u64> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(0);
+    }
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<Mint>::unpack(&data)?;
+    let Ok(config) = mint_state.get_extension::<TransferFeeConfig>() else {
+        return Ok(0);
+    };
+    f(config, Clock::get()?.epoch).ok_or_else(|| error!(FlashLoanError::TransferFeeOverflow))
+}
+
+// Fee withheld from a transfer of `amount`
+pub fn transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    with_fee_config(mint, |config, epoch| config.calculate_epoch_fee(epoch, amount))
+}
+
+// Amount to send so that `net` arrives after the transfer fee
+pub fn gross_for_net(mint: &AccountInfo, net: u64) -> Result<u64> {
+    let fee = with_fee_config(mint, |config, epoch| {
+        config.calculate_inverse_epoch_fee(epoch, net)
+    })?;
+    net.checked_add(fee).ok_or_else(|| error!(FlashLoanError::TransferFeeOverflow))
+}
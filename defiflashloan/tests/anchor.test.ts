@@ -2,32 +2,248 @@
 const { SystemProgram } = web3;
 const { PublicKey, Keypair } = web3;
 
+// Protocol config PDA, created with the wallet as admin and treasury on first use
+async function ensureProtocolConfig(program, provider) {
+  const [protocolConfig] = PublicKey.findProgramAddressSync(
+    [Buffer.from("protocol_config")],
+    program.programId
+  );
+  if (!(await program.account.protocolConfig.fetchNullable(protocolConfig))) {
+    await program.methods
+      .initializeProtocol(provider.wallet.publicKey)
+      .accounts({
+        protocolConfig,
+        admin: provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  }
+  return protocolConfig;
+}
+
+// Registry PDA and the registry page the next initialized pool is listed on
+async function openRegistryPage(program) {
+  const [registry] = PublicKey.findProgramAddressSync(
+    [Buffer.from("registry")],
+    program.programId
+  );
+  const state = await program.account.registry.fetchNullable(registry);
+  const nextSlot = state ? state.nextSlot : new BN(0);
+  const page = nextSlot.divn(32);
+  const [registryPage] = PublicKey.findProgramAddressSync(
+    [Buffer.from("registry_page"), page.toArrayLike(Buffer, "le", 4)],
+    program.programId
+  );
+  return { registry, registryPage, nextSlot };
+}
+
+// Pool PDA for `tokenMint` created in registry slot `index`
+function poolAddress(program, tokenMint, index) {
+  const [pool] = PublicKey.findProgramAddressSync(
+    [Buffer.from("pool"), tokenMint.toBuffer(), index.toArrayLike(Buffer, "le", 8)],
+    program.programId
+  );
+  return pool;
+}
+
+// Another pool for `tokenMint`, created by the wallet with no creator fee share
+async function openAlternatePool(program, provider, protocolConfig, tokenMint) {
+  const { registry, registryPage, nextSlot } = await openRegistryPage(program);
+  const pool = poolAddress(program, tokenMint, nextSlot);
+  const vault = anchor.utils.token.associatedAddress({ mint: tokenMint, owner: pool });
+  const [poolConfig] = PublicKey.findProgramAddressSync(
+    [Buffer.from("pool_config"), pool.toBuffer()],
+    program.programId
+  );
+  const [configHistory] = PublicKey.findProgramAddressSync(
+    [Buffer.from("config_history"), pool.toBuffer()],
+    program.programId
+  );
+  const [shareMint] = PublicKey.findProgramAddressSync(
+    [Buffer.from("share_mint"), pool.toBuffer()],
+    program.programId
+  );
+  await program.methods
+    .initializePool(0)
+    .accounts({
+      protocolConfig,
+      pool,
+      poolConfig,
+      configHistory,
+      loanVault: vault,
+      shareMint,
+      registry,
+      registryPage,
+      tokenMint,
+      admin: provider.wallet.publicKey,
+      tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+      associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .rpc();
+  return { pool, vault, poolConfig, configHistory, shareMint };
+}
+
+// Fresh borrower with SOL, a funded token account and a loan state on `pool`
+async function openBorrower(program, provider, pool, tokenMint, funding) {
+  const kp = Keypair.generate();
+  const airdrop = await provider.connection.requestAirdrop(kp.publicKey, web3.LAMPORTS_PER_SOL);
+  await provider.connection.confirmTransaction(airdrop);
+  const tokenAccount = await createTokenAccount(provider, tokenMint, kp.publicKey);
+  if (funding > 0) {
+    await mintTo(provider, tokenMint, tokenAccount, provider.wallet.publicKey, [], funding);
+  }
+  const [loanState] = PublicKey.findProgramAddressSync(
+    [Buffer.from("loan_state"), pool.toBuffer(), kp.publicKey.toBuffer()],
+    program.programId
+  );
+  await program.methods
+    .initLoanState()
+    .accounts({
+      pool,
+      loanState,
+      borrower: kp.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([kp])
+    .rpc();
+  return { kp, tokenAccount, loanState };
+}
+
+// LP position funding `alt` with `amount` freshly minted tokens from the wallet
+async function depositInto(program, provider, tokenMint, alt, amount) {
+  const lpAccount = await createTokenAccount(provider, tokenMint, provider.wallet.publicKey);
+  await mintTo(provider, tokenMint, lpAccount, provider.wallet.publicKey, [], amount);
+  const lpShares = await createTokenAccount(provider, alt.shareMint, provider.wallet.publicKey);
+  const [lpPosition] = PublicKey.findProgramAddressSync(
+    [Buffer.from("lp_position"), alt.pool.toBuffer(), provider.wallet.publicKey.toBuffer()],
+    program.programId
+  );
+  const depositAccounts = {
+    pool: alt.pool,
+    loanVault: alt.vault,
+    shareMint: alt.shareMint,
+    lpPosition,
+    lpFeeAccumulator: null,
+    depositorAccount: lpAccount,
+    depositorShareAccount: lpShares,
+    depositor: provider.wallet.publicKey,
+    tokenMint,
+    tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+    systemProgram: SystemProgram.programId,
+  };
+  await program.methods.depositLiquidity(new BN(amount), new BN(0)).accounts(depositAccounts).rpc();
+  return { lpAccount, lpShares, lpPosition, depositAccounts };
+}
+
+// Drop the timelock on `alt`, then queue a change through `queue` and apply it at once
+async function applyConfigNow(program, provider, protocolConfig, alt, queue) {
+  await program.methods
+    .overridePoolParam({ timelock: {} }, new BN(0), false)
+    .accounts({
+      protocolConfig,
+      pool: alt.pool,
+      poolConfig: alt.poolConfig,
+      configHistory: alt.configHistory,
+      admin: provider.wallet.publicKey,
+    })
+    .rpc();
+  await queue.accounts({ pool: alt.pool, poolConfig: alt.poolConfig, admin: provider.wallet.publicKey }).rpc();
+  await program.methods
+    .applyPoolConfig()
+    .accounts({
+      pool: alt.pool,
+      poolConfig: alt.poolConfig,
+      configHistory: alt.configHistory,
+      protocolConfig,
+      keeperRegistry: null,
+      keeper: null,
+      keeperAuthority: null,
+    })
+    .rpc();
+}
+
+// A flash_borrow for `user` on `alt`, ready to send, and its matching
+// flash_repay instruction; `sources` are extra token accounts the repay may draw on
+async function splitLoan(program, tokenMint, alt, user, amount, sources = []) {
+  const [loanReceipt] = PublicKey.findProgramAddressSync(
+    [Buffer.from("loan_receipt"), user.loanState.toBuffer()],
+    program.programId
+  );
+  const borrow = program.methods
+    .flashBorrow(new BN(amount), null)
+    .accounts({
+      pool: alt.pool,
+      poolConfig: alt.poolConfig,
+      loanVault: alt.vault,
+      borrowerAccount: user.tokenAccount,
+      borrower: user.kp.publicKey,
+      loanState: user.loanState,
+      tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+      loanReceipt,
+      tokenMint,
+      instructionsSysvar: web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+      systemProgram: SystemProgram.programId,
+      feeExperimentStats: null,
+      poolAdmin: null,
+      accountingPrice: null,
+      insuranceFund: null,
+      insuranceVault: null,
+      insurancePolicy: null,
+    });
+  const repayIx = await program.methods
+    .flashRepay(new BN(amount))
+    .accounts({
+      pool: alt.pool,
+      loanVault: alt.vault,
+      borrowerAccount: user.tokenAccount,
+      borrower: user.kp.publicKey,
+      loanState: user.loanState,
+      loanStats: null,
+      tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+      loanReceipt,
+      tokenMint,
+      shareMint: alt.shareMint,
+      accountingPrice: null,
+      protocolConfig: null,
+      treasuryAccount: null,
+      creatorFeeAccount: null,
+    })
+    .remainingAccounts(sources.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false })))
+    .instruction();
+  return { borrow, repayIx };
+}
+
 describe("Defi Flash Loan Program", () => {
   const provider = anchor.AnchorProvider.env(); // Explicitly use AnchorProvider
   anchor.setProvider(provider); // Set the global provider
 
   const program = anchor.workspace.FlashLoan;
+  const attacker = anchor.workspace.Attacker; // Hostile callback fixture from programs/attacker
 
   // Create keypairs for accounts used in tests
-  const loanVaultKp = Keypair.generate();
   const borrowerKp = Keypair.generate();
   const loanStatsKp = Keypair.generate();
-  const loanStateKp = Keypair.generate();
 
   let loanAmount = new BN(500000); // Example loan amount
   let loanExpiration = new BN(Math.floor(Date.now() / 1000) + 60); // 1 min expiration
 
   // Setup token mint and token accounts for borrower and loan vault
   let tokenMint = null;
+  let poolPda = null;
+  let poolConfigPda = null;
+  let configHistoryPda = null;
+  let protocolConfigPda = null;
+  let shareMintPda = null;
   let loanVaultTokenAccount = null;
   let borrowerTokenAccount = null;
+  let loanStatePda = null;
+  let programRegistryPda = null;
+  let loanEscrowPda = null;
 
   before(async () => {
     // Airdrop SOL to all necessary accounts
-    await provider.connection.requestAirdrop(loanVaultKp.publicKey, 2 * web3.LAMPORTS_PER_SOL);
     await provider.connection.requestAirdrop(borrowerKp.publicKey, 2 * web3.LAMPORTS_PER_SOL);
-    await provider.connection.requestAirdrop(loanStatsKp.publicKey, 1 * web3.LAMPORTS_PER_SOL);
-    await provider.connection.requestAirdrop(loanStateKp.publicKey, 1 * web3.LAMPORTS_PER_SOL);
 
     // Create a mint for the tokens
     tokenMint = await createMint(
@@ -36,36 +252,595 @@ describe("Defi Flash Loan Program", () => {
       9 // Decimals for the mint
     );
 
-    // Create token accounts
-    loanVaultTokenAccount = await createTokenAccount(
-      provider, tokenMint, loanVaultKp.publicKey
+    // Create the pool; its vault is the pool PDA's associated token account
+    protocolConfigPda = await ensureProtocolConfig(program, provider);
+    const protocolConfig = protocolConfigPda;
+    const { registry, registryPage, nextSlot } = await openRegistryPage(program);
+    poolPda = poolAddress(program, tokenMint, nextSlot);
+    loanVaultTokenAccount = anchor.utils.token.associatedAddress({
+      mint: tokenMint,
+      owner: poolPda,
+    });
+    [poolConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pool_config"), poolPda.toBuffer()],
+      program.programId
+    );
+    [configHistoryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("config_history"), poolPda.toBuffer()],
+      program.programId
+    );
+    [shareMintPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("share_mint"), poolPda.toBuffer()],
+      program.programId
     );
 
+    await program.methods
+      .initializePool(0) // No creator fee share
+      .accounts({
+        protocolConfig,
+        pool: poolPda,
+        poolConfig: poolConfigPda,
+        configHistory: configHistoryPda,
+        loanVault: loanVaultTokenAccount,
+        shareMint: shareMintPda,
+        registry,
+        registryPage,
+        tokenMint: tokenMint,
+        admin: provider.wallet.publicKey,
+        tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    // Create the borrower token account
     borrowerTokenAccount = await createTokenAccount(
       provider, tokenMint, borrowerKp.publicKey
     );
 
+    // Open the borrower's loan state on the pool
+    [loanStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("loan_state"), poolPda.toBuffer(), borrowerKp.publicKey.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .initLoanState()
+      .accounts({
+        pool: poolPda,
+        loanState: loanStatePda,
+        borrower: borrowerKp.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([borrowerKp])
+      .rpc();
+
     // Mint tokens to the loan vault to fund the flash loans
     await mintTo(
       provider, tokenMint, loanVaultTokenAccount, provider.wallet.publicKey, [], 1000000 // 1 million tokens
     );
+
+    // Fund the borrower with enough to cover loan fees on repayment
+    await mintTo(
+      provider, tokenMint, borrowerTokenAccount, provider.wallet.publicKey, [], 100000
+    );
+
+    // Create the statistics account loans record into
+    await program.methods
+      .initLoanStats()
+      .accounts({
+        loanStats: loanStatsKp.publicKey,
+        payer: provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([loanStatsKp])
+      .rpc();
+
+    // Allowlist the attacker fixture and open the loan escrow, so the hostile
+    // callback tests get past the registry and delegate checks
+    [programRegistryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("program_registry"), poolPda.toBuffer()],
+      program.programId
+    );
+    [loanEscrowPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("loan_escrow"), poolPda.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .registerTargetProgram(attacker.programId)
+      .accounts({
+        pool: poolPda,
+        programRegistry: programRegistryPda,
+        admin: provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    await program.methods
+      .initializeLoanEscrow()
+      .accounts({
+        pool: poolPda,
+        loanEscrow: loanEscrowPda,
+        tokenMint: tokenMint,
+        payer: provider.wallet.publicKey,
+        tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  });
+
+  it("initializes the pool with a PDA-owned vault", async () => {
+    const pool = await program.account.pool.fetch(poolPda);
+    assert.ok(pool.tokenMint.equals(tokenMint));
+    assert.ok(pool.vault.equals(loanVaultTokenAccount));
+
+    const vault = await getTokenAccount(provider, loanVaultTokenAccount);
+    assert.ok(vault.owner.equals(poolPda));
+
+    // New pools start with the default risk parameters
+    const config = await program.account.poolConfig.fetch(poolConfigPda);
+    assert.ok(config.pool.equals(poolPda));
+    assert.ok(config.feeBps.eq(new BN(50)));
+    assert.ok(config.maxLoanAmount.eq(new BN(1000000)));
+    assert.ok(config.feeCurve.kinkUtilizationBps.eq(new BN(8000)));
+
+    // The pool is discoverable through the registry
+    const [registry] = PublicKey.findProgramAddressSync(
+      [Buffer.from("registry")],
+      program.programId
+    );
+    const [firstPage] = PublicKey.findProgramAddressSync(
+      [Buffer.from("registry_page"), new BN(0).toArrayLike(Buffer, "le", 4)],
+      program.programId
+    );
+    const page = await program.account.registryPage.fetch(firstPage);
+    assert.ok(page.entries.some((entry) => entry.pool.equals(poolPda) && entry.tokenMint.equals(tokenMint)));
+    const registryState = await program.account.registry.fetch(registry);
+    assert.ok(registryState.poolCount.gten(1));
+  });
+
+  it("queues pool config changes behind the timelock", async () => {
+    await program.methods
+      .updatePoolConfig({
+        feeBps: new BN(40),
+        maxLoanAmount: new BN(2000000),
+        loanCooldown: new BN(60),
+        timelock: new BN(86400),
+      })
+      .accounts({
+        pool: poolPda,
+        poolConfig: poolConfigPda,
+        protocolConfig: protocolConfigPda,
+        admin: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    // The change is queued but the live parameters are untouched
+    const config = await program.account.poolConfig.fetch(poolConfigPda);
+    assert.ok(config.pending.params.feeBps.eq(new BN(40)));
+    assert.ok(config.feeBps.eq(new BN(50)));
+
+    try {
+      await program.methods
+        .applyPoolConfig()
+        .accounts({
+          pool: poolPda,
+          poolConfig: poolConfigPda,
+          configHistory: configHistoryPda,
+          protocolConfig: protocolConfigPda,
+          keeperRegistry: null,
+          keeper: null,
+          keeperAuthority: null,
+        })
+        .rpc();
+      assert.fail("applied a change before its timelock");
+    } catch (err) {
+      assert.include(err.toString(), "ConfigChangeNotReady");
+    }
+  });
+
+  it("lets the protocol admin freeze a pool parameter against the curator", async () => {
+    const overrideFee = (freeze: boolean) =>
+      program.methods
+        .overridePoolParam({ feeBps: {} }, new BN(50), freeze)
+        .accounts({
+          protocolConfig: protocolConfigPda,
+          pool: poolPda,
+          poolConfig: poolConfigPda,
+          configHistory: configHistoryPda,
+          admin: provider.wallet.publicKey,
+        })
+        .rpc();
+
+    await overrideFee(true);
+    let config = await program.account.poolConfig.fetch(poolConfigPda);
+    assert.equal(config.frozenParams, 1);
+    const history = await program.account.configHistory.fetch(configHistoryPda);
+    const last = history.entries[(history.totalChanges.toNumber() - 1) % history.entries.length];
+    assert.ok(last.field.frozenParams);
+    assert.ok(last.authority.equals(provider.wallet.publicKey));
+
+    // Queueing a different fee is refused while it is frozen
+    try {
+      await program.methods
+        .updatePoolConfig({
+          feeBps: new BN(40),
+          maxLoanAmount: new BN(2000000),
+          loanCooldown: new BN(60),
+          timelock: new BN(86400),
+        })
+        .accounts({
+          pool: poolPda,
+          poolConfig: poolConfigPda,
+          protocolConfig: protocolConfigPda,
+          admin: provider.wallet.publicKey,
+        })
+        .rpc();
+      assert.fail("queued a change to a frozen parameter");
+    } catch (err) {
+      assert.include(err.toString(), "ParamFrozen");
+    }
+
+    await overrideFee(false);
+    config = await program.account.poolConfig.fetch(poolConfigPda);
+    assert.equal(config.frozenParams, 0);
+    assert.ok(config.feeBps.eq(new BN(50)));
+  });
+
+  it("only accepts fee tiers that get cheaper as loans grow", async () => {
+    const tier = (minLoanAmount: number, feeBps: number) => ({
+      minLoanAmount: new BN(minLoanAmount),
+      feeBps: new BN(feeBps),
+    });
+
+    const setFeeTiers = (tiers, inUsd = false) =>
+      program.methods
+        .setFeeTiers(tiers, inUsd)
+        .accounts({ pool: poolPda, poolConfig: poolConfigPda, admin: provider.wallet.publicKey })
+        .rpc();
+
+    try {
+      await setFeeTiers([tier(0, 50), tier(100000, 80)]);
+      assert.fail("set a tier charging larger loans more");
+    } catch (err) {
+      assert.include(err.toString(), "InvalidFeeTiers");
+    }
+
+    // USD tiers need the accounting oracle to value loans
+    try {
+      await setFeeTiers([tier(0, 100), tier(10000000, 50)], true);
+      assert.fail("set USD tiers on a pool without an accounting oracle");
+    } catch (err) {
+      assert.include(err.toString(), "InvalidFeeTiers");
+    }
+
+    // Tiers are queued behind the timelock with the other parameters
+    await setFeeTiers([tier(0, 100), tier(100000, 50), tier(500000, 25)]);
+    let config = await program.account.poolConfig.fetch(poolConfigPda);
+    assert.equal(config.pending.feeTierCount, 3);
+    assert.equal(config.pending.feeTiersInUsd, false);
+    assert.ok(config.pending.feeTiers[2].feeBps.eq(new BN(25)));
+    // The fee change queued earlier is kept alongside them
+    assert.ok(config.pending.params.feeBps.eq(new BN(40)));
+    const pool = await program.account.pool.fetch(poolPda);
+    assert.equal(pool.feeTierCount, 0);
+
+    // Queue an empty table again so the pending change leaves the tiers off
+    await setFeeTiers([]);
+    config = await program.account.poolConfig.fetch(poolConfigPda);
+    assert.equal(config.pending.feeTierCount, 0);
+  });
+
+  it("rotates the pool admin only once the new key accepts", async () => {
+    const newAdminKp = Keypair.generate();
+
+    await program.methods
+      .proposeAdmin(newAdminKp.publicKey)
+      .accounts({ pool: poolPda, admin: provider.wallet.publicKey })
+      .rpc();
+
+    let pool = await program.account.pool.fetch(poolPda);
+    assert.ok(pool.admin.equals(provider.wallet.publicKey));
+    assert.ok(pool.pendingAdmin.equals(newAdminKp.publicKey));
+
+    await program.methods
+      .acceptAdmin()
+      .accounts({ pool: poolPda, pendingAdmin: newAdminKp.publicKey })
+      .signers([newAdminKp])
+      .rpc();
+
+    pool = await program.account.pool.fetch(poolPda);
+    assert.ok(pool.admin.equals(newAdminKp.publicKey));
+
+    // Hand the pool back so later tests can keep using the wallet as admin
+    await program.methods
+      .proposeAdmin(provider.wallet.publicKey)
+      .accounts({ pool: poolPda, admin: newAdminKp.publicKey })
+      .signers([newAdminKp])
+      .rpc();
+    await program.methods
+      .acceptAdmin()
+      .accounts({ pool: poolPda, pendingAdmin: provider.wallet.publicKey })
+      .rpc();
+  });
+
+  it("keeps the recovery key out while the admin sends heartbeats", async () => {
+    const recoveryKp = Keypair.generate();
+    const [adminRecoveryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("admin_recovery"), poolPda.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .setRecoveryKey(recoveryKp.publicKey, new BN(30))
+      .accounts({
+        pool: poolPda,
+        adminRecovery: adminRecoveryPda,
+        admin: provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    // The admin heartbeat was just recorded, so recovery cannot start yet
+    try {
+      await program.methods
+        .startAdminRecovery()
+        .accounts({ pool: poolPda, adminRecovery: adminRecoveryPda, recoveryKey: recoveryKp.publicKey })
+        .signers([recoveryKp])
+        .rpc();
+      assert.fail("started recovery while the admin was active");
+    } catch (err) {
+      assert.include(err.toString(), "AdminStillActive");
+    }
+
+    await program.methods
+      .adminHeartbeat()
+      .accounts({ pool: poolPda, adminRecovery: adminRecoveryPda, admin: provider.wallet.publicKey })
+      .rpc();
+
+    const adminRecovery = await program.account.adminRecovery.fetch(adminRecoveryPda);
+    assert.ok(adminRecovery.recoveryKey.equals(recoveryKp.publicKey));
+    assert.ok(adminRecovery.recoveryStartedAt.eq(new BN(0)));
+  });
+
+  // Malicious callbacks must fail before they can touch the loan; these run
+  // before the successful loan so the cooldown does not mask the errors
+  it("rejects reentrant and unregistered callbacks", async () => {
+    const attack = (callbackProgram) =>
+      program.methods
+        .executeFlashLoan(loanAmount, loanExpiration, false, Buffer.from([]))
+        .accounts({
+          pool: poolPda,
+          poolConfig: poolConfigPda,
+          loanVault: loanVaultTokenAccount,
+          borrowerAccount: borrowerTokenAccount,
+          borrower: borrowerKp.publicKey,
+          loanOwner: borrowerKp.publicKey,
+          loanStats: loanStatsKp.publicKey,
+          loanState: loanStatePda,
+          tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+          tokenMint: tokenMint,
+          shareMint: shareMintPda,
+          borrowCredit: null,
+          statsAccumulator: null,
+          sessionKey: null,
+          preAuth: null,
+          instructionsSysvar: null,
+          programRegistry: null,
+          loanEscrow: null,
+          callbackDelegate: null,
+          borrowerReputation: null,
+          feeExperimentStats: null,
+          integrator: null,
+          accountingPrice: null,
+          protocolConfig: null,
+          treasuryAccount: null,
+          creatorFeeAccount: null,
+        })
+        .remainingAccounts([
+          { pubkey: callbackProgram, isSigner: false, isWritable: false },
+          // Smuggle the vault in as writable alongside the callback
+          { pubkey: loanVaultTokenAccount, isSigner: false, isWritable: true },
+        ])
+        .signers([borrowerKp])
+        .rpc();
+
+    // Calling back into the flash loan program is refused outright
+    try {
+      await attack(program.programId);
+      assert.fail("reentered the flash loan program");
+    } catch (err) {
+      assert.include(err.toString(), "InvalidCallbackProgram");
+    }
+
+    // Any other program must be allowlisted in the pool's registry
+    try {
+      await attack(TokenInstructions.TOKEN_PROGRAM_ID);
+      assert.fail("invoked an unregistered callback");
+    } catch (err) {
+      assert.include(err.toString(), "CallbackProgramNotRegistered");
+    }
+
+    // Neither attempt moved funds
+    const vault = await getTokenAccount(provider, loanVaultTokenAccount);
+    assert.ok(new BN(vault.amount).eq(new BN(1000000)));
+  });
+
+  // Registered but hostile callbacks, from the programs/attacker fixture
+  it("fails a callback that re-enters the flash loan program", async () => {
+    const loanAccounts = {
+      pool: poolPda,
+      poolConfig: poolConfigPda,
+      loanVault: loanVaultTokenAccount,
+      borrowerAccount: borrowerTokenAccount,
+      borrower: borrowerKp.publicKey,
+      loanOwner: borrowerKp.publicKey,
+      loanStats: loanStatsKp.publicKey,
+      loanState: loanStatePda,
+      tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+      tokenMint: tokenMint,
+      shareMint: shareMintPda,
+      borrowCredit: null,
+      statsAccumulator: null,
+      sessionKey: null,
+      preAuth: null,
+      instructionsSysvar: null,
+      programRegistry: programRegistryPda,
+      loanEscrow: null,
+      callbackDelegate: null,
+      borrowerReputation: null,
+      feeExperimentStats: null,
+      integrator: null,
+      accountingPrice: null,
+      protocolConfig: null,
+      treasuryAccount: null,
+      creatorFeeAccount: null,
+    };
+
+    // The attacker's callback replays a second loan on the same pool
+    const inner = await program.methods
+      .executeFlashLoan(loanAmount, loanExpiration, false, Buffer.from([]))
+      .accounts({ ...loanAccounts, programRegistry: null })
+      .instruction();
+    const callback = await attacker.methods
+      .reenter(inner.data)
+      .accounts({ flashLoanProgram: program.programId })
+      .instruction();
+
+    const borrowerBefore = await getTokenAccount(provider, borrowerTokenAccount);
+    try {
+      await program.methods
+        .executeFlashLoan(loanAmount, loanExpiration, false, callback.data)
+        .accounts(loanAccounts)
+        .remainingAccounts([
+          { pubkey: attacker.programId, isSigner: false, isWritable: false },
+          { pubkey: program.programId, isSigner: false, isWritable: false },
+          ...inner.keys,
+        ])
+        .signers([borrowerKp])
+        .rpc();
+      assert.fail("re-entered the flash loan program from a callback");
+    } catch (err) {
+      // The runtime refuses the nested call back into the lender
+      assert.include(err.toString(), "reentrancy not allowed");
+    }
+
+    // The whole transaction unwound: no loan left open, no funds moved
+    const vault = await getTokenAccount(provider, loanVaultTokenAccount);
+    const borrower = await getTokenAccount(provider, borrowerTokenAccount);
+    assert.ok(new BN(vault.amount).eq(new BN(1000000)));
+    assert.ok(new BN(borrower.amount).eq(new BN(borrowerBefore.amount)));
+    const loanState = await program.account.loanState.fetch(loanStatePda);
+    assert.ok(loanState.active === false);
+  });
+
+  it("fails a callback that takes the escrowed loan without repaying", async () => {
+    const thiefKp = Keypair.generate();
+    const thiefTokenAccount = await createTokenAccount(provider, tokenMint, thiefKp.publicKey);
+    const [flashDelegatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("flash_delegate")],
+      attacker.programId
+    );
+
+    const callback = await attacker.methods
+      .skipRepayment()
+      .accounts({
+        loanEscrow: loanEscrowPda,
+        flashDelegate: flashDelegatePda,
+        destination: thiefTokenAccount,
+        tokenMint: tokenMint,
+        tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+      })
+      .instruction();
+
+    try {
+      await program.methods
+        .executeFlashLoan(loanAmount, loanExpiration, false, callback.data)
+        .accounts({
+          pool: poolPda,
+          poolConfig: poolConfigPda,
+          loanVault: loanVaultTokenAccount,
+          borrowerAccount: borrowerTokenAccount,
+          borrower: borrowerKp.publicKey,
+          loanOwner: borrowerKp.publicKey,
+          loanStats: loanStatsKp.publicKey,
+          loanState: loanStatePda,
+          tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+          tokenMint: tokenMint,
+          shareMint: shareMintPda,
+          borrowCredit: null,
+          statsAccumulator: null,
+          sessionKey: null,
+          preAuth: null,
+          instructionsSysvar: null,
+          programRegistry: programRegistryPda,
+          loanEscrow: loanEscrowPda,
+          callbackDelegate: flashDelegatePda,
+          borrowerReputation: null,
+          feeExperimentStats: null,
+          integrator: null,
+          accountingPrice: null,
+          protocolConfig: null,
+          treasuryAccount: null,
+          creatorFeeAccount: null,
+        })
+        .remainingAccounts([
+          { pubkey: attacker.programId, isSigner: false, isWritable: false },
+          ...callback.keys,
+        ])
+        .signers([borrowerKp])
+        .rpc();
+      assert.fail("kept an unrepaid loan");
+    } catch (err) {
+      assert.include(err.toString(), "IncorrectRepayment");
+    }
+
+    // The theft unwound with the failed loan
+    const vault = await getTokenAccount(provider, loanVaultTokenAccount);
+    const thief = await getTokenAccount(provider, thiefTokenAccount);
+    const escrow = await getTokenAccount(provider, loanEscrowPda);
+    assert.ok(new BN(vault.amount).eq(new BN(1000000)));
+    assert.ok(new BN(thief.amount).eqn(0));
+    assert.ok(new BN(escrow.amount).eqn(0));
   });
 
   it("executes flash loan", async () => {
+    const vaultBefore = await getTokenAccount(provider, loanVaultTokenAccount);
+    const borrowerBefore = await getTokenAccount(provider, borrowerTokenAccount);
+
     // Prepare the flash loan transaction
     const txHash = await program.methods
       .executeFlashLoan(
         loanAmount,         // The amount to borrow
         loanExpiration,     // Loan expiration time
+        false,              // Repay the fee on top of the principal
+        Buffer.from([]),    // No callback instruction data
       )
       .accounts({
+        pool: poolPda,                          // Pool PDA signing for the vault
+        poolConfig: poolConfigPda,              // Pool risk parameters
         loanVault: loanVaultTokenAccount,       // Loan pool
         borrowerAccount: borrowerTokenAccount,  // Borrower's token account
         borrower: borrowerKp.publicKey,         // Borrower signer
+        loanOwner: borrowerKp.publicKey,        // Served borrower, keys the loan state
         loanStats: loanStatsKp.publicKey,       // Statistics account
-        loanState: loanStateKp.publicKey,       // State account
+        loanState: loanStatePda,       // State account
         tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID, // Token program
         tokenMint: tokenMint,                   // The mint for the loan tokens
+        shareMint: shareMintPda,                // LP share mint, read for SharePriceUpdated
+        borrowCredit: null,                     // No prepaid credits, pay the fee in cash
+        statsAccumulator: null,                 // Stats are written directly to loanStats
+        sessionKey: null,                       // Borrower signs directly
+        preAuth: null,                          // No keeper pre-authorization
+        instructionsSysvar: null,               // Only needed for quoted loans
+        programRegistry: null,                  // Only needed for callbacks
+        loanEscrow: null,                       // Disburse straight to the borrower
+        callbackDelegate: null,                 // Only needed for escrowed loans
+        borrowerReputation: null,               // Standard tier, no reputation update
+        feeExperimentStats: null,               // No fee experiment on this pool
+        integrator: null,                       // Only needed for receiver loans
+        accountingPrice: null,                  // Pool reports fees in token units only
+        protocolConfig: null,                   // Only needed to forward the protocol fee
+        treasuryAccount: null,                  // Reserve the protocol fee in the vault
+        creatorFeeAccount: null,                // Reserve the creator fee in the vault
       })
       .signers([borrowerKp])
       .rpc();
@@ -82,16 +857,1609 @@ describe("Defi Flash Loan Program", () => {
     console.log("Loan Vault Token Amount:", updatedLoanVaultAccount.amount.toString());
     console.log("Borrower Token Amount:", updatedBorrowerAccount.amount.toString());
 
-    // Check that the loan was transferred to the borrower
-    assert.ok(new BN(updatedBorrowerAccount.amount).eq(loanAmount));
-
-    // Check that the vault balance has decreased by the loan amount
-    assert.ok(new BN(updatedLoanVaultAccount.amount).eq(new BN(1000000).sub(loanAmount)));
+    // The loan is repaid after the (empty) callback, so only the fee moves:
+    // the vault grows by exactly the fee and the borrower pays exactly that
+    const stats = await program.account.loanStats.fetch(loanStatsKp.publicKey);
+    const fee = stats.totalFeesCollected;
+    assert.ok(fee.gtn(0));
+    assert.ok(stats.totalLoans.eq(loanAmount));
+    assert.ok(new BN(updatedLoanVaultAccount.amount).eq(new BN(vaultBefore.amount).add(fee)));
+    assert.ok(new BN(updatedBorrowerAccount.amount).eq(new BN(borrowerBefore.amount).sub(fee)));
 
     // Check for successful repayment and loan state reset
-    const loanState = await program.account.loanState.fetch(loanStateKp.publicKey);
+    const loanState = await program.account.loanState.fetch(loanStatePda);
     assert.ok(loanState.active === false); // Ensure the loan is no longer active
   });
+
+  it("runs a simple flash loan from just the pool and borrower account", async () => {
+    const vaultBefore = await getTokenAccount(provider, loanVaultTokenAccount);
+
+    // Every other account is a PDA or follows from the pool
+    await program.methods
+      .simpleFlashLoan(new BN(1000))
+      .accounts({
+        pool: poolPda,
+        borrowerAccount: borrowerTokenAccount,
+        borrower: borrowerKp.publicKey,
+        feeExperimentStats: null,
+        instructionsSysvar: null,
+        accountingPrice: null,
+        protocolConfig: null,
+        treasuryAccount: null,
+        creatorFeeAccount: null,
+      })
+      .signers([borrowerKp])
+      .rpc();
+
+    // The borrower paid the fee out of their own balance
+    const vaultAfter = await getTokenAccount(provider, loanVaultTokenAccount);
+    assert.ok(new BN(vaultAfter.amount).gt(new BN(vaultBefore.amount)));
+  });
+
+  it("nets a loan from one pool against a deposit into another of the mint", async () => {
+    // A second pool for the same mint receives the deposit leg
+    const target = await openAlternatePool(program, provider, protocolConfigPda, tokenMint);
+    const targetPool = target.pool;
+    const targetVault = target.vault;
+    const targetShareMint = target.shareMint;
+
+    const bot = await openBorrower(program, provider, poolPda, tokenMint, 50000);
+    const botShares = await createTokenAccount(provider, targetShareMint, bot.kp.publicKey);
+    const [lpPosition] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lp_position"), targetPool.toBuffer(), bot.kp.publicKey.toBuffer()],
+      program.programId
+    );
+    const sourceBefore = await getTokenAccount(provider, loanVaultTokenAccount);
+
+    await program.methods
+      .executeNettedFlashLoan(new BN(100000), new BN(20000), new BN(20000))
+      .accounts({
+        sourcePool: poolPda,
+        sourceConfig: poolConfigPda,
+        sourceVault: loanVaultTokenAccount,
+        sourceShareMint: shareMintPda,
+        loanState: bot.loanState,
+        targetPool,
+        targetVault,
+        targetShareMint,
+        lpPosition,
+        lpFeeAccumulator: null,
+        borrowerAccount: bot.tokenAccount,
+        borrowerShareAccount: botShares,
+        borrower: bot.kp.publicKey,
+        tokenMint: tokenMint,
+        tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: null,
+        accountingPrice: null,
+      })
+      .signers([bot.kp])
+      .rpc();
+
+    // The principal never moved: the source vault gained only the fee and the
+    // bot paid just the fee and the deposit
+    const sourceAfter = await getTokenAccount(provider, loanVaultTokenAccount);
+    const fee = new BN(sourceAfter.amount).sub(new BN(sourceBefore.amount));
+    assert.ok(fee.gtn(0));
+    const targetAccount = await getTokenAccount(provider, targetVault);
+    assert.ok(new BN(targetAccount.amount).eq(new BN(20000)));
+    const botAccount = await getTokenAccount(provider, bot.tokenAccount);
+    assert.ok(new BN(botAccount.amount).eq(new BN(30000).sub(fee)));
+    const shares = await getTokenAccount(provider, botShares);
+    assert.ok(new BN(shares.amount).eq(new BN(20000)));
+    const loanState = await program.account.loanState.fetch(bot.loanState);
+    assert.ok(loanState.loanCount.eqn(1));
+  });
+
+  it("lends fee-free from a public goods pool for a lamport charge", async () => {
+    // Donated liquidity only: the pool has no LP shares, so it may go fee-free
+    const goods = await openAlternatePool(program, provider, protocolConfigPda, tokenMint);
+    await mintTo(provider, tokenMint, goods.vault, provider.wallet.publicKey, [], 100000);
+    const charge = new BN(5000);
+    await program.methods
+      .setPublicGoodsMode(3, charge)
+      .accounts({ pool: goods.pool, shareMint: goods.shareMint, admin: provider.wallet.publicKey })
+      .rpc();
+
+    const user = await openBorrower(program, provider, goods.pool, tokenMint, 0);
+    const [loanReceipt] = PublicKey.findProgramAddressSync(
+      [Buffer.from("loan_receipt"), user.loanState.toBuffer()],
+      program.programId
+    );
+    const repayIx = await program.methods
+      .flashRepay(new BN(50000))
+      .accounts({
+        pool: goods.pool,
+        loanVault: goods.vault,
+        borrowerAccount: user.tokenAccount,
+        borrower: user.kp.publicKey,
+        loanState: user.loanState,
+        loanStats: null,
+        tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+        loanReceipt,
+        tokenMint: tokenMint,
+        shareMint: goods.shareMint,
+        accountingPrice: null,
+        protocolConfig: null,
+        treasuryAccount: null,
+        creatorFeeAccount: null,
+      })
+      .instruction();
+    await program.methods
+      .flashBorrow(new BN(50000), null)
+      .accounts({
+        pool: goods.pool,
+        poolConfig: goods.poolConfig,
+        loanVault: goods.vault,
+        borrowerAccount: user.tokenAccount,
+        borrower: user.kp.publicKey,
+        loanState: user.loanState,
+        tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+        loanReceipt,
+        tokenMint: tokenMint,
+        instructionsSysvar: web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        feeExperimentStats: null,
+        poolAdmin: provider.wallet.publicKey,
+        accountingPrice: null,
+        insuranceFund: null,
+        insuranceVault: null,
+        insurancePolicy: null,
+      })
+      .postInstructions([repayIx])
+      .signers([user.kp])
+      .rpc();
+
+    // A borrower holding no tokens repaid just the principal
+    const vault = await getTokenAccount(provider, goods.vault);
+    assert.ok(new BN(vault.amount).eq(new BN(100000)));
+    const loanState = await program.account.loanState.fetch(user.loanState);
+    assert.equal(loanState.dayLoanCount, 1);
+
+    // Every other loan path is closed, so the daily limit cannot be bypassed
+    try {
+      await program.methods
+        .simpleFlashLoan(new BN(1000))
+        .accounts({
+          pool: goods.pool,
+          borrowerAccount: user.tokenAccount,
+          borrower: user.kp.publicKey,
+          feeExperimentStats: null,
+          instructionsSysvar: null,
+          accountingPrice: null,
+        })
+        .signers([user.kp])
+        .rpc();
+      assert.fail("took a standard loan from a public goods pool");
+    } catch (err) {
+      assert.include(err.toString(), "PoolPaused");
+    }
+  });
+
+  it("insures a loan and pays an approved claim from premiums", async () => {
+    const insured = await openAlternatePool(program, provider, protocolConfigPda, tokenMint);
+    await mintTo(provider, tokenMint, insured.vault, provider.wallet.publicKey, [], 100000);
+    const [insuranceFund] = PublicKey.findProgramAddressSync(
+      [Buffer.from("insurance_fund"), insured.pool.toBuffer()],
+      program.programId
+    );
+    const [insuranceVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("insurance_vault"), insuranceFund.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .configureInsuranceFund(10, new BN(1000))
+      .accounts({
+        protocolConfig: protocolConfigPda,
+        pool: insured.pool,
+        insuranceFund,
+        insuranceVault,
+        tokenMint,
+        admin: provider.wallet.publicKey,
+        tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const user = await openBorrower(program, provider, insured.pool, tokenMint, 5000);
+    const [insurancePolicy] = PublicKey.findProgramAddressSync(
+      [Buffer.from("insurance_policy"), insuranceFund.toBuffer(), user.kp.publicKey.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .openInsurancePolicy()
+      .accounts({
+        insuranceFund,
+        insurancePolicy,
+        borrower: user.kp.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([user.kp])
+      .rpc();
+
+    const [loanReceipt] = PublicKey.findProgramAddressSync(
+      [Buffer.from("loan_receipt"), user.loanState.toBuffer()],
+      program.programId
+    );
+    const repayIx = await program.methods
+      .flashRepay(new BN(50000))
+      .accounts({
+        pool: insured.pool,
+        loanVault: insured.vault,
+        borrowerAccount: user.tokenAccount,
+        borrower: user.kp.publicKey,
+        loanState: user.loanState,
+        loanStats: null,
+        tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+        loanReceipt,
+        tokenMint: tokenMint,
+        shareMint: insured.shareMint,
+        accountingPrice: null,
+        protocolConfig: null,
+        treasuryAccount: null,
+        creatorFeeAccount: null,
+      })
+      .instruction();
+    await program.methods
+      .flashBorrow(new BN(50000), null)
+      .accounts({
+        pool: insured.pool,
+        poolConfig: insured.poolConfig,
+        loanVault: insured.vault,
+        borrowerAccount: user.tokenAccount,
+        borrower: user.kp.publicKey,
+        loanState: user.loanState,
+        tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+        loanReceipt,
+        tokenMint: tokenMint,
+        instructionsSysvar: web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        feeExperimentStats: null,
+        poolAdmin: null,
+        accountingPrice: null,
+        insuranceFund,
+        insuranceVault,
+        insurancePolicy,
+      })
+      .postInstructions([repayIx])
+      .signers([user.kp])
+      .rpc();
+
+    // 10 bps of the principal went to the fund on top of the loan fee
+    const vault = await getTokenAccount(provider, insuranceVault);
+    assert.ok(new BN(vault.amount).eqn(50));
+    const policy = await program.account.insurancePolicy.fetch(insurancePolicy);
+    assert.ok(policy.insuredLoans.eqn(1));
+    assert.ok(policy.premiumsPaid.eqn(50));
+    const loanSlot = policy.recentLoans[0].slot;
+
+    // A slot the policy never insured cannot be claimed against
+    const uninsuredSlot = loanSlot.addn(1);
+    const [uninsuredClaim] = PublicKey.findProgramAddressSync(
+      [Buffer.from("insurance_claim"), insurancePolicy.toBuffer(), uninsuredSlot.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    try {
+      await program.methods
+        .fileInsuranceClaim(uninsuredSlot, new BN(10), Array(32).fill(0))
+        .accounts({
+          insuranceFund,
+          insurancePolicy,
+          insuranceClaim: uninsuredClaim,
+          borrower: user.kp.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user.kp])
+        .rpc();
+      assert.fail("claimed against an uninsured slot");
+    } catch (err) {
+      assert.include(err.toString(), "LoanNotInsured");
+    }
+
+    const [insuranceClaim] = PublicKey.findProgramAddressSync(
+      [Buffer.from("insurance_claim"), insurancePolicy.toBuffer(), loanSlot.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    await program.methods
+      .fileInsuranceClaim(loanSlot, new BN(40), Array(32).fill(7))
+      .accounts({
+        insuranceFund,
+        insurancePolicy,
+        insuranceClaim,
+        borrower: user.kp.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([user.kp])
+      .rpc();
+    const before = await getTokenAccount(provider, user.tokenAccount);
+    await program.methods
+      .adjudicateInsuranceClaim(new BN(40))
+      .accounts({
+        protocolConfig: protocolConfigPda,
+        pool: insured.pool,
+        insuranceFund,
+        insuranceVault,
+        insuranceClaim,
+        claimantAccount: user.tokenAccount,
+        tokenMint,
+        admin: provider.wallet.publicKey,
+        tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const after = await getTokenAccount(provider, user.tokenAccount);
+    assert.ok(new BN(after.amount).sub(new BN(before.amount)).eqn(40));
+    const claim = await program.account.insuranceClaim.fetch(insuranceClaim);
+    assert.ok(claim.paidAmount.eqn(40));
+    assert.deepEqual(claim.status, { paid: {} });
+    const fund = await program.account.insuranceFund.fetch(insuranceFund);
+    assert.ok(fund.totalPaidOut.eqn(40));
+    assert.equal(fund.pendingClaims, 0);
+  });
+
+  it("withholds LP fees from the share price for time-weighted distribution", async () => {
+    const weighted = await openAlternatePool(program, provider, protocolConfigPda, tokenMint);
+    const [lpFeeAccumulator] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lp_fee_accumulator"), weighted.pool.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .initializeLpFeeAccumulator(new BN(3600))
+      .accounts({
+        pool: weighted.pool,
+        shareMint: weighted.shareMint,
+        lpFeeAccumulator,
+        admin: provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const lpAccount = await createTokenAccount(provider, tokenMint, provider.wallet.publicKey);
+    await mintTo(provider, tokenMint, lpAccount, provider.wallet.publicKey, [], 100000);
+    const lpShares = await createTokenAccount(provider, weighted.shareMint, provider.wallet.publicKey);
+    const [lpPosition] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lp_position"), weighted.pool.toBuffer(), provider.wallet.publicKey.toBuffer()],
+      program.programId
+    );
+    const depositAccounts = {
+      pool: weighted.pool,
+      loanVault: weighted.vault,
+      shareMint: weighted.shareMint,
+      lpPosition,
+      lpFeeAccumulator: null,
+      depositorAccount: lpAccount,
+      depositorShareAccount: lpShares,
+      depositor: provider.wallet.publicKey,
+      tokenMint: tokenMint,
+      tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    };
+    // Every deposit must move the position's weight on the accumulator
+    try {
+      await program.methods.depositLiquidity(new BN(100000), new BN(0)).accounts(depositAccounts).rpc();
+      assert.fail("deposited without the LP fee accumulator");
+    } catch (err) {
+      assert.include(err.toString(), "LpFeeAccumulatorMissing");
+    }
+    await program.methods
+      .depositLiquidity(new BN(100000), new BN(0))
+      .accounts({ ...depositAccounts, lpFeeAccumulator })
+      .rpc();
+    const position = await program.account.lpPosition.fetch(lpPosition);
+    assert.ok(position.feeShares.eqn(100000));
+    let accumulator = await program.account.lpFeeAccumulator.fetch(lpFeeAccumulator);
+    assert.ok(accumulator.totalShares.eqn(100000));
+
+    const user = await openBorrower(program, provider, weighted.pool, tokenMint, 5000);
+    const [loanReceipt] = PublicKey.findProgramAddressSync(
+      [Buffer.from("loan_receipt"), user.loanState.toBuffer()],
+      program.programId
+    );
+    const repayIx = await program.methods
+      .flashRepay(new BN(50000))
+      .accounts({
+        pool: weighted.pool,
+        loanVault: weighted.vault,
+        borrowerAccount: user.tokenAccount,
+        borrower: user.kp.publicKey,
+        loanState: user.loanState,
+        loanStats: null,
+        tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+        loanReceipt,
+        tokenMint: tokenMint,
+        shareMint: weighted.shareMint,
+        accountingPrice: null,
+        protocolConfig: null,
+        treasuryAccount: null,
+        creatorFeeAccount: null,
+      })
+      .instruction();
+    await program.methods
+      .flashBorrow(new BN(50000), null)
+      .accounts({
+        pool: weighted.pool,
+        poolConfig: weighted.poolConfig,
+        loanVault: weighted.vault,
+        borrowerAccount: user.tokenAccount,
+        borrower: user.kp.publicKey,
+        loanState: user.loanState,
+        tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+        loanReceipt,
+        tokenMint: tokenMint,
+        instructionsSysvar: web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        feeExperimentStats: null,
+        poolAdmin: null,
+        accountingPrice: null,
+        insuranceFund: null,
+        insuranceVault: null,
+        insurancePolicy: null,
+      })
+      .postInstructions([repayIx])
+      .signers([user.kp])
+      .rpc();
+
+    // The LP share of the fee waits in the vault outside the share price
+    const pool = await program.account.pool.fetch(weighted.pool);
+    assert.ok(pool.lpFeesWithheld.gtn(0));
+    const vault = await getTokenAccount(provider, weighted.vault);
+    const reserved = pool.protocolFeesAccrued.add(pool.creatorFeesAccrued).add(pool.lpFeesWithheld);
+    assert.ok(new BN(vault.amount).sub(reserved).eqn(100000));
+
+    // The epoch has to run its course before its fees are spread
+    try {
+      await program.methods
+        .checkpointLpFees()
+        .accounts({
+          pool: weighted.pool,
+          lpFeeAccumulator,
+          keeperRegistry: null,
+          keeper: null,
+          keeperAuthority: null,
+        })
+        .rpc();
+      assert.fail("closed an LP fee epoch early");
+    } catch (err) {
+      assert.include(err.toString(), "EpochNotElapsed");
+    }
+    accumulator = await program.account.lpFeeAccumulator.fetch(lpFeeAccumulator);
+    assert.equal(accumulator.epoch, 0);
+    assert.ok(accumulator.feesUnclaimed.eqn(0));
+  });
+
+  it("rebalances liquidity between pools of a mint for IOU shares", async () => {
+    const source = await openAlternatePool(program, provider, protocolConfigPda, tokenMint);
+    const target = await openAlternatePool(program, provider, protocolConfigPda, tokenMint);
+    await mintTo(provider, tokenMint, source.vault, provider.wallet.publicKey, [], 100000);
+    const [rebalanceRoute] = PublicKey.findProgramAddressSync(
+      [Buffer.from("rebalance_route"), source.pool.toBuffer(), target.pool.toBuffer()],
+      program.programId
+    );
+    const [iouShareAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("rebalance_iou"), rebalanceRoute.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .configureRebalanceRoute(new BN(30000), new BN(50000), provider.wallet.publicKey)
+      .accounts({
+        protocolConfig: protocolConfigPda,
+        sourcePool: source.pool,
+        targetPool: target.pool,
+        targetShareMint: target.shareMint,
+        rebalanceRoute,
+        iouShareAccount,
+        admin: provider.wallet.publicKey,
+        tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const routeAccounts = {
+      protocolConfig: protocolConfigPda,
+      rebalanceRoute,
+      sourcePool: source.pool,
+      sourceVault: source.vault,
+      sourceShareMint: source.shareMint,
+      targetPool: target.pool,
+      targetVault: target.vault,
+      targetShareMint: target.shareMint,
+      iouShareAccount,
+      tokenMint,
+      operator: provider.wallet.publicKey,
+      tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+    };
+    await program.methods.rebalanceLiquidity(new BN(30000)).accounts(routeAccounts).rpc();
+
+    // The source still counts the moved principal, and holds target shares for it
+    const sourcePool = await program.account.pool.fetch(source.pool);
+    assert.ok(sourcePool.rebalancedOut.eqn(30000));
+    const iou = await getTokenAccount(provider, iouShareAccount);
+    assert.ok(new BN(iou.amount).eqn(30000));
+    const targetVault = await getTokenAccount(provider, target.vault);
+    assert.ok(new BN(targetVault.amount).eqn(30000));
+
+    // A second move would take the outstanding principal past the route limit
+    try {
+      await program.methods.rebalanceLiquidity(new BN(30000)).accounts(routeAccounts).rpc();
+      assert.fail("rebalanced past the outstanding limit");
+    } catch (err) {
+      assert.include(err.toString(), "RebalanceLimitExceeded");
+    }
+
+    const { sourceShareMint, ...unwindAccounts } = routeAccounts;
+    await program.methods.unwindRebalance(new BN(30000)).accounts(unwindAccounts).rpc();
+    const unwound = await program.account.pool.fetch(source.pool);
+    assert.ok(unwound.rebalancedOut.eqn(0));
+    const sourceVault = await getTokenAccount(provider, source.vault);
+    assert.ok(new BN(sourceVault.amount).eqn(100000));
+    const route = await program.account.rebalanceRoute.fetch(rebalanceRoute);
+    assert.ok(route.outstandingPrincipal.eqn(0));
+    assert.ok(route.iouShares.eqn(0));
+  });
+
+  it("sets a per-pool dust threshold and reconciles written-off dust", async () => {
+    const dusty = await openAlternatePool(program, provider, protocolConfigPda, tokenMint);
+    try {
+      await program.methods
+        .setDustThreshold(new BN(10001))
+        .accounts({ protocolConfig: protocolConfigPda, pool: dusty.pool, admin: provider.wallet.publicKey })
+        .rpc();
+      assert.fail("set a dust threshold above the maximum");
+    } catch (err) {
+      assert.include(err.toString(), "InvalidDustThreshold");
+    }
+    await program.methods
+      .setDustThreshold(new BN(5))
+      .accounts({ protocolConfig: protocolConfigPda, pool: dusty.pool, admin: provider.wallet.publicKey })
+      .rpc();
+
+    // Exact repayments write nothing off, so reconciliation has nothing to cover
+    await program.methods.reconcileDust().accounts({ pool: dusty.pool }).rpc();
+    const pool = await program.account.pool.fetch(dusty.pool);
+    assert.ok(pool.dustThreshold.eqn(5));
+    assert.ok(pool.dustOwed.eqn(0));
+    assert.ok(pool.totalDustWrittenOff.eqn(0));
+  });
+
+  it("refuses a pre-authorized loan outside its window and cancels it", async () => {
+    const scheduled = await openBorrower(program, provider, poolPda, tokenMint, 10000);
+    const now = Math.floor(Date.now() / 1000);
+    const preAuthFor = (nonce: number) =>
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("pre_auth"),
+          poolPda.toBuffer(),
+          scheduled.kp.publicKey.toBuffer(),
+          new BN(nonce).toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      )[0];
+    const createAccounts = (nonce: number) => ({
+      preAuth: preAuthFor(nonce),
+      pool: poolPda,
+      borrower: scheduled.kp.publicKey,
+      systemProgram: SystemProgram.programId,
+    });
+
+    // A window must end after it starts
+    try {
+      await program.methods
+        .createPreAuth(new BN(0), new BN(100000), new BN(1000), SystemProgram.programId, Array(32).fill(0), new BN(now + 3600), new BN(now + 3600))
+        .accounts(createAccounts(0))
+        .signers([scheduled.kp])
+        .rpc();
+      assert.fail("created a pre-authorization with an empty window");
+    } catch (err) {
+      assert.include(err.toString(), "InvalidPreAuthWindow");
+    }
+
+    // A loan scheduled an hour out cannot be run by a keeper yet
+    await program.methods
+      .createPreAuth(new BN(1), new BN(100000), new BN(1000), SystemProgram.programId, Array(32).fill(0), new BN(now + 3600), new BN(now + 7200))
+      .accounts(createAccounts(1))
+      .signers([scheduled.kp])
+      .rpc();
+    const preAuth = preAuthFor(1);
+    try {
+      await program.methods
+        .executePreAuthorizedLoan(Buffer.from([]))
+        .accounts({
+          pool: poolPda,
+          poolConfig: poolConfigPda,
+          loanVault: loanVaultTokenAccount,
+          borrowerAccount: scheduled.tokenAccount,
+          borrower: provider.wallet.publicKey,  // Keeper running the loan
+          loanOwner: scheduled.kp.publicKey,
+          loanStats: loanStatsKp.publicKey,
+          loanState: scheduled.loanState,
+          tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+          tokenMint: tokenMint,
+          shareMint: shareMintPda,
+          borrowCredit: null,
+          statsAccumulator: null,
+          sessionKey: null,
+          preAuth,
+          instructionsSysvar: null,
+          programRegistry: null,
+          loanEscrow: null,
+          callbackDelegate: null,
+          borrowerReputation: null,
+          feeExperimentStats: null,
+          integrator: null,
+          accountingPrice: null,
+          protocolConfig: null,
+          treasuryAccount: null,
+          creatorFeeAccount: null,
+        })
+        .rpc();
+      assert.fail("executed a pre-authorized loan before its window");
+    } catch (err) {
+      assert.include(err.toString(), "PreAuthOutsideWindow");
+    }
+    const pending = await program.account.preAuth.fetch(preAuth);
+    assert.isFalse(pending.executed);
+    assert.ok(pending.loanAmount.eqn(100000));
+
+    // Cancelling closes the account and returns its rent
+    await program.methods
+      .cancelPreAuth()
+      .accounts({ preAuth, borrower: scheduled.kp.publicKey })
+      .signers([scheduled.kp])
+      .rpc();
+    assert.isNull(await provider.connection.getAccountInfo(preAuth));
+  });
+
+  it("holds emergency withdrawals until withdrawals stay paused past the delay", async () => {
+    const PAUSE_WITHDRAW = 4;
+    const stuck = await openAlternatePool(program, provider, protocolConfigPda, tokenMint);
+    const lp = await depositInto(program, provider, tokenMint, stuck, 100000);
+    await applyConfigNow(program, provider, protocolConfigPda, stuck, program.methods.setEmergencyWithdrawDelay(new BN(86400)));
+    const config = await program.account.poolConfig.fetch(stuck.poolConfig);
+    assert.ok(config.emergencyWithdrawDelay.eqn(86400));
+
+    const emergencyWithdraw = () =>
+      program.methods
+        .emergencyWithdraw(new BN(50000))
+        .accounts({
+          pool: stuck.pool,
+          poolConfig: stuck.poolConfig,
+          loanVault: stuck.vault,
+          shareMint: stuck.shareMint,
+          lpPosition: lp.lpPosition,
+          lpFeeAccumulator: null,
+          ownerAccount: lp.lpAccount,
+          ownerShareAccount: lp.lpShares,
+          owner: provider.wallet.publicKey,
+          tokenMint,
+          tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+    // Open withdrawals leave nothing to escape from
+    try {
+      await emergencyWithdraw();
+      assert.fail("emergency withdrew while withdrawals were open");
+    } catch (err) {
+      assert.include(err.toString(), "EmergencyWithdrawUnavailable");
+    }
+
+    // A fresh pause has to outlast the delay first
+    const pauseAccounts = { pool: stuck.pool, configHistory: stuck.configHistory, admin: provider.wallet.publicKey };
+    await program.methods.pausePool(PAUSE_WITHDRAW).accounts(pauseAccounts).rpc();
+    let pool = await program.account.pool.fetch(stuck.pool);
+    assert.ok(pool.withdrawPausedSince.gtn(0));
+    try {
+      await emergencyWithdraw();
+      assert.fail("emergency withdrew before the delay");
+    } catch (err) {
+      assert.include(err.toString(), "EmergencyWithdrawUnavailable");
+    }
+
+    // Unpausing restarts the clock
+    await program.methods.unpausePool(PAUSE_WITHDRAW).accounts(pauseAccounts).rpc();
+    pool = await program.account.pool.fetch(stuck.pool);
+    assert.ok(pool.withdrawPausedSince.eqn(0));
+    const shares = await getTokenAccount(provider, lp.lpShares);
+    assert.ok(new BN(shares.amount).eqn(100000));
+  });
+
+  it("stops deposits and borrows for good once a pool winds down", async () => {
+    const PAUSE_BORROW = 1;
+    const PAUSE_DEPOSIT = 2;
+    const retiring = await openAlternatePool(program, provider, protocolConfigPda, tokenMint);
+    const lp = await depositInto(program, provider, tokenMint, retiring, 100000);
+    const user = await openBorrower(program, provider, retiring.pool, tokenMint, 1000);
+    const pauseAccounts = { pool: retiring.pool, configHistory: retiring.configHistory, admin: provider.wallet.publicKey };
+    await program.methods.beginWindDown().accounts(pauseAccounts).rpc();
+
+    // Clearing the pause flags does not resume either operation
+    await program.methods.unpausePool(PAUSE_BORROW | PAUSE_DEPOSIT).accounts(pauseAccounts).rpc();
+    await mintTo(provider, tokenMint, lp.lpAccount, provider.wallet.publicKey, [], 1000);
+    try {
+      await program.methods.depositLiquidity(new BN(1000), new BN(0)).accounts(lp.depositAccounts).rpc();
+      assert.fail("deposited into a winding-down pool");
+    } catch (err) {
+      assert.include(err.toString(), "PoolPaused");
+    }
+    const { borrow, repayIx } = await splitLoan(program, tokenMint, retiring, user, 10000);
+    try {
+      await borrow.postInstructions([repayIx]).signers([user.kp]).rpc();
+      assert.fail("borrowed from a winding-down pool");
+    } catch (err) {
+      assert.include(err.toString(), "PoolPaused");
+    }
+    try {
+      await program.methods.beginWindDown().accounts(pauseAccounts).rpc();
+      assert.fail("began a second wind-down");
+    } catch (err) {
+      assert.include(err.toString(), "PoolWindingDown");
+    }
+
+    // LPs can still redeem on the way out
+    await program.methods
+      .withdrawLiquidity(new BN(100000), new BN(0))
+      .accounts({
+        pool: retiring.pool,
+        loanVault: retiring.vault,
+        shareMint: retiring.shareMint,
+        lpPosition: lp.lpPosition,
+        lpFeeAccumulator: null,
+        ownerAccount: lp.lpAccount,
+        ownerShareAccount: lp.lpShares,
+        owner: provider.wallet.publicKey,
+        tokenMint,
+        tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+    const vault = await getTokenAccount(provider, retiring.vault);
+    assert.ok(new BN(vault.amount).eqn(0));
+  });
+
+  it("caps a borrower's first loan at the probation amount", async () => {
+    const guarded = await openAlternatePool(program, provider, protocolConfigPda, tokenMint);
+    await depositInto(program, provider, tokenMint, guarded, 100000);
+    await program.methods
+      .overridePoolParam({ loanCooldown: {} }, new BN(0), false)
+      .accounts({
+        protocolConfig: protocolConfigPda,
+        pool: guarded.pool,
+        poolConfig: guarded.poolConfig,
+        configHistory: guarded.configHistory,
+        admin: provider.wallet.publicKey,
+      })
+      .rpc();
+    await applyConfigNow(program, provider, protocolConfigPda, guarded, program.methods.setProbationLoanAmount(new BN(1000)));
+    const user = await openBorrower(program, provider, guarded.pool, tokenMint, 1000);
+
+    const fullSize = await splitLoan(program, tokenMint, guarded, user, 20000);
+    try {
+      await fullSize.borrow.postInstructions([fullSize.repayIx]).signers([user.kp]).rpc();
+      assert.fail("took a full-size first loan");
+    } catch (err) {
+      assert.include(err.toString(), "ProbationLoanTooLarge");
+    }
+
+    // One settled loan within the cap lifts it
+    const probation = await splitLoan(program, tokenMint, guarded, user, 1000);
+    await probation.borrow.postInstructions([probation.repayIx]).signers([user.kp]).rpc();
+    const again = await splitLoan(program, tokenMint, guarded, user, 20000);
+    await again.borrow.postInstructions([again.repayIx]).signers([user.kp]).rpc();
+    const loanState = await program.account.loanState.fetch(user.loanState);
+    assert.ok(loanState.loanCount.eqn(2));
+  });
+
+  it("repays a split loan from several of the borrower's token accounts", async () => {
+    const pool = await openAlternatePool(program, provider, protocolConfigPda, tokenMint);
+    await depositInto(program, provider, tokenMint, pool, 100000);
+    const user = await openBorrower(program, provider, pool.pool, tokenMint, 0);
+    const feeAccount = await createTokenAccount(provider, tokenMint, user.kp.publicKey);
+    await mintTo(provider, tokenMint, feeAccount, provider.wallet.publicKey, [], 1000);
+
+    // Every extra source must belong to the borrower
+    const foreign = await createTokenAccount(provider, tokenMint, provider.wallet.publicKey);
+    const stolen = await splitLoan(program, tokenMint, pool, user, 20000, [foreign]);
+    try {
+      await stolen.borrow.postInstructions([stolen.repayIx]).signers([user.kp]).rpc();
+      assert.fail("repaid from another owner's account");
+    } catch (err) {
+      assert.include(err.toString(), "InvalidRepaySource");
+    }
+
+    // The principal comes out of the main account, the fee out of the next one
+    const { borrow, repayIx } = await splitLoan(program, tokenMint, pool, user, 20000, [feeAccount]);
+    await borrow.postInstructions([repayIx]).signers([user.kp]).rpc();
+    const main = await getTokenAccount(provider, user.tokenAccount);
+    assert.ok(new BN(main.amount).eqn(0));
+    const remaining = new BN((await getTokenAccount(provider, feeAccount)).amount);
+    assert.ok(remaining.ltn(1000));
+    const vault = await getTokenAccount(provider, pool.vault);
+    assert.ok(new BN(vault.amount).eq(new BN(100000 + 1000).sub(remaining)));
+  });
+
+  it("rejects a borrower past the pool's concurrent borrower cap", async () => {
+    const capped = await openAlternatePool(program, provider, protocolConfigPda, tokenMint);
+    await depositInto(program, provider, tokenMint, capped, 100000);
+    await applyConfigNow(program, provider, protocolConfigPda, capped, program.methods.setMaxConcurrentBorrowers(1));
+    const first = await openBorrower(program, provider, capped.pool, tokenMint, 1000);
+    const second = await openBorrower(program, provider, capped.pool, tokenMint, 1000);
+
+    // A second open receipt in the same transaction exceeds the cap
+    const a = await splitLoan(program, tokenMint, capped, first, 10000);
+    const b = await splitLoan(program, tokenMint, capped, second, 10000);
+    try {
+      await b.borrow
+        .preInstructions([await a.borrow.instruction()])
+        .postInstructions([b.repayIx, a.repayIx])
+        .signers([first.kp, second.kp])
+        .rpc();
+      assert.fail("opened more receipts than the cap");
+    } catch (err) {
+      assert.include(err.toString(), "PoolSaturated");
+    }
+
+    // Alone, either borrower fits
+    const alone = await splitLoan(program, tokenMint, capped, first, 10000);
+    await alone.borrow.postInstructions([alone.repayIx]).signers([first.kp]).rpc();
+    const pool = await program.account.pool.fetch(capped.pool);
+    assert.equal(pool.openReceipts, 0);
+  });
+
+  it("defers fast path stats to the pool's accumulator until synced", async () => {
+    const fast = await openBorrower(program, provider, poolPda, tokenMint, 10000);
+    const [statsAccumulatorPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("stats_accumulator"), poolPda.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .initStatsAccumulator()
+      .accounts({
+        pool: poolPda,
+        statsAccumulator: statsAccumulatorPda,
+        loanStats: loanStatsKp.publicKey,
+        admin: provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    const statsBefore = await program.account.loanStats.fetch(loanStatsKp.publicKey);
+
+    await program.methods
+      .executeFlashLoanFast(new BN(100000), loanExpiration, Buffer.from([]))
+      .accounts({
+        pool: poolPda,
+        poolConfig: poolConfigPda,
+        loanVault: loanVaultTokenAccount,
+        borrowerAccount: fast.tokenAccount,
+        borrower: fast.kp.publicKey,
+        loanOwner: fast.kp.publicKey,
+        loanStats: null,                        // The fast path never touches LoanStats
+        loanState: fast.loanState,
+        tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+        tokenMint: tokenMint,
+        shareMint: shareMintPda,
+        borrowCredit: null,
+        statsAccumulator: statsAccumulatorPda,
+        sessionKey: null,
+        preAuth: null,
+        instructionsSysvar: null,
+        programRegistry: null,
+        loanEscrow: null,
+        callbackDelegate: null,
+        borrowerReputation: null,
+        feeExperimentStats: null,
+        integrator: null,
+        accountingPrice: null,
+        protocolConfig: null,
+        treasuryAccount: null,
+        creatorFeeAccount: null,
+      })
+      .signers([fast.kp])
+      .rpc();
+
+    // The loan sits in the accumulator and LoanStats is untouched
+    const pending = await program.account.statsAccumulator.fetch(statsAccumulatorPda);
+    assert.ok(pending.pendingLoanCount.eqn(1));
+    assert.ok(pending.pendingLoans.eqn(100000));
+    assert.ok(pending.pendingFees.gtn(0));
+    let stats = await program.account.loanStats.fetch(loanStatsKp.publicKey);
+    assert.ok(stats.totalLoanCount.eq(statsBefore.totalLoanCount));
+
+    // The crank folds it in and empties the accumulator
+    await program.methods
+      .syncStats()
+      .accounts({
+        pool: poolPda,
+        loanStats: loanStatsKp.publicKey,
+        statsAccumulator: statsAccumulatorPda,
+        keeperRegistry: null,
+        keeper: null,
+        keeperAuthority: null,
+      })
+      .rpc();
+    stats = await program.account.loanStats.fetch(loanStatsKp.publicKey);
+    assert.ok(stats.totalLoanCount.eq(statsBefore.totalLoanCount.addn(1)));
+    assert.ok(stats.totalLoans.eq(statsBefore.totalLoans.addn(100000)));
+    assert.ok(stats.totalFeesCollected.eq(statsBefore.totalFeesCollected.add(pending.pendingFees)));
+    const synced = await program.account.statsAccumulator.fetch(statsAccumulatorPda);
+    assert.ok(synced.pendingLoanCount.eqn(0));
+
+    // An accumulator only syncs into the LoanStats it was opened for
+    const otherStatsKp = Keypair.generate();
+    await program.methods
+      .initLoanStats()
+      .accounts({
+        loanStats: otherStatsKp.publicKey,
+        payer: provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([otherStatsKp])
+      .rpc();
+    try {
+      await program.methods
+        .syncStats()
+        .accounts({
+          pool: poolPda,
+          loanStats: otherStatsKp.publicKey,
+          statsAccumulator: statsAccumulatorPda,
+          keeperRegistry: null,
+          keeper: null,
+          keeperAuthority: null,
+        })
+        .rpc();
+      assert.fail("synced into an unrelated LoanStats");
+    } catch (err) {
+      assert.include(err.toString(), "ConstraintHasOne");
+    }
+  });
+
+  it("rejects a flash_borrow without a matching flash_repay", async () => {
+    const [loanReceiptPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("loan_receipt"), loanStatePda.toBuffer()],
+      program.programId
+    );
+    const borrowAccounts = {
+      pool: poolPda,
+      poolConfig: poolConfigPda,
+      loanVault: loanVaultTokenAccount,
+      borrowerAccount: borrowerTokenAccount,
+      borrower: borrowerKp.publicKey,
+      loanState: loanStatePda,
+      tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+      loanReceipt: loanReceiptPda,
+      tokenMint: tokenMint,
+      instructionsSysvar: web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+      systemProgram: web3.SystemProgram.programId,
+      feeExperimentStats: null,
+      poolAdmin: null,
+      accountingPrice: null,
+      insuranceFund: null,
+      insuranceVault: null,
+      insurancePolicy: null,
+    };
+
+    try {
+      await program.methods
+        .flashBorrow(new BN(1000), null)
+        .accounts(borrowAccounts)
+        .signers([borrowerKp])
+        .rpc();
+      assert.fail("borrowed without repaying");
+    } catch (err) {
+      assert.include(err.toString(), "MissingFlashRepay");
+    }
+
+    // A repay for a different amount does not count either
+    const repayIx = await program.methods
+      .flashRepay(new BN(999))
+      .accounts({
+        pool: poolPda,
+        loanVault: loanVaultTokenAccount,
+        borrowerAccount: borrowerTokenAccount,
+        borrower: borrowerKp.publicKey,
+        loanState: loanStatePda,
+        loanStats: null,
+        tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+        loanReceipt: loanReceiptPda,
+        tokenMint: tokenMint,
+        shareMint: shareMintPda,
+        accountingPrice: null,
+        protocolConfig: null,
+        treasuryAccount: null,
+        creatorFeeAccount: null,
+      })
+      .instruction();
+
+    try {
+      await program.methods
+        .flashBorrow(new BN(1000), null)
+        .accounts(borrowAccounts)
+        .postInstructions([repayIx])
+        .signers([borrowerKp])
+        .rpc();
+      assert.fail("borrowed with a mismatched repay");
+    } catch (err) {
+      assert.include(err.toString(), "MissingFlashRepay");
+    }
+  });
+});
+
+// Liquidity provider flows
+describe("Liquidity provision", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.FlashLoan;
+
+  let tokenMint = null;
+  let poolPda = null;
+  let poolConfigPda = null;
+  let configHistoryPda = null;
+  let shareMintPda = null;
+  let loanVaultTokenAccount = null;
+  let depositorTokenAccount = null;
+  let depositorShareAccount = null;
+  let lpPositionPda = null;
+
+  before(async () => {
+    tokenMint = await createMint(provider, provider.wallet.publicKey, 9);
+
+    const protocolConfig = await ensureProtocolConfig(program, provider);
+    const { registry, registryPage, nextSlot } = await openRegistryPage(program);
+    poolPda = poolAddress(program, tokenMint, nextSlot);
+    loanVaultTokenAccount = anchor.utils.token.associatedAddress({
+      mint: tokenMint,
+      owner: poolPda,
+    });
+    [poolConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pool_config"), poolPda.toBuffer()],
+      program.programId
+    );
+    [configHistoryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("config_history"), poolPda.toBuffer()],
+      program.programId
+    );
+    [shareMintPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("share_mint"), poolPda.toBuffer()],
+      program.programId
+    );
+    [lpPositionPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lp_position"), poolPda.toBuffer(), provider.wallet.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initializePool(0) // No creator fee share
+      .accounts({
+        protocolConfig,
+        pool: poolPda,
+        poolConfig: poolConfigPda,
+        configHistory: configHistoryPda,
+        loanVault: loanVaultTokenAccount,
+        shareMint: shareMintPda,
+        registry,
+        registryPage,
+        tokenMint: tokenMint,
+        admin: provider.wallet.publicKey,
+        tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    depositorTokenAccount = await createTokenAccount(provider, tokenMint, provider.wallet.publicKey);
+    await mintTo(provider, tokenMint, depositorTokenAccount, provider.wallet.publicKey, [], 1000000);
+    depositorShareAccount = await createTokenAccount(provider, shareMintPda, provider.wallet.publicKey);
+  });
+
+  it("mints and burns LP shares for deposits and withdrawals", async () => {
+    await program.methods
+      .depositLiquidity(new BN(600000), new BN(600000)) // First deposit is priced 1:1
+      .accounts({
+        pool: poolPda,
+        loanVault: loanVaultTokenAccount,
+        shareMint: shareMintPda,
+        lpPosition: lpPositionPda,
+        lpFeeAccumulator: null,
+        depositorAccount: depositorTokenAccount,
+        depositorShareAccount: depositorShareAccount,
+        depositor: provider.wallet.publicKey,
+        tokenMint: tokenMint,
+        tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .withdrawLiquidity(new BN(200000), new BN(200000))
+      .accounts({
+        pool: poolPda,
+        loanVault: loanVaultTokenAccount,
+        shareMint: shareMintPda,
+        lpPosition: lpPositionPda,
+        lpFeeAccumulator: null,
+        ownerAccount: depositorTokenAccount,
+        ownerShareAccount: depositorShareAccount,
+        owner: provider.wallet.publicKey,
+        tokenMint: tokenMint,
+        tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const position = await program.account.lpPosition.fetch(lpPositionPda);
+    assert.ok(position.depositedAmount.eq(new BN(400000)));
+    assert.ok(position.shares.eq(new BN(400000)));
+
+    // Freshly deposited shares sit out governance snapshots for the holding period
+    const snapshotShares = await program.methods
+      .viewSnapshotShares()
+      .accounts({
+        pool: poolPda,
+        lpPosition: lpPositionPda,
+        ownerShareAccount: depositorShareAccount,
+        owner: provider.wallet.publicKey,
+      })
+      .view();
+    assert.ok(snapshotShares.eqn(0));
+
+    const vault = await getTokenAccount(provider, loanVaultTokenAccount);
+    assert.ok(new BN(vault.amount).eq(new BN(400000)));
+
+    // The first deposit is priced 1:1, so 400k shares remain outstanding
+    const shares = await getTokenAccount(provider, depositorShareAccount);
+    assert.ok(new BN(shares.amount).eq(new BN(400000)));
+
+    // 600k in, 200k out and no fees earned yet
+    const statement = await program.methods
+      .generateLpStatement()
+      .accounts({
+        pool: poolPda,
+        loanVault: loanVaultTokenAccount,
+        shareMint: shareMintPda,
+        lpPosition: lpPositionPda,
+        lpShareAccount: depositorShareAccount,
+        lp: provider.wallet.publicKey,
+      })
+      .view();
+    assert.ok(statement.totalDeposited.eq(new BN(600000)));
+    assert.equal(statement.depositCount, 1);
+    assert.ok(statement.totalWithdrawn.eq(new BN(200000)));
+    assert.equal(statement.withdrawalCount, 1);
+    assert.ok(statement.currentValue.eq(new BN(400000)));
+    assert.ok(statement.feeIncome.eqn(0));
+
+    // Minimums above what the pool would pay out are refused
+    try {
+      await program.methods
+        .depositLiquidity(new BN(1000), new BN(1001))
+        .accounts({
+          pool: poolPda,
+          loanVault: loanVaultTokenAccount,
+          shareMint: shareMintPda,
+          lpPosition: lpPositionPda,
+          lpFeeAccumulator: null,
+          depositorAccount: depositorTokenAccount,
+          depositorShareAccount: depositorShareAccount,
+          depositor: provider.wallet.publicKey,
+          tokenMint: tokenMint,
+          tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      assert.fail("minted fewer shares than the minimum");
+    } catch (err) {
+      assert.include(err.toString(), "SlippageExceeded");
+    }
+    try {
+      await program.methods
+        .withdrawLiquidity(new BN(1000), new BN(1001))
+        .accounts({
+          pool: poolPda,
+          loanVault: loanVaultTokenAccount,
+          shareMint: shareMintPda,
+          lpPosition: lpPositionPda,
+          lpFeeAccumulator: null,
+          ownerAccount: depositorTokenAccount,
+          ownerShareAccount: depositorShareAccount,
+          owner: provider.wallet.publicKey,
+          tokenMint: tokenMint,
+          tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      assert.fail("paid out less than the minimum");
+    } catch (err) {
+      assert.include(err.toString(), "SlippageExceeded");
+    }
+  });
+
+  it("escrows shares for a locker and lets it release them", async () => {
+    const lockerKp = Keypair.generate();
+    const [shareLockPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("share_lock"),
+        poolPda.toBuffer(),
+        provider.wallet.publicKey.toBuffer(),
+        lockerKp.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+    const [escrowPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("share_escrow"), shareLockPda.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .lockShares(new BN(100000), lockerKp.publicKey, new BN(Math.floor(Date.now() / 1000) + 3600))
+      .accounts({
+        pool: poolPda,
+        shareMint: shareMintPda,
+        shareLock: shareLockPda,
+        escrowShareAccount: escrowPda,
+        ownerShareAccount: depositorShareAccount,
+        owner: provider.wallet.publicKey,
+        tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const locked = await getTokenAccount(provider, depositorShareAccount);
+    assert.ok(new BN(locked.amount).eq(new BN(300000)));
+
+    // The locker may release before expiry
+    await program.methods
+      .unlockShares()
+      .accounts({
+        shareLock: shareLockPda,
+        pool: poolPda,
+        locker: lockerKp.publicKey,
+        escrowShareAccount: escrowPda,
+        destinationShareAccount: depositorShareAccount,
+        owner: provider.wallet.publicKey,
+        authority: lockerKp.publicKey,
+        tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+      })
+      .signers([lockerKp])
+      .rpc();
+
+    const released = await getTokenAccount(provider, depositorShareAccount);
+    assert.ok(new BN(released.amount).eq(new BN(400000)));
+  });
+
+  it("blocks deposits but not withdrawals while deposits are paused", async () => {
+    const PAUSE_BORROW = 1;
+    const PAUSE_DEPOSIT = 2;
+
+    await program.methods
+      .pausePool(PAUSE_BORROW | PAUSE_DEPOSIT)
+      .accounts({ pool: poolPda, configHistory: configHistoryPda, admin: provider.wallet.publicKey })
+      .rpc();
+
+    try {
+      await program.methods
+        .depositLiquidity(new BN(1000), new BN(0))
+        .accounts({
+          pool: poolPda,
+          loanVault: loanVaultTokenAccount,
+          shareMint: shareMintPda,
+          lpPosition: lpPositionPda,
+          lpFeeAccumulator: null,
+          depositorAccount: depositorTokenAccount,
+          depositorShareAccount: depositorShareAccount,
+          depositor: provider.wallet.publicKey,
+          tokenMint: tokenMint,
+          tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      assert.fail("deposited into a paused pool");
+    } catch (err) {
+      assert.include(err.toString(), "PoolPaused");
+    }
+
+    await program.methods
+      .withdrawLiquidity(new BN(100000), new BN(0))
+      .accounts({
+        pool: poolPda,
+        loanVault: loanVaultTokenAccount,
+        shareMint: shareMintPda,
+        lpPosition: lpPositionPda,
+        lpFeeAccumulator: null,
+        ownerAccount: depositorTokenAccount,
+        ownerShareAccount: depositorShareAccount,
+        owner: provider.wallet.publicKey,
+        tokenMint: tokenMint,
+        tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    await program.methods
+      .unpausePool(PAUSE_BORROW | PAUSE_DEPOSIT)
+      .accounts({ pool: poolPda, configHistory: configHistoryPda, admin: provider.wallet.publicKey })
+      .rpc();
+
+    // Both pause flag changes are on record
+    const history = await program.account.configHistory.fetch(configHistoryPda);
+    assert.ok(history.totalChanges.eq(new BN(2)));
+    assert.ok(history.entries[0].newValue.eq(new BN(PAUSE_BORROW | PAUSE_DEPOSIT)));
+    assert.ok(history.entries[1].newValue.eq(new BN(0)));
+
+    const shares = await getTokenAccount(provider, depositorShareAccount);
+    assert.ok(new BN(shares.amount).eq(new BN(300000)));
+  });
+
+  // Depositing a flash loan back into the pool must not price the new shares
+  // against a vault with the loan missing from it
+  it("prices deposits made during an open loan against the full pool", async () => {
+    const [loanStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("loan_state"), poolPda.toBuffer(), provider.wallet.publicKey.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .initLoanState()
+      .accounts({
+        pool: poolPda,
+        loanState: loanStatePda,
+        borrower: provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    const [loanReceiptPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("loan_receipt"), loanStatePda.toBuffer()],
+      program.programId
+    );
+
+    const loan = new BN(200000);
+    const balanceBefore = await getTokenAccount(provider, depositorTokenAccount);
+    const sharesBefore = await getTokenAccount(provider, depositorShareAccount);
+
+    // flash_borrow -> deposit_liquidity -> flash_repay in one transaction
+    const depositIx = await program.methods
+      .depositLiquidity(loan, new BN(0))
+      .accounts({
+        pool: poolPda,
+        loanVault: loanVaultTokenAccount,
+        shareMint: shareMintPda,
+        lpPosition: lpPositionPda,
+        lpFeeAccumulator: null,
+        depositorAccount: depositorTokenAccount,
+        depositorShareAccount: depositorShareAccount,
+        depositor: provider.wallet.publicKey,
+        tokenMint: tokenMint,
+        tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+    const repayIx = await program.methods
+      .flashRepay(loan)
+      .accounts({
+        pool: poolPda,
+        loanVault: loanVaultTokenAccount,
+        borrowerAccount: depositorTokenAccount,
+        borrower: provider.wallet.publicKey,
+        loanState: loanStatePda,
+        loanStats: null,
+        tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+        loanReceipt: loanReceiptPda,
+        tokenMint: tokenMint,
+        shareMint: shareMintPda,
+        accountingPrice: null,
+        protocolConfig: null,
+        treasuryAccount: null,
+        creatorFeeAccount: null,
+      })
+      .instruction();
+    // Tagged with an idempotency key, kept on the receipt while the loan is open
+    const clientId = Array.from(Buffer.from("deposit-loop-001"));
+    await program.methods
+      .flashBorrow(loan, clientId)
+      .accounts({
+        pool: poolPda,
+        poolConfig: poolConfigPda,
+        loanVault: loanVaultTokenAccount,
+        borrowerAccount: depositorTokenAccount,
+        borrower: provider.wallet.publicKey,
+        loanState: loanStatePda,
+        tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+        loanReceipt: loanReceiptPda,
+        tokenMint: tokenMint,
+        instructionsSysvar: web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        feeExperimentStats: null,
+        poolAdmin: null,
+        accountingPrice: null,
+        insuranceFund: null,
+        insuranceVault: null,
+        insurancePolicy: null,
+      })
+      .postInstructions([depositIx, repayIx])
+      .rpc();
+
+    // The open loan counted as LP assets, so the deposit minted at the
+    // pre-loan rate of one share per token
+    const sharesAfter = await getTokenAccount(provider, depositorShareAccount);
+    const minted = new BN(sharesAfter.amount).sub(new BN(sharesBefore.amount));
+    assert.ok(minted.eq(loan));
+
+    // Redeeming them straight away returns no more than was put in: the
+    // borrower paid the fee and earns back only their share of it
+    await program.methods
+      .withdrawLiquidity(minted, new BN(0))
+      .accounts({
+        pool: poolPda,
+        loanVault: loanVaultTokenAccount,
+        shareMint: shareMintPda,
+        lpPosition: lpPositionPda,
+        lpFeeAccumulator: null,
+        ownerAccount: depositorTokenAccount,
+        ownerShareAccount: depositorShareAccount,
+        owner: provider.wallet.publicKey,
+        tokenMint: tokenMint,
+        tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+    const balanceAfter = await getTokenAccount(provider, depositorTokenAccount);
+    assert.ok(new BN(balanceAfter.amount).lte(new BN(balanceBefore.amount)));
+
+    // Resending the same borrow after it landed is refused by its client id,
+    // even though its receipt was closed long ago; the compute budget
+    // instruction only keeps the runtime from deduplicating the transaction
+    try {
+      await program.methods
+        .flashBorrow(loan, clientId)
+        .accounts({
+          pool: poolPda,
+          poolConfig: poolConfigPda,
+          loanVault: loanVaultTokenAccount,
+          borrowerAccount: depositorTokenAccount,
+          borrower: provider.wallet.publicKey,
+          loanState: loanStatePda,
+          tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+          loanReceipt: loanReceiptPda,
+          tokenMint: tokenMint,
+          instructionsSysvar: web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          systemProgram: SystemProgram.programId,
+          feeExperimentStats: null,
+          poolAdmin: null,
+          accountingPrice: null,
+          insuranceFund: null,
+          insuranceVault: null,
+          insurancePolicy: null,
+        })
+        .preInstructions([web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 400000 })])
+        .postInstructions([depositIx, repayIx])
+        .rpc();
+      assert.fail("the retried borrow went through");
+    } catch (err) {
+      assert.include(err.toString(), "DuplicateClientId");
+    }
+  });
+
+  it("bonds, slashes and unbonds a keeper", async () => {
+    const protocolConfig = await ensureProtocolConfig(program, provider);
+    const [keeperRegistry] = PublicKey.findProgramAddressSync([Buffer.from("keeper_registry")], program.programId);
+    const [stakeVault] = PublicKey.findProgramAddressSync([Buffer.from("keeper_stake_vault")], program.programId);
+    const [rewardVault] = PublicKey.findProgramAddressSync([Buffer.from("keeper_reward_vault")], program.programId);
+    // The stake mint is fixed once the registry exists
+    const existing = await program.account.keeperRegistry.fetchNullable(keeperRegistry);
+    const stakeMint = existing ? existing.stakeMint : await createMint(provider, provider.wallet.publicKey, 0);
+    await program.methods
+      .configureKeeperRegistry(new BN(1000), new BN(10), new BN(3600))
+      .accounts({
+        protocolConfig,
+        keeperRegistry,
+        stakeVault,
+        rewardVault,
+        stakeMint,
+        admin: provider.wallet.publicKey,
+        tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const authority = Keypair.generate();
+    const airdrop = await provider.connection.requestAirdrop(authority.publicKey, web3.LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(airdrop);
+    const sourceAccount = await createTokenAccount(provider, stakeMint, authority.publicKey);
+    await mintTo(provider, stakeMint, sourceAccount, provider.wallet.publicKey, [], 5000);
+    const [keeper] = PublicKey.findProgramAddressSync(
+      [Buffer.from("keeper"), authority.publicKey.toBuffer()],
+      program.programId
+    );
+    const register = (amount) =>
+      program.methods
+        .registerKeeper(new BN(amount))
+        .accounts({
+          keeperRegistry,
+          keeper,
+          stakeVault,
+          sourceAccount,
+          stakeMint,
+          authority: authority.publicKey,
+          tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+    try {
+      await register(999);
+      assert.fail("registered below the minimum bond");
+    } catch (err) {
+      assert.include(err.toString(), "KeeperStakeTooLow");
+    }
+    await register(2000);
+
+    // A crank with nothing to do pays nothing
+    const [statsAccumulatorPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("stats_accumulator"), poolPda.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .syncStats()
+      .accounts({
+        pool: poolPda,
+        loanStats: loanStatsKp.publicKey,
+        statsAccumulator: statsAccumulatorPda,
+        keeperRegistry,
+        keeper,
+        keeperAuthority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+    let state = await program.account.keeper.fetch(keeper);
+    assert.ok(state.cranksRun.eqn(0));
+
+    // Slashing moves bond to the reward vault
+    const rewardsBefore = await getTokenAccount(provider, rewardVault);
+    await program.methods
+      .slashKeeper(new BN(500), Array(32).fill(7))
+      .accounts({
+        protocolConfig,
+        keeperRegistry,
+        keeper,
+        authority: authority.publicKey,
+        stakeVault,
+        rewardVault,
+        stakeMint,
+        admin: provider.wallet.publicKey,
+        tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+    state = await program.account.keeper.fetch(keeper);
+    assert.ok(state.stakedAmount.eqn(1500));
+    assert.ok(state.totalSlashed.eqn(500));
+    const rewardsAfter = await getTokenAccount(provider, rewardVault);
+    assert.ok(new BN(rewardsAfter.amount).sub(new BN(rewardsBefore.amount)).eqn(500));
+
+    // An exiting keeper can neither top up nor withdraw before unbonding
+    await program.methods
+      .beginKeeperExit()
+      .accounts({ keeperRegistry, keeper, authority: authority.publicKey })
+      .signers([authority])
+      .rpc();
+    try {
+      await register(1000);
+      assert.fail("topped up an exiting keeper");
+    } catch (err) {
+      assert.include(err.toString(), "KeeperExiting");
+    }
+    try {
+      await program.methods
+        .withdrawKeeperStake()
+        .accounts({
+          keeperRegistry,
+          keeper,
+          stakeVault,
+          destinationAccount: sourceAccount,
+          stakeMint,
+          authority: authority.publicKey,
+          tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+        })
+        .signers([authority])
+        .rpc();
+      assert.fail("withdrew a bond before unbonding");
+    } catch (err) {
+      assert.include(err.toString(), "KeeperStillUnbonding");
+    }
+  });
 });
 
 // Helper function to create token mint